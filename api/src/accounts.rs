@@ -203,6 +203,8 @@ impl Account {
         limit: Option<u16>,
         require_state_indices: bool,
     ) -> Result<Self, BasicErrorWith404> {
+        context.check_account_state_tracked(address.inner())?;
+
         let sharding_enabled = context
             .node_config
             .storage
@@ -6,9 +6,10 @@ use crate::{
     accept_type::AcceptType,
     metrics,
     response::{
-        bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
-        block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
-        ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError, StdApiError,
+        account_state_untracked, bcs_api_disabled, block_not_found_by_height,
+        block_not_found_by_version, block_pruned_by_height, json_api_disabled, state_key_untracked,
+        version_not_found, version_pruned, ForbiddenError, InternalError, NotFoundError,
+        ServiceUnavailableError, StdApiError,
     },
 };
 use anyhow::{anyhow, bail, ensure, format_err, Context as AnyhowContext, Result};
@@ -37,7 +38,9 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     on_chain_config::{GasSchedule, GasScheduleV2, OnChainConfig, OnChainExecutionConfig},
     state_store::{
-        state_key::{inner::StateKeyInner, prefix::StateKeyPrefix, StateKey},
+        state_key::{
+            filter::StateKeyFilter, inner::StateKeyInner, prefix::StateKeyPrefix, StateKey,
+        },
         state_value::StateValue,
         TStateView,
     },
@@ -78,6 +81,7 @@ pub struct Context {
     simulate_txn_stats: Arc<FunctionStats>,
     pub indexer_reader: Option<Arc<dyn IndexerReader>>,
     pub wait_for_hash_active_connections: Arc<AtomicUsize>,
+    state_key_filter: Option<Arc<StateKeyFilter>>,
 }
 
 impl std::fmt::Debug for Context {
@@ -107,6 +111,13 @@ impl Context {
                 )),
             )
         };
+        // The state key filter is owned by the DB (as it tracks new objects)
+        let state_key_filter = if node_config.storage.partial_state.enabled {
+            db.get_state_key_filter()
+                .expect("Failed to get the state key filter from the DB!")
+        } else {
+            None
+        };
         Self {
             chain_id,
             db,
@@ -131,6 +142,7 @@ impl Context {
             simulate_txn_stats,
             indexer_reader,
             wait_for_hash_active_connections: Arc::new(AtomicUsize::new(0)),
+            state_key_filter,
         }
     }
 
@@ -1356,6 +1368,50 @@ impl Context {
         }
     }
 
+    /// Returns an error if the node only stores partial state, and the state
+    /// of the given account is not fully tracked (e.g., an object transferred
+    /// to a tracked account, whose state written before was not stored).
+    pub fn check_account_state_tracked<E: ForbiddenError>(
+        &self,
+        address: &AccountAddress,
+    ) -> Result<(), E> {
+        match &self.state_key_filter {
+            Some(state_key_filter) if !state_key_filter.is_address_fully_tracked(address) => {
+                Err(account_state_untracked(address))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the first signer of the transaction (i.e., the sender, a secondary
+    /// signer or the fee payer) whose state is not tracked, if the node only
+    /// stores partial state. Transactions can't be validated without this state.
+    pub fn find_untracked_transaction_signer(
+        &self,
+        txn: &SignedTransaction,
+    ) -> Option<AccountAddress> {
+        let state_key_filter = self.state_key_filter.as_ref()?;
+        let authenticator = txn.authenticator_ref();
+        std::iter::once(txn.sender())
+            .chain(authenticator.secondary_signer_addresses())
+            .chain(authenticator.fee_payer_address())
+            .find(|address| !state_key_filter.is_address_tracked(address))
+    }
+
+    /// Returns an error if the node only stores partial state, and the given
+    /// state key is not tracked.
+    pub fn check_state_key_tracked<E: ForbiddenError>(
+        &self,
+        state_key: &StateKey,
+    ) -> Result<(), E> {
+        match &self.state_key_filter {
+            Some(state_key_filter) if !state_key_filter.is_tracked(state_key) => {
+                Err(state_key_untracked(state_key))
+            },
+            _ => Ok(()),
+        }
+    }
+
     pub fn check_api_output_enabled<E: ForbiddenError>(
         &self,
         api_name: &'static str,
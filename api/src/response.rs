@@ -31,7 +31,9 @@
 
 use super::{accept_type::AcceptType, bcs_payload::Bcs};
 use aptos_api_types::{Address, AptosError, AptosErrorCode, HashValue, LedgerInfo};
+use aptos_types::state_store::state_key::StateKey;
 use move_core_types::{
+    account_address::AccountAddress,
     identifier::{IdentStr, Identifier},
     language_storage::StructTag,
 };
@@ -596,6 +598,36 @@ pub fn api_forbidden<S: Display, E: ForbiddenError>(identifier: S, extra_help: S
     )
 }
 
+pub fn account_state_untracked<E: ForbiddenError>(address: &AccountAddress) -> E {
+    E::forbidden_with_code_no_info(
+        format!(
+            "State of account({}) is not stored by this node, as it only stores partial state",
+            address
+        ),
+        AptosErrorCode::StateNotTracked,
+    )
+}
+
+pub fn state_key_untracked<E: ForbiddenError>(state_key: &StateKey) -> E {
+    E::forbidden_with_code_no_info(
+        format!(
+            "State value({:?}) is not stored by this node, as it only stores partial state",
+            state_key
+        ),
+        AptosErrorCode::StateNotTracked,
+    )
+}
+
+pub fn state_untracked<E: ForbiddenError>(operation: &str) -> E {
+    E::forbidden_with_code_no_info(
+        format!(
+            "{} read state that is not stored by this node, as it only stores partial state",
+            operation
+        ),
+        AptosErrorCode::StateNotTracked,
+    )
+}
+
 pub fn version_not_found<E: NotFoundError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
     build_not_found(
         "Ledger version",
@@ -284,6 +284,7 @@ impl StateApi {
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
             })?;
+        self.context.check_account_state_tracked(address.inner())?;
 
         let (ledger_info, ledger_version, state_view) = self.context.state_view(ledger_version)?;
         let bytes = state_view
@@ -338,6 +339,7 @@ impl StateApi {
         ledger_version: Option<U64>,
     ) -> BasicResultWith404<MoveModuleBytecode> {
         let state_key = StateKey::module(address.inner(), &name);
+        self.context.check_state_key_tracked(&state_key)?;
         let (ledger_info, ledger_version, state_view) = self
             .context
             .state_view(ledger_version.map(|inner| inner.0))?;
@@ -429,6 +431,7 @@ impl StateApi {
 
         // Retrieve value from the state key
         let state_key = StateKey::table_item(&TableHandle(table_handle.into()), &raw_key);
+        self.context.check_state_key_tracked(&state_key)?;
         let bytes = state_view
             .get_state_value_bytes(&state_key)
             .context(format!(
@@ -484,6 +487,7 @@ impl StateApi {
 
         let state_key =
             StateKey::table_item(&TableHandle(table_handle.into()), &table_item_request.key.0);
+        self.context.check_state_key_tracked(&state_key)?;
         let bytes = state_view
             .get_state_value_bytes(&state_key)
             .context(format!(
@@ -546,6 +550,7 @@ impl StateApi {
                     &ledger_info,
                 )
             })?;
+        self.context.check_state_key_tracked(&state_key)?;
         let state_value = state_view
             .get_state_value(&state_key)
             .context(format!("Failed fetching state value. key: {}", request.key,))
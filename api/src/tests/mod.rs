@@ -22,7 +22,10 @@ mod view_function;
 mod webauthn_secp256r1_ecdsa;
 
 use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
-use aptos_config::config::{internal_indexer_db_config::InternalIndexerDBConfig, NodeConfig};
+use aptos_config::config::{
+    internal_indexer_db_config::InternalIndexerDBConfig, NodeConfig, PartialStateConfig,
+};
+use aptos_types::account_address::AccountAddress;
 
 fn new_test_context(test_name: String) -> TestContext {
     new_test_context_with_config(test_name, NodeConfig::default())
@@ -32,6 +35,19 @@ fn new_test_context_with_config(test_name: String, node_config: NodeConfig) -> T
     super_new_test_context(test_name, node_config, false)
 }
 
+fn new_test_context_with_partial_state(
+    test_name: String,
+    tracked_addresses: Vec<AccountAddress>,
+) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.storage.partial_state = PartialStateConfig {
+        enabled: true,
+        tracked_addresses,
+        tracked_table_handles: vec![],
+    };
+    new_test_context_with_config(test_name, node_config)
+}
+
 fn new_test_context_with_db_sharding_and_internal_indexer(test_name: String) -> TestContext {
     let mut node_config = NodeConfig::default();
     node_config.storage.rocksdb_configs.enable_storage_sharding = true;
@@ -1,8 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
 use aptos_crypto::ed25519::Ed25519Signature;
 use aptos_types::transaction::{
     authenticator::TransactionAuthenticator, EntryFunction, TransactionPayload,
@@ -153,3 +154,94 @@ async fn test_simulate_txn_with_aggregator() {
         unreachable!("Simulation uses Ed25519 authenticator.");
     }
 }
+
+async fn simulate_aptos_transfer_with_access_list(
+    context: &mut TestContext,
+    expected_status: u16,
+) -> (String, serde_json::Value) {
+    let alice = &mut context.gen_account();
+    let bob = &mut context.gen_account();
+    let txn = context.mint_user_account(alice).await;
+    context.commit_block(&vec![txn]).await;
+
+    let txn = context.account_transfer_to(alice, bob.address(), SMALL_TRANSFER_AMOUNT);
+    if let TransactionAuthenticator::Ed25519 {
+        public_key,
+        signature: _,
+    } = txn.authenticator_ref()
+    {
+        let resp = context
+            .expect_status_code(expected_status)
+            .post(
+                "/transactions/simulate?include_access_list=true",
+                json!({
+                    "sender": txn.sender().to_string(),
+                    "sequence_number": txn.sequence_number().to_string(),
+                    "max_gas_amount": txn.max_gas_amount().to_string(),
+                    "gas_unit_price": txn.gas_unit_price().to_string(),
+                    "expiration_timestamp_secs": txn.expiration_timestamp_secs().to_string(),
+                    "payload": {
+                        "type": "entry_function_payload",
+                        "function": "0x1::aptos_account::transfer",
+                        "type_arguments": [],
+                        "arguments": [
+                            bob.address().to_standard_string(), SMALL_TRANSFER_AMOUNT.to_string(),
+                        ]
+                    },
+                    "signature": {
+                        "type": "ed25519_signature",
+                        "public_key": public_key.to_string(),
+                        "signature": Ed25519Signature::dummy_signature().to_string(),
+                    }
+                }),
+            )
+            .await;
+        (alice.address().to_hex_literal(), resp)
+    } else {
+        unreachable!("Simulation uses Ed25519 authenticator.");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_access_list() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.simulation_access_list_enabled = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let (sender, resp) = simulate_aptos_transfer_with_access_list(&mut context, 200).await;
+    assert!(resp[0]["success"].as_bool().is_some_and(|v| v));
+
+    let access_list = &resp[0]["access_list"];
+    let is_sender_account = |access: &serde_json::Value| {
+        access["type"] == "resource"
+            && access["address"] == sender.as_str()
+            && access["resource"] == "0x1::account::Account"
+    };
+    // The sequence number of the sender is both read and written
+    for set in ["read_set", "write_set"] {
+        assert!(access_list[set]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(is_sender_account));
+    }
+    // The transfer function is read from the code of the framework
+    assert!(access_list["read_set"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|access| access["type"] == "module" && access["module"] == "0x1::aptos_account"));
+    // The gas schedule is read when creating the VM
+    assert!(access_list["read_set"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|access| access["type"] == "resource"
+            && access["resource"] == "0x1::gas_schedule::GasScheduleV2"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_access_list_disabled() {
+    let mut context = new_test_context(current_function_name!());
+    let (_, resp) = simulate_aptos_transfer_with_access_list(&mut context, 403).await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_partial_state};
 use aptos_api_test_context::{current_function_name, TestContext};
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
@@ -65,6 +65,32 @@ async fn test_get_account_resource_struct_tag_not_found() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_partial_state() {
+    let tracked_address = AccountAddress::from_hex_literal("0xA550C18").unwrap();
+    let mut context =
+        new_test_context_with_partial_state(current_function_name!(), vec![tracked_address]);
+
+    // Verify the state of tracked accounts and special addresses is returned
+    context
+        .get(&get_account_resource("0xA550C18", "0x1::account::Account"))
+        .await;
+    context
+        .get(&get_account_resource("0x1", "0x1::chain_id::ChainId"))
+        .await;
+
+    // Verify the state of untracked accounts is rejected
+    let untracked_address = context.validator_owner.to_hex_literal();
+    let resp = context
+        .expect_status_code(403)
+        .get(&get_account_resource(
+            &untracked_address,
+            "0x1::account::Account",
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "state_not_tracked");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource_with_version() {
     let mut context = new_test_context(current_function_name!());
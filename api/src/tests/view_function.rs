@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{new_test_context, new_test_context_with_config};
+use super::{new_test_context, new_test_context_with_config, new_test_context_with_partial_state};
 use aptos_api_test_context::{current_function_name, TestContext};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_config::config::{NodeConfig, ViewFilter, ViewFunctionId};
@@ -43,6 +43,26 @@ async fn test_simple_view() {
     context.check_golden_output_no_prune(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_partial_state() {
+    let tracked_address = AccountAddress::from_hex_literal("0xA550C18").unwrap();
+    let mut context =
+        new_test_context_with_partial_state(current_function_name!(), vec![tracked_address]);
+
+    // Verify view functions that only read tracked state are executed
+    context
+        .post("/view", build_coin_balance_request(&tracked_address))
+        .await;
+
+    // Verify view functions that read untracked state are rejected
+    let untracked_address = context.validator_owner;
+    let resp = context
+        .expect_status_code(403)
+        .post("/view", build_coin_balance_request(&untracked_address))
+        .await;
+    assert_eq!(resp["error_code"], "state_not_tracked");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_view_gas_used_header() {
     let mut context = new_test_context(current_function_name!());
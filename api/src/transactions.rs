@@ -12,7 +12,7 @@ use crate::{
     metrics::WAIT_TRANSACTION_GAUGE,
     page::Page,
     response::{
-        api_disabled, api_forbidden, transaction_not_found_by_hash,
+        api_disabled, api_forbidden, state_untracked, transaction_not_found_by_hash,
         transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
         BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
         ForbiddenError, InsufficientStorageError, InternalError,
@@ -24,14 +24,16 @@ use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
     AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
     HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
-    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+    Transaction, TransactionAccessList, TransactionData, TransactionOnChainData,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_gas_profiling::{traced_entry_function, ExecutionTrace, ExecutionTracer};
 use aptos_types::{
     account_address::AccountAddress,
     mempool_status::MempoolStatusCode,
+    state_store::TStateView,
     transaction::{
         EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
         RawTransactionWithData, SignedTransaction, TransactionPayload,
@@ -39,7 +41,8 @@ use aptos_types::{
     vm_status::StatusCode,
     APTOS_COIN_TYPE,
 };
-use aptos_vm::{AptosSimulationVM, AptosVM};
+use aptos_vm::{data_cache::AsMoveResolver, AptosSimulationVM, AptosVM};
+use aptos_vm_logging::log_schema::AdapterLogSchema;
 use move_core_types::{ident_str, language_storage::ModuleId, vm_status::VMStatus};
 use poem_openapi::{
     param::{Path, Query},
@@ -489,6 +492,9 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
+        /// If set to true, the state read and written by the transaction is returned in its
+        /// `access_list`. This is only available as JSON, and must be enabled in the API config.
+        include_access_list: Query<Option<bool>>,
         data: SubmitTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
@@ -503,6 +509,18 @@ impl TransactionsApi {
         if !self.context.node_config.api.transaction_simulation_enabled {
             return Err(api_disabled("Simulate transaction"));
         }
+        let include_access_list = include_access_list.0.unwrap_or_default();
+        if include_access_list {
+            if !self.context.node_config.api.simulation_access_list_enabled {
+                return Err(api_disabled("Simulation access list"));
+            }
+            if accept_type == AcceptType::Bcs {
+                return Err(SubmitTransactionError::bad_request_with_code_no_info(
+                    "BCS is not supported for simulation access lists",
+                    AptosErrorCode::BcsNotSupported,
+                ));
+            }
+        }
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
 
@@ -617,11 +635,121 @@ impl TransactionsApi {
                 );
             }
 
-            api.simulate(&accept_type, ledger_info, signed_transaction)
+            api.simulate(
+                &accept_type,
+                ledger_info,
+                signed_transaction,
+                include_access_list,
+            )
         })
         .await
     }
 
+    /// Trace simulated transaction
+    ///
+    /// Simulates the transaction like `/transactions/simulate`, and returns its execution trace:
+    /// the call tree of the transaction (with the type arguments, arguments and return values of
+    /// every call), the resources it accessed, the events it emitted, and the execution gas used
+    /// by every call (in internal gas units). Calls that didn't return are on the abort path.
+    ///
+    /// The trace is only available as JSON. This endpoint must be enabled in the API config.
+    #[oai(
+        path = "/transactions/simulate/trace",
+        method = "post",
+        operation_id = "trace_simulated_transaction",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn trace_simulated_transaction(
+        &self,
+        accept_type: AcceptType,
+        data: SubmitTransactionPost,
+    ) -> SimulateTransactionResult<serde_json::Value> {
+        data.verify()
+            .context("Simulated transaction invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_trace_simulated_transaction")?;
+        if !self.context.node_config.api.transaction_simulation_enabled {
+            return Err(api_disabled("Simulate transaction"));
+        }
+        if !self.context.node_config.api.transaction_tracing_enabled {
+            return Err(api_disabled("Trace transaction"));
+        }
+        if accept_type == AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code_no_info(
+                "BCS is not supported for transaction traces",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Trace transaction", &accept_type)?;
+
+        let api = self.clone();
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let ledger_info = context.get_latest_ledger_info()?;
+            let signed_transaction = api.get_signed_transaction(&ledger_info, data)?;
+
+            // The simulation filter applies to traced simulations as well
+            if !context.node_config.api.simulation_filter.allows(
+                aptos_crypto::HashValue::zero(),
+                ledger_info.timestamp(),
+                &signed_transaction,
+            ) {
+                return Err(SubmitTransactionError::forbidden_with_code(
+                    "Transaction not allowed by simulation filter",
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                ));
+            }
+
+            api.trace_simulated(ledger_info, signed_transaction)
+        })
+        .await
+    }
+
+    /// Trace transaction by version
+    ///
+    /// Re-executes a committed user transaction on the state right before it, and returns its
+    /// execution trace: the call tree of the transaction (with the type arguments, arguments and
+    /// return values of every call), the resources it accessed, the events it emitted, and the
+    /// execution gas used by every call (in internal gas units). Calls that didn't return are on
+    /// the abort path. If the version has been pruned, a 410 will be returned.
+    ///
+    /// The trace is only available as JSON. This endpoint must be enabled in the API config.
+    #[oai(
+        path = "/transactions/by_version/:txn_version/trace",
+        method = "get",
+        operation_id = "trace_transaction_by_version",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn trace_transaction_by_version(
+        &self,
+        accept_type: AcceptType,
+        /// Version of transaction to trace
+        txn_version: Path<U64>,
+    ) -> BasicResultWith404<serde_json::Value> {
+        fail_point_poem("endpoint_trace_transaction_by_version")?;
+        if !self.context.node_config.api.transaction_tracing_enabled {
+            return Err(api_disabled("Trace transaction"));
+        }
+        if accept_type == AcceptType::Bcs {
+            return Err(BasicErrorWith404::bad_request_with_code_no_info(
+                "BCS is not supported for transaction traces",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Trace transaction", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || api.trace_transaction_by_version_inner(txn_version.0)).await
+    }
+
     /// Encode submission
     ///
     /// This endpoint accepts an EncodeSubmissionRequest, which internally is a
@@ -1184,6 +1312,16 @@ impl TransactionsApi {
 
     /// Submits a single transaction, and converts mempool codes to errors
     async fn create_internal(&self, txn: SignedTransaction) -> Result<(), AptosError> {
+        if let Some(address) = self.context.find_untracked_transaction_signer(&txn) {
+            return Err(AptosError::new_with_error_code(
+                format!(
+                    "State of account({}) is not stored by this node, as it only stores partial state",
+                    address
+                ),
+                AptosErrorCode::StateNotTracked,
+            ));
+        }
+
         let (mempool_status, vm_status_opt) = self
             .context
             .submit_transaction(txn)
@@ -1296,6 +1434,9 @@ impl TransactionsApi {
                         ledger_info,
                     ),
                 ),
+                AptosErrorCode::StateNotTracked => Err(
+                    SubmitTransactionError::forbidden_from_aptos_error(error, ledger_info),
+                ),
                 _ => Err(SubmitTransactionError::internal_from_aptos_error(
                     error,
                     ledger_info,
@@ -1352,6 +1493,7 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
         txn: SignedTransaction,
+        include_access_list: bool,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         // The caller must ensure that the signature is not valid, as otherwise
         // a malicious actor could execute the transaction without their knowledge
@@ -1365,8 +1507,30 @@ impl TransactionsApi {
 
         // Simulate transaction
         let state_view = self.context.latest_state_view_poem(&ledger_info)?;
-        let (vm_status, output) =
-            AptosSimulationVM::create_vm_and_simulate_signed_transaction(&txn, &state_view);
+        let (vm_status, output, access_list) = if include_access_list {
+            let (vm_status, output, access_list) =
+                AptosSimulationVM::create_vm_and_simulate_signed_transaction_with_access_list(
+                    &txn,
+                    &state_view,
+                );
+            let access_list = TransactionAccessList::try_from(&access_list)
+                .context("Failed to convert the access list of the simulated transaction")
+                .map_err(|err| {
+                    SubmitTransactionError::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+            (vm_status, output, Some(access_list))
+        } else {
+            let (vm_status, output) =
+                AptosSimulationVM::create_vm_and_simulate_signed_transaction(&txn, &state_view);
+            (vm_status, output, None)
+        };
+        if state_view.has_read_untracked_state() {
+            return Err(state_untracked("Transaction simulation"));
+        }
         let version = ledger_info.version();
 
         // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
@@ -1449,6 +1613,7 @@ impl TransactionsApi {
                                 },
                                 _ => (),
                             }
+                            user_txn.access_list = access_list.clone();
                             user_transactions.push(user_txn);
                         },
                         _ => {
@@ -1474,6 +1639,135 @@ impl TransactionsApi {
         result.map(|r| r.with_gas_used(Some(output.gas_used())))
     }
 
+    /// Simulates the transaction with an execution tracer
+    pub fn trace_simulated(
+        &self,
+        ledger_info: LedgerInfo,
+        txn: SignedTransaction,
+    ) -> SimulateTransactionResult<serde_json::Value> {
+        // The caller must ensure that the signature is not valid, as otherwise
+        // a malicious actor could execute the transaction without their knowledge
+        if txn.verify_signature().is_ok() {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Simulated transactions must not have a valid signature",
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let entry_function = traced_entry_function(txn.payload()).map_err(|err| {
+            SubmitTransactionError::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+
+        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let (vm_status, output, tracer) =
+            AptosSimulationVM::create_vm_and_simulate_signed_transaction_with_modified_gas_meter(
+                &txn,
+                &state_view,
+                |gas_meter| ExecutionTracer::new(gas_meter, entry_function.as_ref()),
+            )
+            .map_err(|status| {
+                SubmitTransactionError::internal_with_code(
+                    format!("Failed to simulate the transaction: {}", status),
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        BasicResponse::try_from_json((
+            render_execution_trace(&vm_status, output.gas_used(), tracer.finish()),
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+        .map(|r| r.with_gas_used(Some(output.gas_used())))
+    }
+
+    /// Re-executes the committed user transaction at the given version with an execution tracer
+    fn trace_transaction_by_version_inner(
+        &self,
+        version: U64,
+    ) -> BasicResultWith404<serde_json::Value> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let txn_data = self
+            .get_by_version(version.0, &ledger_info)
+            .context(format!("Failed to get transaction by version {}", version))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let txn = match txn_data {
+            GetByVersionResponse::Found(TransactionData::OnChain(TransactionOnChainData {
+                transaction: aptos_types::transaction::Transaction::UserTransaction(txn),
+                ..
+            })) => txn,
+            GetByVersionResponse::Found(_) => {
+                return Err(BasicErrorWith404::bad_request_with_code(
+                    "Only user transactions can be traced",
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                ))
+            },
+            GetByVersionResponse::VersionTooNew => {
+                return Err(transaction_not_found_by_version(version.0, &ledger_info))
+            },
+            GetByVersionResponse::VersionTooOld => {
+                return Err(version_pruned(version.0, &ledger_info))
+            },
+        };
+        let entry_function = traced_entry_function(txn.payload()).map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+
+        // A user transaction is never the first transaction, so the version can't underflow
+        let state_view = self
+            .context
+            .state_view_at_version(version.0 - 1)
+            .context(format!(
+                "Failed to get the state before version {}",
+                version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let vm = AptosVM::new(&state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let resolver = state_view.as_move_resolver();
+        let (vm_status, output, tracer) = vm
+            .execute_user_transaction_with_modified_gas_meter(
+                &resolver,
+                &txn,
+                &log_context,
+                |gas_meter| ExecutionTracer::new(gas_meter, entry_function.as_ref()),
+            )
+            .map_err(|status| {
+                BasicErrorWith404::internal_with_code(
+                    format!("Failed to execute the transaction: {}", status),
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        BasicResponse::try_from_json((
+            render_execution_trace(&vm_status, output.gas_used(), tracer.finish()),
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+
     /// Encode message as BCS
     pub fn get_signing_message(
         &self,
@@ -1553,6 +1847,18 @@ fn override_gas_parameters(
     SignedTransaction::new_signed_transaction(raw_txn, signed_txn.authenticator())
 }
 
+fn render_execution_trace(
+    vm_status: &VMStatus,
+    gas_used: u64,
+    trace: ExecutionTrace,
+) -> serde_json::Value {
+    serde_json::json!({
+        "vm_status": vm_status.to_string(),
+        "gas_used": gas_used.to_string(),
+        "trace": trace,
+    })
+}
+
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
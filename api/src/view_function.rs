@@ -7,8 +7,8 @@ use crate::{
     context::{api_spawn_blocking, FunctionStats},
     failpoint::fail_point_poem,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        ForbiddenError, InternalError,
+        state_untracked, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResultWith404, ForbiddenError, InternalError,
     },
     ApiTags, Context,
 };
@@ -140,6 +140,9 @@ fn view_request(
         view_function.args.clone(),
         context.node_config.api.max_gas_view_function,
     );
+    if state_view.has_read_untracked_state() {
+        return Err(state_untracked("View function"));
+    }
     let values = output.values.map_err(|err| {
         BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
     })?;
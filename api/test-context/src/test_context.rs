@@ -141,6 +141,7 @@ pub fn new_test_context(
                 BUFFERED_STATE_TARGET_ITEMS,
                 DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
                 None,
+                node_config.storage.partial_state.state_key_filter(),
             )
             .unwrap(),
         )
@@ -86,6 +86,8 @@ pub enum AptosErrorCode {
     VersionPruned = 200,
     /// Block is fully or partially pruned
     BlockPruned = 201,
+    /// State is not stored by this node (i.e., the node only stores partial state)
+    StateNotTracked = 202,
 
     /// The API's inputs were invalid
     InvalidInput = 300,
@@ -10,12 +10,14 @@ use crate::{
 use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
     ed25519::{self, Ed25519PublicKey, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH},
+    hash::CryptoHash,
     multi_ed25519::{self, MultiEd25519PublicKey, BITMAP_NUM_OF_BYTES, MAX_NUM_OF_KEYS},
     secp256k1_ecdsa, secp256r1_ecdsa,
     secp256r1_ecdsa::PUBLIC_KEY_LENGTH,
     ValidCryptoMaterial,
 };
 use aptos_types::{
+    access_path::Path,
     account_address::AccountAddress,
     aggregate_signature::AggregateSignature,
     block_metadata::BlockMetadata,
@@ -24,6 +26,7 @@ use aptos_types::{
     dkg::{DKGTranscript, DKGTranscriptMetadata},
     jwks::{jwk::JWK, ProviderJWKs, QuorumCertifiedUpdate},
     keyless,
+    state_store::state_key::{inner::StateKeyInner, StateKey},
     transaction::{
         authenticator::{
             AccountAuthenticator, AnyPublicKey, AnySignature, MultiKey, MultiKeyAuthenticator,
@@ -38,6 +41,7 @@ use poem_openapi::{Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
+    collections::BTreeSet,
     convert::{From, Into, TryFrom, TryInto},
     fmt,
     str::FromStr,
@@ -297,6 +301,7 @@ impl
             request: (txn, payload).into(),
             events,
             timestamp: timestamp.into(),
+            access_list: None,
         })
     }
 }
@@ -413,6 +418,12 @@ pub struct UserTransaction {
     /// Events generated by the transaction
     pub events: Vec<Event>,
     pub timestamp: U64,
+    /// The state read and written by the transaction, only returned by simulations which
+    /// request it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    #[oai(skip_serializing_if_is_none)]
+    pub access_list: Option<TransactionAccessList>,
 }
 
 /// A state checkpoint transaction
@@ -1112,6 +1123,145 @@ impl WriteSetChange {
     }
 }
 
+/// The state read and written by a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionAccessList {
+    /// State read during the execution, including state which doesn't exist
+    pub read_set: Vec<StateAccess>,
+    /// State written or deleted by the transaction
+    pub write_set: Vec<StateAccess>,
+}
+
+impl TryFrom<&aptos_vm::access_list::TransactionAccessList> for TransactionAccessList {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        access_list: &aptos_vm::access_list::TransactionAccessList,
+    ) -> anyhow::Result<Self> {
+        let convert = |state_keys: &BTreeSet<StateKey>| {
+            state_keys
+                .iter()
+                .map(|state_key| {
+                    StateAccess::try_from_state_key(
+                        state_key,
+                        access_list.aggregator_v1_keys.contains(state_key),
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        Ok(Self {
+            read_set: convert(&access_list.read_set)?,
+            write_set: convert(&access_list.write_set)?,
+        })
+    }
+}
+
+/// A state item accessed by a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum StateAccess {
+    Module(ModuleAccess),
+    Resource(ResourceAccess),
+    ResourceGroup(ResourceGroupAccess),
+    TableItem(TableItemAccess),
+    AggregatorV1(AggregatorV1Access),
+}
+
+impl StateAccess {
+    /// Aggregators (V1) are stored as table items, so whether the key belongs to an aggregator
+    /// is known from the output of the transaction only.
+    pub fn try_from_state_key(
+        state_key: &StateKey,
+        is_aggregator_v1: bool,
+    ) -> anyhow::Result<Self> {
+        let state_key_hash = state_key.hash().to_hex_literal();
+        Ok(match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => match access_path.get_path() {
+                Path::Code(module_id) => StateAccess::Module(ModuleAccess {
+                    address: access_path.address.into(),
+                    state_key_hash,
+                    module: module_id.into(),
+                }),
+                Path::Resource(typ) => StateAccess::Resource(ResourceAccess {
+                    address: access_path.address.into(),
+                    state_key_hash,
+                    resource: typ.into(),
+                }),
+                Path::ResourceGroup(typ) => StateAccess::ResourceGroup(ResourceGroupAccess {
+                    address: access_path.address.into(),
+                    state_key_hash,
+                    resource_group: typ.into(),
+                }),
+            },
+            StateKeyInner::TableItem { handle, key } => {
+                let handle: HexEncodedBytes = handle.0.to_vec().into();
+                let key: HexEncodedBytes = key.to_owned().into();
+                if is_aggregator_v1 {
+                    StateAccess::AggregatorV1(AggregatorV1Access {
+                        state_key_hash,
+                        handle,
+                        key,
+                    })
+                } else {
+                    StateAccess::TableItem(TableItemAccess {
+                        state_key_hash,
+                        handle,
+                        key,
+                    })
+                }
+            },
+            StateKeyInner::Raw(_) => bail!(
+                "Can't convert account raw key {:?} to StateAccess",
+                state_key
+            ),
+        })
+    }
+}
+
+/// Access to a module
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleAccess {
+    pub address: Address,
+    /// State key hash
+    pub state_key_hash: String,
+    pub module: MoveModuleId,
+}
+
+/// Access to a resource
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceAccess {
+    pub address: Address,
+    /// State key hash
+    pub state_key_hash: String,
+    pub resource: MoveStructTag,
+}
+
+/// Access to a resource group
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceGroupAccess {
+    pub address: Address,
+    /// State key hash
+    pub state_key_hash: String,
+    pub resource_group: MoveStructTag,
+}
+
+/// Access to a table item
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TableItemAccess {
+    pub state_key_hash: String,
+    pub handle: HexEncodedBytes,
+    pub key: HexEncodedBytes,
+}
+
+/// Access to an aggregator (V1), which is stored as a table item
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct AggregatorV1Access {
+    pub state_key_hash: String,
+    pub handle: HexEncodedBytes,
+    pub key: HexEncodedBytes,
+}
+
 /// An enum representing the different transaction signatures available
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
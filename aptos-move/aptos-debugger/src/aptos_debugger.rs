@@ -1,16 +1,19 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::step_debugger::{with_step_debugger, StepDebugger};
 use anyhow::{bail, format_err, Result};
 use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
-use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
+use aptos_gas_profiling::{
+    traced_entry_function, ExecutionTrace, ExecutionTracer, GasProfiler, TransactionGasLog,
+};
 use aptos_rest_client::Client;
 use aptos_types::{
     account_address::AccountAddress,
     block_executor::config::{
         BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
     },
-    state_store::TStateView,
+    state_store::{state_key::StateKey, state_value::StateValue, StateView, TStateView},
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, BlockOutput,
         SignedTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionPayload,
@@ -19,7 +22,8 @@ use aptos_types::{
     vm_status::VMStatus,
 };
 use aptos_validator_interface::{
-    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, ForkedStateView,
+    RestDebuggerInterface,
 };
 use aptos_vm::{
     block_executor::{AptosTransactionOutput, BlockAptosVM},
@@ -29,7 +33,7 @@ use aptos_vm::{
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::output::VMOutput;
 use itertools::Itertools;
-use std::{path::Path, sync::Arc, time::Instant};
+use std::{collections::HashMap, path::Path, sync::Arc, time::Instant};
 
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
@@ -148,6 +152,51 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    pub fn execute_transaction_at_version_with_tracer(
+        &self,
+        version: Version,
+        txn: SignedTransaction,
+    ) -> Result<(VMStatus, VMOutput, ExecutionTrace)> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let txn = txn
+            .check_signature()
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        let entry_function = traced_entry_function(txn.payload())?;
+
+        let vm = AptosVM::new(&state_view);
+        let resolver = state_view.as_move_resolver();
+
+        let (status, output, tracer) = vm.execute_user_transaction_with_modified_gas_meter(
+            &resolver,
+            &txn,
+            &log_context,
+            |gas_meter| ExecutionTracer::new(gas_meter, entry_function.as_ref()),
+        )?;
+
+        Ok((status, output, tracer.finish()))
+    }
+
+    /// Executes the transaction with the step debugger attached to the VM, so that it can be
+    /// stepped through interactively.
+    pub fn execute_transaction_at_version_with_step_debugger(
+        &self,
+        version: Version,
+        txn: SignedTransaction,
+        step_debugger: StepDebugger,
+    ) -> Result<(VMStatus, VMOutput)> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+
+        let vm = AptosVM::new(&state_view);
+        let resolver = state_view.as_move_resolver();
+
+        Ok(with_step_debugger(step_debugger, || {
+            vm.execute_user_transaction(&resolver, &txn, &log_context)
+        }))
+    }
+
     pub async fn execute_past_transactions(
         &self,
         begin: Version,
@@ -321,6 +370,14 @@ impl AptosDebugger {
         Ok(ret)
     }
 
+    pub async fn get_committed_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        self.debugger.get_committed_transactions(begin, limit).await
+    }
+
     pub async fn get_version_by_account_sequence(
         &self,
         account: AccountAddress,
@@ -348,6 +405,61 @@ impl AptosDebugger {
     pub fn state_view_at_version(&self, version: Version) -> DebuggerStateView {
         DebuggerStateView::new(self.debugger.clone(), version)
     }
+
+    /// Forks the chain after the transaction at `fork_version`, with the given state values
+    /// overriding the state of the chain.
+    pub fn fork_at_version_with_overrides(
+        &self,
+        fork_version: Version,
+        overrides: HashMap<StateKey, Option<StateValue>>,
+    ) -> ForkedStateView {
+        ForkedStateView::new_with_overrides(self.debugger.clone(), fork_version, overrides)
+    }
+
+    /// Executes the transactions (block by block) on top of the fork. The write set of every
+    /// transaction is applied to the fork, so later transactions observe the new state (and
+    /// not the state committed on chain).
+    pub fn execute_transactions_on_fork(
+        &self,
+        forked_view: &ForkedStateView,
+        txns: Vec<Transaction>,
+        concurrency_level: usize,
+    ) -> Result<Vec<TransactionOutput>> {
+        let mut ret = vec![];
+        let mut cur: Vec<SignatureVerifiedTransaction> = vec![];
+        for txn in txns {
+            if txn.is_block_start() && !cur.is_empty() {
+                let to_execute = std::mem::take(&mut cur);
+                ret.extend(Self::execute_block_on_fork(
+                    forked_view,
+                    &to_execute,
+                    concurrency_level,
+                )?);
+            }
+            cur.push(txn.into());
+        }
+        if !cur.is_empty() {
+            ret.extend(Self::execute_block_on_fork(
+                forked_view,
+                &cur,
+                concurrency_level,
+            )?);
+        }
+        Ok(ret)
+    }
+
+    fn execute_block_on_fork(
+        forked_view: &ForkedStateView,
+        sig_verified_txns: &[SignatureVerifiedTransaction],
+        concurrency_level: usize,
+    ) -> Result<Vec<TransactionOutput>> {
+        let outputs = execute_block_no_limit(sig_verified_txns, forked_view, concurrency_level)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+        for output in &outputs {
+            forked_view.apply_write_set(output.write_set());
+        }
+        Ok(outputs)
+    }
 }
 
 fn print_transaction_stats(sig_verified_txns: &[SignatureVerifiedTransaction], version: u64) {
@@ -419,9 +531,9 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
         .any(|event| event.event_key() == Some(&new_epoch_event_key))
 }
 
-fn execute_block_no_limit(
+pub(crate) fn execute_block_no_limit<S: StateView + Sync>(
     sig_verified_txns: &[SignatureVerifiedTransaction],
-    state_view: &DebuggerStateView,
+    state_view: &S,
     concurrency_level: usize,
 ) -> Result<Vec<TransactionOutput>, VMStatus> {
     BlockAptosVM::execute_block::<_, NoOpTransactionCommitHook<AptosTransactionOutput, VMStatus>>(
@@ -432,6 +544,8 @@ fn execute_block_no_limit(
                 concurrency_level,
                 allow_fallback: true,
                 discard_failed_blocks: false,
+                conflict_analytics_dir: None,
+                adaptive_concurrency: None,
             },
             onchain: BlockExecutorConfigFromOnchain::new_no_block_limit(),
         },
@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aptos_debugger::{execute_block_no_limit, AptosDebugger},
+    common::Opts,
+};
+use anyhow::{ensure, format_err, Context, Result};
+use aptos_consensus::transaction_shuffler::{create_transaction_shuffler, TransactionShuffler};
+use aptos_rest_client::Client;
+use aptos_types::{
+    on_chain_config::TransactionShufflerType,
+    transaction::{
+        signature_verified_transaction::SignatureVerifiedTransaction, Transaction, Version,
+    },
+};
+use clap::Parser;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use url::Url;
+
+/// The concurrency level used as the (sequential) baseline for the speedups
+const SEQUENTIAL_CONCURRENCY_LEVEL: usize = 1;
+
+#[derive(Parser)]
+#[clap(
+    about = "Replay committed blocks through different transaction shufflers, and measure \
+    the speedup the block executor achieves (over sequential execution) for each ordering."
+)]
+pub struct Command {
+    #[clap(flatten)]
+    opts: Opts,
+
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long)]
+    limit: u64,
+
+    /// The shufflers to benchmark, each a YAML file containing a `TransactionShufflerType`
+    /// (e.g., `conflict_aware: { conflict_window_size: 32 }`). The original (committed)
+    /// order is always benchmarked as well, as the baseline.
+    #[clap(long, value_parser, num_args = 0..)]
+    shuffler_config_files: Vec<PathBuf>,
+
+    #[clap(long)]
+    repeat_execution_times: Option<u64>,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
+            AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.opts.target.db_path {
+            AptosDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+
+        // Collect the shufflers to benchmark, starting with the original order
+        let mut shufflers = vec![(
+            "original".to_string(),
+            create_transaction_shuffler(TransactionShufflerType::NoShuffling),
+        )];
+        for config_file in &self.shuffler_config_files {
+            let contents = fs::read_to_string(config_file)
+                .with_context(|| format!("Failed to read the config file {:?}", config_file))?;
+            let shuffler_type: TransactionShufflerType = serde_yaml::from_str(&contents)
+                .with_context(|| format!("Failed to parse the config file {:?}", config_file))?;
+            shufflers.push((
+                config_file.display().to_string(),
+                create_transaction_shuffler(shuffler_type),
+            ));
+        }
+
+        let mut concurrency_levels = self.opts.concurrency_level.clone();
+        concurrency_levels.push(SEQUENTIAL_CONCURRENCY_LEVEL);
+        concurrency_levels.sort_unstable();
+        concurrency_levels.dedup();
+
+        let (txns, _) = debugger
+            .get_committed_transactions(self.begin_version, self.limit)
+            .await?;
+        let blocks = split_into_blocks(self.begin_version, txns);
+        ensure!(!blocks.is_empty(), "No transactions found to benchmark!");
+        println!(
+            "Benchmarking {} shufflers over {} blocks (concurrency levels: {:?})",
+            shufflers.len(),
+            blocks.len(),
+            concurrency_levels
+        );
+
+        for (name, shuffler) in &shufflers {
+            let result = benchmark_shuffler(
+                &debugger,
+                &blocks,
+                shuffler,
+                &concurrency_levels,
+                self.repeat_execution_times.unwrap_or(1),
+            )?;
+            let sequential_time = result.execution_times[&SEQUENTIAL_CONCURRENCY_LEVEL];
+            for (concurrency_level, execution_time) in &result.execution_times {
+                println!(
+                    "{}: concurrency_level={}, {} txns in {}ms ({:.0} tps), speedup: {:.2}x",
+                    name,
+                    concurrency_level,
+                    result.num_txns,
+                    execution_time.as_millis(),
+                    result.num_txns as f64 / execution_time.as_secs_f64(),
+                    sequential_time.as_secs_f64() / execution_time.as_secs_f64(),
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A committed block, along with the version of its first transaction
+struct Block {
+    begin_version: Version,
+    txns: Vec<Transaction>,
+}
+
+/// The result of benchmarking a single shuffler over all blocks
+struct ShufflerBenchmarkResult {
+    num_txns: usize,
+    // The total (fastest of the repeats) execution time for each concurrency level
+    execution_times: BTreeMap<usize, Duration>,
+}
+
+/// Splits the transactions into blocks (at the block start transactions)
+fn split_into_blocks(begin_version: Version, txns: Vec<Transaction>) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut cur = vec![];
+    let mut cur_version = begin_version;
+    for txn in txns {
+        if txn.is_block_start() && !cur.is_empty() {
+            let block_txns = std::mem::take(&mut cur);
+            let num_txns = block_txns.len() as u64;
+            blocks.push(Block {
+                begin_version: cur_version,
+                txns: block_txns,
+            });
+            cur_version += num_txns;
+        }
+        cur.push(txn);
+    }
+    if !cur.is_empty() {
+        blocks.push(Block {
+            begin_version: cur_version,
+            txns: cur,
+        });
+    }
+    blocks
+}
+
+/// Shuffles the user transactions of the block. The system transactions before the first user
+/// transaction (e.g., block metadata and validator transactions) stay at the start of the block,
+/// and all other system transactions (e.g., state checkpoints) are moved to the end of the block.
+fn shuffle_block(
+    txns: &[Transaction],
+    shuffler: &Arc<dyn TransactionShuffler>,
+) -> Vec<Transaction> {
+    let num_leading_txns = txns
+        .iter()
+        .position(|txn| matches!(txn, Transaction::UserTransaction(_)))
+        .unwrap_or(txns.len());
+    let (leading_txns, remaining_txns) = txns.split_at(num_leading_txns);
+
+    let mut user_txns = vec![];
+    let mut trailing_txns = vec![];
+    for txn in remaining_txns {
+        match txn {
+            Transaction::UserTransaction(user_txn) => user_txns.push(user_txn.clone()),
+            _ => trailing_txns.push(txn.clone()),
+        }
+    }
+
+    leading_txns
+        .iter()
+        .cloned()
+        .chain(
+            shuffler
+                .shuffle(user_txns)
+                .into_iter()
+                .map(Transaction::UserTransaction),
+        )
+        .chain(trailing_txns)
+        .collect()
+}
+
+fn benchmark_shuffler(
+    debugger: &AptosDebugger,
+    blocks: &[Block],
+    shuffler: &Arc<dyn TransactionShuffler>,
+    concurrency_levels: &[usize],
+    repeat_execution_times: u64,
+) -> Result<ShufflerBenchmarkResult> {
+    let mut result = ShufflerBenchmarkResult {
+        num_txns: 0,
+        execution_times: BTreeMap::new(),
+    };
+    for block in blocks {
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
+            shuffle_block(&block.txns, shuffler)
+                .into_iter()
+                .map(|txn| txn.into())
+                .collect();
+        let state_view = debugger.state_view_at_version(block.begin_version);
+        result.num_txns += sig_verified_txns.len();
+
+        // Warm up the state view and the VM caches with an untimed run, so that the first
+        // timed run (e.g., of the original order, sequentially) isn't slowed down by them
+        execute_block_no_limit(
+            &sig_verified_txns,
+            &state_view,
+            SEQUENTIAL_CONCURRENCY_LEVEL,
+        )
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        for concurrency_level in concurrency_levels {
+            let mut fastest_time = Duration::MAX;
+            for _ in 0..repeat_execution_times.max(1) {
+                let start_time = Instant::now();
+                execute_block_no_limit(&sig_verified_txns, &state_view, *concurrency_level)
+                    .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+                fastest_time = fastest_time.min(start_time.elapsed());
+            }
+            *result
+                .execution_times
+                .entry(*concurrency_level)
+                .or_default() += fastest_time;
+        }
+    }
+    Ok(result)
+}
@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{execute_past_transactions, execute_pending_block};
+use crate::{
+    benchmark_transaction_shufflers, execute_past_transactions, execute_pending_block,
+    gas_schedule_impact, replay_with_overrides,
+};
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
@@ -33,15 +36,21 @@ pub struct Opts {
 
 #[derive(Parser)]
 pub enum Command {
+    AnalyzeGasScheduleImpact(gas_schedule_impact::Command),
+    BenchmarkTransactionShufflers(benchmark_transaction_shufflers::Command),
     ExecutePastTransactions(execute_past_transactions::Command),
     ExecutePendingBlock(execute_pending_block::Command),
+    ReplayWithOverrides(replay_with_overrides::Command),
 }
 
 impl Command {
     pub async fn run(self) -> Result<()> {
         match self {
+            Command::AnalyzeGasScheduleImpact(cmd) => cmd.run().await,
+            Command::BenchmarkTransactionShufflers(cmd) => cmd.run().await,
             Command::ExecutePastTransactions(cmd) => cmd.run().await,
             Command::ExecutePendingBlock(cmd) => cmd.run().await,
+            Command::ReplayWithOverrides(cmd) => cmd.run().await,
         }
     }
 }
@@ -0,0 +1,169 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    aptos_debugger::AptosDebugger,
+    common::Opts,
+    replay_with_overrides::{execute_with_overrides, StateOverrides},
+};
+use anyhow::{ensure, Context, Result};
+use aptos_gas_profiling::{FrameName, GasScheduleImpact};
+use aptos_gas_schedule::LATEST_GAS_FEATURE_VERSION;
+use aptos_gas_schedule_updator::current_gas_schedule;
+use aptos_rest_client::Client;
+use aptos_types::{
+    on_chain_config::GasScheduleV2,
+    transaction::{MultisigTransactionPayload, Transaction, TransactionPayload},
+};
+use clap::Parser;
+use std::{fs, path::PathBuf};
+use url::Url;
+
+#[derive(Parser)]
+#[clap(
+    about = "Replay committed transactions under the current and a proposed gas schedule, and \
+    report the change of the gas used per entry function and module, as well as the transactions \
+    that would newly run out of gas."
+)]
+pub struct Command {
+    #[clap(flatten)]
+    opts: Opts,
+
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long)]
+    limit: u64,
+
+    /// A JSON file containing the proposed `GasScheduleV2`. If not set, the gas schedule of this
+    /// build is proposed, i.e., the one `aptos-gas-schedule-updator` generates a proposal for.
+    #[clap(long, value_parser)]
+    gas_schedule_file: Option<PathBuf>,
+
+    /// The feature version of the gas schedule of this build to propose, if no file is given.
+    #[clap(long, conflicts_with = "gas_schedule_file")]
+    gas_feature_version: Option<u64>,
+
+    /// The directory to write the report to.
+    #[clap(long, value_parser, default_value = "gas-schedule-impact")]
+    output_dir: PathBuf,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
+            AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.opts.target.db_path {
+            AptosDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+        ensure!(
+            self.begin_version > 0,
+            "Can't replay the genesis transaction!"
+        );
+
+        let gas_schedule = match &self.gas_schedule_file {
+            Some(path) => {
+                let contents = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read the gas schedule {:?}", path))?;
+                serde_json::from_str::<GasScheduleV2>(&contents)
+                    .with_context(|| format!("Failed to parse the gas schedule {:?}", path))?
+            },
+            None => current_gas_schedule(
+                self.gas_feature_version
+                    .unwrap_or(LATEST_GAS_FEATURE_VERSION),
+            ),
+        };
+        let state_overrides = StateOverrides {
+            replace_gas_schedule: Some(gas_schedule),
+            ..StateOverrides::default()
+        };
+
+        let (txns, _) = debugger
+            .get_committed_transactions(self.begin_version, self.limit)
+            .await?;
+
+        // Every transaction is executed on its own original pre-state, as otherwise the changes
+        // of the outputs of previous transactions (e.g., a transaction that newly runs out of
+        // gas) would be attributed to the gas schedule.
+        let (current_outputs, proposed_outputs) = execute_with_overrides(
+            &debugger,
+            self.begin_version,
+            txns.clone(),
+            &state_overrides,
+            1,
+            true,
+        )?;
+
+        let mut impact = GasScheduleImpact::new();
+        for (idx, (txn, (current, proposed))) in txns
+            .iter()
+            .zip(current_outputs.iter().zip(&proposed_outputs))
+            .enumerate()
+        {
+            if let Some(entry_point) = entry_point(txn) {
+                impact.add_transaction(
+                    self.begin_version + idx as u64,
+                    &entry_point,
+                    current,
+                    proposed,
+                );
+            }
+        }
+
+        let report_path = self.output_dir;
+        impact.generate_html_report(
+            &report_path,
+            format!(
+                "Gas Schedule Impact - Transactions {} to {}",
+                self.begin_version,
+                self.begin_version + txns.len().saturating_sub(1) as u64
+            ),
+        )?;
+        fs::write(
+            report_path.join("impact.json"),
+            serde_json::to_string_pretty(&impact)?,
+        )?;
+
+        let total = &impact.total;
+        println!(
+            "{} user transactions: {} -> {} gas units ({:+})",
+            total.num_txns,
+            total.current,
+            total.proposed,
+            total.delta()
+        );
+        println!(
+            "{} transactions would newly run out of gas, {} would newly fail for other reasons",
+            impact.newly_out_of_gas.len(),
+            impact.newly_failed.len()
+        );
+        println!("Report saved to {}.", report_path.display());
+
+        Ok(())
+    }
+}
+
+/// Returns the entry point of a user transaction, or `None` for other transactions (whose gas
+/// usage doesn't depend on the gas schedule) and multisig transactions without a payload.
+fn entry_point(txn: &Transaction) -> Option<FrameName> {
+    let user_txn = match txn {
+        Transaction::UserTransaction(user_txn) => user_txn,
+        _ => return None,
+    };
+    let entry_func = match user_txn.payload() {
+        TransactionPayload::Script(_) => return Some(FrameName::Script),
+        TransactionPayload::EntryFunction(entry_func) => entry_func,
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_func)) => entry_func,
+            None => return None,
+        },
+        TransactionPayload::ModuleBundle(_) => return None,
+    };
+    Some(FrameName::Function {
+        module_id: entry_func.module().clone(),
+        name: entry_func.function().to_owned(),
+        ty_args: entry_func.ty_args().to_vec(),
+    })
+}
@@ -3,6 +3,10 @@
 
 pub mod aptos_debugger;
 pub mod bcs_txn_decoder;
+pub mod benchmark_transaction_shufflers;
 pub mod common;
 pub mod execute_past_transactions;
 pub mod execute_pending_block;
+pub mod gas_schedule_impact;
+pub mod replay_with_overrides;
+pub mod step_debugger;
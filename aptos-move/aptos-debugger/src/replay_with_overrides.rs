@@ -0,0 +1,820 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{aptos_debugger::AptosDebugger, common::Opts};
+use anyhow::{bail, ensure, format_err, Context, Result};
+use aptos_rest_client::Client;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{
+        fungible_store::{primary_store, FungibleStoreResource},
+        CoinStoreResource, ObjectGroupResource,
+    },
+    contract_event::ContractEvent,
+    on_chain_config::{FeatureFlag, Features, GasScheduleV2, OnChainConfig},
+    state_store::{state_key::StateKey, state_value::StateValue, StateView, TStateView},
+    transaction::{Transaction, TransactionOutput, TransactionStatus, Version},
+    write_set::WriteOp,
+};
+use aptos_vm::move_vm_ext::flush_warm_vm_cache;
+use clap::Parser;
+use move_binary_format::{access::ModuleAccess, CompiledModule};
+use move_core_types::{language_storage::StructTag, move_resource::MoveStructType};
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{Display, Formatter},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+use url::Url;
+
+#[derive(Parser)]
+#[clap(
+    about = "Replay committed transactions with state overrides (e.g., patched modules, modified \
+    resources or balances, a different gas schedule or feature flags), and print the difference \
+    between the original and the new outputs of every transaction."
+)]
+pub struct Command {
+    #[clap(flatten)]
+    opts: Opts,
+
+    #[clap(long)]
+    begin_version: u64,
+
+    #[clap(long)]
+    limit: u64,
+
+    /// A YAML file containing the `StateOverrides` to apply, e.g.:
+    /// `{ modules: [coin.mv], balances: { "0xcafe": 1000 }, enable_features: [MODULE_EVENT] }`
+    #[clap(long, value_parser)]
+    overrides_file: PathBuf,
+
+    /// Execute every transaction on its own original pre-state, instead of on top of the new
+    /// outputs of the previous transactions.
+    #[clap(long)]
+    isolated: bool,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
+            AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
+        } else if let Some(db_path) = self.opts.target.db_path {
+            AptosDebugger::db(db_path)?
+        } else {
+            unreachable!("Must provide one target.");
+        };
+        ensure!(
+            self.begin_version > 0,
+            "Can't replay the genesis transaction!"
+        );
+
+        let contents = fs::read_to_string(&self.overrides_file).with_context(|| {
+            format!(
+                "Failed to read the overrides file {:?}",
+                self.overrides_file
+            )
+        })?;
+        let state_overrides: StateOverrides =
+            serde_yaml::from_str(&contents).with_context(|| {
+                format!(
+                    "Failed to parse the overrides file {:?}",
+                    self.overrides_file
+                )
+            })?;
+
+        let (txns, _) = debugger
+            .get_committed_transactions(self.begin_version, self.limit)
+            .await?;
+        let concurrency_level = self.opts.concurrency_level.first().copied().unwrap_or(1);
+        let (original_outputs, new_outputs) = execute_with_overrides(
+            &debugger,
+            self.begin_version,
+            txns,
+            &state_overrides,
+            concurrency_level,
+            self.isolated,
+        )?;
+
+        let mut num_changed_txns = 0;
+        for (idx, (original, new)) in original_outputs.iter().zip(&new_outputs).enumerate() {
+            let diff = TransactionOutputDiff::new(original, new);
+            if !diff.is_empty() {
+                num_changed_txns += 1;
+                println!("Version {}:\n{}", self.begin_version + idx as u64, diff);
+            }
+        }
+        println!(
+            "{} of {} transactions have a different output with the overrides",
+            num_changed_txns,
+            original_outputs.len()
+        );
+
+        Ok(())
+    }
+}
+
+/// The state overrides to replay the transactions with. All fields are optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StateOverrides {
+    /// Compiled modules (`.mv` files), replacing the published modules with the same ids
+    pub modules: Vec<PathBuf>,
+    /// Resources to replace (or delete)
+    pub resources: Vec<ResourceOverride>,
+    /// The APT balance (in octas) of the given accounts (which must have a `CoinStore` or a
+    /// primary fungible store). If an account has both, the whole balance is put in the
+    /// `CoinStore`, and the fungible store is emptied.
+    pub balances: BTreeMap<AccountAddress, u64>,
+    /// A gas schedule replacing the on-chain one
+    pub replace_gas_schedule: Option<GasScheduleV2>,
+    /// Entries of the (on-chain or replaced) gas schedule to modify (e.g.,
+    /// `txn.max_execution_gas: 100`)
+    pub gas_schedule: BTreeMap<String, u64>,
+    /// The names of the feature flags to enable (e.g., `MODULE_EVENT`)
+    pub enable_features: Vec<String>,
+    /// The names of the feature flags to disable
+    pub disable_features: Vec<String>,
+}
+
+/// A resource to replace with the given (hex encoded) BCS bytes, or to delete if no bytes are given
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceOverride {
+    pub address: AccountAddress,
+    /// The type of the resource (e.g., `0x1::account::Account`)
+    pub struct_tag: String,
+    /// The resource group the resource is a member of, if any (e.g., `0x1::object::ObjectGroup`)
+    pub resource_group: Option<String>,
+    pub bcs_hex: Option<String>,
+}
+
+impl StateOverrides {
+    /// Returns the overridden state values. Overrides that modify existing values (i.e., the
+    /// balances, the gas schedule and the features) are applied on top of the given state.
+    /// Overridden values keep the metadata (i.e., the deposits) of the values they replace.
+    pub fn to_state_values(
+        &self,
+        state_view: &impl StateView,
+    ) -> Result<HashMap<StateKey, Option<StateValue>>> {
+        let mut overrides = HashMap::new();
+
+        for module_path in &self.modules {
+            let bytes = fs::read(module_path)
+                .with_context(|| format!("Failed to read the module {:?}", module_path))?;
+            let module = CompiledModule::deserialize(&bytes)
+                .map_err(|err| format_err!("Failed to deserialize {:?}: {:?}", module_path, err))?;
+            let state_key = StateKey::module_id(&module.self_id());
+            let state_value = override_state_value(&overrides, state_view, &state_key, bytes)?;
+            overrides.insert(state_key, Some(state_value));
+        }
+
+        for resource in &self.resources {
+            let struct_tag = StructTag::from_str(&resource.struct_tag)?;
+            let bytes = match &resource.bcs_hex {
+                Some(bcs_hex) => Some(hex::decode(bcs_hex.trim_start_matches("0x"))?),
+                None => None,
+            };
+            match &resource.resource_group {
+                Some(resource_group) => {
+                    let group_tag = StructTag::from_str(resource_group)?;
+                    update_resource_group(
+                        &mut overrides,
+                        state_view,
+                        &resource.address,
+                        &group_tag,
+                        |group| {
+                            match bytes {
+                                Some(bytes) => group.insert(struct_tag, bytes),
+                                None => group.remove(&struct_tag),
+                            };
+                            Ok(())
+                        },
+                    )?;
+                },
+                None => {
+                    let state_key = StateKey::resource(&resource.address, &struct_tag)?;
+                    let state_value = match bytes {
+                        Some(bytes) => Some(override_state_value(
+                            &overrides, state_view, &state_key, bytes,
+                        )?),
+                        None => None,
+                    };
+                    overrides.insert(state_key, state_value);
+                },
+            }
+        }
+
+        for (address, balance) in &self.balances {
+            let state_key = StateKey::resource_typed::<CoinStoreResource>(address)?;
+            let coin_store = match get_state_value(&overrides, state_view, &state_key)? {
+                Some(state_value) => {
+                    Some(bcs::from_bytes::<CoinStoreResource>(state_value.bytes())?)
+                },
+                None => None,
+            };
+            let has_coin_store = coin_store.is_some();
+            if let Some(coin_store) = coin_store {
+                let coin_store = CoinStoreResource::new(
+                    *balance,
+                    coin_store.frozen(),
+                    coin_store.deposit_events().clone(),
+                    coin_store.withdraw_events().clone(),
+                );
+                let state_value = override_state_value(
+                    &overrides,
+                    state_view,
+                    &state_key,
+                    bcs::to_bytes(&coin_store)?,
+                )?;
+                overrides.insert(state_key, Some(state_value));
+            }
+
+            // The fungible store holds the balance that isn't in the CoinStore
+            let mut has_fungible_store = false;
+            update_resource_group(
+                &mut overrides,
+                state_view,
+                &primary_store(address),
+                &ObjectGroupResource::struct_tag(),
+                |group| {
+                    let has_concurrent_balance = group
+                        .keys()
+                        .any(|struct_tag| struct_tag.name.as_str() == "ConcurrentFungibleBalance");
+                    ensure!(
+                        !has_concurrent_balance,
+                        "The primary fungible store of {} has a concurrent balance, which \
+                        isn't supported",
+                        address
+                    );
+                    if let Some(bytes) = group.get_mut(&FungibleStoreResource::struct_tag()) {
+                        let store: FungibleStoreResource = bcs::from_bytes(bytes)?;
+                        let store = FungibleStoreResource::new(
+                            store.metadata(),
+                            if has_coin_store { 0 } else { *balance },
+                            store.frozen(),
+                        );
+                        *bytes = bcs::to_bytes(&store)?;
+                        has_fungible_store = true;
+                    }
+                    Ok(())
+                },
+            )?;
+            ensure!(
+                has_coin_store || has_fungible_store,
+                "The account {} has neither a CoinStore nor a primary fungible store",
+                address
+            );
+        }
+
+        if self.replace_gas_schedule.is_some() || !self.gas_schedule.is_empty() {
+            let mut gas_schedule = match &self.replace_gas_schedule {
+                Some(gas_schedule) => gas_schedule.clone(),
+                None => GasScheduleV2::fetch_config(state_view)
+                    .ok_or_else(|| format_err!("Failed to fetch the gas schedule"))?,
+            };
+            for (name, value) in &self.gas_schedule {
+                match gas_schedule.entries.iter_mut().find(|(key, _)| key == name) {
+                    Some((_, entry)) => *entry = *value,
+                    None => bail!("Unknown gas schedule entry: {}", name),
+                }
+            }
+            let state_key = StateKey::on_chain_config::<GasScheduleV2>()?;
+            let state_value = override_state_value(
+                &overrides,
+                state_view,
+                &state_key,
+                bcs::to_bytes(&gas_schedule)?,
+            )?;
+            overrides.insert(state_key, Some(state_value));
+        }
+
+        if !self.enable_features.is_empty() || !self.disable_features.is_empty() {
+            let mut features = Features::fetch_config(state_view)
+                .ok_or_else(|| format_err!("Failed to fetch the features"))?;
+            for name in &self.enable_features {
+                features.enable(parse_feature_flag(name)?);
+            }
+            for name in &self.disable_features {
+                features.disable(parse_feature_flag(name)?);
+            }
+            let state_key = StateKey::on_chain_config::<Features>()?;
+            let state_value = override_state_value(
+                &overrides,
+                state_view,
+                &state_key,
+                bcs::to_bytes(&features)?,
+            )?;
+            overrides.insert(state_key, Some(state_value));
+        }
+
+        Ok(overrides)
+    }
+}
+
+/// Executes the committed transactions starting at `begin_version` without and with the state
+/// overrides, and returns the (original, new) outputs.
+///
+/// By default, the transactions are executed (block by block) on top of the state before
+/// `begin_version`, so a transaction observes the outputs of the previous ones. If `isolated`,
+/// every transaction is executed on its own original pre-state instead (with the overrides
+/// applied on top of it), so its new output doesn't depend on how the overrides changed the
+/// outputs of the previous transactions.
+pub fn execute_with_overrides(
+    debugger: &AptosDebugger,
+    begin_version: Version,
+    txns: Vec<Transaction>,
+    state_overrides: &StateOverrides,
+    concurrency_level: usize,
+    isolated: bool,
+) -> Result<(Vec<TransactionOutput>, Vec<TransactionOutput>)> {
+    if !isolated {
+        return execute_block_with_overrides(
+            debugger,
+            begin_version,
+            txns,
+            state_overrides,
+            concurrency_level,
+        );
+    }
+
+    let mut original_outputs = vec![];
+    let mut new_outputs = vec![];
+    for (idx, txn) in txns.into_iter().enumerate() {
+        let (original, new) = execute_block_with_overrides(
+            debugger,
+            begin_version + idx as u64,
+            vec![txn],
+            state_overrides,
+            1,
+        )?;
+        original_outputs.extend(original);
+        new_outputs.extend(new);
+    }
+    Ok((original_outputs, new_outputs))
+}
+
+fn execute_block_with_overrides(
+    debugger: &AptosDebugger,
+    begin_version: Version,
+    txns: Vec<Transaction>,
+    state_overrides: &StateOverrides,
+    concurrency_level: usize,
+) -> Result<(Vec<TransactionOutput>, Vec<TransactionOutput>)> {
+    ensure!(begin_version > 0, "Can't replay the genesis transaction!");
+    let overrides =
+        state_overrides.to_state_values(&debugger.state_view_at_version(begin_version))?;
+
+    // Both executions start from the same fork, so that the outputs only differ because
+    // of the overrides. The warm VM is flushed, as it may have cached overridden modules.
+    let fork_version = begin_version - 1;
+    flush_warm_vm_cache();
+    let original_outputs = debugger.execute_transactions_on_fork(
+        &debugger.fork_at_version_with_overrides(fork_version, HashMap::new()),
+        txns.clone(),
+        concurrency_level,
+    )?;
+    flush_warm_vm_cache();
+    let new_outputs = debugger.execute_transactions_on_fork(
+        &debugger.fork_at_version_with_overrides(fork_version, overrides),
+        txns,
+        concurrency_level,
+    )?;
+    Ok((original_outputs, new_outputs))
+}
+
+/// Returns the value of the given state key, taking the previous overrides into account
+fn get_state_value(
+    overrides: &HashMap<StateKey, Option<StateValue>>,
+    state_view: &impl StateView,
+    state_key: &StateKey,
+) -> Result<Option<StateValue>> {
+    Ok(match overrides.get(state_key) {
+        Some(state_value) => state_value.clone(),
+        None => state_view.get_state_value(state_key)?,
+    })
+}
+
+/// Returns a state value with the given bytes, which keeps the metadata of the value it
+/// replaces (if any), so that the overrides don't change the deposits of the existing state
+fn override_state_value(
+    overrides: &HashMap<StateKey, Option<StateValue>>,
+    state_view: &impl StateView,
+    state_key: &StateKey,
+    bytes: Vec<u8>,
+) -> Result<StateValue> {
+    Ok(match get_state_value(overrides, state_view, state_key)? {
+        Some(state_value) => state_value.map_bytes(|_| Ok(bytes.into()))?,
+        None => StateValue::new_legacy(bytes.into()),
+    })
+}
+
+/// Applies the given update to the members of a resource group (which is deleted if it ends up
+/// empty). Nothing is overridden if the update doesn't modify the group.
+fn update_resource_group(
+    overrides: &mut HashMap<StateKey, Option<StateValue>>,
+    state_view: &impl StateView,
+    address: &AccountAddress,
+    group_tag: &StructTag,
+    update: impl FnOnce(&mut BTreeMap<StructTag, Vec<u8>>) -> Result<()>,
+) -> Result<()> {
+    let state_key = StateKey::resource_group(address, group_tag);
+    let original_group: BTreeMap<StructTag, Vec<u8>> =
+        match get_state_value(overrides, state_view, &state_key)? {
+            Some(state_value) => bcs::from_bytes(state_value.bytes())?,
+            None => BTreeMap::new(),
+        };
+    let mut group = original_group.clone();
+    update(&mut group)?;
+    if group != original_group {
+        let state_value = if group.is_empty() {
+            None
+        } else {
+            Some(override_state_value(
+                overrides,
+                state_view,
+                &state_key,
+                bcs::to_bytes(&group)?,
+            )?)
+        };
+        overrides.insert(state_key, state_value);
+    }
+    Ok(())
+}
+
+fn parse_feature_flag(name: &str) -> Result<FeatureFlag> {
+    FeatureFlag::from_str(name).map_err(|_| format_err!("Unknown feature flag: {}", name))
+}
+
+/// The difference between the original output of a transaction, and its output with overrides
+#[derive(Debug, Default, PartialEq)]
+pub struct TransactionOutputDiff {
+    /// The (original, new) status, if different
+    pub status: Option<(TransactionStatus, TransactionStatus)>,
+    /// The (original, new) gas used, if different
+    pub gas_used: Option<(u64, u64)>,
+    /// The (original, new) writes of the state keys written differently (`None` if the state
+    /// key wasn't written)
+    pub writes: Vec<(StateKey, Option<WriteOp>, Option<WriteOp>)>,
+    /// The events only emitted by the original transaction
+    pub removed_events: Vec<ContractEvent>,
+    /// The events only emitted with the overrides
+    pub added_events: Vec<ContractEvent>,
+}
+
+impl TransactionOutputDiff {
+    pub fn new(original: &TransactionOutput, new: &TransactionOutput) -> Self {
+        let status = (original.status() != new.status())
+            .then(|| (original.status().clone(), new.status().clone()));
+        let gas_used =
+            (original.gas_used() != new.gas_used()).then(|| (original.gas_used(), new.gas_used()));
+
+        let mut writes = vec![];
+        for (state_key, original_write) in original.write_set().iter() {
+            let new_write = new.write_set().get(state_key);
+            if new_write != Some(original_write) {
+                writes.push((
+                    state_key.clone(),
+                    Some(original_write.clone()),
+                    new_write.cloned(),
+                ));
+            }
+        }
+        for (state_key, new_write) in new.write_set().iter() {
+            if original.write_set().get(state_key).is_none() {
+                writes.push((state_key.clone(), None, Some(new_write.clone())));
+            }
+        }
+
+        // Events are matched regardless of their order
+        let mut added_events = new.events().to_vec();
+        let mut removed_events = vec![];
+        for event in original.events() {
+            match added_events.iter().position(|new_event| new_event == event) {
+                Some(idx) => {
+                    added_events.remove(idx);
+                },
+                None => removed_events.push(event.clone()),
+            }
+        }
+
+        Self {
+            status,
+            gas_used,
+            writes,
+            removed_events,
+            added_events,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+impl Display for TransactionOutputDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some((original, new)) = &self.status {
+            writeln!(f, "  status: {:?} -> {:?}", original, new)?;
+        }
+        if let Some((original, new)) = &self.gas_used {
+            writeln!(f, "  gas used: {} -> {}", original, new)?;
+        }
+        for (state_key, original, new) in &self.writes {
+            match (original, new) {
+                (Some(original), Some(new)) => {
+                    writeln!(f, "  ~ {:?}: {:?} -> {:?}", state_key, original, new)?
+                },
+                (Some(original), None) => writeln!(f, "  - {:?}: {:?}", state_key, original)?,
+                (None, Some(new)) => writeln!(f, "  + {:?}: {:?}", state_key, new)?,
+                (None, None) => unreachable!("A state key must be written at least once"),
+            }
+        }
+        for event in &self.removed_events {
+            writeln!(f, "  - event {:?}", event)?;
+        }
+        for event in &self.added_events {
+            writeln!(f, "  + event {:?}", event)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        event::{EventHandle, EventKey},
+        on_chain_config::CurrentTimeMicroseconds,
+        state_store::{in_memory_state_view::InMemoryStateView, state_value::StateValueMetadata},
+        transaction::{ExecutionStatus, TransactionAuxiliaryData},
+        write_set::WriteSetMut,
+    };
+    use move_core_types::language_storage::TypeTag;
+
+    fn create_output(
+        writes: Vec<(StateKey, WriteOp)>,
+        events: Vec<ContractEvent>,
+        gas_used: u64,
+    ) -> TransactionOutput {
+        TransactionOutput::new(
+            WriteSetMut::new(writes).freeze().unwrap(),
+            events,
+            gas_used,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+            TransactionAuxiliaryData::default(),
+        )
+    }
+
+    fn create_write(value: &[u8]) -> WriteOp {
+        WriteOp::legacy_modification(value.to_vec().into())
+    }
+
+    fn create_event(data: &[u8]) -> ContractEvent {
+        ContractEvent::new_v2(TypeTag::Bool, data.to_vec())
+    }
+
+    #[test]
+    fn test_transaction_output_diff() {
+        let (a, b, c) = (
+            StateKey::raw(b"a"),
+            StateKey::raw(b"b"),
+            StateKey::raw(b"c"),
+        );
+        let original = create_output(
+            vec![
+                (a.clone(), create_write(b"1")),
+                (b.clone(), create_write(b"1")),
+            ],
+            vec![create_event(b"x"), create_event(b"y")],
+            10,
+        );
+        assert!(TransactionOutputDiff::new(&original, &original).is_empty());
+
+        let new = create_output(
+            vec![
+                (a.clone(), create_write(b"1")),
+                (c.clone(), create_write(b"2")),
+            ],
+            vec![create_event(b"y"), create_event(b"z")],
+            12,
+        );
+        assert_eq!(TransactionOutputDiff::new(&original, &new), TransactionOutputDiff {
+            status: None,
+            gas_used: Some((10, 12)),
+            writes: vec![
+                (b, Some(create_write(b"1")), None),
+                (c, None, Some(create_write(b"2"))),
+            ],
+            removed_events: vec![create_event(b"x")],
+            added_events: vec![create_event(b"z")],
+        });
+    }
+
+    #[test]
+    fn test_state_overrides() {
+        let account = AccountAddress::random();
+        let event_handle = EventHandle::new(EventKey::new(0, account), 0);
+        let coin_store = CoinStoreResource::new(100, false, event_handle.clone(), event_handle);
+        let gas_schedule = GasScheduleV2 {
+            feature_version: 1,
+            entries: vec![("txn.max_execution_gas".to_string(), 100)],
+        };
+        let state_view = InMemoryStateView::new(HashMap::from([
+            (
+                StateKey::resource_typed::<CoinStoreResource>(&account).unwrap(),
+                StateValue::new_legacy(bcs::to_bytes(&coin_store).unwrap().into()),
+            ),
+            (
+                StateKey::on_chain_config::<GasScheduleV2>().unwrap(),
+                StateValue::new_legacy(bcs::to_bytes(&gas_schedule).unwrap().into()),
+            ),
+            (
+                StateKey::on_chain_config::<Features>().unwrap(),
+                StateValue::new_legacy(bcs::to_bytes(&Features::default()).unwrap().into()),
+            ),
+        ]));
+
+        let state_overrides: StateOverrides = serde_yaml::from_str(&format!(
+            "{{ balances: {{ \"{}\": 5 }}, gas_schedule: {{ txn.max_execution_gas: 200 }}, \
+            disable_features: [MODULE_EVENT] }}",
+            account.to_hex_literal()
+        ))
+        .unwrap();
+        let overridden_view = InMemoryStateView::new(
+            state_overrides
+                .to_state_values(&state_view)
+                .unwrap()
+                .into_iter()
+                .map(|(state_key, state_value)| (state_key, state_value.unwrap()))
+                .collect(),
+        );
+
+        let coin_store: CoinStoreResource = bcs::from_bytes(
+            &overridden_view
+                .get_state_value_bytes(
+                    &StateKey::resource_typed::<CoinStoreResource>(&account).unwrap(),
+                )
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(coin_store.coin(), 5);
+        assert_eq!(
+            GasScheduleV2::fetch_config(&overridden_view)
+                .unwrap()
+                .entries,
+            vec![("txn.max_execution_gas".to_string(), 200)]
+        );
+        assert!(!Features::fetch_config(&overridden_view)
+            .unwrap()
+            .is_module_event_enabled());
+
+        // The gas schedule can be replaced (and modified on top)
+        let state_overrides = StateOverrides {
+            replace_gas_schedule: Some(GasScheduleV2 {
+                feature_version: 2,
+                entries: vec![
+                    ("txn.max_execution_gas".to_string(), 300),
+                    ("txn.max_io_gas".to_string(), 400),
+                ],
+            }),
+            gas_schedule: BTreeMap::from([("txn.max_io_gas".to_string(), 500)]),
+            ..StateOverrides::default()
+        };
+        let overrides = state_overrides.to_state_values(&state_view).unwrap();
+        let gas_schedule: GasScheduleV2 = bcs::from_bytes(
+            overrides[&StateKey::on_chain_config::<GasScheduleV2>().unwrap()]
+                .as_ref()
+                .unwrap()
+                .bytes(),
+        )
+        .unwrap();
+        assert_eq!(gas_schedule, GasScheduleV2 {
+            feature_version: 2,
+            entries: vec![
+                ("txn.max_execution_gas".to_string(), 300),
+                ("txn.max_io_gas".to_string(), 500),
+            ],
+        });
+
+        // Unknown feature flags and gas schedule entries are rejected
+        let state_overrides = StateOverrides {
+            enable_features: vec!["NOT_A_FEATURE".to_string()],
+            ..StateOverrides::default()
+        };
+        assert!(state_overrides.to_state_values(&state_view).is_err());
+        let state_overrides = StateOverrides {
+            gas_schedule: BTreeMap::from([("txn.not_an_entry".to_string(), 1)]),
+            ..StateOverrides::default()
+        };
+        assert!(state_overrides.to_state_values(&state_view).is_err());
+
+        // Accounts without any store are rejected
+        let state_overrides = StateOverrides {
+            balances: BTreeMap::from([(AccountAddress::random(), 5)]),
+            ..StateOverrides::default()
+        };
+        assert!(state_overrides.to_state_values(&state_view).is_err());
+    }
+
+    #[test]
+    fn test_resource_group_overrides() {
+        let account = AccountAddress::random();
+        let group_key =
+            StateKey::resource_group(&primary_store(&account), &ObjectGroupResource::struct_tag());
+        let fungible_store = FungibleStoreResource::new(AccountAddress::TEN, 100, false);
+        let group = BTreeMap::from([(
+            FungibleStoreResource::struct_tag(),
+            bcs::to_bytes(&fungible_store).unwrap(),
+        )]);
+        let state_view = InMemoryStateView::new(HashMap::from([(
+            group_key.clone(),
+            StateValue::new_legacy(bcs::to_bytes(&group).unwrap().into()),
+        )]));
+
+        // Override the balance of the primary fungible store, and add a member to its group
+        let state_overrides = StateOverrides {
+            resources: vec![ResourceOverride {
+                address: primary_store(&account),
+                struct_tag: "0x1::object::Untransferable".to_string(),
+                resource_group: Some("0x1::object::ObjectGroup".to_string()),
+                bcs_hex: Some("0x00".to_string()),
+            }],
+            balances: BTreeMap::from([(account, 5)]),
+            ..StateOverrides::default()
+        };
+        let overrides = state_overrides.to_state_values(&state_view).unwrap();
+        let group: BTreeMap<StructTag, Vec<u8>> =
+            bcs::from_bytes(overrides[&group_key].as_ref().unwrap().bytes()).unwrap();
+        let fungible_store: FungibleStoreResource =
+            bcs::from_bytes(&group[&FungibleStoreResource::struct_tag()]).unwrap();
+        assert_eq!(fungible_store.balance(), 5);
+        assert_eq!(
+            group[&StructTag::from_str("0x1::object::Untransferable").unwrap()],
+            vec![0]
+        );
+
+        // Removing the last member of a group deletes the group
+        let state_overrides = StateOverrides {
+            resources: vec![ResourceOverride {
+                address: primary_store(&account),
+                struct_tag: FungibleStoreResource::struct_tag().to_canonical_string(),
+                resource_group: Some("0x1::object::ObjectGroup".to_string()),
+                bcs_hex: None,
+            }],
+            ..StateOverrides::default()
+        };
+        let overrides = state_overrides.to_state_values(&state_view).unwrap();
+        assert_eq!(overrides[&group_key], None);
+    }
+
+    #[test]
+    fn test_overrides_keep_metadata() {
+        let account = AccountAddress::random();
+        let event_handle = EventHandle::new(EventKey::new(0, account), 0);
+        let coin_store = CoinStoreResource::new(100, false, event_handle.clone(), event_handle);
+        let coin_store_key = StateKey::resource_typed::<CoinStoreResource>(&account).unwrap();
+        let metadata =
+            StateValueMetadata::new(50, 10, &CurrentTimeMicroseconds { microseconds: 1 });
+        let state_view = InMemoryStateView::new(HashMap::from([(
+            coin_store_key.clone(),
+            StateValue::new_with_metadata(
+                bcs::to_bytes(&coin_store).unwrap().into(),
+                metadata.clone(),
+            ),
+        )]));
+
+        // Overriding the balance keeps the deposits of the existing coin store, and a new
+        // resource doesn't have any metadata
+        let state_overrides = StateOverrides {
+            resources: vec![ResourceOverride {
+                address: account,
+                struct_tag: "0x1::account::Account".to_string(),
+                resource_group: None,
+                bcs_hex: Some("0x00".to_string()),
+            }],
+            balances: BTreeMap::from([(account, 5)]),
+            ..StateOverrides::default()
+        };
+        let overrides = state_overrides.to_state_values(&state_view).unwrap();
+        let (coin_store_metadata, coin_store_bytes) =
+            overrides[&coin_store_key].clone().unwrap().unpack();
+        assert_eq!(coin_store_metadata, metadata);
+        let coin_store: CoinStoreResource = bcs::from_bytes(&coin_store_bytes).unwrap();
+        assert_eq!(coin_store.coin(), 5);
+
+        let account_key = StateKey::resource(
+            &account,
+            &StructTag::from_str("0x1::account::Account").unwrap(),
+        )
+        .unwrap();
+        assert!(overrides[&account_key]
+            .clone()
+            .unwrap()
+            .into_metadata()
+            .is_none());
+    }
+}
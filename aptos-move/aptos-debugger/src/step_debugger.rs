@@ -0,0 +1,594 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! An interactive debugger which steps through the execution of a Move transaction instruction
+//! by instruction. It is driven by a line-oriented REPL, see [`StepDebugger`].
+
+use anyhow::{bail, format_err, Result};
+use aptos_types::transaction::{EntryFunction, MultisigTransactionPayload, TransactionPayload};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+};
+use move_vm_runtime::{tracing::set_debug_hook, DebugHook, InstructionState};
+use std::{
+    collections::BTreeSet,
+    fmt,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+/// The function a breakpoint refers to.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BreakpointFunction {
+    Script,
+    Function(ModuleId, Identifier),
+}
+
+/// A breakpoint on the first instruction of a function, or on an instruction at a given offset.
+///
+/// Breakpoints are written as `<address>::<module>::<function>[@<offset>]`, or `script[@<offset>]`
+/// for the main function of a script.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Breakpoint {
+    pub function: BreakpointFunction,
+    pub offset: Option<u16>,
+}
+
+impl Breakpoint {
+    pub fn script() -> Self {
+        Self {
+            function: BreakpointFunction::Script,
+            offset: None,
+        }
+    }
+
+    pub fn function(module_id: ModuleId, function: Identifier) -> Self {
+        Self {
+            function: BreakpointFunction::Function(module_id, function),
+            offset: None,
+        }
+    }
+
+    /// Returns the breakpoint on the code executed by the payload, if there is any.
+    pub fn for_payload(payload: &TransactionPayload) -> Option<Self> {
+        let entry_function = |entry_func: &EntryFunction| {
+            Self::function(
+                entry_func.module().clone(),
+                entry_func.function().to_owned(),
+            )
+        };
+        match payload {
+            TransactionPayload::Script(_) => Some(Self::script()),
+            TransactionPayload::EntryFunction(entry_func) => Some(entry_function(entry_func)),
+            TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+                Some(MultisigTransactionPayload::EntryFunction(entry_func)) => {
+                    Some(entry_function(entry_func))
+                },
+                None => None,
+            },
+            TransactionPayload::ModuleBundle(_) => None,
+        }
+    }
+
+    fn matches(&self, module_id: Option<&ModuleId>, function: &str, pc: u16) -> bool {
+        let function_matches = match (&self.function, module_id) {
+            (BreakpointFunction::Script, None) => true,
+            (BreakpointFunction::Function(bp_module_id, bp_function), Some(module_id)) => {
+                bp_module_id == module_id && bp_function.as_str() == function
+            },
+            _ => false,
+        };
+        function_matches && self.offset.unwrap_or(0) == pc
+    }
+}
+
+impl FromStr for Breakpoint {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (function, offset) = match s.trim().split_once('@') {
+            Some((function, offset)) => {
+                let offset = offset
+                    .parse::<u16>()
+                    .map_err(|err| format_err!("Invalid offset '{}': {}", offset, err))?;
+                (function, Some(offset))
+            },
+            None => (s.trim(), None),
+        };
+
+        let function = if function == "script" {
+            BreakpointFunction::Script
+        } else {
+            let parts = function.split("::").collect::<Vec<_>>();
+            if parts.len() != 3 {
+                bail!(
+                    "Invalid function '{}', expected <address>::<module>::<function>",
+                    function
+                );
+            }
+            let address = AccountAddress::from_hex_literal(parts[0])
+                .map_err(|err| format_err!("Invalid address '{}': {}", parts[0], err))?;
+            BreakpointFunction::Function(
+                ModuleId::new(address, Identifier::new(parts[1])?),
+                Identifier::new(parts[2])?,
+            )
+        };
+        Ok(Self { function, offset })
+    }
+}
+
+impl fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.function {
+            BreakpointFunction::Script => write!(f, "script")?,
+            BreakpointFunction::Function(module_id, function) => {
+                write!(f, "{}::{}", module_id.short_str_lossless(), function)?
+            },
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "@{}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Command {
+    Step,
+    Next,
+    Finish,
+    Continue,
+    Detach,
+    Break(Breakpoint),
+    Delete(Breakpoint),
+    Breakpoints,
+    Locals,
+    Stack,
+    Backtrace,
+    Code,
+    Help,
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (command, arg) = s.split_once(' ').unwrap_or((s, ""));
+        let arg = arg.trim();
+        let breakpoint = || {
+            if arg.is_empty() {
+                bail!("Missing breakpoint, expected <address>::<module>::<function>[@<offset>]")
+            }
+            arg.parse::<Breakpoint>()
+        };
+
+        Ok(match command {
+            "step" | "s" => Command::Step,
+            "next" | "n" => Command::Next,
+            "finish" | "out" | "f" => Command::Finish,
+            "continue" | "c" => Command::Continue,
+            "detach" => Command::Detach,
+            "break" | "b" => Command::Break(breakpoint()?),
+            "delete" | "d" => Command::Delete(breakpoint()?),
+            "breakpoints" => Command::Breakpoints,
+            "locals" | "l" => Command::Locals,
+            "stack" => Command::Stack,
+            "backtrace" | "bt" => Command::Backtrace,
+            "code" => Command::Code,
+            "help" | "h" => Command::Help,
+            _ => bail!("Unrecognized command '{}', type 'help' for help", command),
+        })
+    }
+}
+
+const HELP: &str = "\
+Commands:
+  step, s                  execute the next instruction, stepping into calls
+  next, n                  execute the next instruction, stepping over calls
+  finish, out, f           run until the current function returns
+  continue, c              run until the next breakpoint
+  detach                   run until the end of the transaction without stopping
+  break, b <breakpoint>    add a breakpoint, e.g., 0x1::coin::transfer or 0x1::coin::transfer@4
+  delete, d <breakpoint>   remove a breakpoint
+  breakpoints              list the breakpoints
+  locals, l                print the locals of the current function
+  stack                    print the operand stack
+  backtrace, bt            print the call stack
+  code                     print the code of the current function
+  help, h                  print this help
+An empty line repeats the last command.";
+
+/// How execution continues after the debugger resumes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RunMode {
+    Step,
+    /// Stop once the call depth is at most the given one.
+    StepOver(usize),
+    /// Stop once the call depth is below the given one.
+    StepOut(usize),
+    Continue,
+    Detached,
+}
+
+/// Why the debugger stopped.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum StopReason {
+    Entry,
+    Breakpoint(Breakpoint),
+    Step,
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopReason::Entry => write!(f, "entry"),
+            StopReason::Breakpoint(breakpoint) => write!(f, "breakpoint {}", breakpoint),
+            StopReason::Step => write!(f, "step"),
+        }
+    }
+}
+
+/// A Move debugger reading commands from `input` and writing its output to `output`.
+///
+/// The debugger does not stop before the entry breakpoint (if given) is reached, so that the
+/// prologue of the transaction is skipped. Reaching the end of the input detaches it.
+pub struct StepDebugger {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>,
+    entry: Option<Breakpoint>,
+    breakpoints: BTreeSet<Breakpoint>,
+    mode: RunMode,
+    last_command: Option<Command>,
+}
+
+impl StepDebugger {
+    pub fn new(
+        input: Box<dyn BufRead + Send>,
+        output: Box<dyn Write + Send>,
+        entry: Option<Breakpoint>,
+    ) -> Self {
+        let mode = if entry.is_some() {
+            RunMode::Continue
+        } else {
+            RunMode::Step
+        };
+        Self {
+            input,
+            output,
+            entry,
+            breakpoints: BTreeSet::new(),
+            mode,
+            last_command: None,
+        }
+    }
+
+    /// Creates a debugger using the standard input and output of the process.
+    pub fn stdio(entry: Option<Breakpoint>) -> Self {
+        Self::new(
+            Box::new(io::BufReader::new(io::stdin())),
+            Box::new(io::stdout()),
+            entry,
+        )
+    }
+
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) {
+        self.breakpoints.insert(breakpoint);
+    }
+
+    fn should_stop(
+        &mut self,
+        module_id: Option<&ModuleId>,
+        function: &str,
+        pc: u16,
+        depth: usize,
+    ) -> Option<StopReason> {
+        if self.mode == RunMode::Detached {
+            return None;
+        }
+        if let Some(breakpoint) = self
+            .breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.matches(module_id, function, pc))
+        {
+            self.entry = None;
+            return Some(StopReason::Breakpoint(breakpoint.clone()));
+        }
+        if let Some(entry) = &self.entry {
+            if entry.matches(module_id, function, pc) {
+                self.entry = None;
+                return Some(StopReason::Entry);
+            }
+            return None;
+        }
+        let stop = match self.mode {
+            RunMode::Step => true,
+            RunMode::StepOver(max_depth) => depth <= max_depth,
+            RunMode::StepOut(max_depth) => depth < max_depth,
+            RunMode::Continue | RunMode::Detached => false,
+        };
+        stop.then_some(StopReason::Step)
+    }
+
+    /// Reads and runs commands until one of them resumes execution.
+    fn repl(&mut self, reason: StopReason, state: &InstructionState) -> io::Result<()> {
+        writeln!(
+            self.output,
+            "Stopped at {}@{} ({}): {:?}",
+            state.function_string(),
+            state.pc(),
+            reason,
+            state.instruction()
+        )?;
+        loop {
+            write!(self.output, "> ")?;
+            self.output.flush()?;
+
+            let mut line = String::new();
+            if self.input.read_line(&mut line)? == 0 {
+                writeln!(self.output, "End of input, detaching")?;
+                self.mode = RunMode::Detached;
+                return Ok(());
+            }
+            let command = if line.trim().is_empty() {
+                match self.last_command.clone() {
+                    Some(command) => command,
+                    None => continue,
+                }
+            } else {
+                match line.parse::<Command>() {
+                    Ok(command) => command,
+                    Err(err) => {
+                        writeln!(self.output, "{}", err)?;
+                        continue;
+                    },
+                }
+            };
+            self.last_command = Some(command.clone());
+
+            let depth = state.call_depth();
+            match command {
+                Command::Step => self.mode = RunMode::Step,
+                Command::Next => self.mode = RunMode::StepOver(depth),
+                Command::Finish => self.mode = RunMode::StepOut(depth),
+                Command::Continue => self.mode = RunMode::Continue,
+                Command::Detach => self.mode = RunMode::Detached,
+                Command::Break(breakpoint) => {
+                    writeln!(self.output, "Added breakpoint {}", breakpoint)?;
+                    self.breakpoints.insert(breakpoint);
+                    continue;
+                },
+                Command::Delete(breakpoint) => {
+                    if self.breakpoints.remove(&breakpoint) {
+                        writeln!(self.output, "Deleted breakpoint {}", breakpoint)?;
+                    } else {
+                        writeln!(self.output, "No breakpoint {}", breakpoint)?;
+                    }
+                    continue;
+                },
+                Command::Breakpoints => {
+                    for (idx, breakpoint) in self.breakpoints.iter().enumerate() {
+                        writeln!(self.output, "[{}] {}", idx, breakpoint)?;
+                    }
+                    continue;
+                },
+                Command::Locals => {
+                    match state.locals() {
+                        Ok(locals) if locals.is_empty() => writeln!(self.output, "(none)")?,
+                        Ok(locals) => {
+                            for (idx, local) in locals.iter().enumerate() {
+                                writeln!(self.output, "[{}] {}", idx, local)?;
+                            }
+                        },
+                        Err(err) => writeln!(self.output, "Failed to print locals: {}", err)?,
+                    }
+                    continue;
+                },
+                Command::Stack => {
+                    match state.operand_stack() {
+                        Ok(values) if values.is_empty() => writeln!(self.output, "(empty)")?,
+                        Ok(values) => {
+                            for (idx, value) in values.iter().enumerate() {
+                                writeln!(self.output, "[{}] {}", idx, value)?;
+                            }
+                        },
+                        Err(err) => {
+                            writeln!(self.output, "Failed to print operand stack: {}", err)?
+                        },
+                    }
+                    continue;
+                },
+                Command::Backtrace => {
+                    writeln!(self.output, "#0 {}@{}", state.function_string(), state.pc())?;
+                    for (idx, (function, pc)) in state.callers().iter().rev().enumerate() {
+                        writeln!(self.output, "#{} {}@{}", idx + 1, function, pc)?;
+                    }
+                    continue;
+                },
+                Command::Code => {
+                    for (idx, instr) in state.code().iter().enumerate() {
+                        let marker = if idx == state.pc() as usize { ">" } else { " " };
+                        writeln!(self.output, "{} [{}] {:?}", marker, idx, instr)?;
+                    }
+                    continue;
+                },
+                Command::Help => {
+                    writeln!(self.output, "{}", HELP)?;
+                    continue;
+                },
+            }
+            return Ok(());
+        }
+    }
+}
+
+impl DebugHook for StepDebugger {
+    fn on_instruction(&mut self, state: &InstructionState) {
+        let reason = match self.should_stop(
+            state.module_id(),
+            state.function_name(),
+            state.pc(),
+            state.call_depth(),
+        ) {
+            Some(reason) => reason,
+            None => return,
+        };
+        if self.repl(reason, state).is_err() {
+            // Without a working terminal there is no one to drive the debugger.
+            self.mode = RunMode::Detached;
+        }
+    }
+}
+
+/// Runs `f` with `debugger` attached to the Move VM. Note that the debugger is attached to all
+/// VMs of the process, so `f` should execute a single transaction sequentially.
+pub fn with_step_debugger<T>(debugger: StepDebugger, f: impl FnOnce() -> T) -> T {
+    struct DetachOnDrop;
+
+    impl Drop for DetachOnDrop {
+        fn drop(&mut self) {
+            set_debug_hook(None);
+        }
+    }
+
+    set_debug_hook(Some(Box::new(debugger)));
+    let _guard = DetachOnDrop;
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn module_id(s: &str) -> ModuleId {
+        let (address, name) = s.split_once("::").unwrap();
+        ModuleId::new(
+            AccountAddress::from_hex_literal(address).unwrap(),
+            Identifier::new(name).unwrap(),
+        )
+    }
+
+    fn debugger(entry: Option<Breakpoint>) -> StepDebugger {
+        StepDebugger::new(Box::new(io::empty()), Box::new(io::sink()), entry)
+    }
+
+    #[test]
+    fn test_parse_breakpoint() {
+        let breakpoint = "0x1::coin::transfer@4".parse::<Breakpoint>().unwrap();
+        assert_eq!(breakpoint, Breakpoint {
+            function: BreakpointFunction::Function(
+                module_id("0x1::coin"),
+                Identifier::new("transfer").unwrap()
+            ),
+            offset: Some(4),
+        });
+        assert_eq!(breakpoint.to_string(), "0x1::coin::transfer@4");
+
+        assert_eq!(
+            "script".parse::<Breakpoint>().unwrap(),
+            Breakpoint::script()
+        );
+        assert!("0x1::coin".parse::<Breakpoint>().is_err());
+        assert!("0x1::coin::transfer@x".parse::<Breakpoint>().is_err());
+        assert!("zz::coin::transfer".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!("s".parse::<Command>().unwrap(), Command::Step);
+        assert_eq!(" next ".parse::<Command>().unwrap(), Command::Next);
+        assert_eq!(
+            "b 0x1::coin::transfer".parse::<Command>().unwrap(),
+            Command::Break(Breakpoint::function(
+                module_id("0x1::coin"),
+                Identifier::new("transfer").unwrap()
+            ))
+        );
+        assert!("break".parse::<Command>().is_err());
+        assert!("jump".parse::<Command>().is_err());
+    }
+
+    #[test]
+    fn test_skip_until_entry() {
+        let coin = module_id("0x1::coin");
+        let entry = Breakpoint::function(coin.clone(), Identifier::new("transfer").unwrap());
+        let mut debugger = debugger(Some(entry));
+
+        // The prologue is skipped
+        let validation = module_id("0x1::transaction_validation");
+        assert_eq!(
+            debugger.should_stop(Some(&validation), "prologue", 0, 0),
+            None
+        );
+        assert_eq!(
+            debugger.should_stop(Some(&validation), "prologue", 1, 0),
+            None
+        );
+
+        assert_eq!(
+            debugger.should_stop(Some(&coin), "transfer", 0, 0),
+            Some(StopReason::Entry)
+        );
+        debugger.mode = RunMode::Step;
+        assert_eq!(
+            debugger.should_stop(Some(&coin), "transfer", 1, 0),
+            Some(StopReason::Step)
+        );
+    }
+
+    #[test]
+    fn test_step_over_and_out() {
+        let coin = module_id("0x1::coin");
+        let mut debugger = debugger(None);
+        assert_eq!(
+            debugger.should_stop(None, "main", 0, 0),
+            Some(StopReason::Step)
+        );
+
+        // Stepping over a call doesn't stop in the callee, but after it returns
+        debugger.mode = RunMode::StepOver(0);
+        assert_eq!(debugger.should_stop(Some(&coin), "withdraw", 0, 1), None);
+        assert_eq!(debugger.should_stop(Some(&coin), "withdraw", 1, 1), None);
+        assert_eq!(
+            debugger.should_stop(None, "main", 3, 0),
+            Some(StopReason::Step)
+        );
+
+        // Stepping out of a callee stops in the caller
+        debugger.mode = RunMode::StepOut(1);
+        assert_eq!(debugger.should_stop(Some(&coin), "withdraw", 2, 1), None);
+        assert_eq!(debugger.should_stop(Some(&coin), "value", 0, 2), None);
+        assert_eq!(
+            debugger.should_stop(None, "main", 4, 0),
+            Some(StopReason::Step)
+        );
+    }
+
+    #[test]
+    fn test_breakpoints() {
+        let coin = module_id("0x1::coin");
+        let withdraw = Breakpoint::function(coin.clone(), Identifier::new("withdraw").unwrap());
+        let mut debugger = debugger(None);
+        debugger.mode = RunMode::Continue;
+        debugger.add_breakpoint(withdraw.clone());
+        debugger.add_breakpoint(Breakpoint {
+            offset: Some(5),
+            ..Breakpoint::script()
+        });
+
+        assert_eq!(debugger.should_stop(None, "main", 0, 0), None);
+        assert_eq!(
+            debugger.should_stop(Some(&coin), "withdraw", 0, 1),
+            Some(StopReason::Breakpoint(withdraw))
+        );
+        assert_eq!(debugger.should_stop(Some(&coin), "withdraw", 1, 1), None);
+        assert_eq!(
+            debugger.should_stop(None, "main", 5, 0),
+            Some(StopReason::Breakpoint("script@5".parse().unwrap()))
+        );
+
+        // Breakpoints are ignored once detached
+        debugger.mode = RunMode::Detached;
+        assert_eq!(debugger.should_stop(None, "main", 5, 0), None);
+    }
+}
@@ -0,0 +1,565 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    flamegraph::render_differential_flamegraph, log::TransactionGasLog, render::Render,
+    report::ensure_dirs_exist,
+};
+use anyhow::Result;
+use aptos_gas_algebra::InternalGas;
+use handlebars::Handlebars;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+const TEMPLATE: &str = include_str!("../templates/diff.html");
+
+/// The name of the file a profile summary is saved to, next to the html report.
+pub const PROFILE_SUMMARY_FILE_NAME: &str = "profile.json";
+
+/// The number of hits and the total cost of an operation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hits {
+    pub hits: usize,
+    pub cost: u64,
+}
+
+/// A serializable summary of a gas profile, which is saved along with its report so that it can
+/// be compared against later profiles. Execution & IO costs are in internal gas units, while
+/// storage fees are in octas.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GasProfileSummary {
+    pub entry_point: String,
+    pub gas_scaling_factor: u64,
+
+    pub exec_io_total: u64,
+    pub intrinsic: u64,
+    pub dependencies: BTreeMap<String, u64>,
+    pub ops: BTreeMap<String, Hits>,
+    pub storage_reads: BTreeMap<String, Hits>,
+    pub storage_writes: BTreeMap<String, Hits>,
+    pub event_writes: BTreeMap<String, Hits>,
+    pub transaction_write: u64,
+
+    pub storage_total: u64,
+    pub storage_refund: u64,
+    pub storage_txn: u64,
+    pub storage_fees: BTreeMap<String, u64>,
+    pub storage_event_fees: BTreeMap<String, u64>,
+
+    /// The folded stack lines of the flamegraphs
+    pub exec_io_lines: Vec<String>,
+    pub storage_lines: Vec<String>,
+}
+
+fn to_hits(ops: Vec<(String, usize, InternalGas)>) -> BTreeMap<String, Hits> {
+    ops.into_iter()
+        .map(|(name, hits, cost)| {
+            (
+                name,
+                Hits {
+                    hits,
+                    cost: cost.into(),
+                },
+            )
+        })
+        .collect()
+}
+
+impl GasProfileSummary {
+    /// The IO costs, i.e., the costs of reading and writing state, events and the transaction.
+    pub fn io_total(&self) -> u64 {
+        [
+            &self.storage_reads,
+            &self.storage_writes,
+            &self.event_writes,
+        ]
+        .into_iter()
+        .flat_map(|ops| ops.values())
+        .map(|op| op.cost)
+        .sum::<u64>()
+            + self.transaction_write
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let path = if path.is_dir() {
+            path.join(PROFILE_SUMMARY_FILE_NAME)
+        } else {
+            path.to_path_buf()
+        };
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
+
+impl TransactionGasLog {
+    pub fn to_summary(&self) -> GasProfileSummary {
+        let aggregated = self.exec_io.aggregate_gas_events();
+
+        let mut storage_fees = BTreeMap::new();
+        for write in &self.storage.write_set_storage {
+            *storage_fees
+                .entry(format!("{}", Render(&write.key)))
+                .or_insert(0) += u64::from(write.cost);
+        }
+        let mut storage_event_fees = BTreeMap::new();
+        for event in &self.storage.events {
+            *storage_event_fees
+                .entry(format!("{}", event.ty))
+                .or_insert(0) += u64::from(event.cost);
+        }
+
+        GasProfileSummary {
+            entry_point: format!("{}", self.entry_point()),
+            gas_scaling_factor: self.exec_io.gas_scaling_factor.into(),
+
+            exec_io_total: self.exec_io.total.into(),
+            intrinsic: self.exec_io.intrinsic_cost.into(),
+            dependencies: self
+                .exec_io
+                .dependencies
+                .iter()
+                .map(|dep| (format!("{}", Render(&dep.id)), u64::from(dep.cost)))
+                .collect(),
+            ops: to_hits(aggregated.ops),
+            storage_reads: to_hits(aggregated.storage_reads),
+            storage_writes: to_hits(aggregated.storage_writes),
+            event_writes: to_hits(aggregated.event_writes),
+            transaction_write: aggregated.transaction_write.into(),
+
+            storage_total: self.storage.total.into(),
+            storage_refund: self.storage.total_refund.into(),
+            storage_txn: self.storage.txn_storage.into(),
+            storage_fees,
+            storage_event_fees,
+
+            exec_io_lines: self.exec_io.to_folded_stack_lines(),
+            storage_lines: self.storage.to_folded_stack_lines(),
+        }
+    }
+}
+
+/// Formats an amount of internal gas in (external) gas units.
+fn fmt_gas(amount: u64, scaling_factor: u64) -> String {
+    let scaled = format!("{:.8}", amount as f64 / scaling_factor as f64);
+    crate::misc::strip_trailing_zeros_and_decimal_point(&scaled).to_string()
+}
+
+fn fmt_apt(amount: u64) -> String {
+    let scaled = format!("{:.8}", amount as f64 / 1_0000_0000f64);
+    crate::misc::strip_trailing_zeros_and_decimal_point(&scaled).to_string()
+}
+
+fn fmt_delta(before: u64, after: u64, fmt: impl Fn(u64) -> String) -> String {
+    if after >= before {
+        format!("+{}", fmt(after - before))
+    } else {
+        format!("-{}", fmt(before - after))
+    }
+}
+
+fn fmt_percentage(before: u64, after: u64) -> String {
+    if before == 0 {
+        "/".to_string()
+    } else {
+        format!(
+            "{:+.2}%",
+            (after as f64 - before as f64) / before as f64 * 100.0
+        )
+    }
+}
+
+fn total_row(name: &str, before: u64, after: u64, fmt: &dyn Fn(u64) -> String) -> Value {
+    json!({
+        "name": name,
+        "before": fmt(before),
+        "after": fmt(after),
+        "delta": fmt_delta(before, after, fmt),
+        "percentage": fmt_percentage(before, after),
+    })
+}
+
+/// Compares two profiles, e.g., of the same entry function before and after a package upgrade.
+pub struct GasProfileComparison<'a> {
+    pub before: &'a GasProfileSummary,
+    pub after: &'a GasProfileSummary,
+}
+
+impl<'a> GasProfileComparison<'a> {
+    pub fn new(before: &'a GasProfileSummary, after: &'a GasProfileSummary) -> Self {
+        Self { before, after }
+    }
+
+    /// The rows of a table comparing operations, which changed the most first. Operations which
+    /// didn't change are left out.
+    fn compare_hits(
+        before: &BTreeMap<String, Hits>,
+        after: &BTreeMap<String, Hits>,
+        fmt: impl Fn(u64) -> String,
+    ) -> Vec<Value> {
+        let names = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+        let mut rows = names
+            .into_iter()
+            .map(|name| {
+                let before = before.get(name).copied().unwrap_or_default();
+                let after = after.get(name).copied().unwrap_or_default();
+                (name, before, after)
+            })
+            .filter(|(_, before, after)| before != after)
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|(_, before, after)| {
+            std::cmp::Reverse((after.cost as i128 - before.cost as i128).abs())
+        });
+        rows.into_iter()
+            .map(|(name, before, after)| {
+                json!({
+                    "name": name,
+                    "hits-before": before.hits,
+                    "hits-after": after.hits,
+                    "cost-before": fmt(before.cost),
+                    "cost-after": fmt(after.cost),
+                    "delta": fmt_delta(before.cost, after.cost, &fmt),
+                    "percentage": fmt_percentage(before.cost, after.cost),
+                })
+            })
+            .collect()
+    }
+
+    fn compare_costs(
+        before: &BTreeMap<String, u64>,
+        after: &BTreeMap<String, u64>,
+        fmt: impl Fn(u64) -> String,
+    ) -> Vec<Value> {
+        let as_hits = |costs: &BTreeMap<String, u64>| {
+            costs
+                .iter()
+                .map(|(name, cost)| {
+                    (
+                        name.clone(),
+                        Hits {
+                            hits: 1,
+                            cost: *cost,
+                        },
+                    )
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        Self::compare_hits(&as_hits(before), &as_hits(after), fmt)
+    }
+
+    pub fn generate_html_report(&self, path: impl AsRef<Path>, header: String) -> Result<()> {
+        let (before, after) = (self.before, self.after);
+        // Both profiles are displayed with the scaling factor of the latest one.
+        let scaling_factor = after.gas_scaling_factor;
+        let fmt_gas_units = |amount: u64| fmt_gas(amount, scaling_factor);
+
+        let mut data = Map::new();
+        data.insert("title".to_string(), Value::String(header));
+        data.insert("entry-before".to_string(), json!(before.entry_point));
+        data.insert("entry-after".to_string(), json!(after.entry_point));
+
+        // Totals
+        let sum = |costs: &BTreeMap<String, u64>| costs.values().sum::<u64>();
+        let ops_total = |ops: &BTreeMap<String, Hits>| ops.values().map(|op| op.cost).sum();
+        data.insert(
+            "totals-gas".to_string(),
+            json!([
+                total_row(
+                    "Execution & IO",
+                    before.exec_io_total,
+                    after.exec_io_total,
+                    &fmt_gas_units
+                ),
+                total_row(
+                    "Intrinsic",
+                    before.intrinsic,
+                    after.intrinsic,
+                    &fmt_gas_units
+                ),
+                total_row(
+                    "Dependencies",
+                    sum(&before.dependencies),
+                    sum(&after.dependencies),
+                    &fmt_gas_units
+                ),
+                total_row(
+                    "Execution",
+                    ops_total(&before.ops),
+                    ops_total(&after.ops),
+                    &fmt_gas_units
+                ),
+                total_row("IO", before.io_total(), after.io_total(), &fmt_gas_units),
+            ]),
+        );
+        data.insert(
+            "totals-storage".to_string(),
+            json!([
+                total_row(
+                    "Storage Fees",
+                    before.storage_total,
+                    after.storage_total,
+                    &fmt_apt
+                ),
+                total_row(
+                    "Storage Refunds",
+                    before.storage_refund,
+                    after.storage_refund,
+                    &fmt_apt
+                ),
+                total_row(
+                    "Transaction",
+                    before.storage_txn,
+                    after.storage_txn,
+                    &fmt_apt
+                ),
+            ]),
+        );
+
+        // Per-instruction and per-operation deltas
+        data.insert(
+            "ops".to_string(),
+            Value::Array(Self::compare_hits(&before.ops, &after.ops, fmt_gas_units)),
+        );
+        data.insert(
+            "dependencies".to_string(),
+            Value::Array(Self::compare_costs(
+                &before.dependencies,
+                &after.dependencies,
+                fmt_gas_units,
+            )),
+        );
+        data.insert(
+            "reads".to_string(),
+            Value::Array(Self::compare_hits(
+                &before.storage_reads,
+                &after.storage_reads,
+                fmt_gas_units,
+            )),
+        );
+        data.insert(
+            "writes".to_string(),
+            Value::Array(Self::compare_hits(
+                &before.storage_writes,
+                &after.storage_writes,
+                fmt_gas_units,
+            )),
+        );
+        data.insert(
+            "event-writes".to_string(),
+            Value::Array(Self::compare_hits(
+                &before.event_writes,
+                &after.event_writes,
+                fmt_gas_units,
+            )),
+        );
+        data.insert(
+            "storage-fees".to_string(),
+            Value::Array(Self::compare_costs(
+                &before.storage_fees,
+                &after.storage_fees,
+                fmt_apt,
+            )),
+        );
+        data.insert(
+            "storage-event-fees".to_string(),
+            Value::Array(Self::compare_costs(
+                &before.storage_event_fees,
+                &after.storage_event_fees,
+                fmt_apt,
+            )),
+        );
+
+        // Differential flamegraphs
+        let graph_exec_io = render_differential_flamegraph(
+            &before.exec_io_lines,
+            &after.exec_io_lines,
+            "Execution & IO (Difference)".to_string(),
+            |count| format!("{} gas units", fmt_gas_units(count)),
+        )?;
+        let graph_storage = render_differential_flamegraph(
+            &before.storage_lines,
+            &after.storage_lines,
+            "Storage (Difference)".to_string(),
+            |count| format!("{} Octa", count),
+        )?;
+        data.insert(
+            "graph-exec-io".to_string(),
+            Value::Bool(graph_exec_io.is_some()),
+        );
+        data.insert(
+            "graph-storage".to_string(),
+            Value::Bool(graph_storage.is_some()),
+        );
+
+        // Rendering the html doc
+        let mut handlebars = Handlebars::new();
+        handlebars.register_template_string("diff", TEMPLATE)?;
+        let html = handlebars.render("diff", &data)?;
+
+        // Writing to disk
+        let path_root = path.as_ref();
+
+        ensure_dirs_exist(path_root)?;
+        let path_assets = path_root.join("assets");
+        ensure_dirs_exist(&path_assets)?;
+
+        if let Some(graph_bytes) = graph_exec_io {
+            fs::write(path_assets.join("exec_io_diff.svg"), graph_bytes)?;
+        }
+        if let Some(graph_bytes) = graph_storage {
+            fs::write(path_assets.join("storage_diff.svg"), graph_bytes)?;
+        }
+        fs::write(path_root.join("index.html"), html)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hits(hits: usize, cost: u64) -> Hits {
+        Hits { hits, cost }
+    }
+
+    fn create_summary(ops: Vec<(&str, Hits)>, storage_fees: Vec<(&str, u64)>) -> GasProfileSummary {
+        GasProfileSummary {
+            entry_point: "0x1::coin::transfer".to_string(),
+            gas_scaling_factor: 100,
+            exec_io_total: 1_000,
+            intrinsic: 100,
+            dependencies: BTreeMap::from([("0x1::coin".to_string(), 50)]),
+            ops: ops
+                .into_iter()
+                .map(|(name, hits)| (name.to_string(), hits))
+                .collect(),
+            storage_reads: BTreeMap::from([("0x1::account::Account".to_string(), hits(1, 30))]),
+            storage_writes: BTreeMap::from([("0x1::coin::CoinStore".to_string(), hits(2, 40))]),
+            event_writes: BTreeMap::new(),
+            transaction_write: 20,
+            storage_total: 5_000,
+            storage_refund: 0,
+            storage_txn: 300,
+            storage_fees: storage_fees
+                .into_iter()
+                .map(|(name, cost)| (name.to_string(), cost))
+                .collect(),
+            storage_event_fees: BTreeMap::new(),
+            exec_io_lines: vec!["0x1::coin::transfer 100".to_string()],
+            storage_lines: vec!["0x1::coin::CoinStore 5000".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_io_total() {
+        let summary = create_summary(vec![], vec![]);
+        assert_eq!(summary.io_total(), 30 + 40 + 20);
+    }
+
+    #[test]
+    fn test_fmt_deltas() {
+        assert_eq!(fmt_delta(100, 250, |amount| fmt_gas(amount, 100)), "+1.5");
+        assert_eq!(fmt_delta(250, 100, |amount| fmt_gas(amount, 100)), "-1.5");
+        assert_eq!(fmt_delta(100, 100, fmt_apt), "+0");
+        assert_eq!(fmt_percentage(200, 250), "+25.00%");
+        assert_eq!(fmt_percentage(200, 150), "-25.00%");
+        assert_eq!(fmt_percentage(0, 150), "/");
+    }
+
+    #[test]
+    fn test_compare_hits() {
+        let before = create_summary(
+            vec![
+                ("add", hits(10, 100)),
+                ("call", hits(2, 500)),
+                ("ld_u64", hits(4, 40)),
+                ("unchanged", hits(1, 10)),
+            ],
+            vec![],
+        );
+        let after = create_summary(
+            vec![
+                ("add", hits(12, 120)),
+                ("call", hits(1, 250)),
+                ("unchanged", hits(1, 10)),
+                ("vec_push_back", hits(3, 90)),
+            ],
+            vec![],
+        );
+        let rows = GasProfileComparison::compare_hits(&before.ops, &after.ops, |amount| {
+            fmt_gas(amount, 100)
+        });
+
+        // Unchanged operations are left out, and the largest (absolute) deltas come first
+        let names = rows
+            .iter()
+            .map(|row| row["name"].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["call", "vec_push_back", "ld_u64", "add"]);
+
+        // Removed and added operations are compared against zero hits
+        assert_eq!(
+            rows[0],
+            json!({
+                "name": "call",
+                "hits-before": 2,
+                "hits-after": 1,
+                "cost-before": "5",
+                "cost-after": "2.5",
+                "delta": "-2.5",
+                "percentage": "-50.00%",
+            })
+        );
+        assert_eq!(rows[1]["hits-before"], 0);
+        assert_eq!(rows[1]["percentage"], "/");
+        assert_eq!(rows[2]["hits-after"], 0);
+        assert_eq!(rows[2]["delta"], "-0.4");
+        assert_eq!(rows[3]["delta"], "+0.2");
+    }
+
+    #[test]
+    fn test_compare_costs() {
+        let before = create_summary(vec![], vec![("0x1::coin::CoinStore", 50_000)]);
+        let after = create_summary(vec![], vec![
+            ("0x1::coin::CoinStore", 50_000),
+            ("0x1::object::ObjectGroup", 1_0000_0000),
+        ]);
+        let rows =
+            GasProfileComparison::compare_costs(&before.storage_fees, &after.storage_fees, fmt_apt);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["name"], "0x1::object::ObjectGroup");
+        assert_eq!(rows[0]["cost-before"], "0");
+        assert_eq!(rows[0]["cost-after"], "1");
+        assert_eq!(rows[0]["delta"], "+1");
+    }
+
+    #[test]
+    fn test_summary_save_and_load() {
+        let summary = create_summary(
+            vec![("add", hits(10, 100))],
+            vec![("0x1::coin::CoinStore", 50_000)],
+        );
+        let dir = tempfile::tempdir().unwrap();
+
+        // A summary can be loaded from its file, or from the directory of its report
+        let path = dir.path().join(PROFILE_SUMMARY_FILE_NAME);
+        summary.save(&path).unwrap();
+        assert_eq!(GasProfileSummary::load(&path).unwrap(), summary);
+        assert_eq!(GasProfileSummary::load(dir.path()).unwrap(), summary);
+
+        // Loading a missing or malformed summary fails
+        assert!(GasProfileSummary::load(dir.path().join("missing.json")).is_err());
+        let malformed_path = dir.path().join("malformed.json");
+        fs::write(&malformed_path, "{}").unwrap();
+        assert!(GasProfileSummary::load(&malformed_path).is_err());
+    }
+}
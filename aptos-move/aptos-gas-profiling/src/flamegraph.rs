@@ -8,16 +8,17 @@ use crate::{
 use inferno::flamegraph::TextTruncateDirection;
 use move_core_types::gas_algebra::InternalGas;
 use regex::Captures;
+use std::collections::BTreeMap;
 
 #[derive(Debug)]
-struct LineBuffer(Vec<String>);
+pub(crate) struct LineBuffer(Vec<String>);
 
 impl LineBuffer {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self(vec![])
     }
 
-    fn push(&mut self, item: impl AsRef<str>, count: impl Into<u64>) {
+    pub(crate) fn push(&mut self, item: impl AsRef<str>, count: impl Into<u64>) {
         let count: u64 = count.into();
 
         if count > 0 {
@@ -25,7 +26,7 @@ impl LineBuffer {
         }
     }
 
-    fn into_inner(self) -> Vec<String> {
+    pub(crate) fn into_inner(self) -> Vec<String> {
         self.0
     }
 }
@@ -33,7 +34,7 @@ impl LineBuffer {
 impl StorageFees {
     /// Convert the storage fee log into folded stack lines, which can
     /// then be used to generate a flamegraph.
-    fn to_folded_stack_lines(&self) -> Vec<String> {
+    pub(crate) fn to_folded_stack_lines(&self) -> Vec<String> {
         let mut lines = LineBuffer::new();
 
         lines.push("transaction", self.txn_storage);
@@ -96,7 +97,7 @@ impl StorageFees {
 impl ExecutionAndIOCosts {
     /// Convert the execution gas log into folded stack lines, which can
     /// then be used to generate a flamegraph.
-    fn to_folded_stack_lines(&self) -> Vec<String> {
+    pub(crate) fn to_folded_stack_lines(&self) -> Vec<String> {
         let mut lines = LineBuffer::new();
 
         lines.push("intrinsic", self.intrinsic_cost);
@@ -239,3 +240,61 @@ impl ExecutionAndIOCosts {
         Ok(Some(graph_content.as_bytes().to_vec()))
     }
 }
+
+/// Tries to generate a differential flamegraph from the folded stack lines of two profiles.
+/// Frames are sized by the second profile, and colored red if they got more expensive or blue if
+/// they got cheaper. None will be returned if both profiles are empty.
+pub(crate) fn render_differential_flamegraph(
+    before: &[String],
+    after: &[String],
+    title: String,
+    fmt_count: impl Fn(u64) -> String,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    // Lines with the same stack are merged, so that every stack has a single pair of counts.
+    let mut counts: BTreeMap<&str, (u64, u64)> = BTreeMap::new();
+    for (lines, is_before) in [(before, true), (after, false)] {
+        for line in lines {
+            let (stack, count) = line
+                .rsplit_once(' ')
+                .ok_or_else(|| anyhow::anyhow!("malformed folded stack line: {}", line))?;
+            let count: u64 = count.parse()?;
+            let entry = counts.entry(stack).or_default();
+            if is_before {
+                entry.0 += count;
+            } else {
+                entry.1 += count;
+            }
+        }
+    }
+    if counts.is_empty() {
+        return Ok(None);
+    }
+    let lines = counts
+        .into_iter()
+        .map(|(stack, (before, after))| format!("{} {} {}", stack, before, after))
+        .collect::<Vec<_>>();
+
+    let mut options = inferno::flamegraph::Options::default();
+    options.text_truncate_direction = TextTruncateDirection::Right;
+    options.title = title;
+
+    let mut graph_content = vec![];
+    inferno::flamegraph::from_lines(
+        &mut options,
+        lines.iter().map(|s| s.as_str()),
+        &mut graph_content,
+    )?;
+    let graph_content = String::from_utf8_lossy(&graph_content);
+
+    let re = regex::Regex::new("([0-9]+(,[0-9]+)*) samples")
+        .expect("should be able to build regex successfully");
+    let graph_content = re.replace_all(&graph_content, |caps: &Captures| {
+        let count: u64 = caps[1]
+            .replace(',', "")
+            .parse()
+            .expect("should be able parse count as u64");
+        fmt_count(count)
+    });
+
+    Ok(Some(graph_content.as_bytes().to_vec()))
+}
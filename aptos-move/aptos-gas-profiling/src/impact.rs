@@ -0,0 +1,311 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    flamegraph::{render_differential_flamegraph, LineBuffer},
+    log::FrameName,
+    report::ensure_dirs_exist,
+};
+use anyhow::Result;
+use aptos_types::transaction::{ExecutionStatus, TransactionOutput, TransactionStatus, Version};
+use handlebars::Handlebars;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeMap, fs, path::Path};
+
+const TEMPLATE: &str = include_str!("../templates/impact.html");
+
+/// The gas used by a group of transactions under the current and the proposed gas schedule.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct GasUsedDelta {
+    pub num_txns: u64,
+    pub current: u64,
+    pub proposed: u64,
+}
+
+impl GasUsedDelta {
+    fn add(&mut self, current: u64, proposed: u64) {
+        self.num_txns += 1;
+        self.current += current;
+        self.proposed += proposed;
+    }
+
+    pub fn delta(&self) -> i128 {
+        self.proposed as i128 - self.current as i128
+    }
+
+    /// The relative change of the gas used, or `None` if no gas was used so far.
+    pub fn percentage(&self) -> Option<f64> {
+        (self.current > 0).then(|| self.delta() as f64 / self.current as f64 * 100.0)
+    }
+}
+
+/// A transaction which succeeds under the current gas schedule, but not under the proposed one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FailingTransaction {
+    pub version: Version,
+    pub entry_point: String,
+    pub current_gas_used: u64,
+    pub proposed_gas_used: u64,
+    pub proposed_status: String,
+}
+
+/// The impact of a gas schedule change on a set of transactions, which have been executed under
+/// both the current and the proposed gas schedule.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct GasScheduleImpact {
+    pub total: GasUsedDelta,
+    /// The gas used per entry point (i.e., entry function or script)
+    pub entry_points: BTreeMap<String, GasUsedDelta>,
+    /// The gas used per module of the entry functions
+    pub modules: BTreeMap<String, GasUsedDelta>,
+    /// The transactions which would run out of gas with the proposed gas schedule
+    pub newly_out_of_gas: Vec<FailingTransaction>,
+    /// The transactions which would fail for other reasons with the proposed gas schedule
+    pub newly_failed: Vec<FailingTransaction>,
+}
+
+fn is_success(status: &TransactionStatus) -> bool {
+    matches!(status, TransactionStatus::Keep(ExecutionStatus::Success))
+}
+
+impl GasScheduleImpact {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outputs of a transaction under the current and the proposed gas schedule.
+    /// Type arguments of the entry point are ignored, so that all its instantiations are grouped.
+    pub fn add_transaction(
+        &mut self,
+        version: Version,
+        entry_point: &FrameName,
+        current: &TransactionOutput,
+        proposed: &TransactionOutput,
+    ) {
+        let (current_gas_used, proposed_gas_used) = (current.gas_used(), proposed.gas_used());
+
+        let entry_point = match entry_point {
+            FrameName::Script => "script".to_string(),
+            FrameName::Function {
+                module_id, name, ..
+            } => {
+                let module = module_id.short_str_lossless();
+                self.modules
+                    .entry(module.clone())
+                    .or_default()
+                    .add(current_gas_used, proposed_gas_used);
+                format!("{}::{}", module, name)
+            },
+        };
+        self.total.add(current_gas_used, proposed_gas_used);
+        self.entry_points
+            .entry(entry_point.clone())
+            .or_default()
+            .add(current_gas_used, proposed_gas_used);
+
+        if is_success(current.status()) && !is_success(proposed.status()) {
+            let failing_txn = FailingTransaction {
+                version,
+                entry_point,
+                current_gas_used,
+                proposed_gas_used,
+                proposed_status: format!("{:?}", proposed.status()),
+            };
+            if proposed.status() == &TransactionStatus::Keep(ExecutionStatus::OutOfGas) {
+                self.newly_out_of_gas.push(failing_txn);
+            } else {
+                self.newly_failed.push(failing_txn);
+            }
+        }
+    }
+
+    /// The groups sorted by the absolute change of their gas used, largest first.
+    fn sorted_by_delta(groups: &BTreeMap<String, GasUsedDelta>) -> Vec<(&str, &GasUsedDelta)> {
+        let mut groups = groups
+            .iter()
+            .map(|(name, delta)| (name.as_str(), delta))
+            .collect::<Vec<_>>();
+        groups.sort_by_key(|(_, delta)| std::cmp::Reverse(delta.delta().abs()));
+        groups
+    }
+
+    fn to_folded_stack_lines(&self, proposed: bool) -> Vec<String> {
+        let mut lines = LineBuffer::new();
+        for (entry_point, delta) in &self.entry_points {
+            let gas_used = if proposed {
+                delta.proposed
+            } else {
+                delta.current
+            };
+            lines.push(entry_point.replace("::", ";"), gas_used);
+        }
+        lines.into_inner()
+    }
+
+    pub fn generate_html_report(&self, path: impl AsRef<Path>, header: String) -> Result<()> {
+        let mut data = Map::new();
+        data.insert("title".to_string(), Value::String(header));
+
+        let fmt_percentage = |delta: &GasUsedDelta| match delta.percentage() {
+            Some(percentage) => format!("{:+.2}%", percentage),
+            None => "/".to_string(),
+        };
+        let convert_group = |(name, delta): (&str, &GasUsedDelta)| {
+            json!({
+                "name": name,
+                "txns": delta.num_txns,
+                "current": delta.current,
+                "proposed": delta.proposed,
+                "delta": format!("{:+}", delta.delta()),
+                "percentage": fmt_percentage(delta),
+            })
+        };
+        data.insert("total".to_string(), convert_group(("total", &self.total)));
+        data.insert(
+            "entry-points".to_string(),
+            Value::Array(
+                Self::sorted_by_delta(&self.entry_points)
+                    .into_iter()
+                    .map(convert_group)
+                    .collect(),
+            ),
+        );
+        data.insert(
+            "modules".to_string(),
+            Value::Array(
+                Self::sorted_by_delta(&self.modules)
+                    .into_iter()
+                    .map(convert_group)
+                    .collect(),
+            ),
+        );
+        data.insert(
+            "newly-out-of-gas".to_string(),
+            serde_json::to_value(&self.newly_out_of_gas)?,
+        );
+        data.insert(
+            "newly-failed".to_string(),
+            serde_json::to_value(&self.newly_failed)?,
+        );
+
+        // Flamegraph of the gas used per entry point, colored by the change
+        let graph = render_differential_flamegraph(
+            &self.to_folded_stack_lines(false),
+            &self.to_folded_stack_lines(true),
+            "Gas Used by Entry Point (Proposed Gas Schedule)".to_string(),
+            |count| format!("{} gas units", count),
+        )?;
+        data.insert("graph".to_string(), Value::Bool(graph.is_some()));
+
+        // Rendering the html doc
+        let mut handlebars = Handlebars::new();
+        handlebars.register_template_string("impact", TEMPLATE)?;
+        let html = handlebars.render("impact", &data)?;
+
+        // Writing to disk
+        let path_root = path.as_ref();
+
+        ensure_dirs_exist(path_root)?;
+        let path_assets = path_root.join("assets");
+        ensure_dirs_exist(&path_assets)?;
+
+        if let Some(graph_bytes) = graph {
+            fs::write(path_assets.join("impact.svg"), graph_bytes)?;
+        }
+        fs::write(path_root.join("index.html"), html)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        transaction::{TransactionAuxiliaryData, TransactionOutput},
+        write_set::WriteSet,
+    };
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+
+    fn create_output(gas_used: u64, status: ExecutionStatus) -> TransactionOutput {
+        TransactionOutput::new(
+            WriteSet::default(),
+            vec![],
+            gas_used,
+            TransactionStatus::Keep(status),
+            TransactionAuxiliaryData::default(),
+        )
+    }
+
+    fn function(module: &str, name: &str) -> FrameName {
+        FrameName::Function {
+            module_id: ModuleId::new(AccountAddress::ONE, Identifier::new(module).unwrap()),
+            name: Identifier::new(name).unwrap(),
+            ty_args: vec![],
+        }
+    }
+
+    #[test]
+    fn test_gas_schedule_impact() {
+        let mut impact = GasScheduleImpact::new();
+        let success = ExecutionStatus::Success;
+        impact.add_transaction(
+            1,
+            &function("coin", "transfer"),
+            &create_output(10, success.clone()),
+            &create_output(12, success.clone()),
+        );
+        impact.add_transaction(
+            2,
+            &function("coin", "transfer"),
+            &create_output(10, success.clone()),
+            &create_output(20, ExecutionStatus::OutOfGas),
+        );
+        impact.add_transaction(
+            3,
+            &function("coin", "register"),
+            &create_output(5, success.clone()),
+            &create_output(4, success.clone()),
+        );
+        impact.add_transaction(
+            4,
+            &FrameName::Script,
+            &create_output(7, success),
+            &create_output(7, ExecutionStatus::MiscellaneousError(None)),
+        );
+
+        assert_eq!(impact.total, GasUsedDelta {
+            num_txns: 4,
+            current: 32,
+            proposed: 43,
+        });
+        assert_eq!(impact.entry_points["0x1::coin::transfer"], GasUsedDelta {
+            num_txns: 2,
+            current: 20,
+            proposed: 32,
+        });
+        assert_eq!(impact.modules["0x1::coin"].delta(), 11);
+        assert!(!impact.modules.contains_key("script"));
+        assert_eq!(impact.entry_points["script"].percentage(), Some(0.0));
+
+        assert_eq!(
+            impact
+                .newly_out_of_gas
+                .iter()
+                .map(|txn| txn.version)
+                .collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(
+            impact
+                .newly_failed
+                .iter()
+                .map(|txn| txn.version)
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+    }
+}
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aggregate;
+mod diff;
 mod erased;
 mod flamegraph;
+mod impact;
 mod log;
 mod misc;
 mod profiler;
 mod render;
 mod report;
+mod trace;
+mod tracer;
 
+pub use diff::{GasProfileComparison, GasProfileSummary, Hits, PROFILE_SUMMARY_FILE_NAME};
+pub use impact::{FailingTransaction, GasScheduleImpact, GasUsedDelta};
 pub use log::{FrameName, TransactionGasLog};
 pub use profiler::GasProfiler;
+pub use trace::{ExecutionTrace, TraceEvent, TraceFrame, TracedValue};
+pub use tracer::{traced_entry_function, ExecutionTracer};
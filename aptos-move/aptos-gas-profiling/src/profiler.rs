@@ -160,6 +160,10 @@ impl<G> GasMeter for GasProfiler<G>
 where
     G: AptosGasMeter,
 {
+    delegate! {
+        fn observes_return_values(&self) -> bool;
+    }
+
     delegate_mut! {
         // Note: we only use this callback for memory tracking, not for charging gas.
         fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView)
@@ -177,6 +181,12 @@ where
             &mut self,
             locals: impl Iterator<Item = impl ValueView> + Clone,
         ) -> PartialVMResult<()>;
+
+        // Note: this doesn't charge gas so no need to record anything.
+        fn observe_return_values(
+            &mut self,
+            ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
     }
 
     record_bytecode! {
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{log::TransactionGasLog, render::Render};
+use crate::{diff::PROFILE_SUMMARY_FILE_NAME, log::TransactionGasLog, render::Render};
 use anyhow::Result;
 use aptos_gas_algebra::{Fee, InternalGas};
 use handlebars::Handlebars;
@@ -14,7 +14,7 @@ use std::{
 
 const TEMPLATE: &str = include_str!("../templates/index.html");
 
-fn ensure_dirs_exist(path: impl AsRef<Path>) -> Result<()> {
+pub(crate) fn ensure_dirs_exist(path: impl AsRef<Path>) -> Result<()> {
     if let Err(err) = fs::create_dir_all(&path) {
         match err.kind() {
             std::io::ErrorKind::AlreadyExists => (),
@@ -348,6 +348,10 @@ impl TransactionGasLog {
         }
         fs::write(path_root.join("index.html"), html)?;
 
+        // Saving a summary of the profile, so that later profiles can be compared against it
+        self.to_summary()
+            .save(path_root.join(PROFILE_SUMMARY_FILE_NAME))?;
+
         Ok(())
     }
 }
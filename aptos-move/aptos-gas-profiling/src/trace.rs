@@ -0,0 +1,403 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::{account_address::AccountAddress, u256::U256};
+use move_vm_types::{
+    delayed_values::delayed_field_id::DelayedFieldID,
+    views::{ValueView, ValueVisitor},
+};
+use serde::{Serialize, Serializer};
+use std::fmt::{self, Display, Formatter};
+
+/// A Move value observed during the execution. The VM doesn't know the names of the struct
+/// fields, so structs are represented by their field values (in declaration order).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TracedValue {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    // Large integers are serialized as strings, as they may not fit into JSON numbers
+    U64(#[serde(serialize_with = "serialize_to_string")] u64),
+    U128(#[serde(serialize_with = "serialize_to_string")] u128),
+    U256(#[serde(serialize_with = "serialize_to_string")] U256),
+    Bool(bool),
+    Address(AccountAddress),
+    /// A `vector<u8>`, serialized as a hex string
+    Bytes(#[serde(serialize_with = "serialize_to_hex")] Vec<u8>),
+    Vector(Vec<TracedValue>),
+    Struct(Vec<TracedValue>),
+    Reference(Box<TracedValue>),
+    /// A delayed field (e.g., an aggregator), whose value is only known after the execution
+    Delayed,
+}
+
+fn serialize_to_string<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+fn serialize_to_hex<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+}
+
+impl TracedValue {
+    pub fn new(value: &impl ValueView) -> Self {
+        let mut builder = TracedValueBuilder::default();
+        value.visit(&mut builder);
+        builder.finish()
+    }
+}
+
+impl Display for TracedValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TracedValue::U8(val) => write!(f, "{}u8", val),
+            TracedValue::U16(val) => write!(f, "{}u16", val),
+            TracedValue::U32(val) => write!(f, "{}u32", val),
+            TracedValue::U64(val) => write!(f, "{}", val),
+            TracedValue::U128(val) => write!(f, "{}u128", val),
+            TracedValue::U256(val) => write!(f, "{}u256", val),
+            TracedValue::Bool(val) => write!(f, "{}", val),
+            TracedValue::Address(addr) => write!(f, "{}", addr.to_hex_literal()),
+            TracedValue::Bytes(bytes) => write!(f, "0x{}", hex::encode(bytes)),
+            TracedValue::Vector(elems) => write!(f, "[{}]", join(elems)),
+            TracedValue::Struct(fields) => write!(f, "{{ {} }}", join(fields)),
+            TracedValue::Reference(val) => write!(f, "&{}", val),
+            TracedValue::Delayed => write!(f, "<delayed>"),
+        }
+    }
+}
+
+pub(crate) fn join(values: &[impl Display]) -> String {
+    values
+        .iter()
+        .map(|val| val.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rebuilds a value from the (depth first) visit of its nodes. Note: references can only be at
+/// the top level, and the referenced value may be visited at the same depth as the reference.
+#[derive(Default)]
+struct TracedValueBuilder {
+    is_reference: bool,
+    // The containers (vectors or structs) being built, along with their depths
+    containers: Vec<(usize, TracedValue)>,
+    root: Option<TracedValue>,
+}
+
+impl TracedValueBuilder {
+    fn add_value(&mut self, depth: usize, value: TracedValue) {
+        self.close_containers(depth);
+        self.add_to_parent(value);
+    }
+
+    fn add_container(&mut self, depth: usize, container: TracedValue) {
+        self.close_containers(depth);
+        self.containers.push((depth, container));
+    }
+
+    /// Closes the containers that can't have any more elements, i.e., all containers at the
+    /// given depth or deeper.
+    fn close_containers(&mut self, depth: usize) {
+        while matches!(self.containers.last(), Some((container_depth, _)) if *container_depth >= depth)
+        {
+            let (_, container) = self.containers.pop().expect("Container must exist");
+            self.add_to_parent(container);
+        }
+    }
+
+    fn add_to_parent(&mut self, value: TracedValue) {
+        match self.containers.last_mut() {
+            Some((_, TracedValue::Vector(elems) | TracedValue::Struct(elems))) => elems.push(value),
+            Some(_) => unreachable!("Only vectors and structs are containers"),
+            None => self.root = Some(value),
+        }
+    }
+
+    fn finish(mut self) -> TracedValue {
+        self.close_containers(0);
+        let value = self.root.unwrap_or(TracedValue::Delayed);
+        if self.is_reference {
+            TracedValue::Reference(Box::new(value))
+        } else {
+            value
+        }
+    }
+}
+
+impl ValueVisitor for TracedValueBuilder {
+    fn visit_delayed(&mut self, depth: usize, _id: DelayedFieldID) {
+        self.add_value(depth, TracedValue::Delayed);
+    }
+
+    fn visit_u8(&mut self, depth: usize, val: u8) {
+        self.add_value(depth, TracedValue::U8(val));
+    }
+
+    fn visit_u16(&mut self, depth: usize, val: u16) {
+        self.add_value(depth, TracedValue::U16(val));
+    }
+
+    fn visit_u32(&mut self, depth: usize, val: u32) {
+        self.add_value(depth, TracedValue::U32(val));
+    }
+
+    fn visit_u64(&mut self, depth: usize, val: u64) {
+        self.add_value(depth, TracedValue::U64(val));
+    }
+
+    fn visit_u128(&mut self, depth: usize, val: u128) {
+        self.add_value(depth, TracedValue::U128(val));
+    }
+
+    fn visit_u256(&mut self, depth: usize, val: U256) {
+        self.add_value(depth, TracedValue::U256(val));
+    }
+
+    fn visit_bool(&mut self, depth: usize, val: bool) {
+        self.add_value(depth, TracedValue::Bool(val));
+    }
+
+    fn visit_address(&mut self, depth: usize, val: AccountAddress) {
+        self.add_value(depth, TracedValue::Address(val));
+    }
+
+    fn visit_struct(&mut self, depth: usize, _len: usize) -> bool {
+        self.add_container(depth, TracedValue::Struct(vec![]));
+        true
+    }
+
+    fn visit_vec(&mut self, depth: usize, _len: usize) -> bool {
+        self.add_container(depth, TracedValue::Vector(vec![]));
+        true
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        self.is_reference = true;
+        true
+    }
+
+    fn visit_vec_u8(&mut self, depth: usize, vals: &[u8]) {
+        self.add_value(depth, TracedValue::Bytes(vals.to_vec()));
+    }
+}
+
+/// A call frame of the execution trace
+#[derive(Clone, Debug, Serialize)]
+pub struct TraceFrame {
+    /// The called function (or `script` for the script of a script transaction)
+    pub function: String,
+    pub ty_args: Vec<String>,
+    pub args: Vec<TracedValue>,
+    pub return_values: Vec<TracedValue>,
+    /// The execution gas used by the frame, including its callees (in internal gas units)
+    pub gas_used: u64,
+    /// False if the function didn't return, i.e., the execution aborted in the function (or
+    /// in one of its callees).
+    pub returned: bool,
+    pub events: Vec<TraceEvent>,
+}
+
+impl TraceFrame {
+    pub(crate) fn new(function: String, ty_args: Vec<String>, args: Vec<TracedValue>) -> Self {
+        Self {
+            function,
+            ty_args,
+            args,
+            return_values: vec![],
+            gas_used: 0,
+            returned: false,
+            events: vec![],
+        }
+    }
+}
+
+/// An event that occurred during the execution of a function
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceEvent {
+    Call(TraceFrame),
+    NativeCall {
+        function: String,
+        ty_args: Vec<String>,
+        args: Vec<TracedValue>,
+        return_values: Vec<TracedValue>,
+        gas_used: u64,
+    },
+    /// A resource loaded from storage (only the first access to the resource loads it)
+    LoadResource {
+        address: AccountAddress,
+        resource_type: String,
+        exists: bool,
+        bytes_loaded: u64,
+    },
+    BorrowGlobal {
+        resource_type: String,
+        is_mut: bool,
+        is_success: bool,
+    },
+    MoveFrom {
+        resource_type: String,
+        value: Option<TracedValue>,
+    },
+    MoveTo {
+        resource_type: String,
+        value: TracedValue,
+        is_success: bool,
+    },
+    EmitEvent {
+        event_type: String,
+        data: TracedValue,
+    },
+}
+
+/// The call tree of a transaction, along with the values, resource accesses and events observed
+/// in every call frame.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExecutionTrace {
+    /// The number of internal gas units per gas unit
+    pub gas_unit_scaling_factor: u64,
+    pub root: TraceFrame,
+}
+
+impl ExecutionTrace {
+    fn fmt_frame(f: &mut Formatter<'_>, frame: &TraceFrame, indent: usize) -> fmt::Result {
+        writeln!(
+            f,
+            "{:indent$}{}{}({}){} [gas: {}]{}",
+            "",
+            frame.function,
+            format_ty_args(&frame.ty_args),
+            join(&frame.args),
+            format_return_values(&frame.return_values),
+            frame.gas_used,
+            if frame.returned { "" } else { " ABORTED" },
+            indent = indent
+        )?;
+        for event in &frame.events {
+            Self::fmt_event(f, event, indent + 2)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_event(f: &mut Formatter<'_>, event: &TraceEvent, indent: usize) -> fmt::Result {
+        match event {
+            TraceEvent::Call(frame) => Self::fmt_frame(f, frame, indent),
+            TraceEvent::NativeCall {
+                function,
+                ty_args,
+                args,
+                return_values,
+                gas_used,
+            } => writeln!(
+                f,
+                "{:indent$}native {}{}({}){} [gas: {}]",
+                "",
+                function,
+                format_ty_args(ty_args),
+                join(args),
+                format_return_values(return_values),
+                gas_used,
+                indent = indent
+            ),
+            TraceEvent::LoadResource {
+                address,
+                resource_type,
+                exists,
+                bytes_loaded,
+            } => writeln!(
+                f,
+                "{:indent$}load {} at {} ({})",
+                "",
+                resource_type,
+                address.to_hex_literal(),
+                if *exists {
+                    format!("{} bytes", bytes_loaded)
+                } else {
+                    "not found".to_string()
+                },
+                indent = indent
+            ),
+            TraceEvent::BorrowGlobal {
+                resource_type,
+                is_mut,
+                is_success,
+            } => writeln!(
+                f,
+                "{:indent$}borrow_global{} {}{}",
+                "",
+                if *is_mut { "_mut" } else { "" },
+                resource_type,
+                if *is_success { "" } else { " FAILED" },
+                indent = indent
+            ),
+            TraceEvent::MoveFrom {
+                resource_type,
+                value,
+            } => match value {
+                Some(value) => writeln!(
+                    f,
+                    "{:indent$}move_from {}: {}",
+                    "",
+                    resource_type,
+                    value,
+                    indent = indent
+                ),
+                None => writeln!(
+                    f,
+                    "{:indent$}move_from {} FAILED",
+                    "",
+                    resource_type,
+                    indent = indent
+                ),
+            },
+            TraceEvent::MoveTo {
+                resource_type,
+                value,
+                is_success,
+            } => writeln!(
+                f,
+                "{:indent$}move_to {}: {}{}",
+                "",
+                resource_type,
+                value,
+                if *is_success { "" } else { " FAILED" },
+                indent = indent
+            ),
+            TraceEvent::EmitEvent { event_type, data } => writeln!(
+                f,
+                "{:indent$}emit {}: {}",
+                "",
+                event_type,
+                data,
+                indent = indent
+            ),
+        }
+    }
+}
+
+fn format_ty_args(ty_args: &[String]) -> String {
+    if ty_args.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", ty_args.join(", "))
+    }
+}
+
+fn format_return_values(return_values: &[TracedValue]) -> String {
+    match return_values {
+        [] => String::new(),
+        [value] => format!(" -> {}", value),
+        values => format!(" -> ({})", join(values)),
+    }
+}
+
+impl Display for ExecutionTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Self::fmt_frame(f, &self.root, 0)
+    }
+}
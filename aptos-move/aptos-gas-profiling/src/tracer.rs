@@ -0,0 +1,563 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::trace::{ExecutionTrace, TraceEvent, TraceFrame, TracedValue};
+use aptos_gas_algebra::{Fee, FeePerGasUnit, InternalGas, NumArgs, NumBytes, NumTypeNodes};
+use aptos_gas_meter::AptosGasMeter;
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{EntryFunction, MultisigTransactionPayload, TransactionPayload},
+    write_set::WriteOpSize,
+};
+use aptos_vm_types::{change_set::ChangeSetInterface, resolver::ExecutorView};
+use move_binary_format::{
+    errors::{PartialVMResult, VMResult},
+    file_format::CodeOffset,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::{IdentStr, Identifier},
+    language_storage::{ModuleId, TypeTag},
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+
+/// The natives that emit events (with the event as the last argument)
+const EVENT_NATIVES: &[&str] = &[
+    "0x1::event::write_to_event_store",
+    "0x1::event::write_module_event_to_store",
+];
+
+/// A gas meter adapter that records the execution trace of a transaction: the call tree (with
+/// the arguments and return values of every call), the gas used by every call frame, the
+/// resources accessed and the events emitted. Like the gas profiler, it doesn't change the
+/// charges of the underlying gas meter, so the traced execution is identical to the original one.
+pub struct ExecutionTracer<G> {
+    base: G,
+
+    frames: Vec<TraceFrame>,
+    // The native function being executed, if any. Natives are called like regular functions,
+    // and the tracer only learns that the callee is a native right before it's executed.
+    pending_native: Option<TraceFrame>,
+}
+
+// TODO: consider switching to a library like https://docs.rs/delegate/latest/delegate/.
+macro_rules! delegate {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&self, $($arg: $ty),*) -> $ret_ty {
+            self.base.$fn($($arg),*)
+        })*
+    };
+}
+
+macro_rules! delegate_mut {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $ty),*) -> $ret_ty {
+            self.base.$fn($($arg),*)
+        })*
+    };
+}
+
+macro_rules! delegate_charge {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $ty),*) -> $ret_ty {
+            self.delegate_charge(|base| base.$fn($($arg),*))
+        })*
+    };
+}
+
+fn trace_values(values: impl Iterator<Item = impl ValueView>) -> Vec<TracedValue> {
+    values.map(|val| TracedValue::new(&val)).collect()
+}
+
+fn function_name(module_id: &ModuleId, func_name: &str) -> String {
+    format!("{}::{}", module_id.short_str_lossless(), func_name)
+}
+
+fn type_name(ty: &impl TypeView) -> String {
+    ty.to_type_tag().to_string()
+}
+
+/// Returns the entry function at the root of the execution trace of a transaction payload (none
+/// for scripts). Multisig transactions can only be traced if their payload is in the transaction.
+pub fn traced_entry_function(
+    payload: &TransactionPayload,
+) -> anyhow::Result<Option<EntryFunction>> {
+    match payload {
+        TransactionPayload::Script(_) => Ok(None),
+        TransactionPayload::EntryFunction(entry_function) => Ok(Some(entry_function.clone())),
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                Ok(Some(entry_function.clone()))
+            },
+            None => anyhow::bail!("Multisig transactions without a payload can't be traced"),
+        },
+        TransactionPayload::ModuleBundle(_) => {
+            anyhow::bail!("Module bundle payload has been removed")
+        },
+    }
+}
+
+impl<G> ExecutionTracer<G> {
+    /// Creates a tracer rooted at the given entry function (see `traced_entry_function`), or at
+    /// a script if there is none.
+    pub fn new(base: G, entry_function: Option<&EntryFunction>) -> Self {
+        match entry_function {
+            Some(entry_function) => Self::new_function(
+                base,
+                entry_function.module().clone(),
+                entry_function.function().to_owned(),
+                entry_function.ty_args().to_vec(),
+            ),
+            None => Self::new_script(base),
+        }
+    }
+
+    pub fn new_script(base: G) -> Self {
+        Self {
+            base,
+            frames: vec![TraceFrame::new("script".to_string(), vec![], vec![])],
+            pending_native: None,
+        }
+    }
+
+    pub fn new_function(
+        base: G,
+        module_id: ModuleId,
+        func_name: Identifier,
+        ty_args: Vec<TypeTag>,
+    ) -> Self {
+        Self {
+            base,
+            frames: vec![TraceFrame::new(
+                function_name(&module_id, func_name.as_str()),
+                ty_args.iter().map(|ty| ty.to_string()).collect(),
+                vec![],
+            )],
+            pending_native: None,
+        }
+    }
+}
+
+impl<G> ExecutionTracer<G>
+where
+    G: AptosGasMeter,
+{
+    fn current_frame(&mut self) -> &mut TraceFrame {
+        self.frames.last_mut().expect("frame must exist")
+    }
+
+    fn record_event(&mut self, event: TraceEvent) {
+        self.current_frame().events.push(event);
+    }
+
+    /// Delegates the charging call to the base gas meter, and adds the cost to the current frame.
+    fn delegate_charge<F, R>(&mut self, charge: F) -> R
+    where
+        F: FnOnce(&mut G) -> R,
+    {
+        let old = self.base.balance_internal();
+        let res = charge(&mut self.base);
+        let new = self.base.balance_internal();
+        let cost = old.checked_sub(new).expect("gas cost must be non-negative");
+
+        self.current_frame().gas_used += u64::from(cost);
+        res
+    }
+
+    fn push_frame(&mut self, frame: TraceFrame) {
+        self.finish_pending_native();
+        self.frames.push(frame);
+    }
+
+    /// Pops the current frame, and adds it (and its gas) to the calling frame.
+    fn pop_frame(&mut self) {
+        let frame = self.frames.pop().expect("frame must exist");
+        let caller = self.current_frame();
+        caller.gas_used += frame.gas_used;
+        caller.events.push(TraceEvent::Call(frame));
+    }
+
+    /// Records the native being executed as a call to a native function. Note: natives that
+    /// request a module to be loaded are never charged, so they are only finished here, once
+    /// the execution moves on.
+    fn finish_pending_native(&mut self) {
+        if let Some(native) = self.pending_native.take() {
+            self.record_native_call(native, vec![]);
+        }
+    }
+
+    fn record_native_call(&mut self, native: TraceFrame, return_values: Vec<TracedValue>) {
+        let caller = self.current_frame();
+        caller.gas_used += native.gas_used;
+        if EVENT_NATIVES.contains(&native.function.as_str()) {
+            if let (Some(event_type), Some(data)) = (native.ty_args.first(), native.args.last()) {
+                caller.events.push(TraceEvent::EmitEvent {
+                    event_type: event_type.clone(),
+                    data: data.clone(),
+                });
+            }
+        }
+        caller.events.push(TraceEvent::NativeCall {
+            function: native.function,
+            ty_args: native.ty_args,
+            args: native.args,
+            return_values,
+            gas_used: native.gas_used,
+        });
+    }
+}
+
+impl<G> GasMeter for ExecutionTracer<G>
+where
+    G: AptosGasMeter,
+{
+    delegate_mut! {
+        // Note: we only use this callback for memory tracking, not for charging gas.
+        fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView)
+            -> PartialVMResult<()>;
+
+        // Note: we don't use this to charge gas so no need to record anything.
+        fn charge_drop_frame(
+            &mut self,
+            locals: impl Iterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+    }
+
+    delegate_charge! {
+        fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()>;
+
+        fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_pack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_unpack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_write_ref(
+            &mut self,
+            new_val: impl ValueView,
+            old_val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_exists(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            exists: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pack<'a>(
+            &mut self,
+            ty: impl TypeView + 'a,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_vec_borrow(
+            &mut self,
+            is_mut: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_push_back(
+            &mut self,
+            ty: impl TypeView,
+            val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pop_back(
+            &mut self,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_unpack(
+            &mut self,
+            ty: impl TypeView,
+            expect_num_elements: NumArgs,
+            elems: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_br_false(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_br_true(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_branch(&mut self, target_offset: CodeOffset) -> PartialVMResult<()>;
+
+        fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()>;
+
+        fn charge_create_ty(&mut self, num_nodes: NumTypeNodes) -> PartialVMResult<()>;
+
+        fn charge_dependency(
+            &mut self,
+            is_new: bool,
+            addr: &AccountAddress,
+            name: &IdentStr,
+            size: NumBytes,
+        ) -> PartialVMResult<()>;
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let traced_args = trace_values(args.clone());
+
+        // The call instruction is charged to the caller.
+        let res =
+            self.delegate_charge(|base| base.charge_call(module_id, func_name, args, num_locals));
+
+        self.push_frame(TraceFrame::new(
+            function_name(module_id, func_name),
+            vec![],
+            traced_args,
+        ));
+
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let ty_names = ty_args.clone().map(|ty| type_name(&ty)).collect();
+        let traced_args = trace_values(args.clone());
+
+        let res = self.delegate_charge(|base| {
+            base.charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+        });
+
+        self.push_frame(TraceFrame::new(
+            function_name(module_id, func_name),
+            ty_names,
+            traced_args,
+        ));
+
+        res
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        // The frame of the native was created (blindly) when the native got called.
+        let native = self.frames.pop().expect("frame must exist");
+        self.pending_native = Some(native);
+
+        self.base
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView> + Clone>,
+    ) -> PartialVMResult<()> {
+        let return_values = ret_vals.clone().map(trace_values);
+
+        let old = self.base.balance_internal();
+        let res = self.base.charge_native_function(amount, ret_vals);
+        let new = self.base.balance_internal();
+        let cost = old.checked_sub(new).expect("gas cost must be non-negative");
+
+        let mut native = self.pending_native.take().expect("native must be pending");
+        native.gas_used += u64::from(cost);
+        match return_values {
+            Some(return_values) => self.record_native_call(native, return_values),
+            // The native either aborted, or dispatched to a Move function (which is executed as
+            // if it was called by the native). In both cases, the native stays on the stack as a
+            // regular frame, which is either on the abort path, or returns with the values
+            // returned by the dispatch target.
+            None => self.frames.push(native),
+        }
+
+        res
+    }
+
+    fn observes_return_values(&self) -> bool {
+        true
+    }
+
+    fn observe_return_values(
+        &mut self,
+        ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        self.finish_pending_native();
+
+        let frame = self.current_frame();
+        frame.return_values = trace_values(ret_vals.clone());
+        frame.returned = true;
+        // The root frame is kept on the stack, so that it can be picked up by `finish`.
+        if self.frames.len() > 1 {
+            self.pop_frame();
+        }
+
+        self.base.observe_return_values(ret_vals)
+    }
+
+    fn charge_load_resource(
+        &mut self,
+        addr: AccountAddress,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+        bytes_loaded: NumBytes,
+    ) -> PartialVMResult<()> {
+        self.record_event(TraceEvent::LoadResource {
+            address: addr,
+            resource_type: type_name(&ty),
+            exists: val.is_some(),
+            bytes_loaded: bytes_loaded.into(),
+        });
+
+        self.delegate_charge(|base| base.charge_load_resource(addr, ty, val, bytes_loaded))
+    }
+
+    fn charge_borrow_global(
+        &mut self,
+        is_mut: bool,
+        is_generic: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.record_event(TraceEvent::BorrowGlobal {
+            resource_type: type_name(&ty),
+            is_mut,
+            is_success,
+        });
+
+        self.delegate_charge(|base| base.charge_borrow_global(is_mut, is_generic, ty, is_success))
+    }
+
+    fn charge_move_from(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.record_event(TraceEvent::MoveFrom {
+            resource_type: type_name(&ty),
+            value: val.as_ref().map(TracedValue::new),
+        });
+
+        self.delegate_charge(|base| base.charge_move_from(is_generic, ty, val))
+    }
+
+    fn charge_move_to(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: impl ValueView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        self.record_event(TraceEvent::MoveTo {
+            resource_type: type_name(&ty),
+            value: TracedValue::new(&val),
+            is_success,
+        });
+
+        self.delegate_charge(|base| base.charge_move_to(is_generic, ty, val, is_success))
+    }
+}
+
+impl<G> AptosGasMeter for ExecutionTracer<G>
+where
+    G: AptosGasMeter,
+{
+    type Algebra = G::Algebra;
+
+    delegate! {
+        fn algebra(&self) -> &Self::Algebra;
+    }
+
+    delegate_mut! {
+        fn algebra_mut(&mut self) -> &mut Self::Algebra;
+
+        fn charge_storage_fee(
+            &mut self,
+            amount: Fee,
+            gas_unit_price: FeePerGasUnit,
+        ) -> PartialVMResult<()>;
+
+        fn charge_io_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+
+        fn charge_io_gas_for_event(&mut self, event: &ContractEvent) -> VMResult<()>;
+
+        fn charge_io_gas_for_write(&mut self, key: &StateKey, op: &WriteOpSize) -> VMResult<()>;
+
+        fn process_storage_fee_for_all(
+            &mut self,
+            change_set: &mut impl ChangeSetInterface,
+            txn_size: NumBytes,
+            gas_unit_price: FeePerGasUnit,
+            executor_view: &dyn ExecutorView,
+        ) -> VMResult<Fee>;
+
+        fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+
+        fn charge_keyless(&mut self) -> VMResult<()>;
+    }
+}
+
+impl<G> ExecutionTracer<G>
+where
+    G: AptosGasMeter,
+{
+    /// Returns the execution trace. The frames that never returned (i.e., the frames on the
+    /// abort path, if the execution aborted) are closed and added to their callers.
+    pub fn finish(mut self) -> ExecutionTrace {
+        self.finish_pending_native();
+        while self.frames.len() > 1 {
+            self.pop_frame();
+        }
+
+        ExecutionTrace {
+            gas_unit_scaling_factor: self.base.gas_unit_scaling_factor().into(),
+            root: self.frames.pop().expect("frame must exist"),
+        }
+    }
+}
@@ -461,6 +461,19 @@ where
 
         self.base.charge_drop_frame(locals)
     }
+
+    #[inline]
+    fn observes_return_values(&self) -> bool {
+        self.base.observes_return_values()
+    }
+
+    #[inline]
+    fn observe_return_values(
+        &mut self,
+        ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        self.base.observe_return_values(ret_vals)
+    }
 }
 
 impl<G> AptosGasMeter for MemoryTrackedGasMeter<G>
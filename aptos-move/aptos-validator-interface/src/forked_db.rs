@@ -0,0 +1,156 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ForkedStateView, RestDebuggerInterface};
+use aptos_config::config::ForkConfig;
+use aptos_crypto::HashValue;
+use aptos_rest_client::Client;
+use aptos_storage_interface::{
+    cached_state_view::ShardedStateCache, state_delta::StateDelta, DbReader, DbReaderWriter,
+    DbWriter, Result, StateFallback, StateSnapshotReceiver,
+};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        state_value::StateValue,
+        ShardedStateUpdates, TStateView,
+    },
+    transaction::{TransactionOutputListWithProof, TransactionToCommit, Version},
+    write_set::TransactionWrite,
+};
+use std::{path::Path, sync::Arc};
+use tokio::runtime::Runtime;
+
+/// The file (in the storage directory) that caches the state fetched from the remote chain
+pub const FORK_CACHE_FILE: &str = "fork_state_cache.bcs";
+
+/// A DB that runs on top of the state of a remote chain at a pinned version (i.e., the DB of a
+/// local testnet in fork mode). Everything is served by the local DB, except for the state
+/// values that don't exist locally, which are fetched from the remote chain (and cached).
+///
+/// Note: local deletions are recorded when they are committed. The state that is deleted by an
+/// executed (but not yet committed) block is not served by the fallback either, as the
+/// `CachedStateView` skips the fallback for the deletions in its speculative state.
+pub struct ForkedDb {
+    db: DbReaderWriter,
+    fallback: Arc<RemoteStateFallback>,
+    // The runtime that fetches the state from the remote chain
+    _runtime: Runtime,
+}
+
+impl ForkedDb {
+    /// Forks the remote chain (as specified by the config) on top of the given DB. The state
+    /// fetched from the remote chain is cached in the given storage directory.
+    pub fn new(
+        db: DbReaderWriter,
+        fork_config: &ForkConfig,
+        storage_dir: &Path,
+    ) -> anyhow::Result<Self> {
+        let runtime = aptos_runtimes::spawn_named_runtime("fork".into(), None);
+        let remote = Arc::new(RestDebuggerInterface::new(Client::new(
+            fork_config.url.clone(),
+        )));
+
+        // The forked view fetches the remote state on the current runtime
+        let forked_view = {
+            let _guard = runtime.enter();
+            ForkedStateView::new_with_cache(
+                remote,
+                fork_config.version,
+                &storage_dir.join(FORK_CACHE_FILE),
+            )?
+        };
+
+        Ok(Self {
+            db,
+            fallback: Arc::new(RemoteStateFallback { forked_view }),
+            _runtime: runtime,
+        })
+    }
+}
+
+impl DbReader for ForkedDb {
+    fn get_read_delegatee(&self) -> &dyn DbReader {
+        self.db.reader.as_ref()
+    }
+
+    fn get_state_fallback(&self) -> Option<Arc<dyn StateFallback>> {
+        Some(self.fallback.clone())
+    }
+}
+
+impl DbWriter for ForkedDb {
+    fn get_state_snapshot_receiver(
+        &self,
+        version: Version,
+        expected_root_hash: HashValue,
+    ) -> Result<Box<dyn StateSnapshotReceiver<StateKey, StateValue>>> {
+        self.db
+            .writer
+            .get_state_snapshot_receiver(version, expected_root_hash)
+    }
+
+    fn finalize_state_snapshot(
+        &self,
+        version: Version,
+        output_with_proof: TransactionOutputListWithProof,
+        ledger_infos: &[LedgerInfoWithSignatures],
+    ) -> Result<()> {
+        self.db
+            .writer
+            .finalize_state_snapshot(version, output_with_proof, ledger_infos)
+    }
+
+    fn save_transactions(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        base_state_version: Option<Version>,
+        ledger_info_with_sigs: Option<&LedgerInfoWithSignatures>,
+        sync_commit: bool,
+        latest_in_memory_state: StateDelta,
+        state_updates_until_last_checkpoint: Option<ShardedStateUpdates>,
+        sharded_state_cache: Option<&ShardedStateCache>,
+    ) -> Result<()> {
+        self.db.writer.save_transactions(
+            txns_to_commit,
+            first_version,
+            base_state_version,
+            ledger_info_with_sigs,
+            sync_commit,
+            latest_in_memory_state,
+            state_updates_until_last_checkpoint,
+            sharded_state_cache,
+        )?;
+
+        // The state deleted locally must no longer be fetched from the remote chain
+        let forked_view = &self.fallback.forked_view;
+        forked_view.apply_deletions(
+            txns_to_commit
+                .iter()
+                .flat_map(|txn_to_commit| txn_to_commit.write_set.iter())
+                .filter(|(_, write_op)| write_op.is_deletion())
+                .map(|(state_key, _)| state_key),
+        );
+        Ok(forked_view.persist_cache()?)
+    }
+}
+
+/// Serves the state that doesn't exist in the local DB from the remote chain
+struct RemoteStateFallback {
+    forked_view: ForkedStateView,
+}
+
+impl StateFallback for RemoteStateFallback {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        // The framework (and the other special addresses) always come from the local genesis,
+        // so that the local validator set and on-chain configs are consistent.
+        if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+            if access_path.address.is_special() {
+                return Ok(None);
+            }
+        }
+        Ok(self.forked_view.get_state_value(state_key)?)
+    }
+}
@@ -0,0 +1,451 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AptosValidatorInterface, DebuggerStateView};
+use anyhow::{ensure, Result};
+use aptos_types::{
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        Result as StateViewResult, StateViewId, TStateView,
+    },
+    transaction::Version,
+    write_set::{TransactionWrite, WriteSet},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// The length of the cache file header (i.e., the fork version)
+const CACHE_HEADER_LEN: usize = 8;
+
+/// The length of the prefix of each cache entry (i.e., the entry length)
+const ENTRY_LENGTH_PREFIX_LEN: usize = 4;
+
+/// An entry of the cache file. The cache file starts with the fork version, followed by the
+/// entries (each prefixed by its length), in the order they were added. Entries are appended as
+/// they are persisted, so persisting the cache only writes the entries added since.
+#[derive(Debug, Deserialize, Serialize)]
+enum CacheEntry {
+    /// A state value fetched from the remote chain
+    Remote(StateKey, Option<StateValue>),
+    /// A local write (or deletion) on top of the fork
+    Local(StateKey, Option<StateValue>),
+}
+
+/// A view of the state of a remote chain, forked at a pinned version (similar to an Anvil or
+/// Hardhat fork). Reads are served (in order) by:
+/// 1. the local writes, i.e., the write sets of the transactions executed on top of the fork,
+/// 2. the cache of the state values previously fetched from the remote chain,
+/// 3. the remote chain itself (at the fork version), in which case the value is cached.
+///
+/// The cache (and the local writes) can be persisted to a file, so that restarting the fork
+/// doesn't fetch the same state values again.
+pub struct ForkedStateView {
+    remote_view: DebuggerStateView,
+    fork_version: Version,
+    cache_path: Option<PathBuf>,
+    remote_cache: RwLock<HashMap<StateKey, Option<StateValue>>>,
+    local_writes: RwLock<HashMap<StateKey, Option<StateValue>>>,
+    // The cache entries added since the cache was last persisted (if it is persisted)
+    pending_entries: Mutex<Vec<CacheEntry>>,
+}
+
+impl ForkedStateView {
+    /// Forks the remote chain at the given version (i.e., all reads observe the state after
+    /// the transaction at `fork_version` was committed). Note: this must be called from within
+    /// a tokio runtime.
+    pub fn new(remote: Arc<dyn AptosValidatorInterface + Send>, fork_version: Version) -> Self {
+        Self::new_with_overrides(remote, fork_version, HashMap::new())
+    }
+
+    /// Same as `new`, but the given state values override the state of the remote chain
+    /// (local writes still take precedence over the overrides).
+    pub fn new_with_overrides(
+        remote: Arc<dyn AptosValidatorInterface + Send>,
+        fork_version: Version,
+        overrides: HashMap<StateKey, Option<StateValue>>,
+    ) -> Self {
+        Self {
+            // The debugger state view reads the state before the given version
+            remote_view: DebuggerStateView::new_with_overrides(remote, fork_version + 1, overrides),
+            fork_version,
+            cache_path: None,
+            remote_cache: RwLock::new(HashMap::new()),
+            local_writes: RwLock::new(HashMap::new()),
+            pending_entries: Mutex::new(vec![]),
+        }
+    }
+
+    /// Same as `new`, but the fetched state values (and the local writes) are cached in the
+    /// given file. If the file already exists, the previously fetched values and local writes
+    /// are loaded (the cache must have been created for the same fork version).
+    pub fn new_with_cache(
+        remote: Arc<dyn AptosValidatorInterface + Send>,
+        fork_version: Version,
+        cache_path: &Path,
+    ) -> Result<Self> {
+        let mut forked_view = Self::new(remote, fork_version);
+        if cache_path.exists() {
+            let bytes = fs::read(cache_path)?;
+            ensure!(
+                bytes.len() >= CACHE_HEADER_LEN,
+                "The cache at {:?} is corrupted",
+                cache_path
+            );
+            let cache_version: Version = bcs::from_bytes(&bytes[..CACHE_HEADER_LEN])?;
+            ensure!(
+                cache_version == fork_version,
+                "The cache at {:?} was created for version {}, but the fork version is {}",
+                cache_path,
+                cache_version,
+                fork_version
+            );
+
+            // Load the entries (in order). A trailing entry may be incomplete if the node
+            // crashed while persisting it, in which case it is dropped.
+            let mut offset = CACHE_HEADER_LEN;
+            let remote_cache = forked_view.remote_cache.get_mut().unwrap();
+            let local_writes = forked_view.local_writes.get_mut().unwrap();
+            while let Some(entry_bytes) = read_length_prefixed(&bytes, offset) {
+                match bcs::from_bytes::<CacheEntry>(entry_bytes)? {
+                    CacheEntry::Remote(state_key, state_value) => {
+                        remote_cache.insert(state_key, state_value)
+                    },
+                    CacheEntry::Local(state_key, state_value) => {
+                        local_writes.insert(state_key, state_value)
+                    },
+                };
+                offset += ENTRY_LENGTH_PREFIX_LEN + entry_bytes.len();
+            }
+            if offset < bytes.len() {
+                OpenOptions::new()
+                    .write(true)
+                    .open(cache_path)?
+                    .set_len(offset as u64)?;
+            }
+        } else {
+            fs::write(cache_path, bcs::to_bytes(&fork_version)?)?;
+        }
+        forked_view.cache_path = Some(cache_path.to_path_buf());
+        Ok(forked_view)
+    }
+
+    pub fn fork_version(&self) -> Version {
+        self.fork_version
+    }
+
+    /// Returns the number of state values fetched from the remote chain so far
+    pub fn num_cached_values(&self) -> usize {
+        self.remote_cache.read().unwrap().len()
+    }
+
+    /// Applies the write set of a locally executed transaction on top of the fork
+    pub fn apply_write_set(&self, write_set: &WriteSet) {
+        let mut local_writes = self.local_writes.write().unwrap();
+        for (state_key, write_op) in write_set.iter() {
+            local_writes.insert(state_key.clone(), write_op.as_state_value());
+            self.add_pending_entry(|| {
+                CacheEntry::Local(state_key.clone(), write_op.as_state_value())
+            });
+        }
+    }
+
+    /// Deletes the given state keys on top of the fork. This is useful if the local writes are
+    /// stored elsewhere (e.g., in a local DB), as deleted keys must not be fetched again.
+    pub fn apply_deletions<'a>(&self, state_keys: impl IntoIterator<Item = &'a StateKey>) {
+        let mut local_writes = self.local_writes.write().unwrap();
+        for state_key in state_keys {
+            local_writes.insert(state_key.clone(), None);
+            self.add_pending_entry(|| CacheEntry::Local(state_key.clone(), None));
+        }
+    }
+
+    /// Appends the state values fetched from the remote chain and the local writes (added
+    /// since the cache was last persisted) to the cache file, if any
+    pub fn persist_cache(&self) -> Result<()> {
+        let cache_path = match &self.cache_path {
+            Some(cache_path) => cache_path,
+            None => return Ok(()),
+        };
+        let pending_entries = std::mem::take(&mut *self.pending_entries.lock().unwrap());
+        if pending_entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut bytes = vec![];
+        for entry in pending_entries {
+            let entry_bytes = bcs::to_bytes(&entry)?;
+            bytes.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&entry_bytes);
+        }
+        // The entries are written at once, so a crash leaves at most one incomplete entry
+        // (which is dropped on load)
+        let mut file = OpenOptions::new().append(true).open(cache_path)?;
+        file.write_all(&bytes)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    fn add_pending_entry(&self, entry: impl FnOnce() -> CacheEntry) {
+        if self.cache_path.is_some() {
+            self.pending_entries.lock().unwrap().push(entry());
+        }
+    }
+}
+
+/// Returns the length prefixed bytes at the given offset, if complete
+fn read_length_prefixed(bytes: &[u8], offset: usize) -> Option<&[u8]> {
+    let start = offset.checked_add(ENTRY_LENGTH_PREFIX_LEN)?;
+    let length_bytes = bytes.get(offset..start)?;
+    let length = u32::from_le_bytes(length_bytes.try_into().ok()?) as usize;
+    bytes.get(start..start.checked_add(length)?)
+}
+
+impl TStateView for ForkedStateView {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        StateViewId::Miscellaneous
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+        if let Some(state_value) = self.local_writes.read().unwrap().get(state_key) {
+            return Ok(state_value.clone());
+        }
+        if let Some(state_value) = self.remote_cache.read().unwrap().get(state_key) {
+            return Ok(state_value.clone());
+        }
+
+        let state_value = self.remote_view.get_state_value(state_key)?;
+        self.remote_cache
+            .write()
+            .unwrap()
+            .insert(state_key.clone(), state_value.clone());
+        self.add_pending_entry(|| CacheEntry::Remote(state_key.clone(), state_value.clone()));
+        Ok(state_value)
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        // The storage usage of the remote chain isn't available
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FilterCondition;
+    use aptos_framework::natives::code::PackageMetadata;
+    use aptos_types::{
+        account_address::AccountAddress,
+        transaction::{Transaction, TransactionInfo},
+        write_set::{WriteOp, WriteSetMut},
+    };
+    use move_core_types::language_storage::ModuleId;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A remote chain with a fixed state, which counts the number of state reads
+    #[derive(Default)]
+    struct MockRemote {
+        state: HashMap<StateKey, StateValue>,
+        num_reads: Arc<AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl AptosValidatorInterface for MockRemote {
+        async fn get_state_value_by_version(
+            &self,
+            state_key: &StateKey,
+            _version: Version,
+        ) -> Result<Option<StateValue>> {
+            self.num_reads.fetch_add(1, Ordering::SeqCst);
+            Ok(self.state.get(state_key).cloned())
+        }
+
+        async fn get_committed_transactions(
+            &self,
+            _start: Version,
+            _limit: u64,
+        ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+            unimplemented!()
+        }
+
+        async fn get_and_filter_committed_transactions(
+            &self,
+            _start: Version,
+            _limit: u64,
+            _filter_condition: FilterCondition,
+            _package_cache: &mut HashMap<
+                ModuleId,
+                (
+                    AccountAddress,
+                    String,
+                    HashMap<(AccountAddress, String), PackageMetadata>,
+                ),
+            >,
+        ) -> Result<
+            Vec<(
+                u64,
+                Transaction,
+                Option<(
+                    AccountAddress,
+                    String,
+                    HashMap<(AccountAddress, String), PackageMetadata>,
+                )>,
+            )>,
+        > {
+            unimplemented!()
+        }
+
+        async fn get_latest_ledger_info_version(&self) -> Result<Version> {
+            unimplemented!()
+        }
+
+        async fn get_version_by_account_sequence(
+            &self,
+            _account: AccountAddress,
+            _seq: u64,
+        ) -> Result<Option<Version>> {
+            unimplemented!()
+        }
+    }
+
+    fn state_key(name: &str) -> StateKey {
+        StateKey::raw(name.as_bytes())
+    }
+
+    fn state_value(value: &str) -> StateValue {
+        StateValue::new_legacy(value.as_bytes().to_vec().into())
+    }
+
+    fn create_remote(num_reads: Arc<AtomicUsize>) -> Arc<dyn AptosValidatorInterface + Send> {
+        Arc::new(MockRemote {
+            state: HashMap::from([
+                (state_key("a"), state_value("remote a")),
+                (state_key("b"), state_value("remote b")),
+            ]),
+            num_reads,
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_forked_state_view_reads() {
+        let num_reads = Arc::new(AtomicUsize::new(0));
+        let forked_view = ForkedStateView::new(create_remote(num_reads.clone()), 10);
+
+        // Remote values are fetched lazily, and only once
+        for _ in 0..3 {
+            assert_eq!(
+                forked_view.get_state_value(&state_key("a")).unwrap(),
+                Some(state_value("remote a"))
+            );
+            assert_eq!(forked_view.get_state_value(&state_key("c")).unwrap(), None);
+        }
+        assert_eq!(num_reads.load(Ordering::SeqCst), 2);
+        assert_eq!(forked_view.num_cached_values(), 2);
+
+        // Local writes take precedence over the remote state
+        let write_set = WriteSetMut::new(vec![
+            (state_key("a"), WriteOp::legacy_deletion()),
+            (
+                state_key("b"),
+                WriteOp::legacy_modification(state_value("local b").bytes().clone()),
+            ),
+        ])
+        .freeze()
+        .unwrap();
+        forked_view.apply_write_set(&write_set);
+        assert_eq!(forked_view.get_state_value(&state_key("a")).unwrap(), None);
+        assert_eq!(
+            forked_view.get_state_value(&state_key("b")).unwrap(),
+            Some(state_value("local b"))
+        );
+        assert_eq!(num_reads.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_forked_state_view_overrides() {
+        let num_reads = Arc::new(AtomicUsize::new(0));
+        let overrides = HashMap::from([
+            (state_key("a"), Some(state_value("override a"))),
+            (state_key("b"), None),
+        ]);
+        let forked_view =
+            ForkedStateView::new_with_overrides(create_remote(num_reads.clone()), 10, overrides);
+
+        // Overridden values are never fetched from the remote chain
+        assert_eq!(
+            forked_view.get_state_value(&state_key("a")).unwrap(),
+            Some(state_value("override a"))
+        );
+        assert_eq!(forked_view.get_state_value(&state_key("b")).unwrap(), None);
+        assert_eq!(num_reads.load(Ordering::SeqCst), 0);
+
+        // Local writes take precedence over the overrides
+        let write_set = WriteSetMut::new(vec![(
+            state_key("a"),
+            WriteOp::legacy_modification(state_value("local a").bytes().clone()),
+        )])
+        .freeze()
+        .unwrap();
+        forked_view.apply_write_set(&write_set);
+        assert_eq!(
+            forked_view.get_state_value(&state_key("a")).unwrap(),
+            Some(state_value("local a"))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_forked_state_view_cache() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache_path = cache_dir.path().join("fork_cache.bcs");
+
+        // Fetch a value and persist the cache
+        let num_reads = Arc::new(AtomicUsize::new(0));
+        let forked_view =
+            ForkedStateView::new_with_cache(create_remote(num_reads.clone()), 10, &cache_path)
+                .unwrap();
+        forked_view.get_state_value(&state_key("a")).unwrap();
+        forked_view.apply_deletions([&state_key("b")]);
+        forked_view.persist_cache().unwrap();
+        assert_eq!(num_reads.load(Ordering::SeqCst), 1);
+
+        // Persisting again only appends the new entries
+        let cache_len = fs::metadata(&cache_path).unwrap().len();
+        forked_view.persist_cache().unwrap();
+        assert_eq!(fs::metadata(&cache_path).unwrap().len(), cache_len);
+        forked_view.get_state_value(&state_key("c")).unwrap();
+        forked_view.persist_cache().unwrap();
+        assert!(fs::metadata(&cache_path).unwrap().len() > cache_len);
+        assert_eq!(num_reads.load(Ordering::SeqCst), 2);
+
+        // Simulate a crash while appending an entry
+        let cache_len = fs::metadata(&cache_path).unwrap().len();
+        let mut file = OpenOptions::new().append(true).open(&cache_path).unwrap();
+        file.write_all(&[100, 0, 0, 0, 1]).unwrap();
+
+        // A new fork at the same version reuses the cached values (and the deletion), and
+        // drops the incomplete entry
+        let forked_view =
+            ForkedStateView::new_with_cache(create_remote(num_reads.clone()), 10, &cache_path)
+                .unwrap();
+        assert_eq!(fs::metadata(&cache_path).unwrap().len(), cache_len);
+        assert_eq!(
+            forked_view.get_state_value(&state_key("a")).unwrap(),
+            Some(state_value("remote a"))
+        );
+        assert_eq!(forked_view.get_state_value(&state_key("b")).unwrap(), None);
+        assert_eq!(forked_view.get_state_value(&state_key("c")).unwrap(), None);
+        assert_eq!(num_reads.load(Ordering::SeqCst), 2);
+
+        // A fork at a different version can't use the cache
+        assert!(
+            ForkedStateView::new_with_cache(create_remote(num_reads), 11, &cache_path).is_err()
+        );
+    }
+}
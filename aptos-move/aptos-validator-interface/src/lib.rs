@@ -2,10 +2,17 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod forked_db;
+mod forked_state_view;
 mod rest_interface;
 mod storage_interface;
 
-pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
+pub use crate::{
+    forked_db::{ForkedDb, FORK_CACHE_FILE},
+    forked_state_view::ForkedStateView,
+    rest_interface::RestDebuggerInterface,
+    storage_interface::DBDebuggerInterface,
+};
 use anyhow::Result;
 use aptos_framework::natives::code::PackageMetadata;
 use aptos_types::{
@@ -91,6 +98,9 @@ pub struct DebuggerStateView {
         )>,
     >,
     version: Version,
+    /// State values that take precedence over the state of the chain (`None` overrides the
+    /// state value as deleted)
+    overrides: HashMap<StateKey, Option<StateValue>>,
 }
 
 async fn handler_thread<'a>(
@@ -135,11 +145,22 @@ async fn handler_thread<'a>(
 
 impl DebuggerStateView {
     pub fn new(db: Arc<dyn AptosValidatorInterface + Send>, version: Version) -> Self {
+        Self::new_with_overrides(db, version, HashMap::new())
+    }
+
+    /// Same as `new`, but the given state values are served instead of the state of the chain
+    /// (e.g., to replay transactions with patched modules or modified resources).
+    pub fn new_with_overrides(
+        db: Arc<dyn AptosValidatorInterface + Send>,
+        version: Version,
+        overrides: HashMap<StateKey, Option<StateValue>>,
+    ) -> Self {
         let (query_sender, thread_receiver) = unbounded_channel();
         tokio::spawn(async move { handler_thread(db, thread_receiver).await });
         Self {
             query_sender: Mutex::new(query_sender),
             version,
+            overrides,
         }
     }
 
@@ -165,6 +186,9 @@ impl TStateView for DebuggerStateView {
     }
 
     fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+        if let Some(state_value) = self.overrides.get(state_key) {
+            return Ok(state_value.clone());
+        }
         self.get_state_value_internal(state_key, self.version)
             .map_err(Into::into)
     }
@@ -0,0 +1,193 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_aggregator::{
+    bounded_math::SignedU128,
+    resolver::{TAggregatorV1View, TDelayedFieldView},
+    types::{DelayedFieldValue, DelayedFieldsSpeculativeError, PanicOr},
+};
+use aptos_infallible::Mutex;
+use aptos_types::{
+    delayed_fields::PanicError,
+    state_store::{
+        errors::StateviewError,
+        state_key::StateKey,
+        state_storage_usage::StateStorageUsage,
+        state_value::{StateValue, StateValueMetadata},
+        Result as StateViewResult, StateView, StateViewId, TStateView,
+    },
+};
+use aptos_vm_types::resolver::{StateStorageView, TModuleView, TResourceView};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::{language_storage::StructTag, value::MoveTypeLayout};
+use move_vm_types::delayed_values::delayed_field_id::DelayedFieldID;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    sync::Arc,
+};
+
+/// The state keys a transaction reads and writes (i.e., its read set and write set).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionAccessList {
+    /// All the keys read during the execution, including the keys which don't exist
+    pub read_set: BTreeSet<StateKey>,
+    /// All the keys written or deleted by the output of the transaction
+    pub write_set: BTreeSet<StateKey>,
+    /// The keys of the aggregators (V1) read or written by the transaction. Aggregators are
+    /// stored as table items, so they can't be told apart from other table items by their keys.
+    pub aggregator_v1_keys: BTreeSet<StateKey>,
+}
+
+/// A state view which records all the keys read through it.
+pub(crate) struct AccessRecordingStateView<'s, S> {
+    state_view: &'s S,
+    read_set: Mutex<BTreeSet<StateKey>>,
+}
+
+impl<'s, S> AccessRecordingStateView<'s, S> {
+    pub(crate) fn new(state_view: &'s S) -> Self {
+        Self {
+            state_view,
+            read_set: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    pub(crate) fn into_read_set(self) -> BTreeSet<StateKey> {
+        self.read_set.into_inner()
+    }
+}
+
+impl<'s, S: TStateView<Key = StateKey>> TStateView for AccessRecordingStateView<'s, S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.state_view.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> StateViewResult<Option<StateValue>> {
+        self.read_set.lock().insert(state_key.clone());
+        self.state_view.get_state_value(state_key)
+    }
+
+    fn get_usage(&self) -> StateViewResult<StateStorageUsage> {
+        self.state_view.get_usage()
+    }
+}
+
+/// An executor view which records the keys read as aggregators (V1). The state view based
+/// implementation of `TAggregatorV1View` reads aggregators like any other state, so these
+/// reads are only distinguishable here.
+pub(crate) struct AggregatorV1RecordingView<'s, S> {
+    state_view: &'s S,
+    aggregator_v1_reads: Mutex<BTreeSet<StateKey>>,
+}
+
+impl<'s, S> AggregatorV1RecordingView<'s, S> {
+    pub(crate) fn new(state_view: &'s S) -> Self {
+        Self {
+            state_view,
+            aggregator_v1_reads: Mutex::new(BTreeSet::new()),
+        }
+    }
+
+    pub(crate) fn into_aggregator_v1_reads(self) -> BTreeSet<StateKey> {
+        self.aggregator_v1_reads.into_inner()
+    }
+}
+
+impl<'s, S: StateView> TResourceView for AggregatorV1RecordingView<'s, S> {
+    type Key = StateKey;
+    type Layout = MoveTypeLayout;
+
+    fn get_resource_state_value(
+        &self,
+        state_key: &Self::Key,
+        maybe_layout: Option<&Self::Layout>,
+    ) -> PartialVMResult<Option<StateValue>> {
+        self.state_view
+            .get_resource_state_value(state_key, maybe_layout)
+    }
+}
+
+impl<'s, S: StateView> TModuleView for AggregatorV1RecordingView<'s, S> {
+    type Key = StateKey;
+
+    fn get_module_state_value(&self, state_key: &Self::Key) -> PartialVMResult<Option<StateValue>> {
+        self.state_view.get_module_state_value(state_key)
+    }
+}
+
+impl<'s, S: StateView> TAggregatorV1View for AggregatorV1RecordingView<'s, S> {
+    type Identifier = StateKey;
+
+    fn get_aggregator_v1_state_value(
+        &self,
+        id: &Self::Identifier,
+    ) -> PartialVMResult<Option<StateValue>> {
+        self.aggregator_v1_reads.lock().insert(id.clone());
+        self.state_view.get_aggregator_v1_state_value(id)
+    }
+}
+
+impl<'s, S: StateView> TDelayedFieldView for AggregatorV1RecordingView<'s, S> {
+    type Identifier = DelayedFieldID;
+    type ResourceGroupTag = StructTag;
+    type ResourceKey = StateKey;
+
+    fn get_delayed_field_value(
+        &self,
+        id: &Self::Identifier,
+    ) -> Result<DelayedFieldValue, PanicOr<DelayedFieldsSpeculativeError>> {
+        self.state_view.get_delayed_field_value(id)
+    }
+
+    fn delayed_field_try_add_delta_outcome(
+        &self,
+        id: &Self::Identifier,
+        base_delta: &SignedU128,
+        delta: &SignedU128,
+        max_value: u128,
+    ) -> Result<bool, PanicOr<DelayedFieldsSpeculativeError>> {
+        self.state_view
+            .delayed_field_try_add_delta_outcome(id, base_delta, delta, max_value)
+    }
+
+    fn generate_delayed_field_id(&self, width: u32) -> Self::Identifier {
+        self.state_view.generate_delayed_field_id(width)
+    }
+
+    fn validate_delayed_field_id(&self, id: &Self::Identifier) -> Result<(), PanicError> {
+        self.state_view.validate_delayed_field_id(id)
+    }
+
+    fn get_reads_needing_exchange(
+        &self,
+        delayed_write_set_ids: &HashSet<Self::Identifier>,
+        skip: &HashSet<Self::ResourceKey>,
+    ) -> Result<
+        BTreeMap<Self::ResourceKey, (StateValueMetadata, u64, Arc<MoveTypeLayout>)>,
+        PanicError,
+    > {
+        self.state_view
+            .get_reads_needing_exchange(delayed_write_set_ids, skip)
+    }
+
+    fn get_group_reads_needing_exchange(
+        &self,
+        delayed_write_set_ids: &HashSet<Self::Identifier>,
+        skip: &HashSet<Self::ResourceKey>,
+    ) -> PartialVMResult<BTreeMap<Self::ResourceKey, (StateValueMetadata, u64)>> {
+        self.state_view
+            .get_group_reads_needing_exchange(delayed_write_set_ids, skip)
+    }
+}
+
+impl<'s, S: StateView> StateStorageView for AggregatorV1RecordingView<'s, S> {
+    fn id(&self) -> StateViewId {
+        StateStorageView::id(self.state_view)
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage, StateviewError> {
+        StateStorageView::get_usage(self.state_view)
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    access_list::{AccessRecordingStateView, AggregatorV1RecordingView, TransactionAccessList},
     block_executor::{AptosTransactionOutput, BlockAptosVM},
     counters::*,
     data_cache::{AsMoveResolver, StorageAdapter},
@@ -43,7 +44,10 @@ use aptos_types::state_store::StateViewId;
 use aptos_types::{
     account_config::{self, new_block_event_key, AccountResource},
     block_executor::{
-        config::{BlockExecutorConfig, BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig},
+        config::{
+            AdaptiveConcurrency, AdaptiveConcurrencyConfig, BlockExecutorConfig,
+            BlockExecutorConfigFromOnchain, BlockExecutorLocalConfig,
+        },
         partitioner::PartitionedTransactions,
     },
     block_metadata::BlockMetadata,
@@ -114,6 +118,7 @@ use std::{
     cmp::{max, min},
     collections::{BTreeMap, BTreeSet},
     marker::Sync,
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -121,6 +126,8 @@ static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 static DISCARD_FAILED_BLOCKS: OnceCell<bool> = OnceCell::new();
+static CONFLICT_ANALYTICS_DIR: OnceCell<PathBuf> = OnceCell::new();
+static ADAPTIVE_CONCURRENCY: OnceCell<AdaptiveConcurrency> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
 
 macro_rules! deprecated_module_bundle {
@@ -236,6 +243,15 @@ impl AptosVM {
         env: Arc<Environment>,
         state_view: &impl StateView,
         inject_create_signer_for_gov_sim: bool,
+    ) -> Self {
+        Self::new_impl(env, state_view, inject_create_signer_for_gov_sim, true)
+    }
+
+    fn new_impl(
+        env: Arc<Environment>,
+        state_view: &impl StateView,
+        inject_create_signer_for_gov_sim: bool,
+        use_warm_vm_cache: bool,
     ) -> Self {
         let _timer = TIMER.timer_with(&["AptosVM::new"]);
 
@@ -243,14 +259,23 @@ impl AptosVM {
             get_gas_parameters(env.features(), state_view);
 
         let resolver = state_view.as_move_resolver();
-        let move_vm = MoveVmExt::new_with_extended_options(
-            gas_feature_version,
-            gas_params.as_ref(),
-            env,
-            None,
-            inject_create_signer_for_gov_sim,
-            &resolver,
-        );
+        let move_vm = if use_warm_vm_cache {
+            MoveVmExt::new_with_extended_options(
+                gas_feature_version,
+                gas_params.as_ref(),
+                env,
+                None,
+                inject_create_signer_for_gov_sim,
+                &resolver,
+            )
+        } else {
+            MoveVmExt::new_with_empty_code_cache(
+                gas_feature_version,
+                gas_params.as_ref(),
+                env,
+                &resolver,
+            )
+        };
 
         // We use an `Option` to handle the VK not being set on-chain, or an incorrect VK being set
         // via governance (although, currently, we do check for that in `keyless_account.move`).
@@ -343,6 +368,34 @@ impl AptosVM {
         }
     }
 
+    /// Sets the directory to write the conflict analytics of parallel block execution to,
+    /// when invoked the first time.
+    pub fn set_conflict_analytics_dir_once(dir: PathBuf) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        CONFLICT_ANALYTICS_DIR.set(dir).ok();
+    }
+
+    /// Get the conflict analytics directory if already set, otherwise return default (None,
+    /// i.e. conflict analytics are not collected).
+    pub fn get_conflict_analytics_dir() -> Option<PathBuf> {
+        CONFLICT_ANALYTICS_DIR.get().cloned()
+    }
+
+    /// Enables adapting the number of parallel execution workers to the contention of the
+    /// workload, when invoked the first time. The concurrency level is the maximum.
+    pub fn set_adaptive_concurrency_once(config: AdaptiveConcurrencyConfig) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        ADAPTIVE_CONCURRENCY
+            .set(AdaptiveConcurrency::new(config))
+            .ok();
+    }
+
+    /// Get the adaptive concurrency of the executor of this process if already set, otherwise
+    /// return default (None, i.e. the concurrency level is static).
+    pub fn get_adaptive_concurrency() -> Option<AdaptiveConcurrency> {
+        ADAPTIVE_CONCURRENCY.get().cloned()
+    }
+
     /// Sets the # of async proof reading threads.
     pub fn set_num_proof_reading_threads_once(mut num_threads: usize) {
         // TODO(grao): Do more analysis to tune this magic number.
@@ -2568,6 +2621,8 @@ impl VMExecutor for AptosVM {
                     concurrency_level: Self::get_concurrency_level(),
                     allow_fallback: true,
                     discard_failed_blocks: Self::get_discard_failed_blocks(),
+                    conflict_analytics_dir: Self::get_conflict_analytics_dir(),
+                    adaptive_concurrency: Self::get_adaptive_concurrency(),
                 },
                 onchain: onchain_config,
             },
@@ -2745,6 +2800,90 @@ impl AptosSimulationVM {
             .expect("Materializing aggregator V1 deltas should never fail");
         (vm_status, txn_output)
     }
+
+    /// Same as `create_vm_and_simulate_signed_transaction`, but also returns the access list of
+    /// the transaction. The VM starts with an empty code cache, so that the modules used by the
+    /// transaction are read from the state view like the rest of its reads.
+    pub fn create_vm_and_simulate_signed_transaction_with_access_list(
+        transaction: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutput, TransactionAccessList) {
+        assert_err!(
+            transaction.verify_signature(),
+            "Simulated transaction should not have a valid signature"
+        );
+
+        // The environment and the gas parameters are read through the recording view as
+        // well, so that the on-chain configs used by the transaction are in its read set.
+        let recording_state_view = AccessRecordingStateView::new(state_view);
+        let env = Arc::new(Environment::new(&recording_state_view));
+        let mut vm = AptosVM::new_impl(env, &recording_state_view, false, false);
+        vm.is_simulation = true;
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+
+        let executor_view = AggregatorV1RecordingView::new(&recording_state_view);
+        let resolver = vm.as_move_resolver(&executor_view);
+        let (vm_status, vm_output) =
+            vm.execute_user_transaction(&resolver, transaction, &log_context);
+        let mut aggregator_v1_keys: BTreeSet<_> = vm_output
+            .aggregator_v1_write_set()
+            .keys()
+            .chain(vm_output.aggregator_v1_delta_set().keys())
+            .cloned()
+            .collect();
+        let txn_output = vm_output
+            .try_materialize_into_transaction_output(&resolver)
+            .expect("Materializing aggregator V1 deltas should never fail");
+        drop(resolver);
+
+        // Aggregators which are only read are not in the output, e.g., if the transaction
+        // aborted after reading them.
+        aggregator_v1_keys.extend(executor_view.into_aggregator_v1_reads());
+        let access_list = TransactionAccessList {
+            read_set: recording_state_view.into_read_set(),
+            write_set: txn_output
+                .write_set()
+                .iter()
+                .map(|(state_key, _)| state_key.clone())
+                .collect(),
+            aggregator_v1_keys,
+        };
+        (vm_status, txn_output, access_list)
+    }
+
+    /// Same as `create_vm_and_simulate_signed_transaction`, but the production gas meter is
+    /// wrapped by the given function (e.g., to trace or profile the execution), and the
+    /// resulting gas meter is returned along with the output.
+    pub fn create_vm_and_simulate_signed_transaction_with_modified_gas_meter<G, F>(
+        transaction: &SignedTransaction,
+        state_view: &impl StateView,
+        modify_gas_meter: F,
+    ) -> Result<(VMStatus, TransactionOutput, G), VMStatus>
+    where
+        F: FnOnce(ProdGasMeter) -> G,
+        G: AptosGasMeter,
+    {
+        assert_err!(
+            transaction.verify_signature(),
+            "Simulated transaction should not have a valid signature"
+        );
+
+        let vm = Self::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+
+        let resolver = state_view.as_move_resolver();
+        let (vm_status, vm_output, gas_meter) =
+            vm.0.execute_user_transaction_with_modified_gas_meter(
+                &resolver,
+                transaction,
+                &log_context,
+                modify_gas_meter,
+            )?;
+        let txn_output = vm_output
+            .try_materialize_into_transaction_output(&resolver)
+            .expect("Materializing aggregator V1 deltas should never fail");
+        Ok((vm_status, txn_output, gas_meter))
+    }
 }
 
 fn create_account_if_does_not_exist(
@@ -106,6 +106,7 @@
 pub mod counters;
 pub mod data_cache;
 
+pub mod access_list;
 pub mod aptos_vm;
 pub mod block_executor;
 mod errors;
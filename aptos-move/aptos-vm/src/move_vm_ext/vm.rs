@@ -100,6 +100,7 @@ impl MoveVmExt {
         env: Arc<Environment>,
         gas_hook: Option<Arc<dyn Fn(DynamicExpression) + Send + Sync>>,
         inject_create_signer_for_gov_sim: bool,
+        use_warm_vm_cache: bool,
         resolver: &impl AptosMoveResolver,
     ) -> Self {
         // TODO(Gas): Right now, we have to use some dummy values for gas parameters if they are not found on-chain.
@@ -125,7 +126,7 @@ impl MoveVmExt {
             },
         };
 
-        let builder = SafeNativeBuilder::new(
+        let mut builder = SafeNativeBuilder::new(
             gas_feature_version,
             native_gas_params,
             misc_gas_params,
@@ -141,17 +142,23 @@ impl MoveVmExt {
             .features()
             .is_enabled(FeatureFlag::DISALLOW_USER_NATIVES);
 
-        Self {
-            inner: WarmVmCache::get_warm_vm(
+        let inner = if use_warm_vm_cache {
+            WarmVmCache::get_warm_vm(
                 builder,
                 vm_config,
                 resolver,
                 env.features().is_enabled(FeatureFlag::VM_BINARY_FORMAT_V7),
                 inject_create_signer_for_gov_sim,
             )
-            .expect("should be able to create Move VM; check if there are duplicated natives"),
-            env,
-        }
+            .expect("should be able to create Move VM; check if there are duplicated natives")
+        } else {
+            MoveVM::new_with_config(
+                aptos_natives_with_builder(&mut builder, inject_create_signer_for_gov_sim),
+                vm_config,
+            )
+        };
+
+        Self { inner, env }
     }
 
     pub fn new(
@@ -160,7 +167,34 @@ impl MoveVmExt {
         env: Arc<Environment>,
         resolver: &impl AptosMoveResolver,
     ) -> Self {
-        Self::new_impl(gas_feature_version, gas_params, env, None, false, resolver)
+        Self::new_impl(
+            gas_feature_version,
+            gas_params,
+            env,
+            None,
+            false,
+            true,
+            resolver,
+        )
+    }
+
+    /// Same as `new`, but the VM is not shared through the warm VM cache, and starts with an
+    /// empty code cache: all the modules used in its sessions are loaded from their resolvers.
+    pub fn new_with_empty_code_cache(
+        gas_feature_version: u64,
+        gas_params: Result<&AptosGasParameters, &String>,
+        env: Arc<Environment>,
+        resolver: &impl AptosMoveResolver,
+    ) -> Self {
+        Self::new_impl(
+            gas_feature_version,
+            gas_params,
+            env,
+            None,
+            false,
+            false,
+            resolver,
+        )
     }
 
     pub fn new_with_extended_options(
@@ -177,6 +211,7 @@ impl MoveVmExt {
             env,
             gas_hook,
             inject_create_signer_for_gov_sim,
+            true,
             resolver,
         )
     }
@@ -64,6 +64,8 @@ impl<S: StateView + Sync + Send + 'static> GlobalExecutor<S> {
                     concurrency_level: self.concurrency_level,
                     allow_fallback: true,
                     discard_failed_blocks: false,
+                    conflict_analytics_dir: None,
+                    adaptive_concurrency: None,
                 },
                 onchain: onchain_config,
             },
@@ -234,6 +234,8 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                                 concurrency_level: concurrency_level_per_shard,
                                 allow_fallback: true,
                                 discard_failed_blocks: false,
+                                conflict_analytics_dir: None,
+                                adaptive_concurrency: None,
                             },
                             onchain: onchain_config,
                         },
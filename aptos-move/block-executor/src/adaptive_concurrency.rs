@@ -0,0 +1,196 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adaptive number of workers for parallel execution.
+//!
+//! The best concurrency level depends on how contended the workload is: with many conflicts,
+//! additional workers mostly produce speculative executions that are aborted, or wait on
+//! dependencies. When adaptive concurrency is configured, the parallel executor measures the
+//! aborts and the idle time of the workers for each block, and chooses the number of workers
+//! for later blocks accordingly, between the configured minimum and the concurrency level.
+//! Otherwise, the statistics aren't collected at all. The chosen number of workers is kept in
+//! the (per executor) `AdaptiveConcurrency` of the config.
+
+use crate::counters::{
+    ADAPTIVE_CONCURRENCY_DECISIONS, ADAPTIVE_CONCURRENCY_LEVEL, PARALLEL_EXECUTION_ABORTS_PER_TXN,
+    PARALLEL_EXECUTION_IDLE_RATIO,
+};
+use aptos_logger::debug;
+use aptos_types::block_executor::config::{AdaptiveConcurrencyConfig, BlockExecutorLocalConfig};
+use std::{
+    cmp::{max, min},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Statistics of the parallel execution of a block, recorded concurrently by the workers. They
+/// are only collected if adaptive concurrency is configured.
+#[derive(Default)]
+pub(crate) struct ParallelExecutionStats {
+    aborts: AtomicU64,
+    idle_nanos: AtomicU64,
+}
+
+impl ParallelExecutionStats {
+    pub(crate) fn record_abort(&self) {
+        self.aborts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the time a worker spent without a task to perform, or waiting on a dependency.
+    pub(crate) fn record_idle_time(&self, duration: Duration) {
+        self.idle_nanos
+            .fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ConcurrencyDecision {
+    Increase,
+    Decrease,
+    Keep,
+}
+
+impl ConcurrencyDecision {
+    fn name(&self) -> &'static str {
+        match self {
+            ConcurrencyDecision::Increase => "increase",
+            ConcurrencyDecision::Decrease => "decrease",
+            ConcurrencyDecision::Keep => "keep",
+        }
+    }
+
+    /// Decreases the number of workers if either the abort rate or the idle time exceeds its
+    /// threshold, and increases it again once both are well below their thresholds.
+    fn decide(
+        config: &AdaptiveConcurrencyConfig,
+        aborts_per_100_txns: u64,
+        idle_percentage: u64,
+    ) -> Self {
+        if aborts_per_100_txns > config.max_aborts_per_100_txns
+            || idle_percentage > config.max_idle_percentage
+        {
+            ConcurrencyDecision::Decrease
+        } else if aborts_per_100_txns * 2 <= config.max_aborts_per_100_txns
+            && idle_percentage * 2 <= config.max_idle_percentage
+        {
+            ConcurrencyDecision::Increase
+        } else {
+            ConcurrencyDecision::Keep
+        }
+    }
+
+    /// Decreases multiplicatively (by a quarter), so that contention is relieved quickly, and
+    /// increases more cautiously (by an eighth), both by at least one worker.
+    fn apply(&self, level: usize, min_level: usize, max_level: usize) -> usize {
+        match self {
+            ConcurrencyDecision::Increase => min(level + max(level / 8, 1), max_level),
+            ConcurrencyDecision::Decrease => {
+                max(level.saturating_sub(max(level / 4, 1)), min_level)
+            },
+            ConcurrencyDecision::Keep => level,
+        }
+    }
+}
+
+/// The range of the number of workers: parallel execution requires at least two.
+fn level_range(
+    config: &BlockExecutorLocalConfig,
+    adaptive: &AdaptiveConcurrencyConfig,
+) -> (usize, usize) {
+    let max_level = max(config.concurrency_level, 2);
+    (
+        adaptive.min_concurrency_level.clamp(2, max_level),
+        max_level,
+    )
+}
+
+/// Returns the number of workers to use for the next block.
+pub(crate) fn concurrency_level(config: &BlockExecutorLocalConfig) -> usize {
+    match &config.adaptive_concurrency {
+        Some(adaptive) => {
+            let (min_level, max_level) = level_range(config, &adaptive.config);
+            adaptive
+                .next_level()
+                .map_or(max_level, |level| level.clamp(min_level, max_level))
+        },
+        None => config.concurrency_level,
+    }
+}
+
+/// Records the statistics of a block that was executed with num_workers workers, when
+/// concurrency_level workers were available, and if adaptive concurrency is configured,
+/// chooses the number of workers for later blocks.
+pub(crate) fn update_concurrency_level(
+    config: &BlockExecutorLocalConfig,
+    concurrency_level: usize,
+    num_workers: usize,
+    num_txns: usize,
+    stats: &ParallelExecutionStats,
+    elapsed: Duration,
+) {
+    let aborts = stats.aborts.load(Ordering::Relaxed);
+    let idle_nanos = stats.idle_nanos.load(Ordering::Relaxed) as u128;
+    let total_nanos = max(elapsed.as_nanos() * num_workers as u128, 1);
+    let idle_percentage = min(idle_nanos * 100 / total_nanos, 100) as u64;
+    let aborts_per_100_txns = aborts * 100 / max(num_txns, 1) as u64;
+
+    PARALLEL_EXECUTION_ABORTS_PER_TXN.observe(aborts as f64 / max(num_txns, 1) as f64);
+    PARALLEL_EXECUTION_IDLE_RATIO.observe(idle_percentage as f64 / 100.0);
+
+    let adaptive = match &config.adaptive_concurrency {
+        Some(adaptive) => adaptive,
+        None => return,
+    };
+    // Blocks too small to use all the available workers say little about the contention
+    // at the current concurrency level.
+    if num_workers < concurrency_level {
+        return;
+    }
+
+    let (min_level, max_level) = level_range(config, &adaptive.config);
+    let decision =
+        ConcurrencyDecision::decide(&adaptive.config, aborts_per_100_txns, idle_percentage);
+    let new_level = decision.apply(concurrency_level, min_level, max_level);
+    adaptive.set_next_level(new_level);
+
+    ADAPTIVE_CONCURRENCY_DECISIONS
+        .with_label_values(&[decision.name()])
+        .inc();
+    ADAPTIVE_CONCURRENCY_LEVEL.set(new_level as i64);
+    debug!(
+        "[BlockSTM]: {} aborts per 100 txns, {}% idle with {} workers, next concurrency level {}",
+        aborts_per_100_txns, idle_percentage, num_workers, new_level
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        let config = AdaptiveConcurrencyConfig {
+            min_concurrency_level: 4,
+            max_aborts_per_100_txns: 40,
+            max_idle_percentage: 30,
+        };
+        use ConcurrencyDecision::*;
+        assert_eq!(ConcurrencyDecision::decide(&config, 41, 0), Decrease);
+        assert_eq!(ConcurrencyDecision::decide(&config, 0, 31), Decrease);
+        assert_eq!(ConcurrencyDecision::decide(&config, 30, 10), Keep);
+        assert_eq!(ConcurrencyDecision::decide(&config, 10, 20), Keep);
+        assert_eq!(ConcurrencyDecision::decide(&config, 20, 15), Increase);
+    }
+
+    #[test]
+    fn test_apply() {
+        use ConcurrencyDecision::*;
+        assert_eq!(Decrease.apply(32, 4, 32), 24);
+        assert_eq!(Decrease.apply(5, 4, 32), 4);
+        assert_eq!(Decrease.apply(4, 4, 32), 4);
+        assert_eq!(Increase.apply(24, 4, 32), 27);
+        assert_eq!(Increase.apply(4, 4, 32), 5);
+        assert_eq!(Increase.apply(31, 4, 32), 32);
+        assert_eq!(Keep.apply(12, 4, 32), 12);
+    }
+}
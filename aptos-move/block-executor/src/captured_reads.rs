@@ -558,29 +558,42 @@ impl<T: Transaction> CapturedReads<T> {
             return false;
         }
 
+        self.data_reads
+            .iter()
+            .all(|(k, r)| Self::validate_data_read(data_map, k, r, idx_to_validate).is_ok())
+    }
+
+    /// Validates a single data read. On failure, returns the index of the transaction whose
+    /// write conflicts with the read, if known.
+    fn validate_data_read(
+        data_map: &VersionedData<T::Key, T::Value>,
+        key: &T::Key,
+        read: &DataRead<T::Value>,
+        idx_to_validate: TxnIndex,
+    ) -> Result<(), Option<TxnIndex>> {
         use MVDataError::*;
         use MVDataOutput::*;
-        self.data_reads.iter().all(|(k, r)| {
-            match data_map.fetch_data(k, idx_to_validate) {
-                Ok(Versioned(version, v)) => {
-                    matches!(
-                        DataRead::from_value_with_layout(version, v).contains(r),
-                        DataReadComparison::Contains
-                    )
-                },
-                Ok(Resolved(value)) => matches!(
-                    DataRead::Resolved(value).contains(r),
-                    DataReadComparison::Contains
-                ),
-                // Dependency implies a validation failure, and if the original read were to
-                // observe an unresolved delta, it would set the aggregator base value in the
-                // multi-versioned data-structure, resolve, and record the resolved value.
-                Err(Dependency(_))
-                | Err(Unresolved(_))
-                | Err(DeltaApplicationFailure)
-                | Err(Uninitialized) => false,
-            }
-        })
+        let (comparison, writer) = match data_map.fetch_data(key, idx_to_validate) {
+            Ok(Versioned(version, v)) => {
+                let writer = version.as_ref().ok().map(|(txn_idx, _)| *txn_idx);
+                (
+                    DataRead::from_value_with_layout(version, v).contains(read),
+                    writer,
+                )
+            },
+            Ok(Resolved(value)) => (DataRead::Resolved(value).contains(read), None),
+            // Dependency implies a validation failure, and if the original read were to
+            // observe an unresolved delta, it would set the aggregator base value in the
+            // multi-versioned data-structure, resolve, and record the resolved value.
+            Err(Dependency(dep_idx)) => return Err(Some(dep_idx)),
+            Err(Unresolved(_)) | Err(DeltaApplicationFailure) | Err(Uninitialized) => {
+                return Err(None)
+            },
+        };
+        match comparison {
+            DataReadComparison::Contains => Ok(()),
+            _ => Err(writer),
+        }
     }
 
     pub(crate) fn validate_group_reads(
@@ -588,46 +601,86 @@ impl<T: Transaction> CapturedReads<T> {
         group_map: &VersionedGroupData<T::Key, T::Tag, T::Value>,
         idx_to_validate: TxnIndex,
     ) -> bool {
-        use MVGroupError::*;
-
         if self.speculative_failure {
             return false;
         }
 
         self.group_reads.iter().all(|(key, group)| {
-            let mut ret = true;
-            if let Some(size) = group.collected_size {
-                ret &= group_map.validate_group_size(key, idx_to_validate, size);
+            Self::validate_group_read(group_map, key, group, idx_to_validate).is_ok()
+        })
+    }
+
+    /// Validates the size and the captured inner reads of a resource group. On failure,
+    /// returns the index of the transaction whose write conflicts with the read, if known.
+    fn validate_group_read(
+        group_map: &VersionedGroupData<T::Key, T::Tag, T::Value>,
+        key: &T::Key,
+        group: &GroupRead<T>,
+        idx_to_validate: TxnIndex,
+    ) -> Result<(), Option<TxnIndex>> {
+        use MVGroupError::*;
+
+        if let Some(size) = group.collected_size {
+            if !group_map.validate_group_size(key, idx_to_validate, size) {
+                return Err(None);
             }
+        }
 
-            ret && group.inner_reads.iter().all(|(tag, r)| {
-                match group_map.fetch_tagged_data(key, tag, idx_to_validate) {
-                    Ok((version, v)) => {
-                        matches!(
-                            DataRead::from_value_with_layout(version, v).contains(r),
-                            DataReadComparison::Contains
-                        )
-                    },
-                    Err(TagNotFound) => {
-                        let sentinel_deletion =
-                            Arc::<T::Value>::new(TransactionWrite::from_state_value(None));
-                        assert!(sentinel_deletion.is_deletion());
-                        matches!(
-                            DataRead::Versioned(Err(StorageVersion), sentinel_deletion, None)
-                                .contains(r),
-                            DataReadComparison::Contains
-                        )
-                    },
-                    Err(Dependency(_)) => false,
-                    Err(Uninitialized) => {
-                        unreachable!("May not be uninitialized if captured for validation");
-                    },
-                    Err(TagSerializationError(_)) => {
-                        unreachable!("Should not require tag serialization");
-                    },
-                }
-            })
-        })
+        for (tag, r) in &group.inner_reads {
+            let (comparison, writer) = match group_map.fetch_tagged_data(key, tag, idx_to_validate)
+            {
+                Ok((version, v)) => {
+                    let writer = version.as_ref().ok().map(|(txn_idx, _)| *txn_idx);
+                    (
+                        DataRead::from_value_with_layout(version, v).contains(r),
+                        writer,
+                    )
+                },
+                Err(TagNotFound) => {
+                    let sentinel_deletion =
+                        Arc::<T::Value>::new(TransactionWrite::from_state_value(None));
+                    assert!(sentinel_deletion.is_deletion());
+                    (
+                        DataRead::Versioned(Err(StorageVersion), sentinel_deletion, None)
+                            .contains(r),
+                        None,
+                    )
+                },
+                Err(Dependency(dep_idx)) => return Err(Some(dep_idx)),
+                Err(Uninitialized) => {
+                    unreachable!("May not be uninitialized if captured for validation");
+                },
+                Err(TagSerializationError(_)) => {
+                    unreachable!("Should not require tag serialization");
+                },
+            };
+            if !matches!(comparison, DataReadComparison::Contains) {
+                return Err(writer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the keys of the data and group reads that no longer pass validation, along with
+    /// the index of the transaction whose write conflicts with each read, if known. Intended to
+    /// be called after a failed validation, e.g. for conflict analytics.
+    pub(crate) fn get_conflicting_reads(
+        &self,
+        data_map: &VersionedData<T::Key, T::Value>,
+        group_map: &VersionedGroupData<T::Key, T::Tag, T::Value>,
+        idx_to_validate: TxnIndex,
+    ) -> Vec<(T::Key, Option<TxnIndex>)> {
+        let data_conflicts = self.data_reads.iter().filter_map(|(k, r)| {
+            Self::validate_data_read(data_map, k, r, idx_to_validate)
+                .err()
+                .map(|writer| (k.clone(), writer))
+        });
+        let group_conflicts = self.group_reads.iter().filter_map(|(k, group)| {
+            Self::validate_group_read(group_map, k, group, idx_to_validate)
+                .err()
+                .map(|writer| (k.clone(), writer))
+        });
+        data_conflicts.chain(group_conflicts).collect()
     }
 
     // This validation needs to be called at commit time
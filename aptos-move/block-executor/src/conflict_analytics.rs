@@ -0,0 +1,469 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in collection of the conflicts that serialize the parallel execution of a block.
+//!
+//! When enabled (by setting `conflict_analytics_dir` in the local block executor config), the
+//! parallel executor records every time a transaction reads an estimate of a resource or a
+//! resource group written by a lower transaction (and has to wait for it), and every time a
+//! transaction is aborted together with the reads that failed validation. At the end of the
+//! block, the recorded conflicts are summarized into a [BlockConflictReport], containing the
+//! dependency graph between the transactions, the abort and re-execution counts per transaction,
+//! and the hottest conflicting keys. The report is written both as JSON and as a Graphviz (DOT)
+//! graph, by a background thread (so that the execution of the next block isn't delayed), and
+//! only the most recent reports are kept.
+
+use aptos_infallible::Mutex;
+use aptos_logger::{error, warn};
+use aptos_mvhashmap::types::{Incarnation, TxnIndex};
+use aptos_types::state_store::StateViewId;
+use dashmap::DashMap;
+use derivative::Derivative;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Write},
+    fs,
+    hash::Hash,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The number of hottest conflicting keys included in a report.
+const NUM_HOT_KEYS: usize = 20;
+/// The maximum number of keys shown on an edge of the Graphviz graph.
+const MAX_KEYS_PER_EDGE_LABEL: usize = 3;
+
+/// The maximum number of reports kept in the directory, the oldest ones are removed first.
+const MAX_SAVED_REPORTS: usize = 1000;
+/// The maximum number of reports waiting to be written. If the writer falls behind, the reports
+/// of further blocks are dropped (instead of accumulating in memory).
+const MAX_PENDING_REPORTS: usize = 16;
+
+type ReportTask = Box<dyn FnOnce() + Send>;
+
+/// Sends the reports to the background thread that writes them.
+static REPORT_WRITER: Lazy<Mutex<SyncSender<ReportTask>>> = Lazy::new(|| {
+    let (sender, receiver) = mpsc::sync_channel::<ReportTask>(MAX_PENDING_REPORTS);
+    thread::Builder::new()
+        .name("conflict_analytics".to_string())
+        .spawn(move || {
+            for task in receiver {
+                task();
+            }
+        })
+        .expect("Failed to spawn the conflict analytics writer");
+    Mutex::new(sender)
+});
+
+/// Returns the name of the report of the block executed on top of the given state view, i.e.,
+/// `block_<block id>` or `chunk_<first version>`. Other blocks (e.g., in tests or replays) don't
+/// have an identifier, so they are named after the time they finished executing.
+pub fn report_name(state_view_id: StateViewId) -> String {
+    match state_view_id {
+        StateViewId::BlockExecution { block_id } => format!("block_{}", block_id.to_hex()),
+        StateViewId::ChunkExecution { first_version } => format!("chunk_{}", first_version),
+        _ => format!(
+            "unidentified_{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        ),
+    }
+}
+
+#[derive(Default)]
+struct TxnCounts {
+    aborts: AtomicU32,
+    re_executions: AtomicU32,
+    dependency_waits: AtomicU32,
+}
+
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+struct ConflictCounts<K> {
+    dependency_waits: u64,
+    aborts: u64,
+    keys: HashSet<K>,
+}
+
+/// Collects the conflicts observed during the parallel execution of a single block. All methods
+/// can be called concurrently by the workers.
+pub struct ConflictAnalytics<K> {
+    txns: Vec<TxnCounts>,
+    /// Keyed by (txn_idx, the lower txn_idx it depends on).
+    dependencies: DashMap<(TxnIndex, TxnIndex), ConflictCounts<K>>,
+    keys: DashMap<K, ConflictCounts<K>>,
+}
+
+impl<K: Clone + Debug + Hash + Eq> ConflictAnalytics<K> {
+    pub fn new(num_txns: TxnIndex) -> Self {
+        Self {
+            txns: (0..num_txns).map(|_| TxnCounts::default()).collect(),
+            dependencies: DashMap::new(),
+            keys: DashMap::new(),
+        }
+    }
+
+    pub(crate) fn record_execution(&self, txn_idx: TxnIndex, incarnation: Incarnation) {
+        if incarnation > 0 {
+            self.txns[txn_idx as usize]
+                .re_executions
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records that txn_idx read an estimate at the given key (of a resource or a resource
+    /// group) written by dep_idx, and thus has to wait for dep_idx to finish its execution.
+    pub(crate) fn record_dependency_wait(&self, txn_idx: TxnIndex, dep_idx: TxnIndex, key: &K) {
+        self.txns[txn_idx as usize]
+            .dependency_waits
+            .fetch_add(1, Ordering::Relaxed);
+
+        {
+            let mut dependency = self.dependencies.entry((txn_idx, dep_idx)).or_default();
+            dependency.dependency_waits += 1;
+            dependency.keys.insert(key.clone());
+        }
+        self.keys.entry(key.clone()).or_default().dependency_waits += 1;
+    }
+
+    /// Records that an incarnation of txn_idx was aborted, along with the reads that failed
+    /// validation and the lower transactions whose writes invalidated them (if known). The
+    /// conflicting reads may be empty, e.g. if a delayed field failed validation at commit time.
+    pub(crate) fn record_abort(&self, txn_idx: TxnIndex, conflicts: &[(K, Option<TxnIndex>)]) {
+        self.txns[txn_idx as usize]
+            .aborts
+            .fetch_add(1, Ordering::Relaxed);
+
+        for (key, writer) in conflicts {
+            self.keys.entry(key.clone()).or_default().aborts += 1;
+            if let Some(writer) = writer.filter(|writer| *writer < txn_idx) {
+                let mut dependency = self.dependencies.entry((txn_idx, writer)).or_default();
+                dependency.aborts += 1;
+                dependency.keys.insert(key.clone());
+            }
+        }
+    }
+
+    pub fn report(&self) -> BlockConflictReport {
+        let txns: Vec<_> = self
+            .txns
+            .iter()
+            .enumerate()
+            .map(|(txn_idx, counts)| TxnConflicts {
+                txn_idx: txn_idx as TxnIndex,
+                aborts: counts.aborts.load(Ordering::Relaxed),
+                re_executions: counts.re_executions.load(Ordering::Relaxed),
+                dependency_waits: counts.dependency_waits.load(Ordering::Relaxed),
+            })
+            .collect();
+
+        let mut dependencies: Vec<_> = self
+            .dependencies
+            .iter()
+            .map(|entry| {
+                let (txn_idx, depends_on) = *entry.key();
+                TxnDependency {
+                    txn_idx,
+                    depends_on,
+                    dependency_waits: entry.dependency_waits,
+                    aborts: entry.aborts,
+                    keys: sorted_key_strings(&entry.keys),
+                }
+            })
+            .collect();
+        dependencies.sort_by_key(|dependency| (dependency.txn_idx, dependency.depends_on));
+
+        let mut hot_keys: Vec<_> = self
+            .keys
+            .iter()
+            .map(|entry| KeyConflicts {
+                key: format!("{:?}", entry.key()),
+                dependency_waits: entry.dependency_waits,
+                aborts: entry.aborts,
+            })
+            .collect();
+        hot_keys.sort_by(|a, b| {
+            (b.dependency_waits + b.aborts)
+                .cmp(&(a.dependency_waits + a.aborts))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        hot_keys.truncate(NUM_HOT_KEYS);
+
+        BlockConflictReport {
+            num_txns: txns.len() as u32,
+            num_aborts: txns.iter().map(|txn| txn.aborts as u64).sum(),
+            num_re_executions: txns.iter().map(|txn| txn.re_executions as u64).sum(),
+            num_dependency_waits: txns.iter().map(|txn| txn.dependency_waits as u64).sum(),
+            txns: txns
+                .into_iter()
+                .filter(|txn| txn.aborts > 0 || txn.dependency_waits > 0)
+                .collect(),
+            dependencies,
+            hot_keys,
+        }
+    }
+}
+
+impl<K: Clone + Debug + Hash + Eq + Send + Sync + 'static> ConflictAnalytics<K> {
+    /// Summarizes the conflicts and saves the report under the given name, on the background
+    /// writer thread.
+    pub(crate) fn save_in_background(self, dir: PathBuf, name: String) {
+        let task: ReportTask = Box::new(move || {
+            if let Err(err) = self.report().save(&dir, &name) {
+                error!(
+                    "[BlockSTM]: Failed to save conflict analytics {} to {:?}: {:?}",
+                    name, dir, err
+                );
+            }
+        });
+        match REPORT_WRITER.lock().try_send(task) {
+            Ok(()) => {},
+            Err(TrySendError::Full(_)) => {
+                warn!("[BlockSTM]: Too many pending conflict analytics reports, dropping one");
+            },
+            Err(TrySendError::Disconnected(_)) => {
+                error!("[BlockSTM]: The conflict analytics writer has stopped");
+            },
+        }
+    }
+}
+
+fn sorted_key_strings<K: Debug>(keys: &HashSet<K>) -> Vec<String> {
+    let mut keys: Vec<_> = keys.iter().map(|key| format!("{:?}", key)).collect();
+    keys.sort();
+    keys
+}
+
+/// The conflicts of a single transaction. Each abort of an incarnation is followed by a
+/// re-execution, unless the block execution halted in the meantime.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TxnConflicts {
+    pub txn_idx: TxnIndex,
+    pub aborts: u32,
+    pub re_executions: u32,
+    pub dependency_waits: u32,
+}
+
+/// An edge of the dependency graph: txn_idx either waited for depends_on to finish executing,
+/// or was aborted because of a write by depends_on, on the given keys.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TxnDependency {
+    pub txn_idx: TxnIndex,
+    pub depends_on: TxnIndex,
+    pub dependency_waits: u64,
+    pub aborts: u64,
+    pub keys: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KeyConflicts {
+    pub key: String,
+    pub dependency_waits: u64,
+    pub aborts: u64,
+}
+
+/// The conflicts observed during the parallel execution of a block. Only the transactions that
+/// were aborted or waited on a dependency are listed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BlockConflictReport {
+    pub num_txns: u32,
+    pub num_aborts: u64,
+    pub num_re_executions: u64,
+    pub num_dependency_waits: u64,
+    pub txns: Vec<TxnConflicts>,
+    pub dependencies: Vec<TxnDependency>,
+    /// The keys with the most dependency waits and aborts, hottest first.
+    pub hot_keys: Vec<KeyConflicts>,
+}
+
+impl BlockConflictReport {
+    /// Renders the dependency graph in the Graphviz DOT format, with an edge from each
+    /// transaction to the lower transactions it depends on.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        writeln!(dot, "digraph block_conflicts {{").unwrap();
+        writeln!(dot, "    node [shape=box];").unwrap();
+        for txn in &self.txns {
+            writeln!(
+                dot,
+                "    {} [label=\"txn {}\\naborts: {}, waits: {}\"];",
+                txn.txn_idx, txn.txn_idx, txn.aborts, txn.dependency_waits
+            )
+            .unwrap();
+        }
+        for dependency in &self.dependencies {
+            let mut label = format!(
+                "waits: {}, aborts: {}",
+                dependency.dependency_waits, dependency.aborts
+            );
+            for key in dependency.keys.iter().take(MAX_KEYS_PER_EDGE_LABEL) {
+                write!(label, "\\n{}", escape_dot_label(key)).unwrap();
+            }
+            if dependency.keys.len() > MAX_KEYS_PER_EDGE_LABEL {
+                write!(
+                    label,
+                    "\\n(+{} more)",
+                    dependency.keys.len() - MAX_KEYS_PER_EDGE_LABEL
+                )
+                .unwrap();
+            }
+            writeln!(
+                dot,
+                "    {} -> {} [label=\"{}\", penwidth={}];",
+                dependency.txn_idx,
+                dependency.depends_on,
+                label,
+                1 + (dependency.dependency_waits + dependency.aborts).ilog2()
+            )
+            .unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+
+    /// Writes the report to `<name>.json` and `<name>.dot` in the given directory, and removes
+    /// the oldest reports from it if there are more than `MAX_SAVED_REPORTS`.
+    pub fn save(&self, dir: &Path, name: &str) -> anyhow::Result<()> {
+        fs::create_dir_all(dir)?;
+        fs::write(
+            dir.join(format!("{}.json", name)),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        fs::write(dir.join(format!("{}.dot", name)), self.to_dot())?;
+        remove_oldest_reports(dir, MAX_SAVED_REPORTS)
+    }
+}
+
+fn remove_oldest_reports(dir: &Path, max_saved_reports: usize) -> anyhow::Result<()> {
+    let mut reports = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path
+            .extension()
+            .map_or(false, |extension| extension == "json")
+        {
+            reports.push((entry.metadata()?.modified()?, path));
+        }
+    }
+    if reports.len() > max_saved_reports {
+        reports.sort();
+        for (_, path) in &reports[..reports.len() - max_saved_reports] {
+            fs::remove_file(path)?;
+            // The graph may not have been written, if saving the report failed
+            let _ = fs::remove_file(path.with_extension("dot"));
+        }
+    }
+    Ok(())
+}
+
+fn escape_dot_label(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_report() {
+        let analytics = ConflictAnalytics::<&str>::new(4);
+        analytics.record_execution(1, 0);
+        analytics.record_dependency_wait(2, 1, &"counter");
+        analytics.record_dependency_wait(3, 1, &"supply");
+        analytics.record_abort(2, &[("counter", Some(1)), ("balance", None)]);
+        analytics.record_execution(2, 1);
+        analytics.record_abort(3, &[("counter", Some(2))]);
+        analytics.record_execution(3, 1);
+
+        let report = analytics.report();
+        assert_eq!(report.num_txns, 4);
+        assert_eq!(report.num_aborts, 2);
+        assert_eq!(report.num_re_executions, 2);
+        assert_eq!(report.num_dependency_waits, 2);
+        assert_eq!(
+            report
+                .txns
+                .iter()
+                .map(|txn| txn.txn_idx)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+
+        assert_eq!(report.dependencies, vec![
+            TxnDependency {
+                txn_idx: 2,
+                depends_on: 1,
+                dependency_waits: 1,
+                aborts: 1,
+                keys: vec!["\"counter\"".to_string()],
+            },
+            TxnDependency {
+                txn_idx: 3,
+                depends_on: 1,
+                dependency_waits: 1,
+                aborts: 0,
+                keys: vec!["\"supply\"".to_string()],
+            },
+            TxnDependency {
+                txn_idx: 3,
+                depends_on: 2,
+                dependency_waits: 0,
+                aborts: 1,
+                keys: vec!["\"counter\"".to_string()],
+            },
+        ]);
+
+        assert_eq!(report.hot_keys, vec![
+            KeyConflicts {
+                key: "\"counter\"".to_string(),
+                dependency_waits: 1,
+                aborts: 2,
+            },
+            KeyConflicts {
+                key: "\"balance\"".to_string(),
+                dependency_waits: 0,
+                aborts: 1,
+            },
+            KeyConflicts {
+                key: "\"supply\"".to_string(),
+                dependency_waits: 1,
+                aborts: 0,
+            },
+        ]);
+
+        let dot = report.to_dot();
+        assert!(dot.contains("3 -> 2 [label=\"waits: 0, aborts: 1\\n\\\"counter\\\"\""));
+    }
+
+    #[test]
+    fn test_report_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = ConflictAnalytics::<&str>::new(1).report();
+        for name in ["block_a", "block_b", "block_c"] {
+            report.save(dir.path(), name).unwrap();
+            // Make sure that the modification times differ
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        remove_oldest_reports(dir.path(), 2).unwrap();
+        let mut files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(
+            files,
+            vec!["block_b.dot", "block_b.json", "block_c.dot", "block_c.json"]
+        );
+    }
+}
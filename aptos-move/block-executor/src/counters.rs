@@ -3,8 +3,8 @@
 
 use aptos_metrics_core::{
     exponential_buckets, register_avg_counter_vec, register_histogram, register_histogram_vec,
-    register_int_counter, register_int_counter_vec, Histogram, HistogramVec, IntCounter,
-    IntCounterVec,
+    register_int_counter, register_int_counter_vec, register_int_gauge, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge,
 };
 use aptos_mvhashmap::BlockStateStats;
 use aptos_types::fee_statement::FeeStatement;
@@ -67,6 +67,43 @@ pub static SPECULATIVE_ABORT_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static PARALLEL_EXECUTION_ABORTS_PER_TXN: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_execution_parallel_aborts_per_txn",
+        "Number of speculative aborts per transaction of a block in parallel execution",
+        exponential_buckets(/*start=*/ 0.01, /*factor=*/ 2.0, /*count=*/ 12).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static PARALLEL_EXECUTION_IDLE_RATIO: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_execution_parallel_idle_ratio",
+        "Fraction of the time the workers were idle (without a task or waiting on a dependency) \
+         during parallel execution of a block",
+        (1..=20).map(|i| i as f64 / 20.0).collect(),
+    )
+    .unwrap()
+});
+
+/// The number of workers chosen by the adaptive concurrency for the next blocks.
+pub static ADAPTIVE_CONCURRENCY_LEVEL: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_execution_adaptive_concurrency_level",
+        "Number of workers chosen by the adaptive concurrency for the next blocks"
+    )
+    .unwrap()
+});
+
+pub static ADAPTIVE_CONCURRENCY_DECISIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_execution_adaptive_concurrency_decisions",
+        "Count of the decisions (increase, decrease, keep) of the adaptive concurrency",
+        &["decision"]
+    )
+    .unwrap()
+});
+
 /// Count of times the BlockSTM is early halted due to exceeding the per-block gas limit.
 pub static EXCEED_PER_BLOCK_GAS_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -3,6 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    adaptive_concurrency,
+    adaptive_concurrency::ParallelExecutionStats,
+    conflict_analytics::{report_name, ConflictAnalytics},
     counters,
     counters::{
         PARALLEL_EXECUTION_SECONDS, RAYON_EXECUTION_SECONDS, TASK_EXECUTE_SECONDS,
@@ -60,6 +63,7 @@ use std::{
         atomic::{AtomicBool, AtomicU32, Ordering},
         Arc,
     },
+    time::Instant,
 };
 
 pub struct BlockExecutor<T, E, S, L, X> {
@@ -394,10 +398,26 @@ where
         last_input_output: &TxnLastInputOutput<T, E::Output, E::Error>,
         versioned_cache: &MVHashMap<T::Key, T::Tag, T::Value, X, T::Identifier>,
         scheduler: &Scheduler,
+        stats: Option<&ParallelExecutionStats>,
+        conflict_analytics: Option<&ConflictAnalytics<T::Key>>,
     ) -> Result<SchedulerTask, PanicError> {
         let aborted = !valid && scheduler.try_abort(txn_idx, incarnation);
 
         if aborted {
+            if let Some(stats) = stats {
+                stats.record_abort();
+            }
+            if let Some(conflict_analytics) = conflict_analytics {
+                let conflicting_reads = last_input_output
+                    .read_set(txn_idx)
+                    .expect("[BlockSTM]: Prior read-set must be recorded")
+                    .get_conflicting_reads(
+                        versioned_cache.data(),
+                        versioned_cache.group_data(),
+                        txn_idx,
+                    );
+                conflict_analytics.record_abort(txn_idx, &conflicting_reads);
+            }
             Self::update_transaction_on_abort(txn_idx, last_input_output, versioned_cache);
             scheduler.finish_abort(txn_idx, incarnation)
         } else {
@@ -465,6 +485,8 @@ where
         executor: &E,
         block: &[T],
         num_workers: usize,
+        stats: Option<&ParallelExecutionStats>,
+        conflict_analytics: Option<&ConflictAnalytics<T::Key>>,
     ) -> Result<(), PanicOr<ParallelBlockExecutionError>> {
         let mut block_limit_processor = shared_commit_state.acquire();
 
@@ -472,6 +494,14 @@ where
             if !Self::validate_commit_ready(txn_idx, versioned_cache, last_input_output)? {
                 // Transaction needs to be re-executed, one final time.
 
+                if let Some(stats) = stats {
+                    stats.record_abort();
+                }
+                if let Some(conflict_analytics) = conflict_analytics {
+                    // Only delayed field reads are validated at commit time.
+                    conflict_analytics.record_abort(txn_idx, &[]);
+                    conflict_analytics.record_execution(txn_idx, incarnation + 1);
+                }
                 Self::update_transaction_on_abort(txn_idx, last_input_output, versioned_cache);
                 // We are going to skip reducing validation index here, as we
                 // are executing immediately, and will reduce it unconditionally
@@ -490,6 +520,8 @@ where
                         scheduler,
                         start_shared_counter,
                         shared_counter,
+                        stats,
+                        conflict_analytics,
                     ),
                 )?;
 
@@ -759,6 +791,8 @@ where
         shared_commit_state: &ExplicitSyncWrapper<BlockGasLimitProcessor<T>>,
         final_results: &ExplicitSyncWrapper<Vec<E::Output>>,
         num_workers: usize,
+        stats: Option<&ParallelExecutionStats>,
+        conflict_analytics: Option<&ConflictAnalytics<T::Key>>,
     ) -> Result<(), PanicOr<ParallelBlockExecutionError>> {
         // Make executor for each task. TODO: fast concurrent executor.
         let init_timer = VM_INIT_SECONDS.start_timer();
@@ -767,6 +801,8 @@ where
 
         let _timer = WORK_WITH_TASK_SECONDS.start_timer();
         let mut scheduler_task = SchedulerTask::Retry;
+        // Start of the period in which the worker has no task to perform.
+        let mut idle_since: Option<Instant> = None;
 
         let drain_commit_queue = || -> Result<(), PanicError> {
             while let Ok(txn_idx) = scheduler.pop_from_commit_queue() {
@@ -799,6 +835,8 @@ where
                     &executor,
                     block,
                     num_workers,
+                    stats,
+                    conflict_analytics,
                 )?;
                 scheduler.queueing_commits_mark_done();
             }
@@ -816,6 +854,8 @@ where
                         last_input_output,
                         versioned_cache,
                         scheduler,
+                        stats,
+                        conflict_analytics,
                     )?
                 },
                 SchedulerTask::ExecutionTask(
@@ -823,6 +863,9 @@ where
                     incarnation,
                     ExecutionTaskType::Execution,
                 ) => {
+                    if let Some(conflict_analytics) = conflict_analytics {
+                        conflict_analytics.record_execution(txn_idx, incarnation);
+                    }
                     let needs_suffix_validation = Self::execute(
                         txn_idx,
                         incarnation,
@@ -836,6 +879,8 @@ where
                             scheduler,
                             start_shared_counter,
                             shared_counter,
+                            stats,
+                            conflict_analytics,
                         ),
                     )?;
                     scheduler.finish_execution(txn_idx, incarnation, needs_suffix_validation)?
@@ -855,9 +900,20 @@ where
                 },
                 SchedulerTask::Retry => scheduler.next_task(),
                 SchedulerTask::Done => {
+                    if let (Some(stats), Some(idle_since)) = (stats, idle_since) {
+                        stats.record_idle_time(idle_since.elapsed());
+                    }
                     drain_commit_queue()?;
                     break Ok(());
                 },
+            };
+
+            if let Some(stats) = stats {
+                if let SchedulerTask::Retry = scheduler_task {
+                    idle_since.get_or_insert_with(Instant::now);
+                } else if let Some(idle_since) = idle_since.take() {
+                    stats.record_idle_time(idle_since.elapsed());
+                }
             }
         }
     }
@@ -887,7 +943,14 @@ where
         }
 
         let num_txns = signature_verified_block.len();
-        let num_workers = self.config.local.concurrency_level.min(num_txns / 2).max(2);
+        let concurrency_level = adaptive_concurrency::concurrency_level(&self.config.local);
+        let num_workers = concurrency_level.min(num_txns / 2).max(2);
+        let stats = self
+            .config
+            .local
+            .adaptive_concurrency
+            .as_ref()
+            .map(|_| ParallelExecutionStats::default());
 
         let shared_commit_state = ExplicitSyncWrapper::new(BlockGasLimitProcessor::new(
             self.config.onchain.block_gas_limit_type.clone(),
@@ -907,8 +970,15 @@ where
 
         let last_input_output = TxnLastInputOutput::new(num_txns);
         let scheduler = Scheduler::new(num_txns);
+        let conflict_analytics = self
+            .config
+            .local
+            .conflict_analytics_dir
+            .as_ref()
+            .map(|_| ConflictAnalytics::new(num_txns));
 
         let timer = RAYON_EXECUTION_SECONDS.start_timer();
+        let start_time = Instant::now();
         self.executor_thread_pool.scope(|s| {
             for _ in 0..num_workers {
                 s.spawn(|_| {
@@ -924,6 +994,8 @@ where
                         &shared_commit_state,
                         &final_results,
                         num_workers,
+                        stats.as_ref(),
+                        conflict_analytics.as_ref(),
                     ) {
                         // If there are multiple errors, they all get logged:
                         // ModulePathReadWriteError and FatalVMError variant is logged at construction,
@@ -941,6 +1013,26 @@ where
         });
         drop(timer);
 
+        if let Some(stats) = &stats {
+            if !shared_maybe_error.load(Ordering::SeqCst) {
+                adaptive_concurrency::update_concurrency_level(
+                    &self.config.local,
+                    concurrency_level,
+                    num_workers,
+                    num_txns as usize,
+                    stats,
+                    start_time.elapsed(),
+                );
+            }
+        }
+
+        if let (Some(conflict_analytics), Some(dir)) = (
+            conflict_analytics,
+            self.config.local.conflict_analytics_dir.as_ref(),
+        ) {
+            conflict_analytics.save_in_background(dir.clone(), report_name(base_view.id()));
+        }
+
         counters::update_state_counters(versioned_cache.stats(), true);
 
         // Explicit async drops.
@@ -139,7 +139,9 @@ subsequent incarnation to finish.
 #[macro_use(defer)]
 extern crate scopeguard;
 
+mod adaptive_concurrency;
 mod captured_reads;
+pub mod conflict_analytics;
 pub mod counters;
 pub mod errors;
 pub mod executor;
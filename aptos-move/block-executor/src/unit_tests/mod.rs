@@ -24,7 +24,7 @@ use aptos_aggregator::{
 };
 use aptos_mvhashmap::types::TxnIndex;
 use aptos_types::{
-    block_executor::config::BlockExecutorConfig,
+    block_executor::config::{AdaptiveConcurrency, AdaptiveConcurrencyConfig, BlockExecutorConfig},
     contract_event::TransactionEvent,
     executable::{ExecutableTestType, ModulePath},
     state_store::state_value::StateValueMetadata,
@@ -266,6 +266,60 @@ where
     baseline.assert_parallel_output(&output);
 }
 
+#[test]
+fn adaptive_concurrency_under_contention() {
+    // Every transaction reads and writes the same key, so the workers mostly wait on
+    // dependencies or get aborted, and fewer workers should be used for the next blocks.
+    let key = KeyType(random::<[u8; 32]>(), false);
+    let transactions: Vec<_> = (0..200)
+        .map(|_| {
+            MockTransaction::from_behavior(MockIncarnation::<KeyType<[u8; 32]>, MockEvent>::new(
+                vec![key],                        // reads
+                vec![(key, random_value(false))], // writes
+                vec![],
+                vec![],
+                1, // gas
+            ))
+        })
+        .collect();
+    let data_view = DeltaDataView::<KeyType<[u8; 32]>> {
+        phantom: PhantomData,
+    };
+
+    let concurrency_level = 8;
+    let adaptive_concurrency = AdaptiveConcurrency::new(AdaptiveConcurrencyConfig {
+        min_concurrency_level: 2,
+        max_aborts_per_100_txns: 10,
+        max_idle_percentage: 10,
+    });
+    let mut config = BlockExecutorConfig::new_no_block_limit(concurrency_level);
+    config.local.adaptive_concurrency = Some(adaptive_concurrency.clone());
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(concurrency_level)
+            .build()
+            .unwrap(),
+    );
+    let block_executor = BlockExecutor::<
+        MockTransaction<KeyType<[u8; 32]>, MockEvent>,
+        MockTask<KeyType<[u8; 32]>, MockEvent>,
+        DeltaDataView<KeyType<[u8; 32]>>,
+        NoOpTransactionCommitHook<MockOutput<KeyType<[u8; 32]>, MockEvent>, usize>,
+        ExecutableTestType,
+    >::new(config, executor_thread_pool, None);
+
+    let baseline = BaselineOutput::generate(&transactions, None);
+    for _ in 0..3 {
+        let output = block_executor.execute_transactions_parallel(&(), &transactions, &data_view);
+        baseline.assert_parallel_output(&output);
+    }
+    assert!(adaptive_concurrency.next_level().unwrap() < concurrency_level);
+
+    // The number of workers chosen by an executor doesn't affect the other executors
+    let other_adaptive_concurrency = AdaptiveConcurrency::new(AdaptiveConcurrencyConfig::default());
+    assert_eq!(other_adaptive_concurrency.next_level(), None);
+}
+
 fn random_value(delete_value: bool) -> ValueType {
     ValueType::from_value(
         (0..32).map(|_| (random::<u8>())).collect::<Vec<u8>>(),
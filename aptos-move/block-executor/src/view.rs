@@ -4,10 +4,12 @@
 #[cfg(test)]
 use crate::types::InputOutputKey;
 use crate::{
+    adaptive_concurrency::ParallelExecutionStats,
     captured_reads::{
         CapturedReads, DataRead, DelayedFieldRead, DelayedFieldReadKind, GroupRead, ReadKind,
         UnsyncReadSet,
     },
+    conflict_analytics::ConflictAnalytics,
     counters,
     scheduler::{DependencyResult, DependencyStatus, Scheduler, TWaitForDependency},
     value_exchange::{
@@ -70,6 +72,7 @@ use std::{
         atomic::{AtomicU32, Ordering},
         Arc,
     },
+    time::Instant,
 };
 
 /// A struct which describes the result of the read from the proxy. The client
@@ -161,6 +164,8 @@ pub(crate) struct ParallelState<'a, T: Transaction, X: Executable> {
     start_counter: u32,
     counter: &'a AtomicU32,
     captured_reads: RefCell<CapturedReads<T>>,
+    stats: Option<&'a ParallelExecutionStats>,
+    conflict_analytics: Option<&'a ConflictAnalytics<T::Key>>,
 }
 
 fn get_delayed_field_value_impl<T: Transaction>(
@@ -443,6 +448,8 @@ impl<'a, T: Transaction, X: Executable> ParallelState<'a, T, X> {
         shared_scheduler: &'a Scheduler,
         start_shared_counter: u32,
         shared_counter: &'a AtomicU32,
+        stats: Option<&'a ParallelExecutionStats>,
+        conflict_analytics: Option<&'a ConflictAnalytics<T::Key>>,
     ) -> Self {
         Self {
             versioned_map: shared_map,
@@ -450,6 +457,31 @@ impl<'a, T: Transaction, X: Executable> ParallelState<'a, T, X> {
             start_counter: start_shared_counter,
             counter: shared_counter,
             captured_reads: RefCell::new(CapturedReads::new()),
+            stats,
+            conflict_analytics,
+        }
+    }
+
+    // Waits for a dependency on dep_idx, observed when reading the given key (a resource or
+    // a resource group), recording the time spent waiting if adaptive concurrency is enabled,
+    // and the dependency if conflict analytics are enabled.
+    fn wait_for_dependency(
+        &self,
+        key: &T::Key,
+        txn_idx: TxnIndex,
+        dep_idx: TxnIndex,
+    ) -> Result<bool, PanicError> {
+        if let Some(conflict_analytics) = self.conflict_analytics {
+            conflict_analytics.record_dependency_wait(txn_idx, dep_idx, key);
+        }
+        match self.stats {
+            Some(stats) => {
+                let start_time = Instant::now();
+                let ret = wait_for_dependency(self.scheduler, txn_idx, dep_idx);
+                stats.record_idle_time(start_time.elapsed());
+                ret
+            },
+            None => wait_for_dependency(self.scheduler, txn_idx, dep_idx),
         }
     }
 
@@ -508,7 +540,7 @@ impl<'a, T: Transaction, X: Executable> ParallelState<'a, T, X> {
                     unreachable!("Reading group size does not require a specific tag look-up");
                 },
                 Err(Dependency(dep_idx)) => {
-                    if !wait_for_dependency(self.scheduler, txn_idx, dep_idx)? {
+                    if !self.wait_for_dependency(group_key, txn_idx, dep_idx)? {
                         return Err(PartialVMError::new(
                             StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
                         )
@@ -633,7 +665,7 @@ impl<'a, T: Transaction, X: Executable> ResourceState<T> for ParallelState<'a, T
                     return ReadResult::Uninitialized;
                 },
                 Err(Dependency(dep_idx)) => {
-                    match wait_for_dependency(self.scheduler, txn_idx, dep_idx) {
+                    match self.wait_for_dependency(key, txn_idx, dep_idx) {
                         Err(e) => {
                             error!("Error {:?} in wait for dependency", e);
                             self.captured_reads.borrow_mut().mark_incorrect_use();
@@ -751,7 +783,7 @@ impl<'a, T: Transaction, X: Executable> ResourceGroupState<T> for ParallelState<
                     return Ok(GroupReadResult::Value(None, None));
                 },
                 Err(Dependency(dep_idx)) => {
-                    if !wait_for_dependency(self.scheduler, txn_idx, dep_idx)? {
+                    if !self.wait_for_dependency(group_key, txn_idx, dep_idx)? {
                         // TODO[agg_v2](cleanup): consider changing from PartialVMResult<GroupReadResult> to GroupReadResult
                         // like in ReadResult for resources.
                         return Err(PartialVMError::new(
@@ -2770,6 +2802,8 @@ mod test {
                         &self.scheduler,
                         self.start_counter,
                         &self.counter,
+                        None,
+                        None,
                     )),
                     1,
                 );
@@ -4,7 +4,7 @@
 use crate::{assert_success, build_package, AptosPackageHooks};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_framework::{natives::code::PackageMetadata, BuildOptions, BuiltPackage};
-use aptos_gas_profiling::TransactionGasLog;
+use aptos_gas_profiling::{ExecutionTrace, TransactionGasLog};
 use aptos_gas_schedule::{
     AptosGasParameters, FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule,
 };
@@ -338,6 +338,20 @@ impl MoveHarness {
         )
     }
 
+    /// Runs a transaction with the execution tracer.
+    pub fn run_with_tracer(
+        &mut self,
+        account: &Account,
+        payload: TransactionPayload,
+    ) -> (TransactionStatus, ExecutionTrace) {
+        let txn = self.create_transaction_payload(account, payload);
+        let (output, trace) = self.executor.execute_transaction_with_tracer(txn).unwrap();
+        if matches!(output.status(), TransactionStatus::Keep(_)) {
+            self.executor.apply_write_set(output.write_set());
+        }
+        (output.status().to_owned(), trace)
+    }
+
     /// Creates a transaction which runs the specified entry point `fun`. Arguments need to be
     /// provided in bcs-serialized form.
     pub fn create_entry_function(
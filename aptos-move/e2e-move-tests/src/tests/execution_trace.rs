@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_gas_profiling::{TraceEvent, TraceFrame, TracedValue};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ExecutionStatus, TransactionStatus},
+};
+
+/// Returns all frames of the call tree (in depth-first order)
+fn frames(frame: &TraceFrame) -> Vec<&TraceFrame> {
+    let mut frames = vec![frame];
+    for event in &frame.events {
+        if let TraceEvent::Call(callee) = event {
+            frames.extend(self::frames(callee));
+        }
+    }
+    frames
+}
+
+/// Returns all events of the call tree, except calls to non-native functions
+fn events(frame: &TraceFrame) -> Vec<&TraceEvent> {
+    frames(frame)
+        .into_iter()
+        .flat_map(|frame| frame.events.iter())
+        .filter(|event| !matches!(event, TraceEvent::Call(_)))
+        .collect()
+}
+
+#[test]
+fn test_trace_coin_transfer() {
+    let mut h = MoveHarness::new();
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    let (status, trace) = h.run_with_tracer(
+        &alice,
+        aptos_stdlib::aptos_coin_transfer(*bob.address(), 1000),
+    );
+    assert_success!(status);
+
+    let root = &trace.root;
+    assert_eq!(root.function, "0x1::coin::transfer");
+    assert_eq!(root.ty_args, vec!["0x1::aptos_coin::AptosCoin".to_string()]);
+    assert!(root.returned);
+
+    // All calls returned, and the gas of every frame includes the gas of its callees
+    for frame in frames(root) {
+        assert!(frame.returned, "{} didn't return", frame.function);
+        let callees_gas_used: u64 = frame
+            .events
+            .iter()
+            .map(|event| match event {
+                TraceEvent::Call(callee) => callee.gas_used,
+                TraceEvent::NativeCall { gas_used, .. } => *gas_used,
+                _ => 0,
+            })
+            .sum();
+        assert!(frame.gas_used >= callees_gas_used);
+    }
+
+    // The arguments of the calls are decoded
+    assert!(frames(root).iter().any(|frame| {
+        frame.args.contains(&TracedValue::Address(*bob.address()))
+            && frame.args.contains(&TracedValue::U64(1000))
+    }));
+
+    // The resources accessed and the events emitted are recorded
+    let events = events(root);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        TraceEvent::LoadResource { address, exists: true, .. } if address == alice.address()
+    )));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, TraceEvent::BorrowGlobal { is_mut: true, .. })));
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, TraceEvent::EmitEvent { .. })));
+
+    // The trace can be rendered
+    assert!(trace.to_string().starts_with("0x1::coin::transfer<"));
+}
+
+#[test]
+fn test_trace_aborted_coin_transfer() {
+    let mut h = MoveHarness::new();
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = h.new_account_at(AccountAddress::from_hex_literal("0xb0b").unwrap());
+
+    // Transfer more than the balance of alice
+    let (status, trace) = h.run_with_tracer(
+        &alice,
+        aptos_stdlib::aptos_coin_transfer(*bob.address(), u64::MAX),
+    );
+    assert!(matches!(
+        status,
+        TransactionStatus::Keep(ExecutionStatus::MoveAbort { .. })
+    ));
+
+    // The frames that didn't return form the abort path, from the root to the aborting function
+    let root = &trace.root;
+    assert!(!root.returned);
+    let mut abort_path = vec![root.function.clone()];
+    let mut frame = root;
+    while let Some(callee) = frame.events.iter().find_map(|event| match event {
+        TraceEvent::Call(callee) if !callee.returned => Some(callee),
+        _ => None,
+    }) {
+        abort_path.push(callee.function.clone());
+        frame = callee;
+    }
+    assert!(abort_path.len() > 1, "abort path: {:?}", abort_path);
+    assert!(abort_path.contains(&"0x1::coin::withdraw".to_string()));
+}
@@ -15,6 +15,7 @@ mod constructor_args;
 mod dependencies;
 mod enum_upgrade;
 mod error_map;
+mod execution_trace;
 mod fee_payer;
 mod fungible_asset;
 mod gas;
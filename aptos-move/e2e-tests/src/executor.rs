@@ -19,7 +19,9 @@ use aptos_crypto::HashValue;
 use aptos_framework::ReleaseBundle;
 use aptos_gas_algebra::DynamicExpression;
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
-use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
+use aptos_gas_profiling::{
+    traced_entry_function, ExecutionTrace, ExecutionTracer, GasProfiler, TransactionGasLog,
+};
 use aptos_gas_schedule::{AptosGasParameters, InitialGasSchedule, LATEST_GAS_FEATURE_VERSION};
 use aptos_keygen::KeyGen;
 use aptos_types::{
@@ -529,6 +531,8 @@ impl FakeExecutor {
                 },
                 allow_fallback: self.allow_block_executor_fallback,
                 discard_failed_blocks: false,
+                conflict_analytics_dir: None,
+                adaptive_concurrency: None,
             },
             onchain: onchain_config,
         };
@@ -706,6 +710,32 @@ impl FakeExecutor {
         ))
     }
 
+    pub fn execute_transaction_with_tracer(
+        &self,
+        txn: SignedTransaction,
+    ) -> anyhow::Result<(TransactionOutput, ExecutionTrace)> {
+        let txn = txn
+            .check_signature()
+            .expect("invalid signature for transaction");
+
+        let log_context = AdapterLogSchema::new(self.data_store.id(), 0);
+        let vm = AptosVM::new(self.get_state_view());
+
+        let entry_function = traced_entry_function(txn.payload())?;
+        let resolver = self.data_store.as_move_resolver();
+        let (_status, output, tracer) = vm.execute_user_transaction_with_modified_gas_meter(
+            &resolver,
+            &txn,
+            &log_context,
+            |gas_meter| ExecutionTracer::new(gas_meter, entry_function.as_ref()),
+        )?;
+
+        Ok((
+            output.try_materialize_into_transaction_output(&resolver)?,
+            tracer.finish(),
+        ))
+    }
+
     fn trace<P: AsRef<Path>, T: Serialize>(dir: P, item: &T) -> usize {
         let dir = dir.as_ref();
         let seq = fs::read_dir(dir).expect("Unable to read trace dir").count();
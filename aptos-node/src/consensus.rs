@@ -12,6 +12,7 @@ use aptos_consensus::{
     network_interface::ConsensusMsg,
 };
 use aptos_consensus_notifications::ConsensusNotifier;
+use aptos_consensus_types::verified_block_feed::VerifiedBlockFeed;
 use aptos_dkg_runtime::{start_dkg_runtime, DKGMessage};
 use aptos_event_notifications::{
     DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
@@ -40,6 +41,7 @@ pub fn create_consensus_observer_runtime(
     consensus_observer_reconfig_subscription: Option<
         ReconfigNotificationListener<DbBackedOnChainConfig>,
     >,
+    verified_block_feed: Option<VerifiedBlockFeed>,
 ) -> Option<Runtime> {
     if node_config
         .consensus_observer
@@ -59,6 +61,7 @@ pub fn create_consensus_observer_runtime(
             consensus_to_mempool_sender,
             db_rw,
             consensus_observer_reconfig_subscription,
+            verified_block_feed,
         );
         Some(consensus_observer_runtime)
     } else {
@@ -212,3 +215,15 @@ pub fn create_jwk_consensus_runtime(
     };
     jwk_consensus_runtime
 }
+
+/// Creates and returns the verified block feed (if the consensus feed is enabled)
+pub fn create_verified_block_feed(node_config: &NodeConfig) -> Option<VerifiedBlockFeed> {
+    let indexer_grpc_config = &node_config.indexer_grpc;
+    if indexer_grpc_config.enabled && indexer_grpc_config.enable_consensus_feed {
+        Some(VerifiedBlockFeed::new(
+            indexer_grpc_config.consensus_feed_capacity,
+        ))
+    } else {
+        None
+    }
+}
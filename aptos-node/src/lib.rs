@@ -665,22 +665,31 @@ pub fn setup_environment_and_start_node(
     );
 
     // Start state sync and get the notification endpoints for mempool and consensus
-    let (aptos_data_client, state_sync_runtimes, mempool_listener, consensus_notifier) =
-        state_sync::start_state_sync_and_get_notification_handles(
-            &node_config,
-            storage_service_network_interfaces,
-            genesis_waypoint,
-            event_subscription_service,
-            db_rw.clone(),
-        )?;
+    let (
+        aptos_data_client,
+        streaming_service_diagnostics,
+        state_sync_runtimes,
+        mempool_listener,
+        consensus_notifier,
+    ) = state_sync::start_state_sync_and_get_notification_handles(
+        &node_config,
+        storage_service_network_interfaces,
+        genesis_waypoint,
+        event_subscription_service,
+        db_rw.clone(),
+    )?;
 
     // Start the node inspection service
     services::start_node_inspection_service(
         &node_config,
         aptos_data_client,
         peers_and_metadata.clone(),
+        streaming_service_diagnostics,
     );
 
+    // Create the verified block feed (if the consensus feed is enabled)
+    let verified_block_feed = consensus::create_verified_block_feed(&node_config);
+
     // Bootstrap the API and indexer
     let (
         mempool_client_receiver,
@@ -689,7 +698,13 @@ pub fn setup_environment_and_start_node(
         indexer_runtime,
         indexer_grpc_runtime,
         internal_indexer_db_runtime,
-    ) = services::bootstrap_api_and_indexer(&node_config, db_rw.clone(), chain_id, indexer_db_opt)?;
+    ) = services::bootstrap_api_and_indexer(
+        &node_config,
+        db_rw.clone(),
+        chain_id,
+        indexer_db_opt,
+        verified_block_feed.clone(),
+    )?;
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -757,6 +772,7 @@ pub fn setup_environment_and_start_node(
         consensus_to_mempool_sender,
         db_rw,
         consensus_observer_reconfig_subscription,
+        verified_block_feed,
     );
 
     Ok(AptosHandle {
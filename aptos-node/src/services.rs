@@ -10,7 +10,9 @@ use aptos_consensus::{
     persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
 };
 use aptos_consensus_notifications::ConsensusNotifier;
+use aptos_consensus_types::verified_block_feed::VerifiedBlockFeed;
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::diagnostics::StreamingServiceDiagnostics;
 use aptos_db_indexer::{db_indexer::InternalIndexerDB, indexer_reader::IndexerReaders};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
@@ -45,6 +47,7 @@ pub fn bootstrap_api_and_indexer(
     db_rw: DbReaderWriter,
     chain_id: ChainId,
     internal_indexer_db: Option<InternalIndexerDB>,
+    verified_block_feed: Option<VerifiedBlockFeed>,
 ) -> anyhow::Result<(
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
@@ -100,6 +103,7 @@ pub fn bootstrap_api_and_indexer(
         db_rw.reader.clone(),
         mempool_client_sender.clone(),
         indexer_reader,
+        verified_block_feed,
     );
 
     // Create the indexer runtime
@@ -194,11 +198,13 @@ pub fn start_node_inspection_service(
     node_config: &NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
 ) {
     aptos_inspection_service::start_inspection_service(
         node_config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        streaming_service_diagnostics,
     )
 }
 
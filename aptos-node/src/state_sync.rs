@@ -6,6 +6,7 @@ use aptos_config::config::{NodeConfig, StateSyncConfig};
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::{client::AptosDataClient, poller};
 use aptos_data_streaming_service::{
+    diagnostics::StreamingServiceDiagnostics,
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
 };
@@ -133,6 +134,7 @@ pub fn start_state_sync_and_get_notification_handles(
     db_rw: DbReaderWriter,
 ) -> anyhow::Result<(
     AptosDataClient,
+    StreamingServiceDiagnostics,
     StateSyncRuntimes,
     MempoolNotificationListener,
     ConsensusNotifier,
@@ -148,7 +150,7 @@ pub fn start_state_sync_and_get_notification_handles(
 
     // Start the data streaming service
     let state_sync_config = node_config.state_sync;
-    let (streaming_service_client, streaming_service_runtime) =
+    let (streaming_service_client, streaming_service_diagnostics, streaming_service_runtime) =
         setup_data_streaming_service(state_sync_config, aptos_data_client.clone())?;
 
     // Create the chunk executor and persistent storage
@@ -207,6 +209,7 @@ pub fn start_state_sync_and_get_notification_handles(
 
     Ok((
         aptos_data_client,
+        streaming_service_diagnostics,
         state_sync_runtimes,
         mempool_listener,
         consensus_notifier,
@@ -217,7 +220,7 @@ pub fn start_state_sync_and_get_notification_handles(
 fn setup_data_streaming_service(
     state_sync_config: StateSyncConfig,
     aptos_data_client: AptosDataClient,
-) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
+) -> anyhow::Result<(StreamingServiceClient, StreamingServiceDiagnostics, Runtime)> {
     // Create the data streaming service
     let (streaming_service_client, streaming_service_listener) =
         new_streaming_service_client_listener_pair();
@@ -228,12 +231,17 @@ fn setup_data_streaming_service(
         streaming_service_listener,
         TimeService::real(),
     );
+    let streaming_service_diagnostics = data_streaming_service.get_streaming_service_diagnostics();
 
     // Start the data streaming service
     let streaming_service_runtime = aptos_runtimes::spawn_named_runtime("stream-serv".into(), None);
     streaming_service_runtime.spawn(data_streaming_service.start_service());
 
-    Ok((streaming_service_client, streaming_service_runtime))
+    Ok((
+        streaming_service_client,
+        streaming_service_diagnostics,
+        streaming_service_runtime,
+    ))
 }
 
 /// Sets up the aptos data client runtime
@@ -11,6 +11,8 @@ use aptos_indexer_grpc_table_info::internal_indexer_db_service::InternalIndexerD
 use aptos_logger::{debug, info};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_types::{ledger_info::LedgerInfoWithSignatures, waypoint::Waypoint};
+#[cfg(not(feature = "consensus-only-perf-test"))]
+use aptos_validator_interface::ForkedDb;
 use aptos_vm::AptosVM;
 use either::Either;
 use std::{fs, path::Path, sync::Arc, time::Instant};
@@ -84,6 +86,21 @@ pub(crate) fn bootstrap_db(
                 (db_arc as Arc<dyn DbReader>, db_rw, Some(db_backup_service))
             },
         };
+
+    // In fork mode, the state that doesn't exist locally is fetched from the remote chain.
+    // Note: genesis must be applied to the local DB first (i.e., without the remote state).
+    let (aptos_db_reader, db_rw) = match &node_config.storage.fork {
+        Some(fork_config) => {
+            info!(
+                "Forking the remote chain at {} (version {})",
+                fork_config.url, fork_config.version
+            );
+            let forked_db = ForkedDb::new(db_rw, fork_config, &node_config.storage.dir())?;
+            let (forked_db, db_rw) = DbReaderWriter::wrap(forked_db);
+            (forked_db as Arc<dyn DbReader>, db_rw)
+        },
+        None => (aptos_db_reader, db_rw),
+    };
     Ok((aptos_db_reader, db_rw, backup_service, internal_indexer_db))
 }
 
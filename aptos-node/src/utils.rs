@@ -67,4 +67,10 @@ pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
     {
         AptosVM::set_processed_transactions_detailed_counters();
     }
+    if let Some(dir) = &node_config.execution.conflict_analytics_dir {
+        AptosVM::set_conflict_analytics_dir_once(dir.clone());
+    }
+    if let Some(adaptive_concurrency) = &node_config.execution.adaptive_concurrency {
+        AptosVM::set_adaptive_concurrency_once(adaptive_concurrency.clone());
+    }
 }
@@ -49,6 +49,14 @@ pub struct ApiConfig {
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    /// Enables the execution tracing of simulated and committed transactions. Tracing re-executes
+    /// transactions and records every call, so it is much more expensive than simulation.
+    #[serde(default = "default_disabled")]
+    pub transaction_tracing_enabled: bool,
+    /// Enables returning the read and write sets of simulated transactions. Simulations which
+    /// request them don't reuse the code cache of the VM, so they are more expensive.
+    #[serde(default = "default_disabled")]
+    pub simulation_access_list_enabled: bool,
     /// Maximum number of transactions that can be sent with the Batch submit API
     pub max_submit_transaction_batch_size: usize,
     /// Maximum page size for transaction paginated APIs
@@ -124,6 +132,8 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            transaction_tracing_enabled: default_disabled(),
+            simulation_access_list_enabled: default_disabled(),
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_block_transactions_page_size: *MAX_RECEIVING_BLOCK_TXNS as u16,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
@@ -88,6 +88,9 @@ pub struct ConsensusConfig {
     pub proof_cache_capacity: u64,
     pub rand_rb_config: ReliableBroadcastConfig,
     pub num_bounded_executor_tasks: u64,
+    /// The number of recent consensus events kept in memory by the flight recorder.
+    /// The recorder is opt-in, i.e., it is disabled by default (a capacity of 0).
+    pub flight_recorder_capacity: usize,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -347,6 +350,7 @@ impl Default for ConsensusConfig {
                 rpc_timeout_ms: 10000,
             },
             num_bounded_executor_tasks: 16,
+            flight_recorder_capacity: 0,
         }
     }
 }
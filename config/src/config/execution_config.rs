@@ -8,7 +8,10 @@ use crate::config::{
     node_config_loader::NodeType, transaction_filter_type::Filter, utils::RootPath, Error,
     NodeConfig,
 };
-use aptos_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
+use aptos_types::{
+    block_executor::config::AdaptiveConcurrencyConfig, chain_id::ChainId, transaction::Transaction,
+    waypoint::Waypoint,
+};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 use std::{
@@ -50,6 +53,12 @@ pub struct ExecutionConfig {
     pub paranoid_hot_potato_verification: bool,
     /// Enables enhanced metrics around processed transactions
     pub processed_transactions_detailed_counters: bool,
+    /// If set, the conflicts observed during parallel execution of each block (dependencies
+    /// between transactions, aborts and re-executions, hot keys) are written to this directory
+    pub conflict_analytics_dir: Option<PathBuf>,
+    /// If set, the number of execution threads is adapted to the contention of the workload,
+    /// with the concurrency level being the maximum
+    pub adaptive_concurrency: Option<AdaptiveConcurrencyConfig>,
     /// Enables filtering of transactions before they are sent to execution
     pub transaction_filter: Filter,
     /// Used during DB bootstrapping
@@ -84,6 +93,8 @@ impl Default for ExecutionConfig {
             paranoid_hot_potato_verification: true,
             discard_failed_blocks: false,
             processed_transactions_detailed_counters: false,
+            conflict_analytics_dir: None,
+            adaptive_concurrency: None,
             transaction_filter: Filter::empty(),
             genesis_waypoint: None,
         }
@@ -17,6 +17,7 @@ use std::{
 const DEFAULT_PROCESSOR_TASK_COUNT: u16 = 20;
 const DEFAULT_PROCESSOR_BATCH_SIZE: u16 = 1000;
 const DEFAULT_OUTPUT_BATCH_SIZE: u16 = 100;
+const DEFAULT_CONSENSUS_FEED_CAPACITY: usize = 100;
 pub const DEFAULT_GRPC_STREAM_PORT: u16 = 50051;
 
 #[derive(Clone, Deserialize, PartialEq, Eq, Serialize)]
@@ -41,6 +42,15 @@ pub struct IndexerGrpcConfig {
 
     /// Number of transactions returned in a single stream response
     pub output_batch_size: u16,
+
+    /// If true, the consensus feed service will be exposed, streaming the blocks
+    /// ordered by the consensus observer (before they are committed), followed
+    /// by their commit decisions. This requires the consensus observer.
+    pub enable_consensus_feed: bool,
+
+    /// Maximum number of consensus feed updates buffered for each subscriber
+    /// (subscribers that fall further behind are disconnected).
+    pub consensus_feed_capacity: usize,
 }
 
 impl Debug for IndexerGrpcConfig {
@@ -55,6 +65,8 @@ impl Debug for IndexerGrpcConfig {
             .field("processor_task_count", &self.processor_task_count)
             .field("processor_batch_size", &self.processor_batch_size)
             .field("output_batch_size", &self.output_batch_size)
+            .field("enable_consensus_feed", &self.enable_consensus_feed)
+            .field("consensus_feed_capacity", &self.consensus_feed_capacity)
             .finish()
     }
 }
@@ -74,6 +86,8 @@ impl Default for IndexerGrpcConfig {
             processor_task_count: DEFAULT_PROCESSOR_TASK_COUNT,
             processor_batch_size: DEFAULT_PROCESSOR_BATCH_SIZE,
             output_batch_size: DEFAULT_OUTPUT_BATCH_SIZE,
+            enable_consensus_feed: false,
+            consensus_feed_capacity: DEFAULT_CONSENSUS_FEED_CAPACITY,
         }
     }
 }
@@ -96,6 +110,15 @@ impl ConfigSanitizer for IndexerGrpcConfig {
                 "storage.enable_indexer or indexer_table_info.enabled must be true if indexer_grpc.enabled is true".to_string(),
             ));
         }
+
+        if node_config.indexer_grpc.enable_consensus_feed
+            && !node_config.consensus_observer.observer_enabled
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "consensus_observer.observer_enabled must be true if indexer_grpc.enable_consensus_feed is true".to_string(),
+            ));
+        }
         Ok(())
     }
 }
@@ -120,7 +143,48 @@ impl ConfigOptimizer for IndexerGrpcConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{IndexerTableInfoConfig, StorageConfig};
+    use crate::config::{ConsensusObserverConfig, IndexerTableInfoConfig, StorageConfig};
+
+    #[test]
+    fn test_sanitize_enable_consensus_feed() {
+        // Create a node config with the consensus feed enabled, but the observer disabled
+        let mut node_config = NodeConfig {
+            storage: StorageConfig {
+                enable_indexer: true,
+                ..Default::default()
+            },
+            indexer_grpc: IndexerGrpcConfig {
+                enabled: true,
+                enable_consensus_feed: true,
+                ..Default::default()
+            },
+            consensus_observer: ConsensusObserverConfig {
+                observer_enabled: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = IndexerGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Enable the consensus observer
+        node_config.consensus_observer.observer_enabled = true;
+
+        // Sanitize the config and verify that it now succeeds
+        IndexerGrpcConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+    }
 
     #[test]
     fn test_sanitize_enable_indexer() {
@@ -18,7 +18,9 @@ pub struct InspectionServiceConfig {
     pub address: String,
     pub port: u16,
     pub expose_configuration: bool,
+    pub expose_consensus_flight_recorder: bool,
     pub expose_peer_information: bool,
+    pub expose_quorum_store_information: bool,
     pub expose_system_information: bool,
 }
 
@@ -28,7 +30,9 @@ impl Default for InspectionServiceConfig {
             address: "0.0.0.0".to_string(),
             port: 9101,
             expose_configuration: false,
+            expose_consensus_flight_recorder: false,
             expose_peer_information: true,
+            expose_quorum_store_information: false,
             expose_system_information: true,
         }
     }
@@ -76,7 +80,8 @@ impl ConfigOptimizer for InspectionServiceConfig {
         let inspection_service_config = &mut node_config.inspection_service;
         let local_inspection_config_yaml = &local_config_yaml["inspection_service"];
 
-        // Enable all endpoints for non-mainnet nodes (to aid debugging)
+        // Enable all endpoints for non-mainnet nodes (to aid debugging). Note: the
+        // flight recorder and quorum store endpoints are always opt-in.
         let mut modified_config = false;
         if let Some(chain_id) = chain_id {
             if !chain_id.is_mainnet() {
@@ -111,7 +116,9 @@ mod tests {
         let mut node_config = NodeConfig {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
+                expose_consensus_flight_recorder: false,
                 expose_peer_information: false,
+                expose_quorum_store_information: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -130,7 +137,17 @@ mod tests {
 
         // Verify all endpoints are still disabled
         assert!(!node_config.inspection_service.expose_configuration);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_consensus_flight_recorder
+        );
         assert!(!node_config.inspection_service.expose_peer_information);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_quorum_store_information
+        );
         assert!(!node_config.inspection_service.expose_system_information);
     }
 
@@ -140,7 +157,9 @@ mod tests {
         let mut node_config = NodeConfig {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
+                expose_consensus_flight_recorder: false,
                 expose_peer_information: false,
+                expose_quorum_store_information: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -157,9 +176,19 @@ mod tests {
         .unwrap();
         assert!(modified_config);
 
-        // Verify all endpoints are now enabled
+        // Verify all endpoints are now enabled (except the opt-in flight recorder and quorum store)
         assert!(node_config.inspection_service.expose_configuration);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_consensus_flight_recorder
+        );
         assert!(node_config.inspection_service.expose_peer_information);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_quorum_store_information
+        );
         assert!(node_config.inspection_service.expose_system_information);
     }
 
@@ -169,7 +198,9 @@ mod tests {
         let mut node_config = NodeConfig {
             inspection_service: InspectionServiceConfig {
                 expose_configuration: false,
+                expose_consensus_flight_recorder: false,
                 expose_peer_information: false,
+                expose_quorum_store_information: false,
                 expose_system_information: false,
                 ..Default::default()
             },
@@ -197,7 +228,17 @@ mod tests {
 
         // Verify only the system information endpoint is now enabled
         assert!(!node_config.inspection_service.expose_configuration);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_consensus_flight_recorder
+        );
         assert!(node_config.inspection_service.expose_peer_information);
+        assert!(
+            !node_config
+                .inspection_service
+                .expose_quorum_store_information
+        );
         assert!(node_config.inspection_service.expose_system_information);
     }
 
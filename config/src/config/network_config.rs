@@ -124,6 +124,8 @@ pub struct NetworkConfig {
     pub max_parallel_deserialization_tasks: Option<usize>,
     /// Whether or not to enable latency aware peer dialing
     pub enable_latency_aware_dialing: bool,
+    /// Configuration for the (opt-in) network message recorder
+    pub message_recorder: MessageRecorderConfig,
 }
 
 impl Default for NetworkConfig {
@@ -165,6 +167,7 @@ impl NetworkConfig {
             outbound_tx_buffer_size_bytes: None,
             max_parallel_deserialization_tasks: None,
             enable_latency_aware_dialing: true,
+            message_recorder: MessageRecorderConfig::default(),
         };
 
         // Configure the number of parallel deserialization tasks
@@ -388,6 +391,37 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// Configuration for recording the network messages exchanged with peers.
+/// Recorded messages are written to a set of rotating capture files and can
+/// later be decoded (or replayed) using `aptos-debugger network-capture`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageRecorderConfig {
+    /// Whether or not to record network messages (disabled by default)
+    pub enabled: bool,
+    /// The directory in which to write the capture files
+    pub output_dir: PathBuf,
+    /// The maximum size of a single capture file before it is rotated
+    pub max_file_size_bytes: u64,
+    /// The maximum number of capture files to retain on disk
+    pub max_num_files: usize,
+    /// The maximum number of messages pending to be written to disk.
+    /// Messages are dropped (and not recorded) if this limit is exceeded.
+    pub max_pending_messages: usize,
+}
+
+impl Default for MessageRecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            output_dir: PathBuf::from("/tmp/aptos_network_capture"),
+            max_file_size_bytes: 100 * 1024 * 1024, // 100 MiB
+            max_num_files: 10,
+            max_pending_messages: 10_000,
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -466,7 +466,7 @@ impl ConfigSanitizer for StateSyncConfig {
 impl ConfigSanitizer for StateSyncDriverConfig {
     fn sanitize(
         node_config: &NodeConfig,
-        _node_type: NodeType,
+        node_type: NodeType,
         _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         let sanitizer_name = Self::get_sanitizer_name();
@@ -483,6 +483,32 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that nodes storing partial state are fullnodes that only
+        // apply transaction outputs (transactions cannot be executed without
+        // the full state).
+        if node_config.storage.partial_state.enabled {
+            if node_type.is_validator() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Partial state storage should not be enabled for validators!".to_string(),
+                ));
+            }
+
+            // Note: fast syncing (i.e., downloading the latest states) is not
+            // supported, as the state snapshot must be complete to be verified.
+            let applies_outputs_when_bootstrapping = state_sync_driver_config.bootstrapping_mode
+                == BootstrappingMode::ApplyTransactionOutputsFromGenesis;
+            let applies_outputs_when_syncing = state_sync_driver_config.continuous_syncing_mode
+                == ContinuousSyncingMode::ApplyTransactionOutputs;
+            if !applies_outputs_when_bootstrapping || !applies_outputs_when_syncing {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Nodes with partial state storage must bootstrap and sync by applying transaction outputs!"
+                        .to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -520,11 +546,13 @@ impl ConfigOptimizer for StateSyncDriverConfig {
 
         // Default to fast sync for all testnet and mainnet nodes
         // because pruning has kicked in, and nodes will struggle
-        // to locate all the data since genesis.
+        // to locate all the data since genesis. Note: nodes with
+        // partial state storage cannot fast sync.
         let mut modified_config = false;
         if let Some(chain_id) = chain_id {
             if (chain_id.is_testnet() || chain_id.is_mainnet())
                 && local_driver_config_yaml["bootstrapping_mode"].is_null()
+                && !node_config.storage.partial_state.enabled
             {
                 state_sync_driver_config.bootstrapping_mode =
                     BootstrappingMode::DownloadLatestStates;
@@ -788,6 +816,54 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_partial_state_sync_modes() {
+        // Create a node config with partial state and execution enabled
+        let mut node_config = create_execution_mode_config();
+        node_config.storage.partial_state.enabled = true;
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Update the syncing modes to apply transaction outputs
+        let state_sync_driver_config = &mut node_config.state_sync.state_sync_driver;
+        state_sync_driver_config.bootstrapping_mode =
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis;
+        state_sync_driver_config.continuous_syncing_mode =
+            ContinuousSyncingMode::ApplyTransactionOutputs;
+
+        // Verify that sanitization fails if the node fast syncs
+        node_config.state_sync.state_sync_driver.bootstrapping_mode =
+            BootstrappingMode::DownloadLatestStates;
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that sanitization now passes for fullnodes (but not validators)
+        node_config.state_sync.state_sync_driver.bootstrapping_mode =
+            BootstrappingMode::ApplyTransactionOutputsFromGenesis;
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
@@ -8,7 +8,11 @@ use crate::{
 };
 use anyhow::{bail, ensure, Result};
 use aptos_logger::warn;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    state_store::{state_key::filter::StateKeyFilter, table::TableHandle},
+};
 use arr_macro::arr;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -196,6 +200,57 @@ pub struct StorageConfig {
     /// If not specificed, will use `dir` as default.
     /// Only allowed when sharding is enabled.
     pub db_path_overrides: Option<DbPathConfig>,
+    /// Partial state storage configuration (for application-specific fullnodes)
+    pub partial_state: PartialStateConfig,
+    /// If set, the node runs on top of the state of a remote chain (local testnets only)
+    pub fork: Option<ForkConfig>,
+}
+
+/// Partial state storage allows a fullnode to follow the ledger (i.e., sync
+/// and verify all transaction infos and outputs) while only storing state
+/// values for a configured set of addresses. The state Merkle tree is still
+/// stored in full, so that state roots can be verified. Reads of untracked
+/// state are refused, and the storage usage is not tracked. Note: partial state
+/// storage requires an unsharded DB, and tracking new addresses (or table
+/// handles) requires a re-sync, as their earlier state was not stored.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PartialStateConfig {
+    /// Whether or not partial state storage is enabled
+    pub enabled: bool,
+    /// The account addresses to store state values for. Special addresses (e.g.,
+    /// the framework at 0x1) and the objects owned by the tracked addresses (e.g.,
+    /// primary fungible stores) are always tracked. Objects are only tracked from
+    /// the version they became owned by a tracked address (e.g., on transfer).
+    pub tracked_addresses: Vec<AccountAddress>,
+    /// The table handles to store table items for. Tables are not tracked by
+    /// their owners (e.g., framework tables must also be listed explicitly).
+    pub tracked_table_handles: Vec<TableHandle>,
+}
+
+impl PartialStateConfig {
+    /// Returns the state key filter to apply (if partial state storage is enabled)
+    pub fn state_key_filter(&self) -> Option<StateKeyFilter> {
+        self.enabled.then(|| {
+            StateKeyFilter::new(
+                self.tracked_addresses.iter().cloned(),
+                self.tracked_table_handles.iter().cloned(),
+            )
+        })
+    }
+}
+
+/// Fork mode allows a local testnet to run on top of the state of a remote chain (e.g.,
+/// mainnet) at a pinned version. State that doesn't exist locally is fetched from the
+/// REST API of the remote chain (and cached locally). Note: the framework (i.e., the
+/// special addresses) and the validator set always come from the local genesis.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForkConfig {
+    /// The REST API endpoint of the remote chain
+    pub url: url::Url,
+    /// The version of the remote chain to fork at
+    pub version: u64,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -345,6 +400,8 @@ impl Default for StorageConfig {
             db_path_overrides: None,
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            partial_state: PartialStateConfig::default(),
+            fork: None,
         }
     }
 }
@@ -496,7 +553,7 @@ impl ConfigSanitizer for StorageConfig {
     fn sanitize(
         node_config: &NodeConfig,
         _node_type: NodeType,
-        _chain_id: Option<ChainId>,
+        chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         let sanitizer_name = Self::get_sanitizer_name();
         let config = &node_config.storage;
@@ -540,6 +597,24 @@ impl ConfigSanitizer for StorageConfig {
             ));
         }
 
+        if config.fork.is_some()
+            && chain_id.map_or(false, |chain_id| {
+                chain_id.is_mainnet() || chain_id.is_testnet()
+            })
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Fork mode is only supported by local testnets.".to_string(),
+            ));
+        }
+
+        if config.partial_state.enabled && config.rocksdb_configs.enable_storage_sharding {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Partial state storage is not supported if sharding is enabled.".to_string(),
+            ));
+        }
+
         if let Some(db_path_overrides) = config.db_path_overrides.as_ref() {
             if !config.rocksdb_configs.enable_storage_sharding {
                 return Err(Error::ConfigSanitizerFailed(
@@ -597,7 +672,11 @@ impl ConfigSanitizer for StorageConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::config::{PrunerConfig, ShardPathConfig, ShardedDbPathConfig};
+    use crate::config::{
+        config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+        PrunerConfig, ShardPathConfig, ShardedDbPathConfig, StorageConfig,
+    };
+    use aptos_types::chain_id::ChainId;
 
     #[test]
     pub fn test_default_prune_window() {
@@ -673,4 +752,50 @@ mod test {
 
         assert!(path_overrides.get_shard_paths().is_err());
     }
+
+    #[test]
+    pub fn test_sanitize_partial_state_with_sharding() {
+        // Create a node config with partial state storage and sharding enabled
+        let mut node_config = NodeConfig::default();
+        node_config.storage.partial_state.enabled = true;
+        node_config.storage.rocksdb_configs.enable_storage_sharding = true;
+
+        // Verify that sanitization fails
+        let error = StorageConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable sharding and verify that sanitization passes
+        node_config.storage.rocksdb_configs.enable_storage_sharding = false;
+        StorageConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    pub fn test_sanitize_fork_on_public_networks() {
+        // Create a node config with fork mode enabled
+        let mut node_config = NodeConfig::default();
+        node_config.storage.fork = Some(ForkConfig {
+            url: "https://api.mainnet.aptoslabs.com/v1".parse().unwrap(),
+            version: 100,
+        });
+
+        // Verify that sanitization fails on mainnet and testnet
+        for chain_id in [ChainId::mainnet(), ChainId::testnet()] {
+            let error = StorageConfig::sanitize(&node_config, NodeType::Validator, Some(chain_id))
+                .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+
+        // Verify that sanitization passes for local testnets
+        StorageConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::test())).unwrap();
+    }
 }
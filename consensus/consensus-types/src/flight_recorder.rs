@@ -0,0 +1,271 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The flight recorder keeps the most recent structured consensus events (e.g., proposal
+//! received, vote sent, QC formed, ordered, executed and committed) in a bounded in-memory
+//! ring buffer. Dumps from several validators can be merged into a per-block timeline.
+
+use crate::{
+    block::Block,
+    common::{Author, Payload, Round},
+};
+use aptos_crypto::HashValue;
+use aptos_infallible::{duration_since_epoch, Mutex};
+use aptos_types::block_info::BlockInfo;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The global flight recorder (disabled until it is initialized with a non-zero capacity)
+pub static FLIGHT_RECORDER: Lazy<FlightRecorder> = Lazy::new(FlightRecorder::default);
+
+/// A single structured consensus event for a block
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ConsensusEvent {
+    /// The time at which the event was recorded (in microseconds since the epoch)
+    pub timestamp_usecs: u64,
+    /// The stage of the block (see `BlockStage`)
+    pub stage: String,
+    pub epoch: u64,
+    pub round: Round,
+    pub block_id: HashValue,
+    /// The timestamp of the block (i.e., when it was proposed)
+    pub block_timestamp_usecs: u64,
+    /// The digests of the batches in the block (only recorded for proposals)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub batches: Vec<HashValue>,
+}
+
+impl ConsensusEvent {
+    /// Creates a new event (recorded now) for the given block and stage
+    pub fn new(
+        stage: &str,
+        epoch: u64,
+        round: Round,
+        block_id: HashValue,
+        block_timestamp_usecs: u64,
+    ) -> Self {
+        Self {
+            timestamp_usecs: duration_since_epoch().as_micros() as u64,
+            stage: stage.into(),
+            epoch,
+            round,
+            block_id,
+            block_timestamp_usecs,
+            batches: vec![],
+        }
+    }
+
+    /// Creates a new event for the given block and stage
+    pub fn from_block(block: &Block, stage: &str) -> Self {
+        Self::new(
+            stage,
+            block.epoch(),
+            block.round(),
+            block.id(),
+            block.timestamp_usecs(),
+        )
+    }
+
+    /// Creates a new event for the given block info and stage
+    pub fn from_block_info(block_info: &BlockInfo, stage: &str) -> Self {
+        Self::new(
+            stage,
+            block_info.epoch(),
+            block_info.round(),
+            block_info.id(),
+            block_info.timestamp_usecs(),
+        )
+    }
+
+    /// Adds the digests of the batches in the given payload to the event
+    pub fn with_batches(mut self, payload: Option<&Payload>) -> Self {
+        self.batches = payload.map_or(vec![], batch_digests);
+        self
+    }
+}
+
+/// Returns the digests of all batches referenced by the payload
+fn batch_digests(payload: &Payload) -> Vec<HashValue> {
+    match payload {
+        Payload::DirectMempool(_) => vec![],
+        Payload::InQuorumStore(proof_with_data) => proof_with_data
+            .proofs
+            .iter()
+            .map(|proof| *proof.digest())
+            .collect(),
+        Payload::InQuorumStoreWithLimit(proof_with_data) => proof_with_data
+            .proof_with_data
+            .proofs
+            .iter()
+            .map(|proof| *proof.digest())
+            .collect(),
+        Payload::QuorumStoreInlineHybrid(inline_batches, proof_with_data, _) => inline_batches
+            .iter()
+            .map(|(batch_info, _)| *batch_info.digest())
+            .chain(proof_with_data.proofs.iter().map(|proof| *proof.digest()))
+            .collect(),
+        Payload::OptQuorumStore(opt_qs_payload) => opt_qs_payload
+            .inline_batches()
+            .batch_infos()
+            .iter()
+            .map(|batch_info| *batch_info.digest())
+            .chain(
+                opt_qs_payload
+                    .opt_batches()
+                    .batch_summary
+                    .iter()
+                    .map(|batch_info| *batch_info.digest()),
+            )
+            .chain(
+                opt_qs_payload
+                    .proof_with_data()
+                    .batch_summary
+                    .iter()
+                    .map(|proof| *proof.digest()),
+            )
+            .collect(),
+    }
+}
+
+/// A dump of the flight recorder of a single validator
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FlightRecorderDump {
+    /// The validator that recorded the events (if known)
+    pub author: Option<Author>,
+    /// The recorded events (oldest first)
+    pub events: Vec<ConsensusEvent>,
+}
+
+#[derive(Default)]
+struct FlightRecorderInner {
+    author: Option<Author>,
+    capacity: usize,
+    events: VecDeque<ConsensusEvent>,
+}
+
+/// A bounded in-memory ring buffer of consensus events. Once the buffer is
+/// full, the oldest events are dropped.
+#[derive(Default)]
+pub struct FlightRecorder {
+    // Allows callers to check if the recorder is enabled without taking the lock
+    enabled: AtomicBool,
+    inner: Mutex<FlightRecorderInner>,
+}
+
+impl FlightRecorder {
+    /// Creates a new flight recorder with the given capacity
+    pub fn new(author: Option<Author>, capacity: usize) -> Self {
+        let flight_recorder = Self::default();
+        flight_recorder.initialize(author, capacity);
+        flight_recorder
+    }
+
+    /// Sets the author and capacity of the recorder. A capacity of zero
+    /// disables the recorder. Excess events are dropped (oldest first).
+    pub fn initialize(&self, author: Option<Author>, capacity: usize) {
+        let mut inner = self.inner.lock();
+        inner.author = author;
+        inner.capacity = capacity;
+        truncate_events(&mut inner.events, capacity);
+        self.enabled.store(capacity > 0, Ordering::Relaxed);
+    }
+
+    /// Returns true iff the recorder is enabled. Callers should check this
+    /// before creating events, as the recorder is disabled by default.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Records the given event (if the recorder is enabled)
+    pub fn record(&self, event: ConsensusEvent) {
+        let mut inner = self.inner.lock();
+        if inner.capacity == 0 {
+            return;
+        }
+
+        let capacity = inner.capacity;
+        truncate_events(&mut inner.events, capacity - 1);
+        inner.events.push_back(event);
+    }
+
+    /// Returns a dump of all recorded events
+    pub fn dump(&self) -> FlightRecorderDump {
+        let inner = self.inner.lock();
+        FlightRecorderDump {
+            author: inner.author,
+            events: inner.events.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Drops the oldest events until at most `max_events` remain
+fn truncate_events(events: &mut VecDeque<ConsensusEvent>, max_events: usize) {
+    while events.len() > max_events {
+        events.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_event(round: Round) -> ConsensusEvent {
+        ConsensusEvent::from_block_info(
+            &BlockInfo::new(1, round, HashValue::random(), HashValue::zero(), 0, 0, None),
+            "ordered",
+        )
+    }
+
+    #[test]
+    fn test_disabled_recorder() {
+        let flight_recorder = FlightRecorder::default();
+        assert!(!flight_recorder.is_enabled());
+
+        flight_recorder.record(create_event(1));
+        assert!(flight_recorder.dump().events.is_empty());
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let author = Author::random();
+        let flight_recorder = FlightRecorder::new(Some(author), 3);
+        for round in 1..=5 {
+            flight_recorder.record(create_event(round));
+        }
+
+        // Verify that only the latest events are kept
+        let dump = flight_recorder.dump();
+        assert_eq!(dump.author, Some(author));
+        let rounds: Vec<Round> = dump.events.iter().map(|event| event.round).collect();
+        assert_eq!(rounds, vec![3, 4, 5]);
+
+        // Verify that shrinking the recorder drops the oldest events
+        flight_recorder.initialize(Some(author), 1);
+        let rounds: Vec<Round> = flight_recorder
+            .dump()
+            .events
+            .iter()
+            .map(|event| event.round)
+            .collect();
+        assert_eq!(rounds, vec![5]);
+    }
+
+    #[test]
+    fn test_dump_serialization() {
+        let flight_recorder = FlightRecorder::new(None, 10);
+        let payload = Payload::empty(true, true);
+        let event = create_event(1).with_batches(Some(&payload));
+        flight_recorder.record(event);
+
+        let dump = flight_recorder.dump();
+        let serialized_dump = serde_json::to_string(&dump).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FlightRecorderDump>(&serialized_dump).unwrap(),
+            dump
+        );
+    }
+}
@@ -10,6 +10,7 @@ pub mod block_retrieval;
 pub mod common;
 pub mod delayed_qc_msg;
 pub mod epoch_retrieval;
+pub mod flight_recorder;
 pub mod order_vote;
 pub mod order_vote_msg;
 pub mod order_vote_proposal;
@@ -20,11 +21,13 @@ pub mod proof_of_store;
 pub mod proposal_ext;
 pub mod proposal_msg;
 pub mod quorum_cert;
+pub mod quorum_store_diagnostics;
 pub mod randomness;
 pub mod request_response;
 pub mod safety_data;
 pub mod sync_info;
 pub mod timeout_2chain;
+pub mod verified_block_feed;
 pub mod vote;
 pub mod vote_data;
 pub mod vote_msg;
@@ -0,0 +1,303 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The quorum store diagnostics expose the internal state of the quorum store, i.e., the
+//! outstanding proofs of store (and whether their batches are available locally), the
+//! current back pressure (and its reason) and the batch fetch failures of each peer.
+//! This helps to debug stalls where proofs exist, but their batches are missing.
+
+use crate::{common::Author, proof_of_store::BatchId};
+use aptos_crypto::HashValue;
+use aptos_infallible::{duration_since_epoch, Mutex};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The global quorum store diagnostics (updated by the quorum store of the current epoch).
+/// The diagnostics are disabled until they are enabled by the node (see `set_enabled`).
+pub static QUORUM_STORE_DIAGNOSTICS: Lazy<QuorumStoreDiagnostics> =
+    Lazy::new(QuorumStoreDiagnostics::default);
+
+/// An outstanding (i.e., uncommitted and unexpired) proof of store
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OutstandingProof {
+    pub digest: HashValue,
+    pub author: Author,
+    pub batch_id: BatchId,
+    pub expiration_usecs: u64,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+    pub gas_bucket_start: u64,
+    /// The time since the proof was received
+    pub age_ms: u64,
+    /// Whether the batch is available in the local batch store
+    pub locally_available: bool,
+}
+
+/// The back pressure state of the quorum store
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackPressureState {
+    pub txn_count: bool,
+    pub proof_count: bool,
+    pub remaining_txns: u64,
+    pub txn_limit: u64,
+    pub remaining_proofs: u64,
+    pub proof_limit: u64,
+}
+
+impl BackPressureState {
+    /// Returns the reason for the back pressure (or `None`, if there is no back pressure)
+    pub fn reason(&self) -> Option<String> {
+        let mut reasons = vec![];
+        if self.txn_count {
+            reasons.push(format!(
+                "{} remaining txns exceed the limit of {}",
+                self.remaining_txns, self.txn_limit
+            ));
+        }
+        if self.proof_count {
+            reasons.push(format!(
+                "{} remaining proofs exceed the limit of {}",
+                self.remaining_proofs, self.proof_limit
+            ));
+        }
+        (!reasons.is_empty()).then(|| reasons.join(", "))
+    }
+}
+
+/// The type of a failed batch fetch from a peer
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BatchFetchFailure {
+    /// The peer responded that the batch was not found
+    NotFound,
+    /// The request failed (e.g., due to a timeout or network error)
+    Error,
+}
+
+/// The batch fetch failures of a single peer (in the current epoch)
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PeerFetchFailures {
+    pub num_not_found: u64,
+    pub num_errors: u64,
+    pub last_failure_digest: Option<HashValue>,
+    pub last_failure_timestamp_usecs: u64,
+    pub last_error: Option<String>,
+}
+
+/// A snapshot of the quorum store diagnostics
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QuorumStoreSnapshot {
+    pub epoch: u64,
+    /// The time at which the outstanding proofs and back pressure were last updated
+    pub last_update_timestamp_usecs: u64,
+    pub outstanding_proofs: Vec<OutstandingProof>,
+    pub back_pressure: BackPressureState,
+    /// The reason for the back pressure (if any)
+    pub back_pressure_reason: Option<String>,
+    pub peer_fetch_failures: BTreeMap<Author, PeerFetchFailures>,
+}
+
+/// Holds the latest quorum store diagnostics
+#[derive(Default)]
+pub struct QuorumStoreDiagnostics {
+    enabled: AtomicBool,
+    snapshot: Mutex<QuorumStoreSnapshot>,
+}
+
+impl QuorumStoreDiagnostics {
+    /// Enables (or disables) the diagnostics. When disabled, all updates are ignored.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns true iff the diagnostics are enabled. Callers should check this
+    /// before gathering (potentially expensive) updates.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Updates the outstanding proofs and back pressure state for the given epoch
+    pub fn update(
+        &self,
+        epoch: u64,
+        outstanding_proofs: Vec<OutstandingProof>,
+        back_pressure: BackPressureState,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut snapshot = self.snapshot.lock();
+        if !Self::update_epoch(&mut snapshot, epoch) {
+            return;
+        }
+        snapshot.last_update_timestamp_usecs = duration_since_epoch().as_micros() as u64;
+        snapshot.outstanding_proofs = outstanding_proofs;
+        snapshot.back_pressure_reason = back_pressure.reason();
+        snapshot.back_pressure = back_pressure;
+    }
+
+    /// Records a failed batch fetch from the given peer
+    pub fn record_fetch_failure(
+        &self,
+        epoch: u64,
+        peer: Author,
+        digest: HashValue,
+        failure: BatchFetchFailure,
+        error: Option<String>,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut snapshot = self.snapshot.lock();
+        if !Self::update_epoch(&mut snapshot, epoch) {
+            return;
+        }
+        let peer_failures = snapshot.peer_fetch_failures.entry(peer).or_default();
+        match failure {
+            BatchFetchFailure::NotFound => peer_failures.num_not_found += 1,
+            BatchFetchFailure::Error => peer_failures.num_errors += 1,
+        }
+        peer_failures.last_failure_digest = Some(digest);
+        peer_failures.last_failure_timestamp_usecs = duration_since_epoch().as_micros() as u64;
+        peer_failures.last_error = error;
+    }
+
+    /// Returns a snapshot of the diagnostics
+    pub fn snapshot(&self) -> QuorumStoreSnapshot {
+        self.snapshot.lock().clone()
+    }
+
+    /// Clears the diagnostics of the previous epoch (if the epoch is newer). Returns
+    /// false iff the epoch is older than the snapshot, i.e., the update must be ignored
+    /// (e.g., a late update from the quorum store of the previous epoch).
+    fn update_epoch(snapshot: &mut QuorumStoreSnapshot, epoch: u64) -> bool {
+        if epoch < snapshot.epoch {
+            return false;
+        }
+        if epoch > snapshot.epoch {
+            *snapshot = QuorumStoreSnapshot {
+                epoch,
+                ..Default::default()
+            };
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_outstanding_proof(author: Author) -> OutstandingProof {
+        OutstandingProof {
+            digest: HashValue::random(),
+            author,
+            batch_id: BatchId::new_for_test(1),
+            expiration_usecs: 100,
+            num_txns: 10,
+            num_bytes: 1000,
+            gas_bucket_start: 0,
+            age_ms: 5,
+            locally_available: false,
+        }
+    }
+
+    #[test]
+    fn test_back_pressure_reason() {
+        let mut back_pressure = BackPressureState {
+            remaining_txns: 200,
+            txn_limit: 100,
+            remaining_proofs: 20,
+            proof_limit: 10,
+            ..Default::default()
+        };
+        assert_eq!(back_pressure.reason(), None);
+
+        back_pressure.txn_count = true;
+        assert_eq!(
+            back_pressure.reason().unwrap(),
+            "200 remaining txns exceed the limit of 100"
+        );
+
+        back_pressure.proof_count = true;
+        assert_eq!(
+            back_pressure.reason().unwrap(),
+            "200 remaining txns exceed the limit of 100, 20 remaining proofs exceed the limit of 10"
+        );
+    }
+
+    #[test]
+    fn test_disabled_diagnostics() {
+        let diagnostics = QuorumStoreDiagnostics::default();
+        assert!(!diagnostics.is_enabled());
+
+        // Verify that updates are ignored while the diagnostics are disabled
+        let peer = Author::random();
+        diagnostics.update(
+            1,
+            vec![create_outstanding_proof(peer)],
+            BackPressureState::default(),
+        );
+        diagnostics.record_fetch_failure(
+            1,
+            peer,
+            HashValue::random(),
+            BatchFetchFailure::NotFound,
+            None,
+        );
+        assert_eq!(diagnostics.snapshot(), QuorumStoreSnapshot::default());
+    }
+
+    #[test]
+    fn test_update_and_record_fetch_failures() {
+        let diagnostics = QuorumStoreDiagnostics::default();
+        diagnostics.set_enabled(true);
+        let peer = Author::random();
+
+        // Update the diagnostics and record several fetch failures
+        let proof = create_outstanding_proof(peer);
+        diagnostics.update(1, vec![proof.clone()], BackPressureState {
+            proof_count: true,
+            remaining_proofs: 20,
+            proof_limit: 10,
+            ..Default::default()
+        });
+        diagnostics.record_fetch_failure(1, peer, proof.digest, BatchFetchFailure::NotFound, None);
+        diagnostics.record_fetch_failure(
+            1,
+            peer,
+            proof.digest,
+            BatchFetchFailure::Error,
+            Some("timeout".into()),
+        );
+
+        // Verify the snapshot
+        let snapshot = diagnostics.snapshot();
+        assert_eq!(snapshot.epoch, 1);
+        assert_eq!(snapshot.outstanding_proofs, vec![proof.clone()]);
+        assert!(snapshot.back_pressure_reason.is_some());
+        let peer_failures = &snapshot.peer_fetch_failures[&peer];
+        assert_eq!(peer_failures.num_not_found, 1);
+        assert_eq!(peer_failures.num_errors, 1);
+        assert_eq!(peer_failures.last_failure_digest, Some(proof.digest));
+        assert_eq!(peer_failures.last_error, Some("timeout".into()));
+
+        // Record a failure in a new epoch and verify the old diagnostics are cleared
+        diagnostics.record_fetch_failure(2, peer, proof.digest, BatchFetchFailure::NotFound, None);
+        let snapshot = diagnostics.snapshot();
+        assert_eq!(snapshot.epoch, 2);
+        assert!(snapshot.outstanding_proofs.is_empty());
+        assert_eq!(snapshot.peer_fetch_failures[&peer].num_not_found, 1);
+        assert_eq!(snapshot.peer_fetch_failures[&peer].num_errors, 0);
+
+        // Verify that (late) updates from an older epoch are ignored
+        diagnostics.update(1, vec![proof.clone()], BackPressureState::default());
+        diagnostics.record_fetch_failure(1, peer, proof.digest, BatchFetchFailure::Error, None);
+        assert_eq!(diagnostics.snapshot(), snapshot);
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The verified block feed broadcasts the blocks ordered by consensus (as soon as they are
+//! ordered and their payloads have been verified), followed later by the commit decisions
+//! that commit them. This allows low-latency consumers (e.g., the indexer gRPC service)
+//! to process blocks before they are executed and committed.
+
+use aptos_types::{
+    block_info::BlockInfo, ledger_info::LedgerInfoWithSignatures, transaction::SignedTransaction,
+};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// An ordered block (and its transactions) that has been verified
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedBlock {
+    pub block_info: BlockInfo,
+    pub transactions: Vec<SignedTransaction>,
+}
+
+impl VerifiedBlock {
+    pub fn new(block_info: BlockInfo, transactions: Vec<SignedTransaction>) -> Self {
+        Self {
+            block_info,
+            transactions,
+        }
+    }
+}
+
+/// A single update of the verified block feed
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerifiedBlockUpdate {
+    /// The given blocks have been ordered by the ordered proof (but not yet committed)
+    OrderedBlocks {
+        blocks: Vec<VerifiedBlock>,
+        ordered_proof: LedgerInfoWithSignatures,
+    },
+    /// The block certified by the commit proof (and all preceding blocks) has been committed
+    CommitDecision {
+        commit_proof: LedgerInfoWithSignatures,
+    },
+}
+
+impl VerifiedBlockUpdate {
+    /// Returns the block info certified by the proof of the update
+    pub fn proof_block_info(&self) -> &BlockInfo {
+        match self {
+            VerifiedBlockUpdate::OrderedBlocks { ordered_proof, .. } => ordered_proof.commit_info(),
+            VerifiedBlockUpdate::CommitDecision { commit_proof } => commit_proof.commit_info(),
+        }
+    }
+}
+
+/// A cloneable handle to the verified block feed. Updates are dropped if there are
+/// no subscribers, and slow subscribers lag behind (and miss updates) once they fall
+/// more than the feed capacity behind.
+#[derive(Clone, Debug)]
+pub struct VerifiedBlockFeed {
+    sender: broadcast::Sender<Arc<VerifiedBlockUpdate>>,
+}
+
+impl VerifiedBlockFeed {
+    /// Creates a new feed that buffers at most `capacity` updates per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Publishes the given update to all subscribers
+    pub fn publish(&self, update: VerifiedBlockUpdate) {
+        // An error is only returned if there are no subscribers
+        let _ = self.sender.send(Arc::new(update));
+    }
+
+    /// Returns a new subscription to the feed (starting from the next published update)
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<VerifiedBlockUpdate>> {
+        self.sender.subscribe()
+    }
+
+    /// Returns the number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_types::{aggregate_signature::AggregateSignature, ledger_info::LedgerInfo};
+    use tokio::sync::broadcast::error::TryRecvError;
+
+    fn create_commit_decision(round: u64) -> VerifiedBlockUpdate {
+        let block_info =
+            BlockInfo::new(1, round, HashValue::random(), HashValue::zero(), 0, 0, None);
+        VerifiedBlockUpdate::CommitDecision {
+            commit_proof: LedgerInfoWithSignatures::new(
+                LedgerInfo::new(block_info, HashValue::zero()),
+                AggregateSignature::empty(),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_publish_and_subscribe() {
+        // Publish an update without any subscribers
+        let verified_block_feed = VerifiedBlockFeed::new(10);
+        verified_block_feed.publish(create_commit_decision(1));
+        assert_eq!(verified_block_feed.subscriber_count(), 0);
+
+        // Subscribe to the feed and publish several updates
+        let mut receiver = verified_block_feed.subscribe();
+        for round in 2..=4 {
+            verified_block_feed.publish(create_commit_decision(round));
+        }
+
+        // Verify that only the updates published after subscribing are received
+        for round in 2..=4 {
+            let update = receiver.try_recv().unwrap();
+            assert_eq!(update.proof_block_info().round(), round);
+        }
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn test_lagging_subscriber() {
+        // Publish more updates than the feed capacity
+        let verified_block_feed = VerifiedBlockFeed::new(2);
+        let mut receiver = verified_block_feed.subscribe();
+        for round in 1..=5 {
+            verified_block_feed.publish(create_commit_decision(round));
+        }
+
+        // Verify that the subscriber lags behind and only receives the latest updates
+        assert_eq!(receiver.try_recv(), Err(TryRecvError::Lagged(3)));
+        assert_eq!(receiver.try_recv().unwrap().proof_block_info().round(), 4);
+        assert_eq!(receiver.try_recv().unwrap().proof_block_info().round(), 5);
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     block_storage::{
         block_tree::BlockTree,
         pending_blocks::PendingBlocks,
-        tracing::{observe_block, BlockStage},
+        tracing::{observe_and_record_block, BlockStage},
         BlockReader,
     },
     counters,
@@ -50,7 +50,7 @@ pub mod sync_manager;
 
 fn update_counters_for_ordered_blocks(ordered_blocks: &[Arc<PipelinedBlock>]) {
     for block in ordered_blocks {
-        observe_block(block.block().timestamp_usecs(), BlockStage::ORDERED);
+        observe_and_record_block(block.block(), BlockStage::ORDERED);
     }
 }
 
@@ -395,10 +395,7 @@ impl BlockStore {
                     qc.certified_block(),
                     pipelined_block.block_info()
                 );
-                observe_block(
-                    pipelined_block.block().timestamp_usecs(),
-                    BlockStage::QC_ADDED,
-                );
+                observe_and_record_block(pipelined_block.block(), BlockStage::QC_ADDED);
             },
             None => bail!("Insert {} without having the block in store first", qc),
         };
@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::counters;
+use aptos_consensus_types::{
+    block::Block,
+    flight_recorder::{ConsensusEvent, FLIGHT_RECORDER},
+};
 use aptos_infallible::duration_since_epoch;
+use aptos_types::block_info::BlockInfo;
 use std::time::Duration;
 
 pub struct BlockStage;
@@ -39,3 +44,30 @@ pub fn observe_block(timestamp: u64, stage: &'static str) {
             .observe(t.as_secs_f64());
     }
 }
+
+/// Record the time during each stage of a block, and the stage in the flight recorder.
+pub fn observe_and_record_block(block: &Block, stage: &'static str) {
+    observe_block(block.timestamp_usecs(), stage);
+    if FLIGHT_RECORDER.is_enabled() {
+        FLIGHT_RECORDER.record(ConsensusEvent::from_block(block, stage));
+    }
+}
+
+/// Record the time during each stage of a proposal, and the stage (along
+/// with the batches in the proposal) in the flight recorder.
+pub fn observe_and_record_proposal(block: &Block, stage: &'static str) {
+    observe_block(block.timestamp_usecs(), stage);
+    if FLIGHT_RECORDER.is_enabled() {
+        FLIGHT_RECORDER
+            .record(ConsensusEvent::from_block(block, stage).with_batches(block.payload()));
+    }
+}
+
+/// Record the time during each stage of a block (identified by its block
+/// info), and the stage in the flight recorder.
+pub fn observe_and_record_block_info(block_info: &BlockInfo, stage: &'static str) {
+    observe_block(block_info.timestamp_usecs(), stage);
+    if FLIGHT_RECORDER.is_enabled() {
+        FLIGHT_RECORDER.record(ConsensusEvent::from_block_info(block_info, stage));
+    }
+}
@@ -29,7 +29,11 @@ use crate::{
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{config::NodeConfig, network_id::PeerNetworkId};
-use aptos_consensus_types::{pipeline, pipelined_block::PipelinedBlock};
+use aptos_consensus_types::{
+    pipeline,
+    pipelined_block::PipelinedBlock,
+    verified_block_feed::{VerifiedBlock, VerifiedBlockFeed, VerifiedBlockUpdate},
+};
 use aptos_crypto::{bls12381, Genesis};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_infallible::Mutex;
@@ -98,6 +102,8 @@ pub struct ConsensusObserver {
 
     // The consensus publisher to forward payload messages
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    // The verified block feed to publish ordered blocks and commit decisions (if enabled)
+    verified_block_feed: Option<VerifiedBlockFeed>,
     // The currently active consensus observer subscription
     active_observer_subscription: Option<ConsensusObserverSubscription>,
     // A handle to storage (used to read the latest state and check progress)
@@ -117,6 +123,7 @@ impl ConsensusObserver {
         sync_notification_sender: UnboundedSender<(u64, Round)>,
         reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
         consensus_publisher: Option<Arc<ConsensusPublisher>>,
+        verified_block_feed: Option<VerifiedBlockFeed>,
         time_service: TimeService,
     ) -> Self {
         // Read the latest ledger info from storage
@@ -142,6 +149,7 @@ impl ConsensusObserver {
             sync_notification_sender,
             reconfig_events,
             consensus_publisher,
+            verified_block_feed,
             active_observer_subscription: None,
             db_reader,
             time_service,
@@ -424,6 +432,9 @@ impl ConsensusObserver {
             ))
         );
 
+        // Publish the ordered block to the verified block feed
+        self.publish_ordered_block(&ordered_block);
+
         // Send the ordered block to the execution pipeline
         if let Err(error) = self
             .execution_client
@@ -445,6 +456,15 @@ impl ConsensusObserver {
 
     /// Forwards the commit decision to the execution pipeline
     fn forward_commit_decision(&self, commit_decision: CommitDecision) {
+        // Publish the commit decision to the verified block feed (if anyone is subscribed)
+        if let Some(verified_block_feed) = &self.verified_block_feed {
+            if verified_block_feed.subscriber_count() > 0 {
+                verified_block_feed.publish(VerifiedBlockUpdate::CommitDecision {
+                    commit_proof: commit_decision.commit_proof().clone(),
+                });
+            }
+        }
+
         // Create a dummy RPC message
         let (response_sender, _response_receiver) = oneshot::channel();
         let commit_request = IncomingCommitRequest {
@@ -962,6 +982,47 @@ impl ConsensusObserver {
         }
     }
 
+    /// Publishes the ordered block (and the transactions of each block)
+    /// to the verified block feed (if the feed is enabled and has subscribers).
+    fn publish_ordered_block(&self, ordered_block: &OrderedBlock) {
+        let verified_block_feed = match &self.verified_block_feed {
+            Some(verified_block_feed) => verified_block_feed,
+            None => return,
+        };
+
+        // Avoid gathering (and cloning) the transactions if nobody is subscribed
+        if verified_block_feed.subscriber_count() == 0 {
+            return;
+        }
+
+        // Gather the verified transactions of each block
+        let mut verified_blocks = vec![];
+        for block in ordered_block.blocks() {
+            match self.block_payload_store.get_verified_transactions(block) {
+                Some(transactions) => {
+                    verified_blocks.push(VerifiedBlock::new(block.block_info(), transactions))
+                },
+                None => {
+                    // This shouldn't happen (the payloads are verified before the block is finalized)
+                    warn!(
+                        LogSchema::new(LogEntry::ConsensusObserver).message(&format!(
+                            "Missing verified payload for block: {}! Not publishing ordered block: {}",
+                            block.block_info(),
+                            ordered_block.proof_block_info()
+                        ))
+                    );
+                    return;
+                },
+            }
+        }
+
+        // Publish the ordered blocks
+        verified_block_feed.publish(VerifiedBlockUpdate::OrderedBlocks {
+            blocks: verified_blocks,
+            ordered_proof: ordered_block.ordered_proof().clone(),
+        });
+    }
+
     /// Produces a list of sorted peers to service our subscription request. Peers
     /// are prioritized by validator distance and latency.
     /// Note: if `previous_subscription_peer` is provided, it will be excluded
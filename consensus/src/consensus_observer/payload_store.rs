@@ -11,7 +11,7 @@ use aptos_config::config::ConsensusObserverConfig;
 use aptos_consensus_types::{common::Round, pipelined_block::PipelinedBlock};
 use aptos_infallible::Mutex;
 use aptos_logger::{error, warn};
-use aptos_types::epoch_state::EpochState;
+use aptos_types::{epoch_state::EpochState, transaction::SignedTransaction};
 use std::{
     collections::{btree_map::Entry, BTreeMap},
     sync::Arc,
@@ -64,6 +64,21 @@ impl BlockPayloadStore {
         self.block_payloads.clone()
     }
 
+    /// Returns the transactions of the given block (if the
+    /// block payload is available and has been verified).
+    pub fn get_verified_transactions(
+        &self,
+        block: &PipelinedBlock,
+    ) -> Option<Vec<SignedTransaction>> {
+        let epoch_and_round = (block.epoch(), block.round());
+        match self.block_payloads.lock().get(&epoch_and_round) {
+            Some(BlockPayloadStatus::AvailableAndVerified(block_payload)) => {
+                Some(block_payload.transaction_payload.transactions())
+            },
+            _ => None,
+        }
+    }
+
     /// Inserts the given block payload data into the payload store
     pub fn insert_block_payload(
         &mut self,
@@ -414,6 +429,44 @@ mod test {
         check_num_verified_payloads(&block_payload_store, 0);
     }
 
+    #[test]
+    fn test_get_verified_transactions() {
+        // Create a new block payload store
+        let consensus_observer_config = ConsensusObserverConfig::default();
+        let block_payload_store = BlockPayloadStore::new(consensus_observer_config);
+
+        // Add some verified blocks to the payload store
+        let num_blocks_in_store = 10;
+        let verified_blocks = create_and_add_blocks_to_store(
+            block_payload_store.clone(),
+            num_blocks_in_store,
+            0,
+            true,
+        );
+
+        // Check that the transactions are returned for all verified blocks
+        for verified_block in &verified_blocks {
+            let transactions = block_payload_store.get_verified_transactions(verified_block);
+            assert_eq!(transactions, Some(vec![]));
+        }
+
+        // Mark the payload of the first block as unverified
+        mark_payload_as_unverified(block_payload_store.clone(), &verified_blocks[0]);
+
+        // Check that no transactions are returned for the unverified block
+        assert!(block_payload_store
+            .get_verified_transactions(&verified_blocks[0])
+            .is_none());
+
+        // Remove the remaining blocks and check that no transactions are returned
+        block_payload_store.remove_committed_blocks(&verified_blocks);
+        for verified_block in &verified_blocks {
+            assert!(block_payload_store
+                .get_verified_transactions(verified_block)
+                .is_none());
+        }
+    }
+
     #[test]
     fn test_insert_block_payload() {
         // Create a new block payload store
@@ -24,6 +24,10 @@ use crate::{
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationSender;
+use aptos_consensus_types::{
+    flight_recorder::FLIGHT_RECORDER, quorum_store_diagnostics::QUORUM_STORE_DIAGNOSTICS,
+    verified_block_feed::VerifiedBlockFeed,
+};
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_logger::prelude::*;
@@ -54,6 +58,17 @@ pub fn start_consensus(
     consensus_publisher: Option<Arc<ConsensusPublisher>>,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
+    let peer_id = node_config.validator_network.as_ref().unwrap().peer_id();
+    FLIGHT_RECORDER.initialize(
+        Some(peer_id),
+        node_config.consensus.flight_recorder_capacity,
+    );
+    QUORUM_STORE_DIAGNOSTICS.set_enabled(
+        node_config
+            .inspection_service
+            .expose_quorum_store_information,
+    );
+
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
     let quorum_store_db = Arc::new(QuorumStoreDB::new(node_config.storage.dir()));
 
@@ -86,7 +101,7 @@ pub fn start_consensus(
     let execution_client = Arc::new(ExecutionProxyClient::new(
         node_config.consensus.clone(),
         Arc::new(execution_proxy),
-        node_config.validator_network.as_ref().unwrap().peer_id(),
+        peer_id,
         self_sender.clone(),
         consensus_network_client.clone(),
         bounded_executor.clone(),
@@ -132,6 +147,7 @@ pub fn start_consensus_observer(
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
     reconfig_events: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    verified_block_feed: Option<VerifiedBlockFeed>,
 ) -> Runtime {
     // Create a consensus observer runtime
     let runtime = aptos_runtimes::spawn_named_runtime("observer".into(), None);
@@ -206,6 +222,7 @@ pub fn start_consensus_observer(
         tx,
         reconfig_events,
         consensus_publisher,
+        verified_block_feed,
         TimeService::real(),
     );
 
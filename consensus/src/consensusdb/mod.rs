@@ -11,7 +11,9 @@ use anyhow::Result;
 use aptos_consensus_types::{block::Block, quorum_cert::QuorumCert};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
-use aptos_schemadb::{schema::Schema, Options, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use aptos_schemadb::{
+    schema::Schema, ColumnFamilyName, Options, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+};
 use aptos_storage_interface::AptosDbError;
 pub use schema::{
     block::BlockSchema,
@@ -30,18 +32,7 @@ pub const CONSENSUS_DB_NAME: &str = "consensus_db";
 
 /// Creates new physical DB checkpoint in directory specified by `checkpoint_path`.
 pub fn create_checkpoint<P: AsRef<Path> + Clone>(db_path: P, checkpoint_path: P) -> Result<()> {
-    let start = Instant::now();
-    let consensus_db_checkpoint_path = checkpoint_path.as_ref().join(CONSENSUS_DB_NAME);
-    std::fs::remove_dir_all(&consensus_db_checkpoint_path).unwrap_or(());
-    ConsensusDB::new(db_path)
-        .db
-        .create_checkpoint(&consensus_db_checkpoint_path)?;
-    info!(
-        path = consensus_db_checkpoint_path,
-        time_ms = %start.elapsed().as_millis(),
-        "Made ConsensusDB checkpoint."
-    );
-    Ok(())
+    ConsensusDB::new(db_path).create_checkpoint(checkpoint_path)
 }
 
 pub struct ConsensusDB {
@@ -50,23 +41,12 @@ pub struct ConsensusDB {
 
 impl ConsensusDB {
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![
-            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
-            BLOCK_CF_NAME,
-            QC_CF_NAME,
-            SINGLE_ENTRY_CF_NAME,
-            NODE_CF_NAME,
-            CERTIFIED_NODE_CF_NAME,
-            DAG_VOTE_CF_NAME,
-            "ordered_anchor_id", // deprecated CF
-        ];
-
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
         let instant = Instant::now();
         let mut opts = Options::default();
         opts.create_if_missing(true);
         opts.create_missing_column_families(true);
-        let db = DB::open(path.clone(), "consensus", column_families, &opts)
+        let db = DB::open(path.clone(), "consensus", Self::column_families(), &opts)
             .expect("ConsensusDB open failed; unable to continue");
 
         info!(
@@ -78,6 +58,45 @@ impl ConsensusDB {
         Self { db }
     }
 
+    /// Opens an existing ConsensusDB in readonly mode (e.g., for offline inspection)
+    pub fn new_readonly<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
+        let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
+        let db = DB::open_cf_readonly(
+            &Options::default(),
+            path,
+            "consensus_readonly",
+            Self::column_families(),
+        )?;
+        Ok(Self { db })
+    }
+
+    fn column_families() -> Vec<ColumnFamilyName> {
+        vec![
+            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+            BLOCK_CF_NAME,
+            QC_CF_NAME,
+            SINGLE_ENTRY_CF_NAME,
+            NODE_CF_NAME,
+            CERTIFIED_NODE_CF_NAME,
+            DAG_VOTE_CF_NAME,
+            "ordered_anchor_id", // deprecated CF
+        ]
+    }
+
+    /// Creates new physical DB checkpoint in directory specified by `checkpoint_path`.
+    pub fn create_checkpoint<P: AsRef<Path>>(&self, checkpoint_path: P) -> Result<()> {
+        let start = Instant::now();
+        let consensus_db_checkpoint_path = checkpoint_path.as_ref().join(CONSENSUS_DB_NAME);
+        std::fs::remove_dir_all(&consensus_db_checkpoint_path).unwrap_or(());
+        self.db.create_checkpoint(&consensus_db_checkpoint_path)?;
+        info!(
+            path = consensus_db_checkpoint_path,
+            time_ms = %start.elapsed().as_millis(),
+            "Made ConsensusDB checkpoint."
+        );
+        Ok(())
+    }
+
     pub fn get_data(
         &self,
     ) -> Result<(
@@ -5,7 +5,7 @@
 #![allow(clippy::unwrap_used)]
 
 use crate::{
-    block_storage::tracing::{observe_block, BlockStage},
+    block_storage::tracing::{observe_and_record_block, BlockStage},
     quorum_store,
 };
 use aptos_consensus_types::pipelined_block::PipelinedBlock;
@@ -1095,7 +1095,7 @@ pub static FETCH_COMMIT_HISTORY_DURATION: Lazy<DurationHistogram> = Lazy::new(||
 /// Update various counters for committed blocks
 pub fn update_counters_for_committed_blocks(blocks_to_commit: &[Arc<PipelinedBlock>]) {
     for block in blocks_to_commit {
-        observe_block(block.block().timestamp_usecs(), BlockStage::COMMITTED);
+        observe_and_record_block(block.block(), BlockStage::COMMITTED);
         let txn_status = block.compute_result().compute_status_for_input_txns();
         NUM_TXNS_PER_BLOCK.observe(txn_status.len() as f64);
         NUM_BYTES_PER_BLOCK
@@ -26,17 +26,37 @@ pub struct MetadataBackendAdapter {
     epoch_to_validators: HashMap<u64, HashMap<Author, usize>>,
     window_size: usize,
     sliding_window: Mutex<BoundedVecDeque<CommitEvent>>,
+    // If set, only the events of the target epoch within this many rounds
+    // of the target round are returned.
+    lookback_rounds: Option<Round>,
 }
 
 impl MetadataBackendAdapter {
     pub fn new(
         window_size: usize,
         epoch_to_validators: HashMap<u64, HashMap<Author, usize>>,
+        lookback_rounds: Option<Round>,
     ) -> Self {
         Self {
             epoch_to_validators,
             window_size,
             sliding_window: Mutex::new(BoundedVecDeque::new(window_size)),
+            lookback_rounds,
+        }
+    }
+
+    fn is_within_lookback(
+        &self,
+        event: &CommitEvent,
+        target_epoch: u64,
+        target_round: Round,
+    ) -> bool {
+        match self.lookback_rounds {
+            Some(lookback_rounds) => {
+                event.epoch() == target_epoch
+                    && event.round().saturating_add(lookback_rounds) >= target_round
+            },
+            None => true,
         }
     }
 
@@ -85,14 +105,15 @@ impl MetadataBackendAdapter {
 impl MetadataBackend for MetadataBackendAdapter {
     fn get_block_metadata(
         &self,
-        _target_epoch: u64,
-        _target_round: Round,
+        target_epoch: u64,
+        target_round: Round,
     ) -> (Vec<NewBlockEvent>, HashValue) {
         let events: Vec<_> = self
             .sliding_window
             .lock()
             .clone()
             .into_iter()
+            .filter(|event| self.is_within_lookback(event, target_epoch, target_round))
             .map(|event| self.convert(event))
             .collect();
         (
@@ -16,6 +16,8 @@ pub trait CommitHistory: Send + Sync {
 
 mod leader_reputation_adapter;
 mod round_robin;
+mod stake_weighted_pseudo_random;
 
 pub use leader_reputation_adapter::{LeaderReputationAdapter, MetadataBackendAdapter};
 pub use round_robin::RoundRobinAnchorElection;
+pub use stake_weighted_pseudo_random::StakeWeightedPseudoRandomAnchorElection;
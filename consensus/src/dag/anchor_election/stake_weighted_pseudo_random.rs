@@ -0,0 +1,59 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::CommitHistory;
+use crate::{
+    dag::{anchor_election::AnchorElection, storage::CommitEvent},
+    liveness::{leader_reputation::VotingPowerRatio, proposer_election::choose_index},
+};
+use aptos_consensus_types::common::{Author, Round};
+
+/// Chooses the anchor of each round pseudo-randomly, weighted by voting power. The choice only
+/// depends on the seed, the epoch and the round, so every validator derives the same anchors
+/// regardless of the order in which it receives (or commits) nodes. Note: the seed is public,
+/// so the anchors are predictable (i.e., this is not a source of unbiasable randomness).
+pub struct StakeWeightedPseudoRandomAnchorElection {
+    epoch: u64,
+    validators: Vec<Author>,
+    voting_powers: Vec<u64>,
+    seed: Vec<u8>,
+}
+
+impl StakeWeightedPseudoRandomAnchorElection {
+    pub fn new(
+        epoch: u64,
+        validators: Vec<Author>,
+        voting_powers: Vec<u64>,
+        seed: Vec<u8>,
+    ) -> Self {
+        assert_eq!(validators.len(), voting_powers.len());
+        assert!(
+            voting_powers.iter().any(|voting_power| *voting_power > 0),
+            "The total voting power must be positive!"
+        );
+        Self {
+            epoch,
+            validators,
+            voting_powers,
+            seed,
+        }
+    }
+}
+
+impl AnchorElection for StakeWeightedPseudoRandomAnchorElection {
+    fn get_anchor(&self, round: Round) -> Author {
+        let mut state = self.seed.clone();
+        state.extend_from_slice(&self.epoch.to_le_bytes());
+        state.extend_from_slice(&round.to_le_bytes());
+        let weights = self.voting_powers.iter().map(|w| *w as u128).collect();
+        self.validators[choose_index(weights, state)]
+    }
+
+    fn update_reputation(&self, _event: CommitEvent) {}
+}
+
+impl CommitHistory for StakeWeightedPseudoRandomAnchorElection {
+    fn get_voting_power_participation_ratio(&self, _round: Round) -> VotingPowerRatio {
+        1.0
+    }
+}
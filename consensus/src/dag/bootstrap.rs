@@ -3,7 +3,10 @@
 
 use super::{
     adapter::{OrderedNotifierAdapter, TLedgerInfoProvider},
-    anchor_election::{AnchorElection, CommitHistory, RoundRobinAnchorElection},
+    anchor_election::{
+        AnchorElection, CommitHistory, RoundRobinAnchorElection,
+        StakeWeightedPseudoRandomAnchorElection,
+    },
     dag_driver::DagDriver,
     dag_fetcher::{DagFetcher, DagFetcherService, FetchRequestHandler},
     dag_handler::NetworkHandler,
@@ -42,13 +45,14 @@ use aptos_channels::{
 };
 use aptos_config::config::DagConsensusConfig;
 use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
 use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::{debug, info};
 use aptos_reliable_broadcast::{RBNetworkSender, ReliableBroadcast};
 use aptos_types::{
     epoch_state::EpochState,
     on_chain_config::{
-        AnchorElectionMode, DagConsensusConfigV1,
+        AnchorElectionMode, DagConsensusConfigV1, LeaderReputationType,
         LeaderReputationType::{ProposerAndVoter, ProposerAndVoterV2},
         OnChainJWKConsensusConfig, OnChainRandomnessConfig, ProposerAndVoterConfig,
         ValidatorTxnConfig,
@@ -393,6 +397,7 @@ impl DagBootstrapper {
     fn build_leader_reputation_components(
         &self,
         config: &ProposerAndVoterConfig,
+        lookback_rounds: Option<Round>,
     ) -> Arc<LeaderReputationAdapter> {
         let num_validators = self.epoch_state.verifier.len();
         let epoch_to_validators_vec = self.storage.get_epoch_to_proposers();
@@ -416,6 +421,7 @@ impl DagBootstrapper {
                     config.voter_window_num_validators_multiplier,
                 ),
             epoch_to_validator_map,
+            lookback_rounds,
         ));
         let heuristic: Box<dyn ReputationHeuristic> = Box::new(ProposerAndVoterHeuristic::new(
             self.self_peer,
@@ -428,17 +434,7 @@ impl DagBootstrapper {
             false,
         ));
 
-        let voting_power: Vec<u64> = self
-            .epoch_state
-            .verifier
-            .get_ordered_account_addresses_iter()
-            .map(|p| {
-                self.epoch_state
-                    .verifier
-                    .get_voting_power(&p)
-                    .expect("No voting power associated with AccountAddress!")
-            })
-            .collect();
+        let voting_power = self.get_voting_powers();
 
         Arc::new(LeaderReputationAdapter::new(
             self.epoch_state.epoch,
@@ -450,6 +446,55 @@ impl DagBootstrapper {
         ))
     }
 
+    fn get_voting_powers(&self) -> Vec<u64> {
+        self.epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .map(|p| {
+                self.epoch_state
+                    .verifier
+                    .get_voting_power(&p)
+                    .expect("No voting power associated with AccountAddress!")
+            })
+            .collect()
+    }
+
+    fn build_leader_reputation_anchor_election(
+        &self,
+        reputation_type: &LeaderReputationType,
+        lookback_rounds: Option<Round>,
+    ) -> (
+        Arc<dyn AnchorElection>,
+        Arc<dyn CommitHistory>,
+        Option<Vec<CommitEvent>>,
+    ) {
+        let (commit_events, leader_reputation) = match reputation_type {
+            ProposerAndVoterV2(config) => {
+                let commit_events = self
+                    .storage
+                    .get_latest_k_committed_events(
+                        std::cmp::max(
+                            config.proposer_window_num_validators_multiplier,
+                            config.voter_window_num_validators_multiplier,
+                        ) as u64
+                            * self.epoch_state.verifier.len() as u64,
+                    )
+                    .expect("Failed to read commit events from storage");
+                (
+                    commit_events,
+                    self.build_leader_reputation_components(config, lookback_rounds),
+                )
+            },
+            ProposerAndVoter(_) => unreachable!("unsupported mode"),
+        };
+
+        (
+            leader_reputation.clone(),
+            leader_reputation,
+            Some(commit_events),
+        )
+    }
+
     fn build_anchor_election(
         &self,
     ) -> (
@@ -465,31 +510,29 @@ impl DagBootstrapper {
                 (election.clone(), election, None)
             },
             AnchorElectionMode::LeaderReputation(reputation_type) => {
-                let (commit_events, leader_reputation) = match reputation_type {
-                    ProposerAndVoterV2(config) => {
-                        let commit_events = self
-                            .storage
-                            .get_latest_k_committed_events(
-                                std::cmp::max(
-                                    config.proposer_window_num_validators_multiplier,
-                                    config.voter_window_num_validators_multiplier,
-                                ) as u64
-                                    * self.epoch_state.verifier.len() as u64,
-                            )
-                            .expect("Failed to read commit events from storage");
-                        (
-                            commit_events,
-                            self.build_leader_reputation_components(config),
-                        )
-                    },
-                    ProposerAndVoter(_) => unreachable!("unsupported mode"),
-                };
-
-                (
-                    leader_reputation.clone(),
-                    leader_reputation,
-                    Some(commit_events),
+                self.build_leader_reputation_anchor_election(reputation_type, None)
+            },
+            AnchorElectionMode::LeaderReputationWithLookback {
+                reputation_type,
+                lookback_rounds,
+            } => self
+                .build_leader_reputation_anchor_election(reputation_type, Some(*lookback_rounds)),
+            AnchorElectionMode::StakeWeightedPseudoRandom => {
+                // The seed must be identical on every validator (regardless of what
+                // each validator has in storage), so it's derived from the epoch state.
+                // Note: this makes the anchors of the epoch predictable.
+                let seed = HashValue::sha3_256_of(
+                    &bcs::to_bytes(self.epoch_state.as_ref())
+                        .expect("Failed to serialize the epoch state"),
                 )
+                .to_vec();
+                let election = Arc::new(StakeWeightedPseudoRandomAnchorElection::new(
+                    self.epoch_state.epoch,
+                    self.epoch_state.verifier.get_ordered_account_addresses(),
+                    self.get_voting_powers(),
+                    seed,
+                ));
+                (election.clone(), election, None)
             },
         }
     }
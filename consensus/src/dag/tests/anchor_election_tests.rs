@@ -0,0 +1,283 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    dag::{
+        anchor_election::{
+            AnchorElection, LeaderReputationAdapter, MetadataBackendAdapter,
+            RoundRobinAnchorElection, StakeWeightedPseudoRandomAnchorElection,
+        },
+        dag_store::{DagStore, InMemDag},
+        order_rule::OrderRule,
+        storage::CommitEvent,
+        tests::{
+            dag_test::MockStorage,
+            helpers::{generate_dag_nodes, MockPayloadManager, TEST_DAG_WINDOW},
+            order_rule_tests::{generate_permutations, generate_virtual_dag, TestNotifier},
+        },
+        CertifiedNode, NodeId,
+    },
+    liveness::leader_reputation::{MetadataBackend, ProposerAndVoterHeuristic},
+};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
+use futures_channel::mpsc::unbounded;
+use proptest::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+const NUM_HOLES: usize = 1;
+const NUM_VALIDATORS: usize = 5;
+const NUM_ROUNDS: u64 = 50;
+const NUM_PERMUTATION: usize = 20;
+const LOOKBACK_ROUNDS: Round = 10;
+
+/// The anchor election strategies that are checked for reorder safety
+#[derive(Clone, Copy, Debug)]
+enum AnchorElectionStrategy {
+    RoundRobin,
+    StakeWeightedPseudoRandom,
+    LeaderReputation,
+    LeaderReputationWithLookback,
+}
+
+const ALL_STRATEGIES: [AnchorElectionStrategy; 4] = [
+    AnchorElectionStrategy::RoundRobin,
+    AnchorElectionStrategy::StakeWeightedPseudoRandom,
+    AnchorElectionStrategy::LeaderReputation,
+    AnchorElectionStrategy::LeaderReputationWithLookback,
+];
+
+fn create_leader_reputation(
+    epoch: u64,
+    validators: Vec<Author>,
+    voting_powers: Vec<u64>,
+    lookback_rounds: Option<Round>,
+) -> Arc<dyn AnchorElection> {
+    let num_validators = validators.len();
+    let validator_indices = validators
+        .iter()
+        .enumerate()
+        .map(|(index, author)| (*author, index))
+        .collect();
+    let backend = Arc::new(MetadataBackendAdapter::new(
+        num_validators * 10,
+        HashMap::from([(epoch, validator_indices)]),
+        lookback_rounds,
+    ));
+    let heuristic = Box::new(ProposerAndVoterHeuristic::new(
+        validators[0],
+        1000,
+        10,
+        1,
+        10,
+        num_validators,
+        num_validators * 10,
+        false,
+    ));
+    Arc::new(LeaderReputationAdapter::new(
+        epoch,
+        HashMap::from([(epoch, validators)]),
+        voting_powers,
+        backend,
+        heuristic,
+        100,
+    ))
+}
+
+/// Creates a new (i.e., stateless) anchor election for every validator
+fn create_anchor_election(
+    strategy: AnchorElectionStrategy,
+    epoch_state: &EpochState,
+) -> Arc<dyn AnchorElection> {
+    let validators = epoch_state.verifier.get_ordered_account_addresses();
+    let voting_powers = validators
+        .iter()
+        .map(|author| epoch_state.verifier.get_voting_power(author).unwrap())
+        .collect();
+    match strategy {
+        AnchorElectionStrategy::RoundRobin => Arc::new(RoundRobinAnchorElection::new(validators)),
+        AnchorElectionStrategy::StakeWeightedPseudoRandom => {
+            Arc::new(StakeWeightedPseudoRandomAnchorElection::new(
+                epoch_state.epoch,
+                validators,
+                voting_powers,
+                b"seed".to_vec(),
+            ))
+        },
+        AnchorElectionStrategy::LeaderReputation => {
+            create_leader_reputation(epoch_state.epoch, validators, voting_powers, None)
+        },
+        AnchorElectionStrategy::LeaderReputationWithLookback => create_leader_reputation(
+            epoch_state.epoch,
+            validators,
+            voting_powers,
+            Some(LOOKBACK_ROUNDS),
+        ),
+    }
+}
+
+/// Runs the order rule of a single validator, which processes the nodes in the given
+/// order (or all at once, if no order is given). Returns the ordered nodes and anchors.
+fn order_nodes(
+    strategy: AnchorElectionStrategy,
+    epoch_state: Arc<EpochState>,
+    dag: &InMemDag,
+    nodes: &[CertifiedNode],
+    order: Option<&[usize]>,
+) -> (Vec<NodeId>, Vec<NodeId>) {
+    let dag = Arc::new(DagStore::new_for_test(
+        dag.clone(),
+        Arc::new(MockStorage::new()),
+        Arc::new(MockPayloadManager {}),
+    ));
+    let anchor_election = create_anchor_election(strategy, &epoch_state);
+    let (tx, mut rx) = unbounded();
+    let mut order_rule = OrderRule::new(
+        epoch_state,
+        1,
+        dag,
+        anchor_election,
+        Arc::new(TestNotifier { tx }),
+        TEST_DAG_WINDOW as Round,
+        None,
+    );
+    match order {
+        Some(order) => {
+            for index in order {
+                order_rule.process_new_node(nodes[*index].metadata());
+            }
+        },
+        None => order_rule.process_all(),
+    }
+
+    let (mut ordered_nodes, mut ordered_anchors) = (vec![], vec![]);
+    while let Ok(Some(batch)) = rx.try_next() {
+        // The anchor is the last node of every ordered batch
+        ordered_anchors.push(batch.last().unwrap().id());
+        ordered_nodes.extend(batch.iter().map(|node| node.id()));
+    }
+    (ordered_nodes, ordered_anchors)
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(20))]
+
+    #[test]
+    fn test_anchor_election_reorder_safety(
+        mut dag_with_holes in generate_virtual_dag(NUM_VALIDATORS, NUM_HOLES, NUM_ROUNDS),
+        mut dag in generate_virtual_dag(NUM_VALIDATORS, 0, NUM_ROUNDS),
+        sequences in generate_permutations(NUM_PERMUTATION, (2 * NUM_VALIDATORS - NUM_HOLES) * NUM_ROUNDS as usize)
+    ) {
+        let (_, validator_verifier) = random_validator_verifier(NUM_VALIDATORS, None, false);
+        let validators = validator_verifier.get_ordered_account_addresses();
+        dag.append(&mut dag_with_holes);
+        let nodes = generate_dag_nodes(&dag, &validators);
+        let epoch_state = Arc::new(EpochState {
+            epoch: 1,
+            verifier: validator_verifier,
+        });
+        let mut dag = InMemDag::new_empty(epoch_state.clone(), 0, TEST_DAG_WINDOW);
+        for round_nodes in &nodes {
+            for node in round_nodes.iter().flatten() {
+                dag.add_node_for_test(node.clone()).unwrap();
+            }
+        }
+        let flatten_nodes: Vec<_> = nodes.into_iter().flatten().flatten().collect();
+
+        for strategy in ALL_STRATEGIES {
+            let (all_nodes, all_anchors) =
+                order_nodes(strategy, epoch_state.clone(), &dag, &flatten_nodes, None);
+            prop_assert!(!all_anchors.is_empty(), "{:?} didn't order any anchors", strategy);
+
+            // Every validator (receiving the nodes in a different order, and missing the
+            // dropped nodes) orders a prefix of the same nodes, with the same anchors
+            for sequence in &sequences {
+                let (ordered_nodes, ordered_anchors) = order_nodes(
+                    strategy,
+                    epoch_state.clone(),
+                    &dag,
+                    &flatten_nodes,
+                    Some(sequence),
+                );
+                prop_assert_eq!(&ordered_nodes[..], &all_nodes[..ordered_nodes.len()], "{:?}", strategy);
+                prop_assert_eq!(&ordered_anchors[..], &all_anchors[..ordered_anchors.len()], "{:?}", strategy);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_stake_weighted_pseudo_random_anchor_election() {
+    let validators: Vec<_> = (0..4).map(|_| Author::random()).collect();
+    let voting_powers = vec![0, 1, 1, 8];
+    let create_election = |seed: &[u8]| {
+        StakeWeightedPseudoRandomAnchorElection::new(
+            1,
+            validators.clone(),
+            voting_powers.clone(),
+            seed.to_vec(),
+        )
+    };
+    let get_anchors = |election: &StakeWeightedPseudoRandomAnchorElection| -> Vec<_> {
+        (1..=1000).map(|round| election.get_anchor(round)).collect()
+    };
+
+    // The same seed always produces the same anchors
+    let anchors = get_anchors(&create_election(b"seed"));
+    assert_eq!(get_anchors(&create_election(b"seed")), anchors);
+    assert_ne!(get_anchors(&create_election(b"other seed")), anchors);
+
+    // The anchors are weighted by voting power
+    let count = |author: &Author| anchors.iter().filter(|anchor| *anchor == author).count();
+    assert_eq!(count(&validators[0]), 0);
+    assert!(count(&validators[3]) > count(&validators[1]) + count(&validators[2]));
+}
+
+#[test]
+fn test_leader_reputation_lookback() {
+    let validators: Vec<_> = (0..4).map(|_| Author::random()).collect();
+    let validator_indices: HashMap<_, _> = validators
+        .iter()
+        .enumerate()
+        .map(|(index, author)| (*author, index))
+        .collect();
+    let backend = MetadataBackendAdapter::new(
+        100,
+        HashMap::from([(1, validator_indices.clone()), (2, validator_indices)]),
+        Some(LOOKBACK_ROUNDS),
+    );
+
+    // Commit an anchor in every even round of epoch 1, and a few of epoch 2
+    let commit_event = |epoch: u64, round: Round| {
+        CommitEvent::new(
+            NodeId::new(epoch, round, validators[round as usize % 4]),
+            validators.clone(),
+            vec![],
+        )
+    };
+    for round in (2..=40).step_by(2) {
+        backend.push(commit_event(1, round));
+    }
+    for round in (2..=6).step_by(2) {
+        backend.push(commit_event(2, round));
+    }
+
+    // Only the events of the target epoch within the lookback window are returned
+    let rounds = |epoch: u64, round: Round| -> Vec<_> {
+        let (events, _) = backend.get_block_metadata(epoch, round);
+        events
+            .iter()
+            .map(|event| (event.epoch(), event.round()))
+            .collect()
+    };
+    assert_eq!(rounds(1, 40), vec![
+        (1, 40),
+        (1, 38),
+        (1, 36),
+        (1, 34),
+        (1, 32),
+        (1, 30)
+    ]);
+    assert_eq!(rounds(2, 12), vec![(2, 6), (2, 4), (2, 2)]);
+    assert_eq!(rounds(2, 20), vec![]);
+}
@@ -25,7 +25,7 @@ use std::sync::Arc;
 /// second layer represents nodes, Some => node exist, None => not exist
 /// third layer is a bitmask that represents compressed strong links (true => linked, false => not linked),
 /// the bitmask ignores non-existing nodes
-fn generate_virtual_dag(
+pub(super) fn generate_virtual_dag(
     num_nodes: usize,
     num_holes: usize,
     round: u64,
@@ -66,7 +66,7 @@ fn generate_virtual_dag(
 
 /// Generate `num_perm` random permutations of how nodes are processed by the order rule
 /// Imagine we have 4 nodes, this generates `num_perm` permutations of [0, 1, 2, 3]
-fn generate_permutations(
+pub(super) fn generate_permutations(
     num_perm: usize,
     total_number: usize,
 ) -> impl Strategy<Value = Vec<Vec<usize>>> {
@@ -37,6 +37,8 @@ pub mod quorum_store;
 mod rand;
 mod recovery_manager;
 mod round_manager;
+#[cfg(test)]
+mod simulation;
 mod state_computer;
 #[cfg(test)]
 mod state_computer_tests;
@@ -61,7 +63,7 @@ mod payload_manager;
 mod qc_aggregator;
 mod transaction_deduper;
 mod transaction_filter;
-mod transaction_shuffler;
+pub mod transaction_shuffler;
 mod txn_hash_and_authenticator_deduper;
 
 use aptos_metrics_core::IntGauge;
@@ -5,6 +5,7 @@
 use crate::{
     network::{NetworkReceivers, NetworkSender},
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
+    simulation::NetworkModel,
     test_utils::{self, consensus_runtime, placeholder_ledger_info, timed_block_on},
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
@@ -77,6 +78,10 @@ pub struct NetworkPlayground {
     drop_config: Arc<RwLock<DropConfig>>,
     /// Allow test code to drop direct-send messages between peers per round.
     drop_config_round: DropConfigRound,
+    /// Allow test code to delay direct-send messages between peers per round.
+    delay_config_round: DelayConfigRound,
+    /// Allow test code to simulate the latency (and drops) of all messages.
+    network_model: Option<Arc<Mutex<NetworkModel>>>,
     /// An executor for spawning node outbound network event handlers
     executor: Handle,
     /// Maps authors to twins IDs
@@ -97,6 +102,8 @@ impl NetworkPlayground {
             timeout_config: Arc::new(RwLock::new(TimeoutConfig::default())),
             drop_config: Arc::new(RwLock::new(DropConfig::default())),
             drop_config_round: DropConfigRound::default(),
+            delay_config_round: DelayConfigRound::default(),
+            network_model: None,
             executor,
             author_to_twin_ids: Arc::new(RwLock::new(AuthorToTwinIds::default())),
             peers_and_metadata: PeersAndMetadata::new(&[NetworkId::Validator]),
@@ -107,6 +114,16 @@ impl NetworkPlayground {
         self.executor.clone()
     }
 
+    /// Simulates the latency (and drops) of all messages using the given model.
+    /// Note: this must be called before any nodes are added.
+    pub fn set_network_model(&mut self, network_model: NetworkModel) {
+        assert!(
+            self.node_consensus_txs.lock().is_empty(),
+            "The network model must be set before adding nodes!"
+        );
+        self.network_model = Some(Arc::new(Mutex::new(network_model)));
+    }
+
     /// HashMap of supported protocols to initialize ConsensusNetworkClient.
     pub fn peer_protocols(&self) -> Arc<PeersAndMetadata> {
         self.peers_and_metadata.clone()
@@ -119,10 +136,12 @@ impl NetworkPlayground {
     /// `deliver_messages` and `wait_for_messages` API's.
     ///
     /// Rpc messages are immediately sent to the destination for handling, so
-    /// they don't block.
+    /// they don't block (unless a network model is set, in which case they
+    /// are delivered after the simulated latency).
     async fn start_node_outbound_handler(
         timeout_config: Arc<RwLock<TimeoutConfig>>,
         drop_config: Arc<RwLock<DropConfig>>,
+        network_model: Option<Arc<Mutex<NetworkModel>>>,
         src_twin_id: TwinId,
         mut network_reqs_rx: aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
         mut outbound_msgs_tx: mpsc::Sender<(TwinId, PeerManagerRequest)>,
@@ -163,28 +182,49 @@ impl NetworkPlayground {
                         continue;
                     }
 
+                    let latency = match &network_model {
+                        Some(network_model) => match network_model
+                            .lock()
+                            .sample_latency(src_twin_id.author, dst_twin_id.author)
+                        {
+                            Some(latency) => Some(latency),
+                            None => {
+                                outbound_req.res_tx.send(Err(RpcError::TimedOut)).unwrap();
+                                continue;
+                            },
+                        },
+                        None => None,
+                    };
+
                     let node_consensus_tx =
                         node_consensus_txs.lock().get(dst_twin_id).unwrap().clone();
-
-                    node_consensus_tx
-                        .push(
-                            (src_twin_id.author, ProtocolId::ConsensusRpcBcs),
-                            ReceivedMessage {
-                                message: NetworkMessage::RpcRequest(RpcRequest {
-                                    protocol_id: outbound_req.protocol_id,
-                                    request_id: 123,
-                                    priority: 0,
-                                    raw_request: outbound_req.data.into(),
-                                }),
-                                sender: PeerNetworkId::new(
-                                    NetworkId::Validator,
-                                    src_twin_id.author,
-                                ),
-                                receive_timestamp_micros: 0,
-                                rpc_replier: Some(Arc::new(outbound_req.res_tx)),
-                            },
-                        )
-                        .unwrap();
+                    let rmsg = ReceivedMessage {
+                        message: NetworkMessage::RpcRequest(RpcRequest {
+                            protocol_id: outbound_req.protocol_id,
+                            request_id: 123,
+                            priority: 0,
+                            raw_request: outbound_req.data.into(),
+                        }),
+                        sender: PeerNetworkId::new(NetworkId::Validator, src_twin_id.author),
+                        receive_timestamp_micros: 0,
+                        rpc_replier: Some(Arc::new(outbound_req.res_tx)),
+                    };
+                    let src_author = src_twin_id.author;
+
+                    match latency {
+                        Some(latency) => {
+                            tokio::spawn(async move {
+                                tokio::time::sleep(latency).await;
+                                let _ = node_consensus_tx
+                                    .push((src_author, ProtocolId::ConsensusRpcBcs), rmsg);
+                            });
+                        },
+                        None => {
+                            node_consensus_tx
+                                .push((src_author, ProtocolId::ConsensusRpcBcs), rmsg)
+                                .unwrap();
+                        },
+                    }
                 },
                 // Other PeerManagerRequest get buffered for `deliver_messages` to
                 // synchronously drain.
@@ -212,6 +252,7 @@ impl NetworkPlayground {
         let fut1 = NetworkPlayground::start_node_outbound_handler(
             Arc::clone(&self.timeout_config),
             Arc::clone(&self.drop_config),
+            self.network_model.clone(),
             twin_id,
             network_reqs_rx,
             self.outbound_msgs_tx.clone(),
@@ -259,6 +300,30 @@ impl NetworkPlayground {
         (source_address, consensus_msg)
     }
 
+    /// Deliver a `ReceivedMessage` from peer `src` to the destination peer
+    /// after the given delay. This does not block the delivery of other messages.
+    fn deliver_message_after_delay(
+        &self,
+        src_twin_id: TwinId,
+        dst_twin_id: TwinId,
+        rmsg: ReceivedMessage,
+        delay: Duration,
+    ) {
+        let node_consensus_tx = self
+            .node_consensus_txs
+            .lock()
+            .get(&dst_twin_id)
+            .unwrap()
+            .clone();
+        self.executor.spawn(async move {
+            tokio::time::sleep(delay).await;
+            let _ = node_consensus_tx.push(
+                (src_twin_id.author, ProtocolId::ConsensusDirectSendBcs),
+                rmsg,
+            );
+        });
+    }
+
     /// Wait for exactly `num_messages` to be enqueued and delivered. Return a
     /// copy of all messages for verification.
     /// While all the sent messages are delivered (except those configured to be dropped),
@@ -371,11 +436,38 @@ impl NetworkPlayground {
             .split_network(&partition_first, &partition_second)
     }
 
+    /// Returns the delay (if any) for the message from 'src' to 'dst'
+    fn get_message_delay(&self, src: &TwinId, dst: &TwinId, msg: ConsensusMsg) -> Option<Duration> {
+        Self::get_message_round(msg)
+            .and_then(|round| self.delay_config_round.get_message_delay(src, dst, round))
+    }
+
     /// Check if the message from 'src_twin_id' to 'dst_twin_id' should be dropped in the given round
     pub fn is_message_dropped_round(&self, src: &TwinId, dst: &TwinId, round: u64) -> bool {
         self.drop_config_round.is_message_dropped(src, dst, round)
     }
 
+    /// Drops all messages from 'src' to 'dst' in the given round
+    pub fn drop_message_for_round(&mut self, round: u64, src: &TwinId, dst: &TwinId) -> bool {
+        self.drop_config_round
+            .0
+            .entry(round)
+            .or_default()
+            .drop_message_for(src, dst)
+    }
+
+    /// Delays all messages from 'src' to 'dst' in the given round
+    pub fn delay_message_for_round(
+        &mut self,
+        round: u64,
+        src: &TwinId,
+        dst: &TwinId,
+        delay: Duration,
+    ) {
+        self.delay_config_round
+            .delay_message_for_round(round, src, dst, delay)
+    }
+
     /// Creates the given per round network partitions
     pub fn split_network_round(
         &mut self,
@@ -427,11 +519,31 @@ impl NetworkPlayground {
                     receive_timestamp_micros: 0,
                     rpc_replier: None,
                 };
-                let consensus_msg = msg.to_message().unwrap();
+                let consensus_msg: ConsensusMsg = msg.to_message().unwrap();
+
+                // Deliver (or delay) the message if it's not dropped
+                if !self.is_message_dropped(&src_twin_id, dst_twin_id, consensus_msg.clone()) {
+                    let mut delay =
+                        self.get_message_delay(&src_twin_id, dst_twin_id, consensus_msg);
+
+                    // Otherwise, use the latency of the network model (if any)
+                    if let (None, Some(network_model)) = (delay, &self.network_model) {
+                        delay = network_model
+                            .lock()
+                            .sample_latency(src_twin_id.author, dst_twin_id.author);
+                        if delay.is_none() {
+                            continue; // The message is dropped by the model
+                        }
+                    }
 
-                // Deliver and copy message it if it's not dropped
-                if !self.is_message_dropped(&src_twin_id, dst_twin_id, consensus_msg) {
-                    self.deliver_message(src_twin_id, *dst_twin_id, rmsg).await;
+                    match delay {
+                        Some(delay) => {
+                            self.deliver_message_after_delay(src_twin_id, *dst_twin_id, rmsg, delay)
+                        },
+                        None => {
+                            self.deliver_message(src_twin_id, *dst_twin_id, rmsg).await;
+                        },
+                    }
                 }
             }
         }
@@ -526,6 +638,25 @@ impl DropConfigRound {
     }
 }
 
+/// Table of per round message delays
+#[derive(Default)]
+struct DelayConfigRound(HashMap<u64, HashMap<(TwinId, TwinId), Duration>>);
+
+impl DelayConfigRound {
+    /// Returns the delay (if any) for the message from 'src' to 'dst' in the given round
+    fn get_message_delay(&self, src: &TwinId, dst: &TwinId, round: u64) -> Option<Duration> {
+        self.0
+            .get(&round)
+            .and_then(|delays| delays.get(&(*src, *dst)))
+            .cloned()
+    }
+
+    /// Delays the messages from 'src' to 'dst' in the given round
+    fn delay_message_for_round(&mut self, round: u64, src: &TwinId, dst: &TwinId, delay: Duration) {
+        self.0.entry(round).or_default().insert((*src, *dst), delay);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    block_storage::tracing::{observe_block, BlockStage},
+    block_storage::tracing::{observe_and_record_block, BlockStage},
     consensus_observer::{
         network_message::ConsensusObserverMessage, publisher::ConsensusPublisher,
     },
@@ -385,7 +385,7 @@ impl BufferManager {
                     .last()
                     .expect("executed_blocks should be not empty")
                     .block();
-                observe_block(block.timestamp_usecs(), BlockStage::COMMIT_CERTIFIED);
+                observe_and_record_block(block, BlockStage::COMMIT_CERTIFIED);
                 // As all the validators broadcast commit votes directly to all other validators,
                 // the proposer do not have to broadcast commit decision again.
                 let commit_proof = aggregated_item.commit_proof.clone();
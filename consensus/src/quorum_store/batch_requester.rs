@@ -9,7 +9,10 @@ use crate::{
         types::{BatchRequest, BatchResponse, PersistedValue},
     },
 };
-use aptos_consensus_types::proof_of_store::BatchInfo;
+use aptos_consensus_types::{
+    proof_of_store::BatchInfo,
+    quorum_store_diagnostics::{BatchFetchFailure, QUORUM_STORE_DIAGNOSTICS},
+};
 use aptos_crypto::HashValue;
 use aptos_executor_types::*;
 use aptos_logger::prelude::*;
@@ -155,14 +158,15 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
                         // send batch request to a set of peers of size request_num_peers
                         if let Some(request_peers) = request_state.next_request_peers(request_num_peers) {
                             for peer in request_peers {
-                                futures.push(network_sender.request_batch(request.clone(), peer, rpc_timeout));
+                                let response = network_sender.request_batch(request.clone(), peer, rpc_timeout);
+                                futures.push(async move { (peer, response.await) });
                             }
                         } else if futures.is_empty() {
                             // end the loop when the futures are drained
                             break;
                         }
                     },
-                    Some(response) = futures.next() => {
+                    Some((peer, response)) = futures.next() => {
                         match response {
                             Ok(BatchResponse::Batch(batch)) => {
                                 counters::RECEIVED_BATCH_RESPONSE_COUNT.inc();
@@ -175,6 +179,13 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
                             // Short-circuit if the chain has moved beyond expiration
                             Ok(BatchResponse::NotFound(ledger_info)) => {
                                 counters::RECEIVED_BATCH_NOT_FOUND_COUNT.inc();
+                                QUORUM_STORE_DIAGNOSTICS.record_fetch_failure(
+                                    epoch,
+                                    peer,
+                                    digest,
+                                    BatchFetchFailure::NotFound,
+                                    None,
+                                );
                                 if ledger_info.commit_info().epoch() == epoch
                                     && ledger_info.commit_info().timestamp_usecs() > expiration
                                     && ledger_info.verify_signatures(&validator_verifier).is_ok()
@@ -187,6 +198,13 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
                             }
                             Err(e) => {
                                 counters::RECEIVED_BATCH_RESPONSE_ERROR_COUNT.inc();
+                                QUORUM_STORE_DIAGNOSTICS.record_fetch_failure(
+                                    epoch,
+                                    peer,
+                                    digest,
+                                    BatchFetchFailure::Error,
+                                    Some(e.to_string()),
+                                );
                                 debug!("QS: batch request error, digest:{}, error:{:?}", digest, e);
                             }
                         }
@@ -382,6 +382,11 @@ impl BatchStore {
         }
     }
 
+    /// Returns true iff the batch is available locally (either in memory or in the db)
+    pub(crate) fn exists_locally(&self, digest: &HashValue) -> bool {
+        self.db_cache.contains_key(digest)
+    }
+
     /// This calls lets the caller subscribe to a batch being added to the batch store.
     /// This can be useful in cases where there are multiple flows to add a batch (like
     /// direct from author batch / batch requester fetch) to the batch store and either
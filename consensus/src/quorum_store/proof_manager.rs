@@ -13,6 +13,7 @@ use crate::{
 use aptos_consensus_types::{
     common::{Payload, PayloadFilter, ProofWithData, TxnSummaryWithExpiration},
     proof_of_store::{BatchInfo, ProofOfStore, ProofOfStoreMsg},
+    quorum_store_diagnostics::{BackPressureState, OutstandingProof, QUORUM_STORE_DIAGNOSTICS},
     request_response::{GetPayloadCommand, GetPayloadResponse},
 };
 use aptos_logger::prelude::*;
@@ -24,9 +25,12 @@ use std::{
     cmp::min,
     collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+/// The interval at which the quorum store diagnostics are updated
+const DIAGNOSTICS_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub enum ProofManagerCommand {
     ReceiveProofs(ProofOfStoreMsg),
@@ -130,6 +134,8 @@ impl BatchQueue {
 }
 
 pub struct ProofManager {
+    epoch: u64,
+    batch_store: Arc<BatchStore>,
     proofs_for_consensus: ProofQueue,
     batch_queue: BatchQueue,
     back_pressure_total_txn_limit: u64,
@@ -141,6 +147,7 @@ pub struct ProofManager {
 
 impl ProofManager {
     pub fn new(
+        epoch: u64,
         my_peer_id: PeerId,
         back_pressure_total_txn_limit: u64,
         back_pressure_total_proof_limit: u64,
@@ -148,6 +155,8 @@ impl ProofManager {
         allow_batches_without_pos_in_proposal: bool,
     ) -> Self {
         Self {
+            epoch,
+            batch_store: batch_store.clone(),
             proofs_for_consensus: ProofQueue::new(my_peer_id),
             batch_queue: BatchQueue::new(batch_store),
             back_pressure_total_txn_limit,
@@ -320,6 +329,47 @@ impl ProofManager {
         }
     }
 
+    /// Updates the quorum store diagnostics with the outstanding proofs (and whether
+    /// their batches are available locally) and the current back pressure state.
+    /// Only the proofs are gathered here; the batch store lookups and the update
+    /// itself run on a blocking task (i.e., outside the main loop).
+    fn update_diagnostics(&self) {
+        let outstanding_proofs: Vec<(BatchInfo, Instant)> = self
+            .proofs_for_consensus
+            .outstanding_proofs()
+            .map(|(proof, insertion_time)| (proof.info().clone(), *insertion_time))
+            .collect();
+        let back_pressure = self.qs_back_pressure();
+        let back_pressure = BackPressureState {
+            txn_count: back_pressure.txn_count,
+            proof_count: back_pressure.proof_count,
+            remaining_txns: self.remaining_total_txn_num,
+            txn_limit: self.back_pressure_total_txn_limit,
+            remaining_proofs: self.remaining_total_proof_num,
+            proof_limit: self.back_pressure_total_proof_limit,
+        };
+
+        let epoch = self.epoch;
+        let batch_store = self.batch_store.clone();
+        tokio::task::spawn_blocking(move || {
+            let outstanding_proofs = outstanding_proofs
+                .into_iter()
+                .map(|(batch_info, insertion_time)| OutstandingProof {
+                    digest: *batch_info.digest(),
+                    author: batch_info.author(),
+                    batch_id: batch_info.batch_id(),
+                    expiration_usecs: batch_info.expiration(),
+                    num_txns: batch_info.num_txns(),
+                    num_bytes: batch_info.num_bytes(),
+                    gas_bucket_start: batch_info.gas_bucket_start(),
+                    age_ms: insertion_time.elapsed().as_millis() as u64,
+                    locally_available: batch_store.exists_locally(batch_info.digest()),
+                })
+                .collect();
+            QUORUM_STORE_DIAGNOSTICS.update(epoch, outstanding_proofs, back_pressure);
+        });
+    }
+
     pub async fn start(
         mut self,
         back_pressure_tx: tokio::sync::mpsc::Sender<BackPressure>,
@@ -330,6 +380,8 @@ impl ProofManager {
             txn_count: false,
             proof_count: false,
         };
+        let diagnostics_enabled = QUORUM_STORE_DIAGNOSTICS.is_enabled();
+        let mut diagnostics_interval = tokio::time::interval(DIAGNOSTICS_UPDATE_INTERVAL);
 
         loop {
             let _timer = counters::PROOF_MANAGER_MAIN_LOOP.start_timer();
@@ -377,6 +429,11 @@ impl ProofManager {
                         }
                     })
                 }
+                    _ = diagnostics_interval.tick(), if diagnostics_enabled => {
+                        monitor!("proof_manager_update_diagnostics", {
+                            self.update_diagnostics();
+                        })
+                    },
             }
         }
     }
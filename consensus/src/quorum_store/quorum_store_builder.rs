@@ -357,6 +357,7 @@ impl InnerBuilder {
 
         let proof_manager_cmd_rx = self.proof_manager_cmd_rx.take().unwrap();
         let proof_manager = ProofManager::new(
+            self.epoch,
             self.author,
             self.config.back_pressure.backlog_txn_limit_count,
             self.config
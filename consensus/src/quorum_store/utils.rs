@@ -715,6 +715,18 @@ impl ProofQueue {
         (remaining_txns_without_duplicates, self.remaining_proofs)
     }
 
+    /// Returns the unexpired and uncommitted proofs (and the time they were inserted)
+    pub(crate) fn outstanding_proofs(&self) -> impl Iterator<Item = &(ProofOfStore, Instant)> {
+        self.author_to_batches
+            .values()
+            .flat_map(|batches| batches.keys())
+            .filter_map(|sort_key| {
+                self.batch_to_proof
+                    .get(&sort_key.batch_key)
+                    .and_then(Option::as_ref)
+            })
+    }
+
     // Mark in the hashmap committed PoS, but keep them until they expire
     pub(crate) fn mark_committed(&mut self, batches: Vec<BatchInfo>) {
         let start = Instant::now();
@@ -4,7 +4,10 @@
 
 use crate::{
     block_storage::{
-        tracing::{observe_block, BlockStage},
+        tracing::{
+            observe_and_record_block, observe_and_record_block_info, observe_and_record_proposal,
+            BlockStage,
+        },
         BlockReader, BlockRetriever, BlockStore, NeedFetchResult,
     },
     counters::{
@@ -463,7 +466,7 @@ impl RoundManager {
         let signature = self.safety_rules.lock().sign_proposal(&proposal)?;
         let signed_proposal =
             Block::new_proposal_from_block_data_and_signature(proposal, signature);
-        observe_block(signed_proposal.timestamp_usecs(), BlockStage::SIGNED);
+        observe_and_record_proposal(&signed_proposal, BlockStage::SIGNED);
         info!(self.new_log(LogEvent::Propose), "{}", signed_proposal);
         Ok(ProposalMsg::new(
             signed_proposal,
@@ -479,10 +482,7 @@ impl RoundManager {
             Err(anyhow::anyhow!("Injected error in process_proposal_msg"))
         });
 
-        observe_block(
-            proposal_msg.proposal().timestamp_usecs(),
-            BlockStage::ROUND_MANAGER_RECEIVED,
-        );
+        observe_and_record_proposal(proposal_msg.proposal(), BlockStage::ROUND_MANAGER_RECEIVED);
         info!(
             self.new_log(LogEvent::ReceiveProposal)
                 .remote_peer(proposal_msg.proposer()),
@@ -825,7 +825,7 @@ impl RoundManager {
             self.round_state.current_round_deadline(),
         );
 
-        observe_block(proposal.timestamp_usecs(), BlockStage::SYNCED);
+        observe_and_record_block(&proposal, BlockStage::SYNCED);
         if self.block_store.vote_back_pressure() {
             counters::CONSENSUS_WITHOLD_VOTE_BACKPRESSURE_TRIGGERED.observe(1.0);
             // In case of back pressure, we delay processing proposal. This is done by resending the
@@ -983,7 +983,7 @@ impl RoundManager {
             block_arc.block()
         ))?;
         if !block_arc.block().is_nil_block() {
-            observe_block(block_arc.block().timestamp_usecs(), BlockStage::VOTED);
+            observe_and_record_block(block_arc.block(), BlockStage::VOTED);
         }
 
         self.storage
@@ -1054,10 +1054,7 @@ impl RoundManager {
                 proposed_block.block()
             ))?;
             if !proposed_block.block().is_nil_block() {
-                observe_block(
-                    proposed_block.block().timestamp_usecs(),
-                    BlockStage::ORDER_VOTED,
-                );
+                observe_and_record_block(proposed_block.block(), BlockStage::ORDER_VOTED);
             }
             let order_vote_msg = OrderVoteMsg::new(order_vote.clone(), qc.as_ref().clone());
             info!(
@@ -1162,10 +1159,7 @@ impl RoundManager {
         match result {
             VoteReceptionResult::NewQuorumCertificate(qc) => {
                 if !vote.is_timeout() {
-                    observe_block(
-                        qc.certified_block().timestamp_usecs(),
-                        BlockStage::QC_AGGREGATED,
-                    );
+                    observe_and_record_block_info(qc.certified_block(), BlockStage::QC_AGGREGATED);
                 }
                 QC_AGGREGATED_FROM_VOTES.inc();
                 self.new_qc_aggregated(qc.clone(), vote.author())
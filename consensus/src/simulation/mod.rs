@@ -0,0 +1,34 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A consensus simulator that runs multiple validators (i.e., the epoch
+//! manager, round manager and pipeline of each validator) on a single-threaded runtime
+//! with virtual time, connected by a simulated network with configurable latencies.
+//!
+//! All timers (e.g., round timeouts) use the paused tokio clock, which is automatically
+//! advanced whenever the runtime is idle. This allows hours of consensus to be simulated
+//! in seconds. The seed determines the network behaviour (e.g., latencies and drops).
+//! Block timestamps and both time services of the epoch manager are also driven by the
+//! virtual clock (see `VirtualTimeService` and `new_virtual_aptos_time_service`).
+//!
+//! Note: runs are not guaranteed to be replayable. The tokio `select!` macro polls its
+//! branches in a random order (and the runtime's rng can't be seeded), so two runs with
+//! the same seed may commit different blocks. The seed only makes failures more likely
+//! to reproduce.
+//!
+//! Scope: the simulator covers the (Jolteon) round manager, proposer election, voting,
+//! ordering and the commit pipeline of a single epoch. It does not cover:
+//! - Payloads: the nodes use a `DirectMempool` payload (with an empty mempool) and the
+//!   `MockQuorumStoreDB`, so quorum store (batches and proofs) is disabled.
+//! - Epoch changes: the validator set is fixed, and no reconfigurations are committed.
+//! - Execution: blocks are executed by a mock execution client (i.e., no state).
+//! - DAG consensus: the on-chain consensus config is always `ConsensusConfigV1`.
+
+mod network_model;
+mod simulator;
+mod simulator_test;
+mod virtual_time_service;
+
+pub use network_model::{LatencyDistribution, NetworkModel};
+pub use simulator::{run_simulation, SimulationConfig, SimulationResult};
+pub use virtual_time_service::{new_virtual_aptos_time_service, VirtualTimeService};
@@ -0,0 +1,183 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::common::Author;
+use aptos_crypto::HashValue;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{collections::HashMap, time::Duration};
+
+/// The distribution of the latency of a single network message
+#[derive(Clone, Debug, PartialEq)]
+pub enum LatencyDistribution {
+    /// Every message takes exactly the given time
+    Constant(Duration),
+    /// The latency is sampled uniformly from [min, max]
+    Uniform { min: Duration, max: Duration },
+    /// The latency is the minimum plus an exponentially distributed delay with the
+    /// given mean (i.e., most messages are fast, but there is a long tail).
+    Exponential { min: Duration, mean: Duration },
+}
+
+impl LatencyDistribution {
+    fn sample(&self, rng: &mut StdRng) -> Duration {
+        match self {
+            LatencyDistribution::Constant(latency) => *latency,
+            LatencyDistribution::Uniform { min, max } => {
+                if min >= max {
+                    return *min;
+                }
+                let latency_us = rng.gen_range(min.as_micros() as u64, max.as_micros() as u64 + 1);
+                Duration::from_micros(latency_us)
+            },
+            LatencyDistribution::Exponential { min, mean } => {
+                // Inverse transform sampling (1 - u is in (0, 1], so the log is finite)
+                let u: f64 = rng.gen();
+                let delay_secs = -(1.0 - u).ln() * mean.as_secs_f64();
+                *min + Duration::from_secs_f64(delay_secs)
+            },
+        }
+    }
+}
+
+/// A seeded model of the simulated network. It determines the latency of every
+/// message, and whether the message is dropped. Every link has its own rng (derived
+/// from the seed and the link), so given the same seed (and the same sequence of
+/// messages on each link), the model always makes the same decisions, regardless of
+/// how the messages of different links are interleaved.
+pub struct NetworkModel {
+    seed: u64,
+    link_rngs: HashMap<(Author, Author), StdRng>,
+    default_latency: LatencyDistribution,
+    link_latencies: HashMap<(Author, Author), LatencyDistribution>,
+    drop_probability: f64,
+}
+
+impl NetworkModel {
+    pub fn new(seed: u64, default_latency: LatencyDistribution, drop_probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&drop_probability),
+            "The drop probability must be in [0, 1]!"
+        );
+        Self {
+            seed,
+            link_rngs: HashMap::new(),
+            default_latency,
+            link_latencies: HashMap::new(),
+            drop_probability,
+        }
+    }
+
+    /// Overrides the latency distribution of messages from `src` to `dst`
+    pub fn set_link_latency(&mut self, src: Author, dst: Author, latency: LatencyDistribution) {
+        self.link_latencies.insert((src, dst), latency);
+    }
+
+    /// Returns the latency of the next message from `src` to `dst`,
+    /// or `None` if the message should be dropped.
+    pub fn sample_latency(&mut self, src: Author, dst: Author) -> Option<Duration> {
+        let seed = self.seed;
+        let rng = self
+            .link_rngs
+            .entry((src, dst))
+            .or_insert_with(|| link_rng(seed, src, dst));
+
+        // Messages to self are never dropped
+        if src != dst && self.drop_probability > 0.0 && rng.gen_bool(self.drop_probability) {
+            return None;
+        }
+
+        let latency = self
+            .link_latencies
+            .get(&(src, dst))
+            .unwrap_or(&self.default_latency);
+        Some(latency.sample(rng))
+    }
+}
+
+/// Derives the rng of the link from `src` to `dst` from the seed of the model
+fn link_rng(seed: u64, src: Author, dst: Author) -> StdRng {
+    let mut bytes = seed.to_le_bytes().to_vec();
+    bytes.extend_from_slice(src.as_ref());
+    bytes.extend_from_slice(dst.as_ref());
+    StdRng::from_seed(*HashValue::sha3_256_of(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_latencies(model: &mut NetworkModel, num_samples: usize) -> Vec<Option<Duration>> {
+        let (src, dst) = (Author::ONE, Author::TWO);
+        (0..num_samples)
+            .map(|_| model.sample_latency(src, dst))
+            .collect()
+    }
+
+    #[test]
+    fn test_latency_distributions() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (min, max) = (Duration::from_millis(10), Duration::from_millis(50));
+        for _ in 0..100 {
+            assert_eq!(LatencyDistribution::Constant(min).sample(&mut rng), min);
+
+            let latency = LatencyDistribution::Uniform { min, max }.sample(&mut rng);
+            assert!(latency >= min && latency <= max);
+
+            let latency = LatencyDistribution::Exponential { min, mean: max }.sample(&mut rng);
+            assert!(latency >= min);
+        }
+    }
+
+    #[test]
+    fn test_network_model_is_deterministic() {
+        let latency = LatencyDistribution::Uniform {
+            min: Duration::from_millis(10),
+            max: Duration::from_millis(500),
+        };
+
+        // The same seed always produces the same latencies and drops
+        let mut model = NetworkModel::new(42, latency.clone(), 0.2);
+        let latencies = sample_latencies(&mut model, 1000);
+        let mut same_seed_model = NetworkModel::new(42, latency.clone(), 0.2);
+        assert_eq!(sample_latencies(&mut same_seed_model, 1000), latencies);
+
+        // Some messages are dropped (but not all)
+        let num_dropped = latencies.iter().filter(|latency| latency.is_none()).count();
+        assert!(num_dropped > 0 && num_dropped < latencies.len());
+
+        // A different seed produces different latencies
+        let mut other_seed_model = NetworkModel::new(43, latency.clone(), 0.2);
+        assert_ne!(sample_latencies(&mut other_seed_model, 1000), latencies);
+
+        // Messages on other links don't affect the latencies of a link
+        let mut interleaved_model = NetworkModel::new(42, latency, 0.2);
+        let interleaved_latencies: Vec<_> = (0..1000)
+            .map(|_| {
+                interleaved_model.sample_latency(Author::TWO, Author::ONE);
+                interleaved_model.sample_latency(Author::ONE, Author::TWO)
+            })
+            .collect();
+        assert_eq!(interleaved_latencies, latencies);
+    }
+
+    #[test]
+    fn test_link_latency_override() {
+        let default_latency = Duration::from_millis(100);
+        let link_latency = Duration::from_millis(5);
+        let mut model = NetworkModel::new(0, LatencyDistribution::Constant(default_latency), 0.0);
+        model.set_link_latency(
+            Author::ONE,
+            Author::TWO,
+            LatencyDistribution::Constant(link_latency),
+        );
+
+        assert_eq!(
+            model.sample_latency(Author::ONE, Author::TWO),
+            Some(link_latency)
+        );
+        assert_eq!(
+            model.sample_latency(Author::TWO, Author::ONE),
+            Some(default_latency)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network_tests::NetworkPlayground,
+    simulation::network_model::{LatencyDistribution, NetworkModel},
+    twins::{
+        scenario_runner::{CommittedBlock, ScenarioResult},
+        twins_node::SMRNode,
+    },
+};
+use aptos_consensus_types::common::Round;
+use aptos_types::on_chain_config::ProposerElectionType;
+use futures::{stream, StreamExt};
+use std::time::{Duration, Instant as WallClockInstant};
+use tokio::{
+    runtime::{Builder, Handle},
+    time::Instant,
+};
+
+/// The configuration of a single simulation run
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// The number of validators (all with equal voting power)
+    pub num_validators: usize,
+    /// The seed of the network model (i.e., of all latencies and drops)
+    pub seed: u64,
+    /// The amount of virtual time to simulate
+    pub duration: Duration,
+    /// The proposer election used by all validators
+    pub proposer_type: ProposerElectionType,
+    /// The initial round timeout of the validators
+    pub round_initial_timeout_ms: u64,
+    /// The latency of every message between two validators
+    pub latency: LatencyDistribution,
+    /// The probability that a message between two validators is dropped
+    pub drop_probability: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            num_validators: 4,
+            seed: 0,
+            duration: Duration::from_secs(60),
+            proposer_type: ProposerElectionType::RotatingProposer(1),
+            round_initial_timeout_ms: 1_000,
+            latency: LatencyDistribution::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+            },
+            drop_probability: 0.0,
+        }
+    }
+}
+
+/// The result of a single simulation run
+#[derive(Clone, Debug)]
+pub struct SimulationResult {
+    /// The number of simulated validators
+    pub num_validators: usize,
+    /// The commits observed by each validator
+    pub commits: ScenarioResult,
+    /// The amount of virtual time that was simulated
+    pub virtual_time_elapsed: Duration,
+    /// The amount of wall clock time the simulation took
+    pub wall_clock_time_elapsed: Duration,
+}
+
+impl SimulationResult {
+    /// Returns the highest round committed by any validator (if any)
+    pub fn highest_committed_round(&self) -> Option<Round> {
+        (0..self.num_validators)
+            .flat_map(|node| self.commits.get_commits(node))
+            .map(|commit_info| commit_info.round())
+            .max()
+    }
+}
+
+/// Runs the validators on a single-threaded runtime with paused (virtual) time,
+/// and collects the commits of each validator until the configured amount of
+/// virtual time has elapsed. Note: the safety of the commits is not verified
+/// here (see `ScenarioResult::check_safety`).
+pub fn run_simulation(config: &SimulationConfig) -> SimulationResult {
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("Failed to create the simulation runtime");
+
+    let wall_clock_start = WallClockInstant::now();
+    runtime.block_on(async {
+        // Start the validators and the simulated network
+        let mut playground = NetworkPlayground::new(Handle::current());
+        playground.set_network_model(NetworkModel::new(
+            config.seed,
+            config.latency.clone(),
+            config.drop_probability,
+        ));
+        let mut nodes = SMRNode::start_num_nodes_on_current_runtime(
+            config.num_validators,
+            &mut playground,
+            config.proposer_type.clone(),
+            config.round_initial_timeout_ms,
+        );
+        tokio::spawn(playground.start());
+
+        // Collect the commits until the simulation ends
+        let virtual_start = Instant::now();
+        let deadline = virtual_start + config.duration;
+        let mut commits = ScenarioResult::default();
+        let mut commit_streams =
+            stream::select_all(nodes.iter_mut().enumerate().map(|(node, smr_node)| {
+                (&mut smr_node.commit_cb_receiver).map(move |commit| (node, commit))
+            }));
+        while let Ok(Some((node, commit))) =
+            tokio::time::timeout_at(deadline, commit_streams.next()).await
+        {
+            commits.add_commit(node, commit.ledger_info().commit_info().clone());
+        }
+        drop(commit_streams);
+        for (node, smr_node) in nodes.iter().enumerate() {
+            let committed_blocks = smr_node.committed_blocks.lock();
+            commits.add_committed_blocks(node, committed_blocks.iter().map(CommittedBlock::from));
+        }
+
+        SimulationResult {
+            num_validators: config.num_validators,
+            commits,
+            virtual_time_elapsed: virtual_start.elapsed(),
+            wall_clock_time_elapsed: wall_clock_start.elapsed(),
+        }
+    })
+}
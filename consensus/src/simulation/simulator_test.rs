@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::simulation::{
+    new_virtual_aptos_time_service, run_simulation, LatencyDistribution, SimulationConfig,
+};
+use aptos_time_service::TimeServiceTrait;
+use std::time::Duration;
+use tokio::runtime::Builder;
+
+#[test]
+/// Simulates a minute of consensus with a healthy network, and verifies that all
+/// validators make progress (without conflicting commits).
+fn test_simulation_makes_progress() {
+    let config = SimulationConfig {
+        duration: Duration::from_secs(60),
+        ..SimulationConfig::default()
+    };
+    let result = run_simulation(&config);
+
+    // The full duration is simulated (using virtual time)
+    assert!(result.virtual_time_elapsed >= config.duration);
+
+    // Every validator commits, and the commits are safe
+    for node in 0..config.num_validators {
+        assert!(!result.commits.get_commits(node).is_empty());
+    }
+    result.commits.check_safety().unwrap();
+    assert!(result.highest_committed_round().unwrap() > 10);
+}
+
+#[test]
+/// Simulates consensus over a lossy network with long tail latencies, and verifies
+/// that the validators still make progress (relying on timeouts), without conflicts.
+fn test_simulation_with_lossy_network() {
+    let config = SimulationConfig {
+        num_validators: 7,
+        seed: 42,
+        duration: Duration::from_secs(300),
+        latency: LatencyDistribution::Exponential {
+            min: Duration::from_millis(20),
+            mean: Duration::from_millis(200),
+        },
+        drop_probability: 0.05,
+        ..SimulationConfig::default()
+    };
+    let result = run_simulation(&config);
+
+    assert!(result.virtual_time_elapsed >= config.duration);
+    result.commits.check_safety().unwrap();
+    assert!(result.highest_committed_round().is_some());
+}
+
+#[test]
+/// Verifies that the mock aptos time service follows the virtual clock of the runtime
+/// (instead of staying frozen at its start time).
+fn test_virtual_aptos_time_service_is_advanced() {
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let aptos_time_service = new_virtual_aptos_time_service();
+        let start_time = aptos_time_service.now_unix_time();
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let elapsed = aptos_time_service.now_unix_time() - start_time;
+        assert!(elapsed >= Duration::from_millis(9_990));
+        assert!(elapsed <= Duration::from_secs(10));
+    });
+}
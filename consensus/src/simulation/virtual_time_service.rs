@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::util::time_service::{ScheduledTask, TimeService};
+use aptos_time_service::MockTimeService;
+use async_trait::async_trait;
+use futures::future::{AbortHandle, Abortable};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// The (virtual) unix timestamp at which every simulation starts
+const VIRTUAL_START_TIMESTAMP: Duration = Duration::from_secs(1_700_000_000);
+
+/// The interval at which the mock aptos time service catches up with the virtual clock
+const MOCK_CLOCK_TICK: Duration = Duration::from_millis(10);
+
+/// A time service that is driven by the tokio clock (instead of the wall clock). When
+/// the tokio clock is paused, all timers and timestamps (e.g., block timestamps) only
+/// depend on the virtual time, so runs with the same seed observe the same timestamps.
+pub struct VirtualTimeService {
+    start_instant: Instant,
+}
+
+impl VirtualTimeService {
+    /// Creates a new time service that starts at the virtual start timestamp.
+    /// Note: this must be called from within a tokio runtime.
+    pub fn new() -> Self {
+        Self {
+            start_instant: Instant::now(),
+        }
+    }
+}
+
+impl Default for VirtualTimeService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TimeService for VirtualTimeService {
+    fn run_after(&self, timeout: Duration, mut task: Box<dyn ScheduledTask>) -> AbortHandle {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let task = Abortable::new(
+            async move {
+                tokio::time::sleep(timeout).await;
+                task.run().await;
+            },
+            abort_registration,
+        );
+        tokio::spawn(task);
+        abort_handle
+    }
+
+    fn get_current_timestamp(&self) -> Duration {
+        VIRTUAL_START_TIMESTAMP + self.start_instant.elapsed()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+/// Returns a mock aptos time service that is advanced along with the tokio clock (every
+/// `MOCK_CLOCK_TICK`), so that the components using it observe the virtual time instead
+/// of a frozen clock.
+/// Note: this must be called from within a tokio runtime.
+pub fn new_virtual_aptos_time_service() -> aptos_time_service::TimeService {
+    let mock_time_service = MockTimeService::new();
+    let driven_time_service = mock_time_service.clone();
+    tokio::spawn(async move {
+        let start_instant = Instant::now();
+        let mut advanced = Duration::ZERO;
+        loop {
+            tokio::time::sleep(MOCK_CLOCK_TICK).await;
+            let elapsed = start_instant.elapsed();
+            driven_time_service.advance_async(elapsed - advanced).await;
+            advanced = elapsed;
+        }
+    });
+    aptos_time_service::TimeService::from_mock(mock_time_service)
+}
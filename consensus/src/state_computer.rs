@@ -18,7 +18,12 @@ use crate::{
 };
 use anyhow::Result;
 use aptos_consensus_notifications::ConsensusNotificationSender;
-use aptos_consensus_types::{block::Block, common::Round, pipelined_block::PipelinedBlock};
+use aptos_consensus_types::{
+    block::Block,
+    common::Round,
+    flight_recorder::{ConsensusEvent, FLIGHT_RECORDER},
+    pipelined_block::PipelinedBlock,
+};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{BlockExecutorTrait, ExecutorResult, StateComputeResult};
 use aptos_infallible::RwLock;
@@ -208,6 +213,7 @@ impl StateComputer for ExecutionProxy {
         let block_executor_onchain_config = block_executor_onchain_config.clone();
 
         let timestamp = block.timestamp_usecs();
+        let (epoch, round) = (block.epoch(), block.round());
         let metadata = if is_randomness_enabled {
             block.new_metadata_with_randomness(&validators, randomness)
         } else {
@@ -235,6 +241,15 @@ impl StateComputer for ExecutionProxy {
             let result = &pipeline_execution_result.result;
 
             observe_block(timestamp, BlockStage::EXECUTED);
+            if FLIGHT_RECORDER.is_enabled() {
+                FLIGHT_RECORDER.record(ConsensusEvent::new(
+                    BlockStage::EXECUTED,
+                    epoch,
+                    round,
+                    block_id,
+                    timestamp,
+                ));
+            }
 
             let compute_status = result.compute_status_for_input_txns();
             // the length of compute_status is user_txns.len() + num_vtxns + 1 due to having blockmetadata
@@ -0,0 +1,335 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction_shuffler::TransactionShuffler;
+use aptos_types::transaction::{
+    analyzed_transaction::{
+        account_resource_location, try_get_user_transaction_read_write_hints, StorageLocation,
+    },
+    SignedTransaction,
+};
+use move_core_types::account_address::AccountAddress;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// An implementation of transaction shuffler, which tries to spread out transactions that access
+/// the same storage locations (i.e., transactions that are likely to conflict during parallel
+/// execution). The conflicts are detected using the read/write hints of `AnalyzedTransaction`:
+/// two transactions conflict if one writes a location that the other reads or writes.
+///
+/// Note: real hints are only available for coin transfers (`coin::transfer` and
+/// `aptos_account::transfer`) and `aptos_account::create_account`. For all other transactions
+/// (e.g., arbitrary entry functions and scripts), only the sender's account resource is assumed
+/// to be written, so the shuffler degrades to spreading out transactions by sender.
+///
+/// Similar to the `SenderAwareShuffler`, it maintains a sliding window of the last
+/// `conflict_window_size` transactions added to the block. When selecting the next transaction,
+/// it prefers (in order):
+/// 1. the first pending transaction that doesn't conflict with the window,
+/// 2. the next transaction in the original order that doesn't conflict with the window,
+/// 3. the first pending transaction (if all transactions conflict).
+///
+/// The relative ordering of the transactions from the same sender is always preserved, and the
+/// ordering of non-conflicting transactions remains unchanged. Note: the pending transactions are
+/// scanned linearly, so the worst case complexity is O(n^2) for blocks with many conflicts.
+pub struct ConflictAwareShuffler {
+    conflict_window_size: usize,
+}
+
+impl ConflictAwareShuffler {
+    pub fn new(conflict_window_size: usize) -> Self {
+        Self {
+            conflict_window_size,
+        }
+    }
+}
+
+impl TransactionShuffler for ConflictAwareShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        // Early return for performance reason if there are no transactions to shuffle, or
+        // if the conflict window is 0 (in which case we don't do any shuffling).
+        if txns.is_empty() || self.conflict_window_size == 0 {
+            return txns;
+        }
+
+        let num_transactions = txns.len();
+        let mut orig_txns: VecDeque<_> = txns.into_iter().map(HintedTransaction::new).collect();
+        let mut pending_txns = PendingTransactions::new();
+        let mut window = ConflictWindow::new(self.conflict_window_size);
+        let mut shuffled_txns = Vec::with_capacity(num_transactions);
+
+        while shuffled_txns.len() < num_transactions {
+            let txn = if let Some(txn) = pending_txns.remove_first_non_conflicting(&window) {
+                txn
+            } else {
+                let mut next_txn = None;
+                while let Some(txn) = orig_txns.pop_front() {
+                    // Transactions must follow any pending transactions from the same sender
+                    if pending_txns.has_sender(&txn.sender) || window.has_conflict(&txn) {
+                        pending_txns.add_transaction(txn);
+                    } else {
+                        next_txn = Some(txn);
+                        break;
+                    }
+                }
+                next_txn.unwrap_or_else(|| {
+                    pending_txns
+                        .remove_first()
+                        .expect("Pending should return a transaction")
+                })
+            };
+
+            let HintedTransaction {
+                txn,
+                read_hints,
+                write_hints,
+                ..
+            } = txn;
+            shuffled_txns.push(txn);
+            window.add_transaction(read_hints, write_hints);
+        }
+        shuffled_txns
+    }
+}
+
+/// A transaction along with its read/write hints
+struct HintedTransaction {
+    txn: SignedTransaction,
+    sender: AccountAddress,
+    read_hints: Vec<StorageLocation>,
+    write_hints: Vec<StorageLocation>,
+}
+
+impl HintedTransaction {
+    fn new(txn: SignedTransaction) -> Self {
+        let sender = txn.sender();
+        let (read_hints, write_hints) = try_get_user_transaction_read_write_hints(&txn)
+            .unwrap_or_else(|| (vec![], vec![account_resource_location(sender)]));
+        Self {
+            txn,
+            sender,
+            read_hints,
+            write_hints,
+        }
+    }
+}
+
+/// The transactions that are pending to be added to the block (in the original order)
+struct PendingTransactions {
+    txns: VecDeque<HintedTransaction>,
+    num_txns_by_sender: HashMap<AccountAddress, usize>,
+}
+
+impl PendingTransactions {
+    fn new() -> Self {
+        Self {
+            txns: VecDeque::new(),
+            num_txns_by_sender: HashMap::new(),
+        }
+    }
+
+    fn add_transaction(&mut self, txn: HintedTransaction) {
+        *self.num_txns_by_sender.entry(txn.sender).or_default() += 1;
+        self.txns.push_back(txn);
+    }
+
+    fn has_sender(&self, sender: &AccountAddress) -> bool {
+        self.num_txns_by_sender.contains_key(sender)
+    }
+
+    /// Removes the first pending transaction that doesn't conflict with the window, and
+    /// isn't preceded by another pending transaction from the same sender.
+    fn remove_first_non_conflicting(
+        &mut self,
+        window: &ConflictWindow,
+    ) -> Option<HintedTransaction> {
+        let mut skipped_senders = HashSet::new();
+        let index = self.txns.iter().position(|txn| {
+            if skipped_senders.contains(&txn.sender) {
+                return false;
+            }
+            if window.has_conflict(txn) {
+                skipped_senders.insert(txn.sender);
+                return false;
+            }
+            true
+        })?;
+        self.remove(index)
+    }
+
+    fn remove_first(&mut self) -> Option<HintedTransaction> {
+        self.remove(0)
+    }
+
+    fn remove(&mut self, index: usize) -> Option<HintedTransaction> {
+        let txn = self.txns.remove(index)?;
+        let num_txns = self
+            .num_txns_by_sender
+            .get_mut(&txn.sender)
+            .expect("Pending sender must exist");
+        *num_txns -= 1;
+        if *num_txns == 0 {
+            self.num_txns_by_sender.remove(&txn.sender);
+        }
+        Some(txn)
+    }
+}
+
+/// A sliding window of the read/write hints of the last transactions added to the block
+struct ConflictWindow {
+    window_size: usize,
+    hints: VecDeque<(Vec<StorageLocation>, Vec<StorageLocation>)>,
+    // The number of reads and writes of each location in the window
+    accesses: HashMap<StorageLocation, (usize, usize)>,
+}
+
+impl ConflictWindow {
+    fn new(window_size: usize) -> Self {
+        Self {
+            window_size,
+            hints: VecDeque::new(),
+            accesses: HashMap::new(),
+        }
+    }
+
+    /// Returns true iff the transaction writes a location read or written in the window,
+    /// or reads a location written in the window.
+    fn has_conflict(&self, txn: &HintedTransaction) -> bool {
+        txn.write_hints
+            .iter()
+            .any(|location| self.accesses.contains_key(location))
+            || txn.read_hints.iter().any(|location| {
+                self.accesses
+                    .get(location)
+                    .map_or(false, |(_, num_writes)| *num_writes > 0)
+            })
+    }
+
+    fn add_transaction(
+        &mut self,
+        read_hints: Vec<StorageLocation>,
+        write_hints: Vec<StorageLocation>,
+    ) {
+        for location in &read_hints {
+            self.accesses.entry(location.clone()).or_default().0 += 1;
+        }
+        for location in &write_hints {
+            self.accesses.entry(location.clone()).or_default().1 += 1;
+        }
+        self.hints.push_back((read_hints, write_hints));
+
+        // Slide the window
+        if self.hints.len() > self.window_size {
+            let (read_hints, write_hints) = self.hints.pop_front().expect("Window can't be empty");
+            for location in read_hints {
+                self.remove_access(location, true);
+            }
+            for location in write_hints {
+                self.remove_access(location, false);
+            }
+        }
+    }
+
+    fn remove_access(&mut self, location: StorageLocation, is_read: bool) {
+        if let Some((num_reads, num_writes)) = self.accesses.get_mut(&location) {
+            if is_read {
+                *num_reads -= 1;
+            } else {
+                *num_writes -= 1;
+            }
+            if *num_reads == 0 && *num_writes == 0 {
+                self.accesses.remove(&location);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transaction_shuffler::{conflict_aware::ConflictAwareShuffler, TransactionShuffler};
+    use aptos_cached_packages::aptos_stdlib;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{RawTransaction, SignedTransaction, TransactionPayload},
+    };
+    use move_core_types::account_address::AccountAddress;
+
+    fn create_signed_transaction(
+        sender: AccountAddress,
+        sequence_number: u64,
+        payload: TransactionPayload,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let raw_transaction =
+            RawTransaction::new(sender, sequence_number, payload, 0, 0, 0, ChainId::new(10));
+        SignedTransaction::new(
+            raw_transaction.clone(),
+            public_key,
+            private_key.sign(&raw_transaction).unwrap(),
+        )
+    }
+
+    fn create_transfer(sender: AccountAddress, receiver: AccountAddress) -> SignedTransaction {
+        create_signed_transaction(sender, 0, aptos_stdlib::aptos_account_transfer(receiver, 1))
+    }
+
+    #[test]
+    fn test_non_conflicting_txns() {
+        let txns: Vec<_> = (0..10)
+            .map(|_| create_transfer(AccountAddress::random(), AccountAddress::random()))
+            .collect();
+        let shuffled_txns = ConflictAwareShuffler::new(5).shuffle(txns.clone());
+
+        // Assert that the ordering is unchanged if there are no conflicts
+        assert_eq!(shuffled_txns, txns);
+    }
+
+    #[test]
+    fn test_conflicting_transfers_are_spread() {
+        // Create transfers to a hot receiver, followed by unrelated transfers
+        let hot_receiver = AccountAddress::random();
+        let mut txns = vec![];
+        for _ in 0..3 {
+            txns.push(create_transfer(AccountAddress::random(), hot_receiver));
+        }
+        for _ in 0..4 {
+            txns.push(create_transfer(
+                AccountAddress::random(),
+                AccountAddress::random(),
+            ));
+        }
+        let shuffled_txns = ConflictAwareShuffler::new(2).shuffle(txns.clone());
+
+        // The transfers to the hot receiver are separated by two unrelated transfers
+        let expected_order = [0, 3, 4, 1, 5, 6, 2];
+        let expected_txns: Vec<_> = expected_order.iter().map(|i| txns[*i].clone()).collect();
+        assert_eq!(shuffled_txns, expected_txns);
+    }
+
+    #[test]
+    fn test_sender_order_is_preserved() {
+        // The first transaction of the sender conflicts, so the second one must wait for it
+        let sender = AccountAddress::random();
+        let hot_receiver = AccountAddress::random();
+        let txns = vec![
+            create_transfer(AccountAddress::random(), hot_receiver),
+            create_signed_transaction(
+                sender,
+                0,
+                aptos_stdlib::aptos_account_transfer(hot_receiver, 1),
+            ),
+            create_signed_transaction(
+                sender,
+                1,
+                aptos_stdlib::aptos_account_transfer(AccountAddress::random(), 1),
+            ),
+            create_transfer(AccountAddress::random(), AccountAddress::random()),
+        ];
+        let shuffled_txns = ConflictAwareShuffler::new(1).shuffle(txns.clone());
+
+        let expected_order = [0, 3, 1, 2];
+        let expected_txns: Vec<_> = expected_order.iter().map(|i| txns[*i].clone()).collect();
+        assert_eq!(shuffled_txns, expected_txns);
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction_shuffler::TransactionShuffler;
+use aptos_types::transaction::SignedTransaction;
+use move_core_types::account_address::AccountAddress;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+/// An implementation of transaction shuffler, which orders the transactions in a block by gas
+/// unit price (highest first). Since transactions from the same sender must be executed in
+/// sequence number order, the relative ordering of the transactions of each sender is preserved,
+/// i.e., a transaction is only selected once all earlier transactions of its sender are selected.
+/// Transactions with the same gas unit price keep their original relative ordering.
+///
+/// The shuffling algorithm is O(n log n): the transactions are grouped into per-sender queues, and
+/// a max-heap holds the first transaction of each sender queue, keyed by (gas unit price, reversed
+/// original index). In every step, the top of the heap is added to the block, and the next
+/// transaction of the same sender (if any) is pushed onto the heap.
+pub struct GasPricePriorityShuffler {}
+
+impl TransactionShuffler for GasPricePriorityShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        // Early return for performance reason if there are no transactions to shuffle
+        if txns.is_empty() {
+            return txns;
+        }
+
+        // Group the transactions by sender, preserving the original order
+        let num_transactions = txns.len();
+        let mut txns_by_senders: HashMap<AccountAddress, VecDeque<(usize, SignedTransaction)>> =
+            HashMap::new();
+        for (index, txn) in txns.into_iter().enumerate() {
+            txns_by_senders
+                .entry(txn.sender())
+                .or_default()
+                .push_back((index, txn));
+        }
+
+        // Initialize the heap with the first transaction of each sender
+        let mut heap = BinaryHeap::with_capacity(txns_by_senders.len());
+        for (sender, sender_txns) in &txns_by_senders {
+            let (index, txn) = sender_txns.front().expect("Sender queue must not be empty");
+            heap.push((txn.gas_unit_price(), Reverse(*index), *sender));
+        }
+
+        // Repeatedly select the highest priority transaction
+        let mut shuffled_txns = Vec::with_capacity(num_transactions);
+        while let Some((_, _, sender)) = heap.pop() {
+            let sender_txns = txns_by_senders
+                .get_mut(&sender)
+                .expect("Sender queue must exist");
+            let (_, txn) = sender_txns
+                .pop_front()
+                .expect("Sender queue must not be empty");
+            shuffled_txns.push(txn);
+
+            if let Some((index, next_txn)) = sender_txns.front() {
+                heap.push((next_txn.gas_unit_price(), Reverse(*index), sender));
+            }
+        }
+        shuffled_txns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::transaction_shuffler::{
+        create_transaction_shuffler, gas_price_priority::GasPricePriorityShuffler,
+        TransactionShuffler,
+    };
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        on_chain_config::TransactionShufflerType,
+        transaction::{RawTransaction, Script, SignedTransaction, TransactionPayload},
+    };
+    use move_core_types::account_address::AccountAddress;
+
+    fn create_signed_transaction(
+        sender: AccountAddress,
+        sequence_number: u64,
+        gas_unit_price: u64,
+    ) -> SignedTransaction {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let raw_transaction = RawTransaction::new(
+            sender,
+            sequence_number,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            gas_unit_price,
+            0,
+            ChainId::new(10),
+        );
+        SignedTransaction::new(
+            raw_transaction.clone(),
+            public_key,
+            private_key.sign(&raw_transaction).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_gas_price_priority_ordering() {
+        let sender_1 = AccountAddress::random();
+        let sender_2 = AccountAddress::random();
+        let sender_3 = AccountAddress::random();
+
+        // The second transaction of sender 1 pays the most, but must follow the first one
+        let txns = vec![
+            create_signed_transaction(sender_1, 0, 100),
+            create_signed_transaction(sender_2, 0, 200),
+            create_signed_transaction(sender_1, 1, 500),
+            create_signed_transaction(sender_3, 0, 200),
+            create_signed_transaction(sender_3, 1, 50),
+        ];
+        let shuffled_txns = GasPricePriorityShuffler {}.shuffle(txns.clone());
+
+        // Transactions with the same price keep their original order
+        let expected_order = [1, 3, 0, 2, 4];
+        let expected_txns: Vec<_> = expected_order.iter().map(|i| txns[*i].clone()).collect();
+        assert_eq!(shuffled_txns, expected_txns);
+    }
+
+    #[test]
+    fn test_gas_price_priority_preserves_sender_order() {
+        let senders: Vec<_> = (0..5).map(|_| AccountAddress::random()).collect();
+        let mut txns = vec![];
+        for sequence_number in 0..10 {
+            for (i, sender) in senders.iter().enumerate() {
+                let gas_unit_price = (sequence_number * 7 + i as u64 * 13) % 11;
+                txns.push(create_signed_transaction(
+                    *sender,
+                    sequence_number,
+                    gas_unit_price,
+                ));
+            }
+        }
+        let shuffled_txns = GasPricePriorityShuffler {}.shuffle(txns.clone());
+        assert_eq!(shuffled_txns.len(), txns.len());
+
+        for sender in senders {
+            let sequence_numbers: Vec<_> = shuffled_txns
+                .iter()
+                .filter(|txn| txn.sender() == sender)
+                .map(|txn| txn.sequence_number())
+                .collect();
+            assert_eq!(sequence_numbers, (0..10).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_composed_gas_price_priority() {
+        let txns = vec![
+            create_signed_transaction(AccountAddress::random(), 0, 100),
+            create_signed_transaction(AccountAddress::random(), 0, 200),
+        ];
+        let reversed_txns: Vec<_> = txns.iter().rev().cloned().collect();
+
+        // The composed shufflers are applied
+        let shuffler = create_transaction_shuffler(TransactionShufflerType::Composed(vec![
+            TransactionShufflerType::GasPricePriority,
+        ]));
+        assert_eq!(shuffler.shuffle(txns.clone()), reversed_txns);
+
+        // Nested composed shufflers are rejected (i.e., the transactions aren't shuffled)
+        let shuffler = create_transaction_shuffler(TransactionShufflerType::Composed(vec![
+            TransactionShufflerType::Composed(vec![TransactionShufflerType::GasPricePriority]),
+        ]));
+        assert_eq!(shuffler.shuffle(txns.clone()), txns);
+    }
+}
@@ -1,12 +1,16 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_logger::info;
+use aptos_logger::{error, info};
 use aptos_types::{on_chain_config::TransactionShufflerType, transaction::SignedTransaction};
+use conflict_aware::ConflictAwareShuffler;
+use gas_price_priority::GasPricePriorityShuffler;
 use sender_aware::SenderAwareShuffler;
 use std::sync::Arc;
 
+mod conflict_aware;
 mod deprecated_fairness;
+mod gas_price_priority;
 mod sender_aware;
 mod use_case_aware;
 
@@ -24,6 +28,34 @@ impl TransactionShuffler for NoOpShuffler {
     }
 }
 
+/// Shuffler that applies a sequence of shufflers (e.g., to spread out conflicting
+/// transactions after ordering them by gas price). Note: each shuffler only sees
+/// the output of the previous one, so later shufflers may undo earlier orderings.
+pub struct ComposedShuffler {
+    shufflers: Vec<Arc<dyn TransactionShuffler>>,
+}
+
+impl ComposedShuffler {
+    pub fn new(shufflers: Vec<Arc<dyn TransactionShuffler>>) -> Self {
+        Self { shufflers }
+    }
+}
+
+impl TransactionShuffler for ComposedShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        self.shufflers
+            .iter()
+            .fold(txns, |txns, shuffler| shuffler.shuffle(txns))
+    }
+}
+
+/// Creates the transaction shuffler for the given (on-chain) shuffler type.
+///
+/// Note on rollout: the `GasPricePriority`, `ConflictAware` and `Composed` shuffler types
+/// are new variants of the on-chain execution config. Validators running an older binary
+/// can't deserialize these variants (including any shuffler nested in `Composed`), so the
+/// binary upgrade must be rolled out to all validators before the governance proposal
+/// that switches the on-chain config to one of the new shuffler types.
 pub fn create_transaction_shuffler(
     shuffler_type: TransactionShufflerType,
 ) -> Arc<dyn TransactionShuffler> {
@@ -78,5 +110,39 @@ pub fn create_transaction_shuffler(
             );
             Arc::new(use_case_aware::UseCaseAwareShuffler { config })
         },
+        GasPricePriority => {
+            info!("Using gas price priority transaction shuffling");
+            Arc::new(GasPricePriorityShuffler {})
+        },
+        ConflictAware {
+            conflict_window_size,
+        } => {
+            info!(
+                "Using conflict aware transaction shuffling with conflict window size {}",
+                conflict_window_size
+            );
+            Arc::new(ConflictAwareShuffler::new(conflict_window_size as usize))
+        },
+        Composed(shuffler_types) => {
+            // The on-chain config may not nest composed shufflers (so that it can't build
+            // arbitrarily deep shufflers), otherwise no shuffling is done.
+            if shuffler_types
+                .iter()
+                .any(|shuffler_type| matches!(shuffler_type, Composed(_)))
+            {
+                error!(
+                    "Nested composed transaction shufflers are not supported, using no-op shuffling instead: {:?}",
+                    shuffler_types
+                );
+                return Arc::new(NoOpShuffler {});
+            }
+            info!("Using composed transaction shuffling: {:?}", shuffler_types);
+            Arc::new(ComposedShuffler::new(
+                shuffler_types
+                    .into_iter()
+                    .map(create_transaction_shuffler)
+                    .collect(),
+            ))
+        },
     }
 }
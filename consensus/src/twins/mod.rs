@@ -3,4 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod basic_twins_test;
-mod twins_node;
+mod scenario;
+pub mod scenario_runner;
+mod scenario_test;
+pub mod twins_node;
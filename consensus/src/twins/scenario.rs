@@ -0,0 +1,356 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure};
+use aptos_consensus_types::common::Round;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::Path,
+};
+
+/// A declarative Twins scenario, loaded from a YAML (or JSON) file.
+///
+/// Node indices `0..num_nodes` identify the nodes in the validator set, and
+/// indices `num_nodes..num_nodes + num_twins` identify the twins. The twin with
+/// index `num_nodes + i` shares the identity (and keys) of node `i`. Nodes with
+/// a twin are faulty; all other nodes are honest.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TwinsScenario {
+    /// A human readable name for the scenario
+    pub name: String,
+    /// The number of nodes in the validator set
+    pub num_nodes: usize,
+    /// The number of twins (the first `num_twins` nodes are twinned)
+    pub num_twins: usize,
+    /// The proposer (node index) for each round. Rounds without a proposer default to node 0.
+    pub proposers: BTreeMap<Round, usize>,
+    /// The network behaviour (partitions, drops and delays) for each round
+    pub rounds: Vec<RoundConfig>,
+    /// The initial round timeout of each node (in milliseconds)
+    pub round_initial_timeout_ms: u64,
+    /// The maximum duration to run the scenario (in seconds)
+    pub duration_secs: u64,
+    /// If set, every honest node must commit a block within this many rounds
+    pub liveness_within_rounds: Option<Round>,
+}
+
+impl Default for TwinsScenario {
+    fn default() -> Self {
+        Self {
+            name: "".into(),
+            num_nodes: 4,
+            num_twins: 0,
+            proposers: BTreeMap::new(),
+            rounds: vec![],
+            round_initial_timeout_ms: 2_000_000, // Disable round timeouts by default
+            duration_secs: 10,
+            liveness_within_rounds: None,
+        }
+    }
+}
+
+/// The network behaviour for a single round. Messages that carry a round
+/// (e.g., proposals and votes) are subject to the configuration of that round.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoundConfig {
+    /// The round to which this configuration applies
+    pub round: Round,
+    /// The network partitions. Messages between partitions are dropped.
+    pub partitions: Vec<Vec<usize>>,
+    /// Additional messages to drop
+    pub drops: Vec<MessageDrop>,
+    /// Messages to delay
+    pub delays: Vec<MessageDelay>,
+}
+
+/// Drops all messages from `from` to `to`
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageDrop {
+    pub from: usize,
+    pub to: usize,
+}
+
+/// Delays all messages from `from` to `to` by `delay_ms` milliseconds
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MessageDelay {
+    pub from: usize,
+    pub to: usize,
+    pub delay_ms: u64,
+}
+
+impl TwinsScenario {
+    /// Loads (and validates) a scenario from the given YAML or JSON file
+    pub fn load_from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let scenario: Self = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => serde_json::from_str(&contents)?,
+            _ => serde_yaml::from_str(&contents)?,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    /// Returns the total number of nodes (including twins)
+    pub fn num_total_nodes(&self) -> usize {
+        self.num_nodes + self.num_twins
+    }
+
+    /// Returns the indices of the honest nodes (i.e., the nodes without twins)
+    pub fn honest_nodes(&self) -> Vec<usize> {
+        (self.num_twins..self.num_nodes).collect()
+    }
+
+    /// Verifies that the scenario is well-formed
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.num_nodes > 0,
+            "The scenario must contain at least one node!"
+        );
+        ensure!(
+            self.num_twins <= self.num_nodes,
+            "The number of twins ({}) cannot exceed the number of nodes ({})!",
+            self.num_twins,
+            self.num_nodes
+        );
+
+        // Proposers must be nodes in the validator set (twins propose with their node)
+        for (round, proposer) in &self.proposers {
+            ensure!(
+                *proposer < self.num_nodes,
+                "Invalid proposer {} for round {}! Proposers must be in 0..{}",
+                proposer,
+                round,
+                self.num_nodes
+            );
+        }
+
+        let mut seen_rounds = HashSet::new();
+        for round_config in &self.rounds {
+            let round = round_config.round;
+            ensure!(
+                seen_rounds.insert(round),
+                "Round {} is configured more than once!",
+                round
+            );
+
+            // Partitions must be disjoint
+            let mut partitioned_nodes = HashSet::new();
+            for node in round_config.partitions.iter().flatten() {
+                self.validate_node(*node, round)?;
+                ensure!(
+                    partitioned_nodes.insert(*node),
+                    "Node {} appears in multiple partitions in round {}!",
+                    node,
+                    round
+                );
+            }
+
+            for MessageDrop { from, to } in &round_config.drops {
+                self.validate_node(*from, round)?;
+                self.validate_node(*to, round)?;
+            }
+            for MessageDelay { from, to, .. } in &round_config.delays {
+                self.validate_node(*from, round)?;
+                self.validate_node(*to, round)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_node(&self, node: usize, round: Round) -> anyhow::Result<()> {
+        if node >= self.num_total_nodes() {
+            bail!(
+                "Invalid node {} in round {}! Nodes must be in 0..{}",
+                node,
+                round,
+                self.num_total_nodes()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// A single round configuration produced by the scenario generator
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GeneratedRound {
+    pub partitions: Vec<Vec<usize>>,
+    pub proposer: usize,
+}
+
+/// Enumerates Twins scenarios following the approach described in the Twins paper:
+/// (i) enumerate all ways of splitting the nodes (including twins) into
+/// `num_partitions` non-empty partitions; (ii) pair each split with every
+/// candidate leader to produce round configurations; and (iii) enumerate all
+/// sequences of `num_rounds` round configurations. As the number of scenarios
+/// grows exponentially with the number of rounds, scenarios can also be sampled.
+#[derive(Clone, Debug)]
+pub struct ScenarioGenerator {
+    num_nodes: usize,
+    num_twins: usize,
+    num_rounds: Round,
+    round_configurations: Vec<GeneratedRound>,
+}
+
+impl ScenarioGenerator {
+    /// Creates a new generator. If `faulty_leaders_only` is true, only faulty
+    /// nodes (i.e., nodes with twins) are chosen as leaders. Returns an error if
+    /// no round configurations can be generated (e.g., there are no leaders).
+    pub fn new(
+        num_nodes: usize,
+        num_twins: usize,
+        num_partitions: usize,
+        num_rounds: Round,
+        faulty_leaders_only: bool,
+    ) -> anyhow::Result<Self> {
+        // Identify the leader candidates
+        let num_leaders = if faulty_leaders_only {
+            num_twins
+        } else {
+            num_nodes
+        };
+
+        // Pair each partition split with every leader candidate
+        let mut round_configurations = vec![];
+        for partitions in set_partitions(num_nodes + num_twins, num_partitions) {
+            for proposer in 0..num_leaders {
+                round_configurations.push(GeneratedRound {
+                    partitions: partitions.clone(),
+                    proposer,
+                });
+            }
+        }
+
+        ensure!(
+            !round_configurations.is_empty(),
+            "No round configurations for {} nodes, {} twins and {} partitions (faulty leaders only: {})!",
+            num_nodes,
+            num_twins,
+            num_partitions,
+            faulty_leaders_only
+        );
+
+        Ok(Self {
+            num_nodes,
+            num_twins,
+            num_rounds,
+            round_configurations,
+        })
+    }
+
+    /// Returns all possible round configurations
+    pub fn round_configurations(&self) -> &[GeneratedRound] {
+        &self.round_configurations
+    }
+
+    /// Returns the number of scenarios (saturating at usize::MAX)
+    pub fn num_scenarios(&self) -> usize {
+        let mut num_scenarios: usize = 1;
+        for _ in 0..self.num_rounds {
+            num_scenarios = num_scenarios.saturating_mul(self.round_configurations.len());
+        }
+        num_scenarios
+    }
+
+    /// Returns an iterator over all scenarios (in a deterministic order)
+    pub fn scenarios(&self) -> impl Iterator<Item = TwinsScenario> + '_ {
+        (0..self.num_scenarios()).map(move |index| self.scenario_at(index))
+    }
+
+    /// Returns the given number of scenarios, sampled deterministically using the seed
+    pub fn sample_scenarios(&self, num_scenarios: usize, seed: u64) -> Vec<TwinsScenario> {
+        let total_scenarios = self.num_scenarios();
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..num_scenarios)
+            .map(|_| self.scenario_at(rng.gen_range(0, total_scenarios)))
+            .collect()
+    }
+
+    /// Returns the scenario with the given index. Each index is decoded (in mixed
+    /// radix) into one round configuration for each round.
+    pub fn scenario_at(&self, index: usize) -> TwinsScenario {
+        let num_configurations = self.round_configurations.len();
+        let mut remaining_index = index;
+
+        let mut proposers = BTreeMap::new();
+        let mut rounds = vec![];
+        for round in 1..=self.num_rounds {
+            let configuration = &self.round_configurations[remaining_index % num_configurations];
+            remaining_index /= num_configurations;
+
+            proposers.insert(round, configuration.proposer);
+            rounds.push(RoundConfig {
+                round,
+                partitions: configuration.partitions.clone(),
+                ..RoundConfig::default()
+            });
+        }
+
+        TwinsScenario {
+            name: format!("generated-{}", index),
+            num_nodes: self.num_nodes,
+            num_twins: self.num_twins,
+            proposers,
+            rounds,
+            ..TwinsScenario::default()
+        }
+    }
+}
+
+/// Returns all ways of splitting the elements `0..num_elements` into exactly
+/// `num_partitions` non-empty partitions (i.e., all set partitions).
+pub fn set_partitions(num_elements: usize, num_partitions: usize) -> Vec<Vec<Vec<usize>>> {
+    let mut set_partitions = vec![];
+    if num_partitions == 0 || num_partitions > num_elements {
+        return set_partitions;
+    }
+
+    let mut assignment = vec![0; num_elements];
+    enumerate_set_partitions(0, 0, num_partitions, &mut assignment, &mut set_partitions);
+    set_partitions
+}
+
+/// Enumerates set partitions using restricted growth strings, i.e., element `i`
+/// may only be assigned to one of the partitions already used, or the next one.
+fn enumerate_set_partitions(
+    index: usize,
+    num_used_partitions: usize,
+    num_partitions: usize,
+    assignment: &mut Vec<usize>,
+    set_partitions: &mut Vec<Vec<Vec<usize>>>,
+) {
+    let num_elements = assignment.len();
+    if index == num_elements {
+        if num_used_partitions == num_partitions {
+            let mut partitions = vec![vec![]; num_partitions];
+            for (element, partition) in assignment.iter().enumerate() {
+                partitions[*partition].push(element);
+            }
+            set_partitions.push(partitions);
+        }
+        return;
+    }
+
+    // Stop early if there are not enough elements left to fill the unused partitions
+    if num_elements - index < num_partitions - num_used_partitions {
+        return;
+    }
+
+    for partition in 0..num_partitions.min(num_used_partitions + 1) {
+        assignment[index] = partition;
+        enumerate_set_partitions(
+            index + 1,
+            num_used_partitions.max(partition + 1),
+            num_partitions,
+            assignment,
+            set_partitions,
+        );
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network_tests::{NetworkPlayground, TwinId},
+    twins::{
+        scenario::{MessageDelay, MessageDrop, TwinsScenario},
+        twins_node::SMRNode,
+    },
+};
+use anyhow::bail;
+use aptos_consensus_types::{block::Block, common::Round};
+use aptos_crypto::HashValue;
+use aptos_types::{
+    block_info::BlockInfo, on_chain_config::ProposerElectionType::RoundProposer,
+    transaction::Version,
+};
+use futures::{stream, StreamExt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+use tokio::{
+    runtime::{Builder, Handle},
+    time::Instant,
+};
+
+/// A block committed by a node (i.e., the parts required to verify the ancestry)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CommittedBlock {
+    pub epoch: u64,
+    pub round: Round,
+    pub id: HashValue,
+    pub parent_id: HashValue,
+}
+
+impl CommittedBlock {
+    fn epoch_and_round(&self) -> (u64, Round) {
+        (self.epoch, self.round)
+    }
+}
+
+impl From<&Block> for CommittedBlock {
+    fn from(block: &Block) -> Self {
+        Self {
+            epoch: block.epoch(),
+            round: block.round(),
+            id: block.id(),
+            parent_id: block.parent_id(),
+        }
+    }
+}
+
+/// The commits observed by each node while running a scenario
+#[derive(Clone, Debug, Default)]
+pub struct ScenarioResult {
+    commits: BTreeMap<usize, Vec<BlockInfo>>,
+    committed_blocks: BTreeMap<usize, Vec<CommittedBlock>>,
+}
+
+impl ScenarioResult {
+    /// Records a commit for the given node
+    pub fn add_commit(&mut self, node: usize, commit_info: BlockInfo) {
+        self.commits.entry(node).or_default().push(commit_info);
+    }
+
+    /// Records the blocks committed by the given node (in commit order)
+    pub fn add_committed_blocks(
+        &mut self,
+        node: usize,
+        committed_blocks: impl IntoIterator<Item = CommittedBlock>,
+    ) {
+        self.committed_blocks
+            .entry(node)
+            .or_default()
+            .extend(committed_blocks);
+    }
+
+    /// Returns the commits observed by the given node
+    pub fn get_commits(&self, node: usize) -> &[BlockInfo] {
+        self.commits
+            .get(&node)
+            .map_or(&[], |commits| commits.as_slice())
+    }
+
+    /// Returns the blocks committed by the given node (in commit order)
+    pub fn get_committed_blocks(&self, node: usize) -> &[CommittedBlock] {
+        self.committed_blocks
+            .get(&node)
+            .map_or(&[], |committed_blocks| committed_blocks.as_slice())
+    }
+
+    /// Verifies that no two nodes (including twins) committed conflicting blocks,
+    /// i.e., different blocks at the same epoch and round (or version), or blocks
+    /// that do not extend each other.
+    pub fn check_safety(&self) -> anyhow::Result<()> {
+        self.check_commit_infos()?;
+        self.check_committed_blocks()
+    }
+
+    /// Verifies that the commit infos of all nodes agree at each epoch and round,
+    /// and at each version (if the commit infos are executed).
+    fn check_commit_infos(&self) -> anyhow::Result<()> {
+        let mut committed_rounds: HashMap<(u64, Round), (usize, HashValue)> = HashMap::new();
+        let mut committed_versions: HashMap<Version, (usize, HashValue)> = HashMap::new();
+        for (node, commits) in &self.commits {
+            for commit_info in commits {
+                let (other_node, other_id) = *committed_rounds
+                    .entry((commit_info.epoch(), commit_info.round()))
+                    .or_insert((*node, commit_info.id()));
+                if other_id != commit_info.id() {
+                    bail!(
+                        "Conflicting commits at epoch {}, round {}! Node {} committed {}, node {} committed {}",
+                        commit_info.epoch(),
+                        commit_info.round(),
+                        other_node,
+                        other_id,
+                        node,
+                        commit_info.id()
+                    );
+                }
+
+                // Ordered (but not executed) commit infos don't have a version
+                if commit_info.is_ordered_only() {
+                    continue;
+                }
+                let (other_node, other_id) = *committed_versions
+                    .entry(commit_info.version())
+                    .or_insert((*node, commit_info.id()));
+                if other_id != commit_info.id() {
+                    bail!(
+                        "Conflicting commits at version {}! Node {} committed {}, node {} committed {}",
+                        commit_info.version(),
+                        other_node,
+                        other_id,
+                        node,
+                        commit_info.id()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that the committed blocks of all nodes form a single chain, i.e.,
+    /// the blocks are unique at each epoch and round, and each committed block
+    /// extends the committed block that precedes it.
+    fn check_committed_blocks(&self) -> anyhow::Result<()> {
+        let mut blocks_by_round: BTreeMap<(u64, Round), (usize, CommittedBlock)> = BTreeMap::new();
+        let mut blocks_by_id: HashMap<HashValue, CommittedBlock> = HashMap::new();
+        for (node, committed_blocks) in &self.committed_blocks {
+            for block in committed_blocks {
+                let (other_node, other_block) = *blocks_by_round
+                    .entry(block.epoch_and_round())
+                    .or_insert((*node, *block));
+                if other_block.id != block.id {
+                    bail!(
+                        "Conflicting blocks committed at epoch {}, round {}! Node {} committed {}, node {} committed {}",
+                        block.epoch,
+                        block.round,
+                        other_node,
+                        other_block.id,
+                        node,
+                        block.id
+                    );
+                }
+                blocks_by_id.insert(block.id, *block);
+            }
+        }
+
+        // Walk the ancestry of each committed block back to the preceding committed block
+        let committed_blocks: Vec<_> = blocks_by_round.values().collect();
+        for window in committed_blocks.windows(2) {
+            let (_, previous_block) = window[0];
+            let (node, block) = window[1];
+
+            let mut ancestor = block;
+            while ancestor.epoch_and_round() > previous_block.epoch_and_round() {
+                match blocks_by_id.get(&ancestor.parent_id) {
+                    Some(parent) => ancestor = parent,
+                    None => break, // The ancestry is unknown (e.g., the parent was state synced)
+                }
+            }
+            if ancestor.epoch_and_round() <= previous_block.epoch_and_round()
+                && ancestor.id != previous_block.id
+            {
+                bail!(
+                    "Node {} committed block {} (epoch {}, round {}), which does not extend the committed block {} (epoch {}, round {})!",
+                    node,
+                    block.id,
+                    block.epoch,
+                    block.round,
+                    previous_block.id,
+                    previous_block.epoch,
+                    previous_block.round
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that every given node committed a block within the given number of rounds
+    pub fn check_liveness(&self, nodes: &[usize], max_round: Round) -> anyhow::Result<()> {
+        for node in nodes {
+            if !self.has_committed_within(*node, max_round) {
+                bail!(
+                    "Node {} did not commit a block within {} rounds! Commits: {:?}",
+                    node,
+                    max_round,
+                    self.get_commits(*node)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn has_committed_within(&self, node: usize, max_round: Round) -> bool {
+        self.get_commits(node)
+            .iter()
+            .any(|commit_info| commit_info.round() <= max_round)
+    }
+}
+
+/// Runs the given scenario and verifies the safety (and liveness, if
+/// specified) invariants. Returns the commits observed by each node.
+///
+/// All nodes run on a single-threaded runtime with paused (virtual) time,
+/// so that message delays and timeouts don't depend on the wall clock.
+pub fn run_scenario(scenario: &TwinsScenario) -> anyhow::Result<ScenarioResult> {
+    scenario.validate()?;
+
+    let honest_nodes = scenario.honest_nodes();
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("Failed to create the scenario runtime");
+    let scenario_result = runtime.block_on(async {
+        // Start the nodes and their twins
+        let mut playground = NetworkPlayground::new(Handle::current());
+        let round_proposers = scenario.proposers.clone().into_iter().collect();
+        let mut nodes = SMRNode::start_num_nodes_with_twins_on_current_runtime(
+            scenario.num_nodes,
+            scenario.num_twins,
+            &mut playground,
+            RoundProposer(HashMap::new()),
+            Some(round_proposers),
+            scenario.round_initial_timeout_ms,
+        );
+        let twin_ids: Vec<TwinId> = nodes.iter().map(|node| node.id).collect();
+
+        // Configure the network behaviour for each round
+        for round_config in &scenario.rounds {
+            let round = round_config.round;
+            let partitions = round_config
+                .partitions
+                .iter()
+                .map(|partition| partition.iter().map(|node| twin_ids[*node]).collect())
+                .collect();
+            playground.split_network_round(&HashMap::from([(round, partitions)]));
+
+            for MessageDrop { from, to } in &round_config.drops {
+                playground.drop_message_for_round(round, &twin_ids[*from], &twin_ids[*to]);
+            }
+            for MessageDelay { from, to, delay_ms } in &round_config.delays {
+                playground.delay_message_for_round(
+                    round,
+                    &twin_ids[*from],
+                    &twin_ids[*to],
+                    Duration::from_millis(*delay_ms),
+                );
+            }
+        }
+        tokio::spawn(playground.start());
+
+        // Collect the commits until the liveness requirement is met (or the scenario ends)
+        let deadline = Instant::now() + Duration::from_secs(scenario.duration_secs);
+        let mut scenario_result = ScenarioResult::default();
+        let mut commit_streams =
+            stream::select_all(nodes.iter_mut().enumerate().map(|(node, smr_node)| {
+                (&mut smr_node.commit_cb_receiver).map(move |commit| (node, commit))
+            }));
+        while let Ok(Some((node, commit))) =
+            tokio::time::timeout_at(deadline, commit_streams.next()).await
+        {
+            scenario_result.add_commit(node, commit.ledger_info().commit_info().clone());
+
+            if let Some(max_round) = scenario.liveness_within_rounds {
+                if scenario_result
+                    .check_liveness(&honest_nodes, max_round)
+                    .is_ok()
+                {
+                    break;
+                }
+            }
+        }
+        drop(commit_streams);
+
+        // Collect the committed blocks (to verify their ancestry)
+        for (node, smr_node) in nodes.iter().enumerate() {
+            let committed_blocks = smr_node.committed_blocks.lock();
+            scenario_result
+                .add_committed_blocks(node, committed_blocks.iter().map(CommittedBlock::from));
+        }
+        scenario_result
+    });
+
+    // Verify the invariants
+    scenario_result.check_safety()?;
+    if let Some(max_round) = scenario.liveness_within_rounds {
+        scenario_result.check_liveness(&honest_nodes, max_round)?;
+    }
+
+    Ok(scenario_result)
+}
@@ -0,0 +1,319 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::twins::{
+    scenario::{set_partitions, RoundConfig, ScenarioGenerator, TwinsScenario},
+    scenario_runner::{run_scenario, CommittedBlock, ScenarioResult},
+};
+use aptos_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use aptos_types::block_info::BlockInfo;
+use std::{collections::HashSet, fs, path::PathBuf};
+
+/// Returns the paths of all scenario files
+fn get_scenario_files() -> Vec<PathBuf> {
+    let scenarios_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/twins/scenarios");
+    let mut scenario_files: Vec<PathBuf> = fs::read_dir(scenarios_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    scenario_files.sort();
+    scenario_files
+}
+
+/// Creates a commit for the given round, version and block ID
+fn create_commit(round: u64, version: u64, id: HashValue) -> BlockInfo {
+    BlockInfo::new(1, round, id, HashValue::zero(), version, 0, None)
+}
+
+/// Creates a committed block (in epoch 1) for the given round and parent
+fn create_committed_block(round: u64, parent_id: HashValue) -> CommittedBlock {
+    CommittedBlock {
+        epoch: 1,
+        round,
+        id: HashValue::random(),
+        parent_id,
+    }
+}
+
+#[test]
+fn test_scenario_files_are_valid() {
+    let scenario_files = get_scenario_files();
+    assert!(!scenario_files.is_empty());
+
+    // Verify that all scenario files can be loaded and have unique names
+    let mut scenario_names = HashSet::new();
+    for scenario_file in scenario_files {
+        let scenario = TwinsScenario::load_from_file(&scenario_file).unwrap_or_else(|error| {
+            panic!(
+                "Invalid scenario file {:?}! Error: {}",
+                scenario_file, error
+            )
+        });
+        assert!(scenario_names.insert(scenario.name));
+    }
+}
+
+#[test]
+fn test_invalid_scenarios() {
+    // Verify that the number of twins cannot exceed the number of nodes
+    let scenario = TwinsScenario {
+        num_nodes: 2,
+        num_twins: 3,
+        ..TwinsScenario::default()
+    };
+    assert!(scenario.validate().is_err());
+
+    // Verify that twins cannot be proposers
+    let scenario = TwinsScenario {
+        num_nodes: 4,
+        num_twins: 1,
+        proposers: [(1, 4)].into_iter().collect(),
+        ..TwinsScenario::default()
+    };
+    assert!(scenario.validate().is_err());
+
+    // Verify that partitions must be disjoint
+    let scenario = TwinsScenario {
+        num_nodes: 4,
+        rounds: vec![RoundConfig {
+            round: 1,
+            partitions: vec![vec![0, 1], vec![1, 2, 3]],
+            ..RoundConfig::default()
+        }],
+        ..TwinsScenario::default()
+    };
+    assert!(scenario.validate().is_err());
+
+    // Verify that nodes must exist
+    let scenario = TwinsScenario {
+        num_nodes: 4,
+        rounds: vec![RoundConfig {
+            round: 1,
+            partitions: vec![vec![0, 1], vec![2, 3, 4]],
+            ..RoundConfig::default()
+        }],
+        ..TwinsScenario::default()
+    };
+    assert!(scenario.validate().is_err());
+
+    // Verify that rounds cannot be configured more than once
+    let scenario = TwinsScenario {
+        num_nodes: 4,
+        rounds: vec![
+            RoundConfig {
+                round: 1,
+                ..RoundConfig::default()
+            },
+            RoundConfig {
+                round: 1,
+                ..RoundConfig::default()
+            },
+        ],
+        ..TwinsScenario::default()
+    };
+    assert!(scenario.validate().is_err());
+}
+
+#[test]
+fn test_scenario_from_yaml() {
+    let scenario_yaml = r#"
+        name: "test"
+        num_nodes: 4
+        num_twins: 1
+        proposers:
+          1: 0
+        rounds:
+          - round: 1
+            partitions: [[0, 1, 2], [3, 4]]
+            drops:
+              - { from: 0, to: 1 }
+            delays:
+              - { from: 2, to: 0, delay_ms: 100 }
+        liveness_within_rounds: 5
+    "#;
+    let scenario: TwinsScenario = serde_yaml::from_str(scenario_yaml).unwrap();
+    scenario.validate().unwrap();
+
+    // Verify the scenario contents
+    assert_eq!(scenario.num_total_nodes(), 5);
+    assert_eq!(scenario.honest_nodes(), vec![1, 2, 3]);
+    assert_eq!(scenario.rounds[0].drops.len(), 1);
+    assert_eq!(scenario.rounds[0].delays[0].delay_ms, 100);
+    assert_eq!(scenario.liveness_within_rounds, Some(5));
+    assert_eq!(
+        scenario.duration_secs,
+        TwinsScenario::default().duration_secs
+    );
+}
+
+#[test]
+fn test_set_partitions() {
+    // Verify the number of set partitions (i.e., the Stirling numbers of the second kind)
+    assert_eq!(set_partitions(3, 1).len(), 1);
+    assert_eq!(set_partitions(4, 2).len(), 7);
+    assert_eq!(set_partitions(5, 2).len(), 15);
+    assert_eq!(set_partitions(5, 3).len(), 25);
+    assert_eq!(set_partitions(4, 4).len(), 1);
+    assert!(set_partitions(2, 3).is_empty());
+    assert!(set_partitions(2, 0).is_empty());
+
+    // Verify that each set partition covers all elements with non-empty partitions
+    for partitions in set_partitions(5, 3) {
+        assert_eq!(partitions.len(), 3);
+        assert!(partitions.iter().all(|partition| !partition.is_empty()));
+
+        let mut elements: Vec<usize> = partitions.into_iter().flatten().collect();
+        elements.sort();
+        assert_eq!(elements, vec![0, 1, 2, 3, 4]);
+    }
+}
+
+#[test]
+fn test_scenario_generator() {
+    // Create a generator for 4 nodes, 1 twin, 2 partitions and 2 rounds
+    let generator = ScenarioGenerator::new(4, 1, 2, 2, false).unwrap();
+
+    // Verify the number of round configurations (15 partition splits * 4 leaders)
+    assert_eq!(generator.round_configurations().len(), 60);
+    assert_eq!(generator.num_scenarios(), 60 * 60);
+
+    // Verify that all generated scenarios are valid and unique
+    let mut scenarios = HashSet::new();
+    for scenario in generator.scenarios() {
+        scenario.validate().unwrap();
+        assert_eq!(scenario.rounds.len(), 2);
+        assert!(scenarios.insert((scenario.proposers, scenario.rounds)));
+    }
+    assert_eq!(scenarios.len(), generator.num_scenarios());
+
+    // Verify that sampling is deterministic
+    let sampled_scenarios = generator.sample_scenarios(10, 0);
+    assert_eq!(sampled_scenarios.len(), 10);
+    assert_eq!(sampled_scenarios, generator.sample_scenarios(10, 0));
+
+    // Verify that only faulty nodes lead if requested
+    let generator = ScenarioGenerator::new(4, 1, 2, 2, true).unwrap();
+    assert_eq!(generator.round_configurations().len(), 15);
+    for scenario in generator.scenarios() {
+        assert!(scenario.proposers.values().all(|proposer| *proposer == 0));
+    }
+
+    // Verify that generators without round configurations are rejected
+    assert!(ScenarioGenerator::new(4, 0, 2, 2, true).is_err());
+    assert!(ScenarioGenerator::new(4, 1, 6, 2, false).is_err());
+}
+
+#[test]
+fn test_scenario_result_safety() {
+    // Verify that identical commits are safe
+    let mut scenario_result = ScenarioResult::default();
+    let block_id = HashValue::random();
+    scenario_result.add_commit(0, create_commit(1, 10, block_id));
+    scenario_result.add_commit(1, create_commit(1, 10, block_id));
+    scenario_result.check_safety().unwrap();
+
+    // Verify that conflicting commits at the same round are detected
+    let mut conflicting_result = scenario_result.clone();
+    conflicting_result.add_commit(4, create_commit(1, 11, HashValue::random()));
+    assert!(conflicting_result.check_safety().is_err());
+
+    // Verify that conflicting commits at the same version are detected
+    let mut conflicting_result = scenario_result.clone();
+    conflicting_result.add_commit(4, create_commit(2, 10, HashValue::random()));
+    assert!(conflicting_result.check_safety().is_err());
+
+    // Verify that commits at the same round of different epochs don't conflict
+    let mut next_epoch_result = scenario_result.clone();
+    let next_epoch_commit =
+        BlockInfo::new(2, 1, HashValue::random(), HashValue::zero(), 11, 0, None);
+    next_epoch_result.add_commit(4, next_epoch_commit);
+    next_epoch_result.check_safety().unwrap();
+
+    // Verify that ordered (i.e., unexecuted) commits are not compared by version
+    let mut ordered_result = ScenarioResult::default();
+    for round in 1..=2 {
+        let ordered_commit = BlockInfo::new(
+            1,
+            round,
+            HashValue::random(),
+            *ACCUMULATOR_PLACEHOLDER_HASH,
+            0,
+            0,
+            None,
+        );
+        ordered_result.add_commit(0, ordered_commit);
+    }
+    ordered_result.check_safety().unwrap();
+}
+
+#[test]
+fn test_scenario_result_ancestry() {
+    // Create a chain of committed blocks: b1 <- b2 <- b3 <- b4
+    let b1 = create_committed_block(1, HashValue::random());
+    let b2 = create_committed_block(2, b1.id);
+    let b3 = create_committed_block(3, b2.id);
+    let b4 = create_committed_block(4, b3.id);
+
+    // Verify that nodes committing prefixes of the same chain are safe
+    let mut scenario_result = ScenarioResult::default();
+    scenario_result.add_committed_blocks(0, [b1, b2, b3, b4]);
+    scenario_result.add_committed_blocks(1, [b1, b2]);
+    scenario_result.check_safety().unwrap();
+
+    // Verify that nodes with (partially) unknown ancestry are not flagged
+    let mut synced_result = ScenarioResult::default();
+    synced_result.add_committed_blocks(0, [b1, b2]);
+    synced_result.add_committed_blocks(1, [b4]);
+    synced_result.check_safety().unwrap();
+
+    // Verify that different blocks at the same round are detected
+    let mut conflicting_result = scenario_result.clone();
+    conflicting_result.add_committed_blocks(2, [b1, create_committed_block(2, b1.id)]);
+    assert!(conflicting_result.check_safety().is_err());
+
+    // Verify that a fork (i.e., a block that skips a committed block) is detected
+    let mut forked_result = ScenarioResult::default();
+    forked_result.add_committed_blocks(0, [b1, b2]);
+    forked_result.add_committed_blocks(1, [b1, create_committed_block(3, b1.id)]);
+    assert!(forked_result.check_safety().is_err());
+}
+
+#[test]
+fn test_scenario_result_liveness() {
+    // Create a result where node 0 commits at round 2 and node 1 commits at round 5
+    let mut scenario_result = ScenarioResult::default();
+    scenario_result.add_commit(0, create_commit(2, 10, HashValue::random()));
+    scenario_result.add_commit(1, create_commit(5, 20, HashValue::random()));
+
+    // Verify the liveness checks
+    scenario_result.check_liveness(&[0, 1], 5).unwrap();
+    assert!(scenario_result.check_liveness(&[0, 1], 4).is_err());
+    assert!(scenario_result.check_liveness(&[0, 1, 2], 5).is_err());
+}
+
+#[test]
+#[ignore] // Runs every scenario file end-to-end (this is slow)
+/// Run the test:
+/// cargo xtest -p consensus twins_scenario_files_test -- --ignored --nocapture
+fn twins_scenario_files_test() {
+    for scenario_file in get_scenario_files() {
+        let scenario = TwinsScenario::load_from_file(&scenario_file).unwrap();
+        if let Err(error) = run_scenario(&scenario) {
+            panic!("Scenario {} failed! Error: {}", scenario.name, error);
+        }
+    }
+}
+
+#[test]
+#[ignore] // Runs a sample of generated scenarios end-to-end (this is slow)
+/// Run the test:
+/// cargo xtest -p consensus twins_generated_scenarios_test -- --ignored --nocapture
+fn twins_generated_scenarios_test() {
+    // Sample scenarios with 4 nodes, 1 twin, 2 partitions and 4 rounds
+    let generator = ScenarioGenerator::new(4, 1, 2, 4, false).unwrap();
+    for scenario in generator.sample_scenarios(5, 0) {
+        if let Err(error) = run_scenario(&scenario) {
+            panic!("Scenario {} failed! Error: {}", scenario.name, error);
+        }
+    }
+}
@@ -12,8 +12,9 @@ use crate::{
     pipeline::buffer_manager::OrderedBlocks,
     quorum_store::quorum_store_db::MockQuorumStoreDB,
     rand::rand_gen::storage::in_memory::InMemRandDb,
+    simulation::{new_virtual_aptos_time_service, VirtualTimeService},
     test_utils::{mock_execution_client::MockExecutionClient, MockStorage},
-    util::time_service::ClockTimeService,
+    util::time_service::{ClockTimeService, TimeService},
 };
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
@@ -22,8 +23,12 @@ use aptos_config::{
     generator::{self, ValidatorSwarm},
     network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_consensus_types::common::{Author, Round};
+use aptos_consensus_types::{
+    block::Block,
+    common::{Author, Round},
+};
 use aptos_event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use aptos_infallible::Mutex;
 use aptos_mempool::mocks::MockSharedMempool;
 use aptos_network::{
     application::interface::{NetworkClient, NetworkServiceEvents},
@@ -52,14 +57,20 @@ use aptos_validator_transaction_pool::VTxnPoolState;
 use futures::{channel::mpsc, StreamExt};
 use maplit::hashmap;
 use std::{collections::HashMap, iter::FromIterator, sync::Arc};
-use tokio::runtime::Runtime;
+use tokio::runtime::{Handle, Runtime};
+
+/// The default round timeout for twins nodes. This is large enough to
+/// disable round timeouts in twins tests (to avoid flakiness).
+const DEFAULT_ROUND_INITIAL_TIMEOUT_MS: u64 = 2_000_000;
 
 /// Auxiliary struct that is preparing SMR for the test
 pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
-    _runtime: Runtime,
+    /// All blocks committed by the node (in commit order)
+    pub committed_blocks: Arc<Mutex<Vec<Block>>>,
+    _runtime: Option<Runtime>,
     _shared_mempool: MockSharedMempool,
     _state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
 }
@@ -80,6 +91,38 @@ impl SMRNode {
         // Create a runtime for the twin
         let thread_name = format!("twin-{}", twin_id.id);
         let runtime = aptos_runtimes::spawn_named_runtime(thread_name, None);
+        let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
+        let mut smr_node = Self::start_on_runtime(
+            playground,
+            config,
+            consensus_config,
+            storage,
+            twin_id,
+            vtxn_pool,
+            runtime.handle().clone(),
+            time_service,
+            aptos_time_service::TimeService::real(),
+        );
+        smr_node._runtime = Some(runtime);
+        smr_node
+    }
+
+    /// Starts the node on the given runtime (instead of creating a dedicated
+    /// runtime for the node), using the given time services. This allows all
+    /// nodes to share a single runtime, e.g., to run the nodes using the
+    /// (paused) virtual time of the runtime.
+    #[allow(clippy::too_many_arguments)]
+    fn start_on_runtime(
+        playground: &mut NetworkPlayground,
+        config: NodeConfig,
+        consensus_config: OnChainConsensusConfig,
+        storage: Arc<MockStorage>,
+        twin_id: TwinId,
+        vtxn_pool: VTxnPoolState,
+        runtime: Handle,
+        time_service: Arc<dyn TimeService>,
+        aptos_time_service: aptos_time_service::TimeService,
+    ) -> Self {
         let _entered_runtime = runtime.enter();
 
         // Setup the network and SMR node
@@ -138,8 +181,6 @@ impl SMRNode {
             })
             .unwrap();
 
-        let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
-
         let (timeout_sender, timeout_receiver) =
             aptos_channels::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
         let (self_sender, self_receiver) =
@@ -160,7 +201,7 @@ impl SMRNode {
             quorum_store_storage,
             reconfig_listener,
             bounded_executor,
-            aptos_time_service::TimeService::real(),
+            aptos_time_service,
             vtxn_pool,
             Arc::new(InMemRandDb::new()),
             None,
@@ -172,10 +213,18 @@ impl SMRNode {
         runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
 
         let (commit_cb_sender, commit_cb_receiver) = mpsc::unbounded::<LedgerInfoWithSignatures>();
+        let committed_blocks = Arc::new(Mutex::new(vec![]));
+        let committed_blocks_clone = committed_blocks.clone();
         runtime.spawn(async move {
             loop {
                 let ordered_blocks = ordered_blocks_events.next().await.unwrap();
                 let commit = ordered_blocks.ordered_proof.clone();
+                committed_blocks_clone.lock().extend(
+                    ordered_blocks
+                        .ordered_blocks
+                        .iter()
+                        .map(|block| block.block().clone()),
+                );
                 execution_client
                     .commit_to_storage(ordered_blocks)
                     .await
@@ -187,8 +236,9 @@ impl SMRNode {
 
         Self {
             id: twin_id,
-            _runtime: runtime,
+            _runtime: None,
             commit_cb_receiver,
+            committed_blocks,
             storage,
             _shared_mempool: shared_mempool,
             _state_sync: state_sync,
@@ -202,6 +252,88 @@ impl SMRNode {
         playground: &mut NetworkPlayground,
         proposer_type: ProposerElectionType,
         round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> Vec<Self> {
+        Self::start_num_nodes_with_twins_and_round_timeout(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            DEFAULT_ROUND_INITIAL_TIMEOUT_MS,
+        )
+    }
+
+    /// Starts a given number of nodes and their twins, using the given round timeout
+    pub fn start_num_nodes_with_twins_and_round_timeout(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        round_initial_timeout_ms: u64,
+    ) -> Vec<Self> {
+        Self::start_nodes(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            round_initial_timeout_ms,
+            None,
+        )
+    }
+
+    /// Starts a given number of nodes (without twins) on the current runtime,
+    /// using the given round timeout. Note: this must be called from within a
+    /// tokio runtime, which is used to run all the nodes.
+    pub fn start_num_nodes_on_current_runtime(
+        num_nodes: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_initial_timeout_ms: u64,
+    ) -> Vec<Self> {
+        Self::start_num_nodes_with_twins_on_current_runtime(
+            num_nodes,
+            0,
+            playground,
+            proposer_type,
+            None,
+            round_initial_timeout_ms,
+        )
+    }
+
+    /// Starts a given number of nodes and their twins on the current runtime,
+    /// using the given round timeout. Note: this must be called from within a
+    /// tokio runtime, which is used to run all the nodes.
+    pub fn start_num_nodes_with_twins_on_current_runtime(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        round_initial_timeout_ms: u64,
+    ) -> Vec<Self> {
+        Self::start_nodes(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            round_initial_timeout_ms,
+            Some(Handle::current()),
+        )
+    }
+
+    /// Starts a given number of nodes and their twins. If a runtime is given,
+    /// all nodes are started on it. Otherwise, each node gets its own runtime.
+    fn start_nodes(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        round_initial_timeout_ms: u64,
+        runtime: Option<Handle>,
     ) -> Vec<Self> {
         assert!(num_nodes >= num_twins);
         let ValidatorSwarm {
@@ -262,6 +394,13 @@ impl SMRNode {
             node_configs.push(twin);
         }
 
+        // Nodes that share a runtime use its (virtual) clock, so that they don't
+        // depend on the wall clock (e.g., for round timeouts and block timestamps).
+        let virtual_time_services = runtime.as_ref().map(|runtime| {
+            let _entered_runtime = runtime.enter();
+            let time_service: Arc<dyn TimeService> = Arc::new(VirtualTimeService::new());
+            (time_service, new_virtual_aptos_time_service())
+        });
         let mut smr_nodes = vec![];
 
         for (smr_id, mut config) in node_configs.into_iter().enumerate() {
@@ -277,8 +416,7 @@ impl SMRNode {
                 .unwrap()
                 .waypoint = Some(waypoint);
             config.base.waypoint = WaypointConfig::FromConfig(waypoint);
-            // Disable timeout in twins test to avoid flakiness
-            config.consensus.round_initial_timeout_ms = 2_000_000;
+            config.consensus.round_initial_timeout_ms = round_initial_timeout_ms;
 
             let author = author_from_config(&config);
 
@@ -290,14 +428,30 @@ impl SMRNode {
             });
 
             let vtxn_pool = VTxnPoolState::default();
-            smr_nodes.push(Self::start(
-                playground,
-                config,
-                consensus_config,
-                storage,
-                twin_id,
-                vtxn_pool,
-            ));
+            let smr_node = match (&runtime, &virtual_time_services) {
+                (Some(runtime), Some((time_service, aptos_time_service))) => {
+                    Self::start_on_runtime(
+                        playground,
+                        config,
+                        consensus_config,
+                        storage,
+                        twin_id,
+                        vtxn_pool,
+                        runtime.clone(),
+                        time_service.clone(),
+                        aptos_time_service.clone(),
+                    )
+                },
+                _ => Self::start(
+                    playground,
+                    config,
+                    consensus_config,
+                    storage,
+                    twin_id,
+                    vtxn_pool,
+                ),
+            };
+            smr_nodes.push(smr_node);
         }
         smr_nodes
     }
@@ -4,18 +4,34 @@
 #![allow(clippy::unwrap_used)]
 
 use crate::{
-    consensusdb::ConsensusDB,
+    consensusdb::{BlockSchema, CertifiedNodeSchema, ConsensusDB, NodeSchema, QCSchema},
+    dag::Node,
+    persistent_liveness_storage::{LedgerRecoveryData, RecoveryData, RootMetadata},
     quorum_store::{
         quorum_store_db::{QuorumStoreDB, QuorumStoreStorage},
         types::PersistedValue,
     },
 };
 use anyhow::{bail, Result};
-use aptos_consensus_types::{block::Block, common::Payload};
-use aptos_crypto::HashValue;
-use aptos_types::transaction::{SignedTransaction, Transaction};
+use aptos_consensus_types::{
+    block::Block,
+    common::{Author, Payload, Round},
+    quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate,
+    vote::Vote,
+};
+use aptos_crypto::{hash::ACCUMULATOR_PLACEHOLDER_HASH, HashValue};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{SignedTransaction, Transaction},
+};
 use clap::Parser;
-use std::{collections::HashMap, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 #[derive(Parser)]
 #[clap(about = "Dump txns from consensus db.")]
@@ -137,3 +153,410 @@ pub fn extract_txns_from_block<'a>(
         None => Ok(vec![]),
     }
 }
+
+/// A summary of a block stored in the ConsensusDB
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockSummary {
+    pub id: HashValue,
+    pub epoch: u64,
+    pub round: Round,
+    pub parent_id: HashValue,
+    pub author: Option<Author>,
+    pub timestamp_usecs: u64,
+    /// The round of the block certified by the QC embedded in this block
+    pub quorum_cert_round: Round,
+}
+
+impl From<&Block> for BlockSummary {
+    fn from(block: &Block) -> Self {
+        Self {
+            id: block.id(),
+            epoch: block.epoch(),
+            round: block.round(),
+            parent_id: block.parent_id(),
+            author: block.author(),
+            timestamp_usecs: block.timestamp_usecs(),
+            quorum_cert_round: block.quorum_cert().certified_block().round(),
+        }
+    }
+}
+
+/// A summary of a quorum certificate stored in the ConsensusDB
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct QuorumCertSummary {
+    pub certified_block_id: HashValue,
+    pub epoch: u64,
+    pub round: Round,
+    /// The block committed by the QC (if any)
+    pub commit_block_id: HashValue,
+    pub commit_round: Round,
+}
+
+impl From<&QuorumCert> for QuorumCertSummary {
+    fn from(quorum_cert: &QuorumCert) -> Self {
+        let certified_block = quorum_cert.certified_block();
+        let commit_info = quorum_cert.commit_info();
+        Self {
+            certified_block_id: certified_block.id(),
+            epoch: certified_block.epoch(),
+            round: certified_block.round(),
+            commit_block_id: commit_info.id(),
+            commit_round: commit_info.round(),
+        }
+    }
+}
+
+/// A summary of a DAG node stored in the ConsensusDB
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DagNodeSummary {
+    pub digest: HashValue,
+    pub epoch: u64,
+    pub round: Round,
+    pub author: Author,
+    pub timestamp_usecs: u64,
+    pub num_parents: usize,
+    /// Whether the node has been certified (otherwise, it is the pending node of this validator)
+    pub certified: bool,
+}
+
+impl DagNodeSummary {
+    fn new(node: &Node, certified: bool) -> Self {
+        Self {
+            digest: node.digest(),
+            epoch: node.epoch(),
+            round: node.round(),
+            author: *node.author(),
+            timestamp_usecs: node.timestamp(),
+            num_parents: node.parents().len(),
+            certified,
+        }
+    }
+}
+
+/// A summary of the recovery data that consensus would construct on startup
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecoveryDataSummary {
+    pub root_block: BlockSummary,
+    pub root_quorum_cert: QuorumCertSummary,
+    /// The blocks (and quorum certs) descending from the root
+    pub blocks: Vec<BlockSummary>,
+    pub quorum_certs: Vec<QuorumCertSummary>,
+    /// The blocks that don't descend from the root (these are pruned on startup)
+    pub blocks_to_prune: Vec<HashValue>,
+    pub last_vote_round: Option<Round>,
+    pub highest_timeout_cert_round: Option<Round>,
+}
+
+/// Offline access to the ConsensusDB, used to inspect and repair the DB (e.g., when
+/// a validator is unable to recover from the persisted blocks and quorum certs).
+/// Note: the node must not be running while the DB is opened.
+pub struct ConsensusDbInspector {
+    consensus_db: ConsensusDB,
+}
+
+impl ConsensusDbInspector {
+    pub fn new(db_dir: &Path) -> Self {
+        Self {
+            consensus_db: ConsensusDB::new(db_dir),
+        }
+    }
+
+    /// Opens the ConsensusDB in readonly mode (i.e., the DB can't be pruned)
+    pub fn new_readonly(db_dir: &Path) -> Result<Self> {
+        Ok(Self {
+            consensus_db: ConsensusDB::new_readonly(db_dir)?,
+        })
+    }
+
+    /// Creates a checkpoint of the ConsensusDB in the given directory
+    pub fn create_checkpoint(&self, checkpoint_dir: &Path) -> Result<()> {
+        self.consensus_db.create_checkpoint(checkpoint_dir)
+    }
+
+    /// Returns all stored blocks (sorted by epoch and round)
+    pub fn get_blocks(&self) -> Result<Vec<Block>> {
+        let mut blocks: Vec<Block> = self
+            .consensus_db
+            .get_all::<BlockSchema>()?
+            .into_iter()
+            .map(|(_, block)| block)
+            .collect();
+        blocks.sort_by_key(|block| (block.epoch(), block.round()));
+        Ok(blocks)
+    }
+
+    /// Returns all stored quorum certs (sorted by the epoch and round of the certified block)
+    pub fn get_quorum_certs(&self) -> Result<Vec<QuorumCert>> {
+        let mut quorum_certs: Vec<QuorumCert> = self
+            .consensus_db
+            .get_all::<QCSchema>()?
+            .into_iter()
+            .map(|(_, quorum_cert)| quorum_cert)
+            .collect();
+        quorum_certs.sort_by_key(|quorum_cert| {
+            let certified_block = quorum_cert.certified_block();
+            (certified_block.epoch(), certified_block.round())
+        });
+        Ok(quorum_certs)
+    }
+
+    /// Returns all stored DAG nodes, i.e., the certified nodes and the
+    /// pending node of this validator (sorted by epoch, round and author).
+    pub fn get_dag_nodes(&self) -> Result<Vec<DagNodeSummary>> {
+        let mut dag_nodes: Vec<DagNodeSummary> = self
+            .consensus_db
+            .get_all::<CertifiedNodeSchema>()?
+            .iter()
+            .map(|(_, certified_node)| DagNodeSummary::new(certified_node, true))
+            .collect();
+        dag_nodes.extend(
+            self.consensus_db
+                .get_all::<NodeSchema>()?
+                .iter()
+                .map(|(_, node)| DagNodeSummary::new(node, false)),
+        );
+        dag_nodes.sort_by_key(|node| (node.epoch, node.round, node.author));
+        Ok(dag_nodes)
+    }
+
+    /// Returns the last vote sent by this validator (if any)
+    pub fn get_last_vote(&self) -> Result<Option<Vote>> {
+        let (last_vote, _, _, _) = self.consensus_db.get_data()?;
+        Ok(last_vote.map(|bytes| bcs::from_bytes(&bytes)).transpose()?)
+    }
+
+    /// Returns the highest 2-chain timeout certificate (if any)
+    pub fn get_highest_2chain_timeout_cert(&self) -> Result<Option<TwoChainTimeoutCertificate>> {
+        let (_, highest_timeout_cert, _, _) = self.consensus_db.get_data()?;
+        Ok(highest_timeout_cert
+            .map(|bytes| bcs::from_bytes(&bytes))
+            .transpose()?)
+    }
+
+    /// Returns a summary of the recovery data that consensus would construct on startup
+    /// (given the latest committed ledger info in storage), or an error explaining why
+    /// recovery would fail. Note: this does not modify the DB.
+    pub fn get_recovery_data(
+        &self,
+        committed_ledger_info: LedgerInfoWithSignatures,
+        order_vote_enabled: bool,
+    ) -> Result<RecoveryDataSummary> {
+        // The root metadata is only used to initialize the executor, so we use a placeholder
+        let root_metadata = RootMetadata {
+            accu_hash: *ACCUMULATOR_PLACEHOLDER_HASH,
+            frozen_root_hashes: vec![],
+            num_leaves: 0,
+        };
+        let mut recovery_data = RecoveryData::new(
+            self.get_last_vote()?,
+            LedgerRecoveryData::new(committed_ledger_info),
+            self.get_blocks()?,
+            root_metadata,
+            self.get_quorum_certs()?,
+            self.get_highest_2chain_timeout_cert()?,
+            order_vote_enabled,
+        )?;
+
+        let mut blocks_to_prune = recovery_data.take_blocks_to_prune();
+        blocks_to_prune.sort();
+        let last_vote_round = recovery_data
+            .last_vote()
+            .map(|vote| vote.vote_data().proposed().round());
+        let highest_timeout_cert_round = recovery_data
+            .highest_2chain_timeout_certificate()
+            .map(|timeout_cert| timeout_cert.round());
+        let (root, _, blocks, quorum_certs) = recovery_data.take();
+        Ok(RecoveryDataSummary {
+            root_block: BlockSummary::from(root.0.as_ref()),
+            root_quorum_cert: QuorumCertSummary::from(&root.1),
+            blocks: blocks.iter().map(BlockSummary::from).collect(),
+            quorum_certs: quorum_certs.iter().map(QuorumCertSummary::from).collect(),
+            blocks_to_prune,
+            last_vote_round,
+            highest_timeout_cert_round,
+        })
+    }
+
+    /// Prunes all blocks above the committed root (i.e., the uncommitted blocks) and the
+    /// quorum certs that certify them. The quorum certs that commit the root are kept, so
+    /// that consensus can still recover the root (the pruned blocks are fetched from peers).
+    /// Returns the ids of the pruned blocks and quorum certs (nothing is deleted if `dry_run`).
+    pub fn prune_blocks_above_root(
+        &self,
+        committed_ledger_info: &LedgerInfoWithSignatures,
+        dry_run: bool,
+    ) -> Result<(Vec<HashValue>, Vec<HashValue>)> {
+        // If the ledger info ends the epoch, the root is the genesis block of the next epoch
+        let ledger_info = committed_ledger_info.ledger_info();
+        let (root_id, committed_epoch_and_round) = if ledger_info.ends_epoch() {
+            let genesis = Block::make_genesis_block_from_ledger_info(ledger_info);
+            (genesis.id(), (genesis.epoch(), genesis.round()))
+        } else {
+            let committed_info = ledger_info.commit_info();
+            (
+                ledger_info.consensus_block_id(),
+                (committed_info.epoch(), committed_info.round()),
+            )
+        };
+
+        // Identify the blocks above the committed root
+        let blocks_to_prune: Vec<HashValue> = self
+            .get_blocks()?
+            .iter()
+            .filter(|block| (block.epoch(), block.round()) > committed_epoch_and_round)
+            .map(|block| block.id())
+            .collect();
+
+        // Identify the quorum certs for the pruned blocks (except those that commit the root)
+        let pruned_block_ids: HashSet<HashValue> = blocks_to_prune.iter().cloned().collect();
+        let quorum_certs_to_prune: Vec<HashValue> = self
+            .get_quorum_certs()?
+            .iter()
+            .filter(|quorum_cert| {
+                let certified_block = quorum_cert.certified_block();
+                (pruned_block_ids.contains(&certified_block.id())
+                    || (certified_block.epoch(), certified_block.round())
+                        > committed_epoch_and_round)
+                    && quorum_cert.commit_info().id() != root_id
+            })
+            .map(|quorum_cert| quorum_cert.certified_block().id())
+            .collect();
+
+        // Delete the blocks and quorum certs
+        if !dry_run {
+            self.consensus_db
+                .delete::<BlockSchema>(blocks_to_prune.clone())?;
+            self.consensus_db
+                .delete::<QCSchema>(quorum_certs_to_prune.clone())?;
+        }
+
+        Ok((blocks_to_prune, quorum_certs_to_prune))
+    }
+
+    /// Exports the entire content of the ConsensusDB as JSON
+    pub fn export_json(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "last_vote": self.get_last_vote()?,
+            "highest_2chain_timeout_cert": self.get_highest_2chain_timeout_cert()?,
+            "blocks": self.get_blocks()?,
+            "quorum_certs": self.get_quorum_certs()?,
+            "dag_nodes": self.get_dag_nodes()?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_consensus_types::block::block_test_utils::{
+        certificate_for_genesis, placeholder_certificate_for_block,
+    };
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature, ledger_info::LedgerInfo,
+        validator_signer::ValidatorSigner,
+    };
+
+    /// Creates an inspector with a single block (and its quorum cert) on top of genesis,
+    /// and returns the inspector, the block and the genesis ledger info.
+    fn create_inspector(
+        db_dir: &TempPath,
+    ) -> (ConsensusDbInspector, Block, LedgerInfoWithSignatures) {
+        let inspector = ConsensusDbInspector::new(db_dir.path());
+
+        // Create a block on top of genesis (and a quorum cert for it)
+        let signer = ValidatorSigner::random(None);
+        let genesis_qc = certificate_for_genesis();
+        let block = Block::new_proposal(
+            Payload::empty(false, true),
+            1,
+            1,
+            genesis_qc.clone(),
+            &signer,
+            vec![],
+        )
+        .unwrap();
+        let block_qc = placeholder_certificate_for_block(
+            &[signer],
+            block.id(),
+            block.round(),
+            genesis_qc.certified_block().id(),
+            genesis_qc.certified_block().round(),
+        );
+        inspector
+            .consensus_db
+            .save_blocks_and_quorum_certificates(vec![block.clone()], vec![block_qc])
+            .unwrap();
+
+        // Create the genesis ledger info (i.e., the committed root)
+        let genesis_ledger_info = LedgerInfoWithSignatures::new(
+            LedgerInfo::mock_genesis(None),
+            AggregateSignature::empty(),
+        );
+
+        (inspector, block, genesis_ledger_info)
+    }
+
+    #[test]
+    fn test_get_blocks_and_recovery_data() {
+        let db_dir = TempPath::new();
+        let (inspector, block, genesis_ledger_info) = create_inspector(&db_dir);
+
+        // Verify the stored blocks, quorum certs and DAG nodes
+        assert_eq!(inspector.get_blocks().unwrap(), vec![block.clone()]);
+        let quorum_certs = inspector.get_quorum_certs().unwrap();
+        assert_eq!(quorum_certs.len(), 1);
+        assert_eq!(quorum_certs[0].certified_block().id(), block.id());
+        assert!(inspector.get_dag_nodes().unwrap().is_empty());
+        assert!(inspector.get_last_vote().unwrap().is_none());
+
+        // Verify the recovery data
+        let recovery_data = inspector
+            .get_recovery_data(genesis_ledger_info, false)
+            .unwrap();
+        assert_eq!(recovery_data.root_block.round, 0);
+        assert_eq!(recovery_data.blocks, vec![BlockSummary::from(&block)]);
+        assert!(recovery_data.blocks_to_prune.is_empty());
+        assert!(recovery_data.last_vote_round.is_none());
+
+        // Verify the JSON export
+        let json = inspector.export_json().unwrap();
+        assert_eq!(json["blocks"].as_array().unwrap().len(), 1);
+        assert_eq!(json["quorum_certs"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune_blocks_above_root() {
+        let db_dir = TempPath::new();
+        let (inspector, block, genesis_ledger_info) = create_inspector(&db_dir);
+
+        // Prune the blocks in dry run mode and verify nothing is deleted
+        let (pruned_blocks, pruned_quorum_certs) = inspector
+            .prune_blocks_above_root(&genesis_ledger_info, true)
+            .unwrap();
+        assert_eq!(pruned_blocks, vec![block.id()]);
+        assert_eq!(pruned_quorum_certs, vec![block.id()]);
+        assert_eq!(inspector.get_blocks().unwrap().len(), 1);
+        assert_eq!(inspector.get_quorum_certs().unwrap().len(), 1);
+
+        // Back up the DB (using the open handle) and prune the blocks
+        let backup_dir = TempPath::new();
+        backup_dir.create_as_dir().unwrap();
+        inspector.create_checkpoint(backup_dir.path()).unwrap();
+        inspector
+            .prune_blocks_above_root(&genesis_ledger_info, false)
+            .unwrap();
+
+        // Verify the blocks are deleted (but still exist in the backup)
+        let backup = ConsensusDbInspector::new_readonly(backup_dir.path()).unwrap();
+        assert_eq!(backup.get_blocks().unwrap(), vec![block]);
+        assert!(inspector.get_blocks().unwrap().is_empty());
+        assert!(inspector.get_quorum_certs().unwrap().is_empty());
+
+        // Verify that consensus can still recover (from the genesis root)
+        let recovery_data = inspector
+            .get_recovery_data(genesis_ledger_info, false)
+            .unwrap();
+        assert!(recovery_data.blocks.is_empty());
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays the committed block metadata history (i.e., the NewBlockEvents) under alternative
+//! leader reputation configs, to estimate how proposer election would have behaved.
+//!
+//! The simulation is counterfactual only in who gets elected: the history (and thus the
+//! reputation of every validator) is the actual one. The failed proposer rate under an
+//! alternative config is estimated using the actual failure rate of each elected validator.
+
+use crate::liveness::{
+    leader_reputation::{
+        LeaderReputation, MetadataBackend, ProposerAndVoterHeuristic, VersionedNewBlockEvent,
+    },
+    proposer_election::ProposerElection,
+};
+use anyhow::{ensure, Result};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    account_config::NewBlockEvent, epoch_state::EpochState, on_chain_config::LeaderReputationType,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The window used for chain health (this doesn't affect the elected proposers)
+const WINDOW_FOR_CHAIN_HEALTH: usize = 100;
+
+/// The committed block metadata history (and the validator sets) to replay
+pub struct SimulationHistory {
+    /// The NewBlockEvents, sorted by epoch and round
+    events: Vec<VersionedNewBlockEvent>,
+    /// The validator set of each epoch in the history
+    epoch_states: HashMap<u64, EpochState>,
+    /// The accumulator root hash at the version of each NewBlockEvent
+    root_hashes: HashMap<u64, HashValue>,
+}
+
+impl SimulationHistory {
+    pub fn new(
+        mut events: Vec<VersionedNewBlockEvent>,
+        epoch_states: HashMap<u64, EpochState>,
+        root_hashes: HashMap<u64, HashValue>,
+    ) -> Self {
+        events.sort_by_key(|event| (event.event.epoch(), event.event.round()));
+        Self {
+            events,
+            epoch_states,
+            root_hashes,
+        }
+    }
+
+    /// Loads the latest `num_blocks` NewBlockEvents (and the corresponding validator
+    /// sets and accumulator root hashes) from the given DB.
+    pub fn load_from_db(db: &dyn DbReader, num_blocks: usize) -> Result<Self> {
+        let mut events = vec![];
+        let mut root_hashes = HashMap::new();
+        for event in db.get_latest_block_events(num_blocks)? {
+            let version = event.transaction_version;
+            events.push(VersionedNewBlockEvent {
+                event: bcs::from_bytes::<NewBlockEvent>(event.event.event_data())?,
+                version,
+            });
+            root_hashes.insert(version, db.get_accumulator_root_hash(version)?);
+        }
+        ensure!(!events.is_empty(), "No NewBlockEvents found in the DB!");
+
+        // The validator set of an epoch is in the ledger info that ends the previous epoch
+        let first_epoch = events.iter().map(|event| event.event.epoch()).min();
+        let last_epoch = events.iter().map(|event| event.event.epoch()).max();
+        let (first_epoch, last_epoch) = (first_epoch.unwrap_or(0), last_epoch.unwrap_or(0));
+        let mut epoch_states = HashMap::new();
+        let mut start_epoch = first_epoch.saturating_sub(1);
+        while start_epoch < last_epoch {
+            let proof = db.get_epoch_ending_ledger_infos(start_epoch, last_epoch)?;
+            ensure!(
+                !proof.ledger_info_with_sigs.is_empty(),
+                "No epoch ending ledger infos found for epochs {} to {}!",
+                start_epoch,
+                last_epoch
+            );
+            for ledger_info in &proof.ledger_info_with_sigs {
+                if let Some(next_epoch_state) = ledger_info.ledger_info().next_epoch_state() {
+                    epoch_states.insert(next_epoch_state.epoch, next_epoch_state.clone());
+                }
+                start_epoch = ledger_info.ledger_info().epoch() + 1;
+            }
+        }
+
+        Ok(Self::new(events, epoch_states, root_hashes))
+    }
+
+    /// Returns the number of NewBlockEvents in the history
+    pub fn num_blocks(&self) -> usize {
+        self.events.len()
+    }
+}
+
+/// A metadata backend that serves the block metadata from the simulation history
+struct SimulationBackend {
+    history: Arc<SimulationHistory>,
+    window_size: usize,
+}
+
+impl MetadataBackend for SimulationBackend {
+    fn get_block_metadata(
+        &self,
+        target_epoch: u64,
+        target_round: Round,
+    ) -> (Vec<NewBlockEvent>, HashValue) {
+        let events = &self.history.events;
+        let end = events.partition_point(|event| {
+            (event.event.epoch(), event.event.round()) <= (target_epoch, target_round)
+        });
+        let start = end.saturating_sub(self.window_size);
+
+        // Return the window with the latest event first (as the AptosDBBackend does)
+        let window: Vec<_> = events[start..end].iter().rev().collect();
+        let root_hash = window
+            .first()
+            .and_then(|event| self.history.root_hashes.get(&event.version))
+            .cloned()
+            .unwrap_or_else(HashValue::zero);
+        (
+            window
+                .into_iter()
+                .map(|event| event.event.clone())
+                .collect(),
+            root_hash,
+        )
+    }
+}
+
+/// The simulated leader election statistics of a single validator
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ValidatorReport {
+    pub author: Author,
+    /// The average share of the total voting power (over all simulated rounds)
+    pub stake_share: f64,
+    /// The share of the rounds (with a known leader) in which the validator was the leader
+    pub actual_share: f64,
+    /// The share of the rounds in which the validator would have been elected
+    pub simulated_share: f64,
+    /// The fraction of the actual leader rounds in which the validator failed to propose
+    pub actual_failure_rate: f64,
+}
+
+/// The result of simulating leader election under a single config
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SimulationReport {
+    pub name: String,
+    pub num_rounds: u64,
+    /// The fraction of the rounds (with a known leader) in which the simulated leader
+    /// matches the actual one. This should be (close to) 1 for the on-chain config.
+    pub leader_match_rate: f64,
+    /// The fraction of the rounds (with a known leader) in which the leader failed to propose
+    pub actual_failed_round_rate: f64,
+    /// The estimated fraction of failed rounds, based on the failure rate of the elected leaders
+    pub estimated_failed_round_rate: f64,
+    /// Jain's fairness index of the simulated shares relative to the stake shares, i.e.,
+    /// 1 if every validator is elected proportionally to its stake (and 1/n in the worst case).
+    pub fairness_index: f64,
+    /// The statistics of each validator (sorted by simulated share, descending)
+    pub validators: Vec<ValidatorReport>,
+}
+
+#[derive(Default)]
+struct ValidatorCounts {
+    stake_share_sum: f64,
+    actual_proposals: u64,
+    actual_failures: u64,
+    simulated_elections: u64,
+}
+
+/// Returns the actual leader of each round covered by the given NewBlockEvent (or `None`,
+/// if it is unknown), starting at the round after `previous_round`.
+fn actual_leaders(
+    event: &NewBlockEvent,
+    proposers: &[Author],
+    previous_round: Option<Round>,
+) -> Vec<(Round, Option<Author>)> {
+    let round = event.round();
+    let failed_indices = event.failed_proposer_indices();
+
+    // The failed proposers are for the rounds right before the block round (or up
+    // to and including the block round, for NIL blocks, i.e., blocks without a proposer).
+    let is_nil_block = event.proposer() == Author::ZERO;
+    let failed_end_round = if is_nil_block { round + 1 } else { round };
+    let failed_start_round = failed_end_round.saturating_sub(failed_indices.len() as u64);
+    let start_round =
+        previous_round.map_or(failed_start_round, |previous_round| previous_round + 1);
+
+    (start_round..=round)
+        .map(|leader_round| {
+            let leader = if leader_round >= failed_start_round && leader_round < failed_end_round {
+                let index = failed_indices[(leader_round - failed_start_round) as usize];
+                proposers.get(index as usize).cloned()
+            } else if leader_round == round && !is_nil_block {
+                Some(event.proposer())
+            } else {
+                None
+            };
+            (leader_round, leader)
+        })
+        .collect()
+}
+
+/// Creates the leader reputation proposer election for the given epoch (as the epoch
+/// manager does), or returns `None` if the validator set of the epoch is unknown.
+fn create_leader_reputation(
+    history: &Arc<SimulationHistory>,
+    epoch: u64,
+    leader_reputation_type: &LeaderReputationType,
+    exclude_round: u64,
+) -> Option<LeaderReputation> {
+    let epoch_state = history.epoch_states.get(&epoch)?;
+    let proposers: Vec<_> = epoch_state
+        .verifier
+        .get_ordered_account_addresses_iter()
+        .collect();
+
+    let config = match leader_reputation_type {
+        LeaderReputationType::ProposerAndVoter(config)
+        | LeaderReputationType::ProposerAndVoterV2(config) => config,
+    };
+    let proposer_window_size = proposers.len() * config.proposer_window_num_validators_multiplier;
+    let voter_window_size = proposers.len() * config.voter_window_num_validators_multiplier;
+    let heuristic = Box::new(ProposerAndVoterHeuristic::new(
+        Author::ZERO,
+        config.active_weight,
+        config.inactive_weight,
+        config.failed_weight,
+        config.failure_threshold_percent,
+        voter_window_size,
+        proposer_window_size,
+        leader_reputation_type.use_reputation_window_from_stale_end(),
+    ));
+    let backend = Arc::new(SimulationBackend {
+        history: history.clone(),
+        window_size: std::cmp::max(proposer_window_size, voter_window_size),
+    });
+
+    let voting_powers: Vec<_> = if config.weight_by_voting_power {
+        proposers
+            .iter()
+            .map(|proposer| epoch_state.verifier.get_voting_power(proposer).unwrap_or(0))
+            .collect()
+    } else {
+        vec![1; proposers.len()]
+    };
+
+    // Use the history of the previous epochs (if available)
+    let mut epoch_to_proposers = HashMap::new();
+    for previous_epoch in
+        epoch.saturating_sub(config.use_history_from_previous_epoch_max_count as u64)..epoch
+    {
+        if let Some(previous_epoch_state) = history.epoch_states.get(&previous_epoch) {
+            epoch_to_proposers.insert(
+                previous_epoch,
+                previous_epoch_state
+                    .verifier
+                    .get_ordered_account_addresses_iter()
+                    .collect(),
+            );
+        }
+    }
+    epoch_to_proposers.insert(epoch, proposers);
+
+    Some(LeaderReputation::new(
+        epoch,
+        epoch_to_proposers,
+        voting_powers,
+        backend,
+        heuristic,
+        exclude_round,
+        leader_reputation_type.use_root_hash_for_seed(),
+        WINDOW_FOR_CHAIN_HEALTH,
+    ))
+}
+
+/// Replays the history under the given leader reputation config, and reports which leaders
+/// would have been elected. Rounds in epochs with an unknown validator set are skipped.
+pub fn simulate_leader_reputation(
+    history: &Arc<SimulationHistory>,
+    name: &str,
+    leader_reputation_type: &LeaderReputationType,
+    exclude_round: u64,
+) -> SimulationReport {
+    let mut counts: BTreeMap<Author, ValidatorCounts> = BTreeMap::new();
+    let mut simulated_leaders = vec![];
+    let (mut num_rounds, mut num_known_rounds, mut num_matches) = (0u64, 0u64, 0u64);
+
+    let mut current_election: Option<(u64, LeaderReputation)> = None;
+    let mut previous_round = None;
+    for event in &history.events {
+        let event = &event.event;
+        let epoch = event.epoch();
+
+        // Create the proposer election for each new epoch
+        if current_election
+            .as_ref()
+            .map_or(true, |(current_epoch, _)| *current_epoch != epoch)
+        {
+            current_election =
+                create_leader_reputation(history, epoch, leader_reputation_type, exclude_round)
+                    .map(|election| (epoch, election));
+            previous_round = None;
+        }
+        let (Some((_, election)), Some(epoch_state)) =
+            (&current_election, history.epoch_states.get(&epoch))
+        else {
+            continue;
+        };
+
+        let proposers: Vec<_> = epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .collect();
+        let total_voting_power = epoch_state.verifier.total_voting_power() as f64;
+        for (round, actual_leader) in actual_leaders(event, &proposers, previous_round) {
+            let simulated_leader = election.get_valid_proposer(round);
+            num_rounds += 1;
+            simulated_leaders.push(simulated_leader);
+            counts
+                .entry(simulated_leader)
+                .or_default()
+                .simulated_elections += 1;
+            for proposer in &proposers {
+                let voting_power = epoch_state.verifier.get_voting_power(proposer).unwrap_or(0);
+                counts.entry(*proposer).or_default().stake_share_sum +=
+                    voting_power as f64 / total_voting_power;
+            }
+
+            // Compare against the actual leader (if it is known)
+            if let Some(actual_leader) = actual_leader {
+                num_known_rounds += 1;
+                if actual_leader == simulated_leader {
+                    num_matches += 1;
+                }
+                let actual_counts = counts.entry(actual_leader).or_default();
+                if round == event.round() && event.proposer() == actual_leader {
+                    actual_counts.actual_proposals += 1;
+                } else {
+                    actual_counts.actual_failures += 1;
+                }
+            }
+        }
+        previous_round = Some(event.round());
+    }
+
+    // Estimate the failed round rate using the actual failure rate of each elected leader
+    let num_failures: u64 = counts.values().map(|counts| counts.actual_failures).sum();
+    let actual_failed_round_rate = ratio(num_failures, num_known_rounds);
+    let failure_rate = |counts: &ValidatorCounts| {
+        let num_leader_rounds = counts.actual_proposals + counts.actual_failures;
+        if num_leader_rounds == 0 {
+            actual_failed_round_rate
+        } else {
+            ratio(counts.actual_failures, num_leader_rounds)
+        }
+    };
+    let estimated_failures: f64 = simulated_leaders
+        .iter()
+        .map(|leader| failure_rate(&counts[leader]))
+        .sum();
+
+    let mut validators: Vec<_> = counts
+        .iter()
+        .map(|(author, counts)| ValidatorReport {
+            author: *author,
+            stake_share: counts.stake_share_sum / std::cmp::max(num_rounds, 1) as f64,
+            actual_share: ratio(
+                counts.actual_proposals + counts.actual_failures,
+                num_known_rounds,
+            ),
+            simulated_share: ratio(counts.simulated_elections, num_rounds),
+            actual_failure_rate: failure_rate(counts),
+        })
+        .collect();
+    validators.sort_by(|a, b| b.simulated_share.total_cmp(&a.simulated_share));
+
+    SimulationReport {
+        name: name.to_string(),
+        num_rounds,
+        leader_match_rate: ratio(num_matches, num_known_rounds),
+        actual_failed_round_rate,
+        estimated_failed_round_rate: estimated_failures / std::cmp::max(num_rounds, 1) as f64,
+        fairness_index: fairness_index(&validators),
+        validators,
+    }
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// Computes Jain's fairness index over the simulated shares normalized by the stake shares
+fn fairness_index(validators: &[ValidatorReport]) -> f64 {
+    let normalized_shares: Vec<f64> = validators
+        .iter()
+        .filter(|validator| validator.stake_share > 0.0)
+        .map(|validator| validator.simulated_share / validator.stake_share)
+        .collect();
+    let sum: f64 = normalized_shares.iter().sum();
+    let sum_of_squares: f64 = normalized_shares.iter().map(|share| share * share).sum();
+    if sum_of_squares == 0.0 {
+        0.0
+    } else {
+        sum * sum / (normalized_shares.len() as f64 * sum_of_squares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_bitvec::BitVec;
+    use aptos_types::{
+        account_address::AccountAddress, on_chain_config::ProposerAndVoterConfig,
+        validator_verifier::random_validator_verifier,
+    };
+
+    fn create_event(
+        round: Round,
+        proposer: Author,
+        failed_proposer_indices: Vec<u64>,
+    ) -> NewBlockEvent {
+        let mut votes_bitvec = BitVec::with_num_bits(4);
+        for voter in 0..3 {
+            votes_bitvec.set(voter);
+        }
+        NewBlockEvent::new(
+            AccountAddress::random(),
+            1,
+            round,
+            round,
+            votes_bitvec.into(),
+            proposer,
+            failed_proposer_indices,
+            round,
+        )
+    }
+
+    #[test]
+    fn test_actual_leaders() {
+        let proposers: Vec<_> = (0..4).map(|_| AccountAddress::random()).collect();
+
+        // Verify the leaders of a block with a failed proposer (and a missing round)
+        let event = create_event(10, proposers[0], vec![2]);
+        assert_eq!(actual_leaders(&event, &proposers, Some(7)), vec![
+            (8, None),
+            (9, Some(proposers[2])),
+            (10, Some(proposers[0])),
+        ]);
+
+        // Verify the leaders of a NIL block (i.e., the block round failed as well)
+        let event = create_event(10, Author::ZERO, vec![1, 2]);
+        assert_eq!(actual_leaders(&event, &proposers, Some(8)), vec![
+            (9, Some(proposers[1])),
+            (10, Some(proposers[2])),
+        ]);
+    }
+
+    #[test]
+    fn test_simulate_leader_reputation() {
+        // Create a round robin history, where the last validator always fails to propose
+        let (_, verifier) = random_validator_verifier(4, None, true);
+        let proposers: Vec<_> = verifier.get_ordered_account_addresses_iter().collect();
+        let mut events = vec![];
+        for round in 1..=200u64 {
+            let leader_index = round % 4;
+            if leader_index == 3 {
+                continue;
+            }
+            let failed_proposer_indices = if leader_index == 0 { vec![3] } else { vec![] };
+            events.push(VersionedNewBlockEvent {
+                event: create_event(
+                    round,
+                    proposers[leader_index as usize],
+                    failed_proposer_indices,
+                ),
+                version: round,
+            });
+        }
+        let epoch_states = HashMap::from([(1, EpochState::new(1, verifier))]);
+        let history = Arc::new(SimulationHistory::new(events, epoch_states, HashMap::new()));
+
+        // Simulate leader reputation (without using the root hash as the seed)
+        let leader_reputation_type =
+            LeaderReputationType::ProposerAndVoter(ProposerAndVoterConfig {
+                active_weight: 1000,
+                inactive_weight: 10,
+                failed_weight: 1,
+                failure_threshold_percent: 10,
+                proposer_window_num_validators_multiplier: 10,
+                voter_window_num_validators_multiplier: 1,
+                weight_by_voting_power: true,
+                use_history_from_previous_epoch_max_count: 5,
+            });
+        let report = simulate_leader_reputation(&history, "test", &leader_reputation_type, 20);
+
+        // Verify the actual statistics
+        assert_eq!(report.num_rounds, 200);
+        assert_eq!(report.actual_failed_round_rate, 0.25);
+        let failing_validator = report
+            .validators
+            .iter()
+            .find(|validator| validator.author == proposers[3])
+            .unwrap();
+        assert_eq!(failing_validator.actual_failure_rate, 1.0);
+        assert_eq!(failing_validator.actual_share, 0.25);
+        assert_eq!(failing_validator.stake_share, 0.25);
+
+        // Verify that the failing validator is rarely elected (once it has a history)
+        assert!(failing_validator.simulated_share < 0.1);
+        assert!(report.estimated_failed_round_rate < report.actual_failed_round_rate);
+        assert!(report.fairness_index > 0.0 && report.fairness_index < 1.0);
+        let total_share: f64 = report
+            .validators
+            .iter()
+            .map(|validator| validator.simulated_share)
+            .sum();
+        assert!((total_share - 1.0).abs() < 1e-9);
+    }
+}
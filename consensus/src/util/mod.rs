@@ -8,9 +8,11 @@ use aptos_types::{
 };
 
 pub mod db_tool;
+pub mod leader_reputation_simulator;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock_time_service;
 pub mod time_service;
+pub mod timeline_tool;
 
 pub fn is_vtxn_expected(
     randomness_config: &OnChainRandomnessConfig,
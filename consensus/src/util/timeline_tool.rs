@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Context, Result};
+use aptos_consensus_types::{
+    common::Round,
+    flight_recorder::{ConsensusEvent, FlightRecorderDump},
+};
+use aptos_crypto::HashValue;
+use clap::Parser;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+#[derive(Parser)]
+#[clap(about = "Merge consensus flight recorder dumps into a per-block timeline (Chrome trace).")]
+pub struct Command {
+    /// The flight recorder dumps (e.g., fetched from the `/consensus_flight_recorder`
+    /// endpoint of the inspection service), one for each validator.
+    #[clap(long, value_parser, num_args = 1.., required = true)]
+    pub dump_files: Vec<PathBuf>,
+
+    /// The file to write the Chrome trace to (loadable in chrome://tracing or Perfetto)
+    #[clap(long, value_parser)]
+    pub output_file: PathBuf,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let mut dumps = vec![];
+        for dump_file in &self.dump_files {
+            let contents = fs::read_to_string(dump_file)
+                .with_context(|| format!("Failed to read the dump file {:?}", dump_file))?;
+            let dump: FlightRecorderDump = serde_json::from_str(&contents)
+                .with_context(|| format!("Failed to parse the dump file {:?}", dump_file))?;
+            dumps.push(dump);
+        }
+
+        let chrome_trace = to_chrome_trace(&dumps);
+        fs::write(&self.output_file, serde_json::to_string(&chrome_trace)?)?;
+        println!(
+            "Wrote the timeline of {} validators to {:?}",
+            dumps.len(),
+            self.output_file
+        );
+
+        Ok(())
+    }
+}
+
+/// Merges the given flight recorder dumps into a per-block timeline in the Chrome trace
+/// format. Each block is shown as a process and each validator as a thread within it.
+/// Every stage is shown as a slice that spans from the previous stage of the block on
+/// the same validator (or from the block timestamp, for the first stage).
+pub fn to_chrome_trace(dumps: &[FlightRecorderDump]) -> Value {
+    // Group the events by block (ordered by epoch and round) and validator
+    let mut block_events: BTreeMap<(u64, Round, HashValue), BTreeMap<usize, Vec<&ConsensusEvent>>> =
+        BTreeMap::new();
+    for (validator_index, dump) in dumps.iter().enumerate() {
+        for event in &dump.events {
+            block_events
+                .entry((event.epoch, event.round, event.block_id))
+                .or_default()
+                .entry(validator_index)
+                .or_default()
+                .push(event);
+        }
+    }
+
+    let mut trace_events = vec![];
+    for (block_index, ((epoch, round, block_id), validator_events)) in
+        block_events.into_iter().enumerate()
+    {
+        trace_events.push(json!({
+            "name": "process_name",
+            "ph": "M",
+            "pid": block_index,
+            "args": { "name": format!("epoch {} round {} ({})", epoch, round, block_id) },
+        }));
+
+        for (validator_index, mut events) in validator_events {
+            trace_events.push(json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": block_index,
+                "tid": validator_index,
+                "args": { "name": validator_name(&dumps[validator_index], validator_index) },
+            }));
+
+            events.sort_by_key(|event| event.timestamp_usecs);
+            let mut start_usecs = events
+                .first()
+                .map_or(0, |event| event.block_timestamp_usecs);
+            for event in events {
+                // Guard against clock skew between the proposer and this validator
+                let start = start_usecs.min(event.timestamp_usecs);
+                let mut args = json!({ "block_id": block_id.to_string() });
+                if !event.batches.is_empty() {
+                    args["batches"] = json!(event.batches);
+                }
+                trace_events.push(json!({
+                    "name": event.stage,
+                    "cat": "consensus",
+                    "ph": "X",
+                    "ts": start,
+                    "dur": event.timestamp_usecs - start,
+                    "pid": block_index,
+                    "tid": validator_index,
+                    "args": args,
+                }));
+                start_usecs = event.timestamp_usecs;
+            }
+        }
+    }
+
+    json!({
+        "traceEvents": trace_events,
+        "displayTimeUnit": "ms",
+    })
+}
+
+fn validator_name(dump: &FlightRecorderDump, validator_index: usize) -> String {
+    match dump.author {
+        Some(author) => author.to_string(),
+        None => format!("validator {}", validator_index),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_consensus_types::common::Author;
+
+    fn create_event(stage: &str, round: Round, block_id: HashValue, time: u64) -> ConsensusEvent {
+        ConsensusEvent {
+            timestamp_usecs: time,
+            stage: stage.into(),
+            epoch: 1,
+            round,
+            block_id,
+            block_timestamp_usecs: 100,
+            batches: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_chrome_trace() {
+        // Create the dumps of two validators for two blocks
+        let (block_1, block_2) = (HashValue::random(), HashValue::random());
+        let dumps = vec![
+            FlightRecorderDump {
+                author: Some(Author::random()),
+                events: vec![
+                    create_event("voted", 1, block_1, 150),
+                    create_event("round_manager_received", 1, block_1, 120),
+                    create_event("voted", 2, block_2, 300),
+                ],
+            },
+            FlightRecorderDump {
+                author: None,
+                events: vec![create_event("committed", 1, block_1, 500)],
+            },
+        ];
+
+        // Verify the number of metadata and slice events
+        let chrome_trace = to_chrome_trace(&dumps);
+        let trace_events = chrome_trace["traceEvents"].as_array().unwrap();
+        let slices: Vec<&Value> = trace_events
+            .iter()
+            .filter(|event| event["ph"] == "X")
+            .collect();
+        assert_eq!(slices.len(), 4);
+        assert_eq!(trace_events.len() - slices.len(), 2 + 3); // 2 blocks and 3 threads
+
+        // Verify that the stages of each validator are chained together
+        let block_1_slices: Vec<(&str, u64, u64)> = slices
+            .iter()
+            .filter(|slice| slice["pid"] == 0 && slice["tid"] == 0)
+            .map(|slice| {
+                (
+                    slice["name"].as_str().unwrap(),
+                    slice["ts"].as_u64().unwrap(),
+                    slice["dur"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(block_1_slices, vec![
+            ("round_manager_received", 100, 20),
+            ("voted", 120, 30),
+        ]);
+    }
+}
@@ -9,21 +9,28 @@ pub enum Cmd {
     #[clap(subcommand)]
     AptosDb(aptos_db_tool::DBTool),
 
+    ConsensusTimeline(aptos_consensus::util::timeline_tool::Command),
+
     Decode(aptos_move_debugger::bcs_txn_decoder::Command),
 
     DumpPendingTxns(aptos_consensus::util::db_tool::Command),
 
     #[clap(subcommand)]
     Move(aptos_move_debugger::common::Command),
+
+    #[clap(subcommand)]
+    NetworkCapture(aptos_network_capture::Command),
 }
 
 impl Cmd {
     pub async fn run(self) -> Result<()> {
         match self {
             Cmd::AptosDb(cmd) => cmd.run().await,
+            Cmd::ConsensusTimeline(cmd) => cmd.run().await,
             Cmd::Decode(cmd) => cmd.run().await,
             Cmd::DumpPendingTxns(cmd) => cmd.run().await,
             Cmd::Move(cmd) => cmd.run().await,
+            Cmd::NetworkCapture(cmd) => cmd.run().await,
         }
     }
 }
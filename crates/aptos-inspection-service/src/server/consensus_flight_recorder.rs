@@ -0,0 +1,43 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_consensus_types::flight_recorder::FLIGHT_RECORDER;
+use hyper::{Body, StatusCode};
+
+// The message to display when the consensus flight recorder endpoint is disabled
+pub const CONSENSUS_FLIGHT_RECORDER_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_consensus_flight_recorder: true";
+
+/// Handles a new consensus flight recorder request
+pub fn handle_consensus_flight_recorder_request(
+    node_config: &NodeConfig,
+) -> (StatusCode, Body, String) {
+    // Only return the recorded events if the endpoint is enabled
+    if node_config
+        .inspection_service
+        .expose_consensus_flight_recorder
+    {
+        let flight_recorder_dump = get_flight_recorder_dump_json();
+        (
+            StatusCode::OK,
+            Body::from(flight_recorder_dump),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(CONSENSUS_FLIGHT_RECORDER_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with all events in the consensus flight recorder
+fn get_flight_recorder_dump_json() -> String {
+    match serde_json::to_string(&FLIGHT_RECORDER.dump()) {
+        Ok(flight_recorder_dump) => flight_recorder_dump,
+        Err(error) => format!("Failed to get the flight recorder events! Error: {}", error),
+    }
+}
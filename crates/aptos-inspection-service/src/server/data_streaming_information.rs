@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_data_client::{client::AptosDataClient, peer_states};
+use aptos_data_streaming_service::diagnostics::StreamingServiceDiagnostics;
+use hyper::{Body, StatusCode};
+use serde_json::{json, Value};
+
+// The message to display when the data streaming information endpoint is disabled
+pub const DATA_STREAMING_INFO_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_peer_information: true";
+
+/// Handles a new data streaming information request
+pub fn handle_data_streaming_information_request(
+    node_config: &NodeConfig,
+    aptos_data_client: AptosDataClient,
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
+) -> (StatusCode, Body, String) {
+    // Only return data streaming information if peer information is exposed
+    if node_config.inspection_service.expose_peer_information {
+        let data_streaming_information =
+            get_data_streaming_information_json(aptos_data_client, streaming_service_diagnostics);
+        (
+            StatusCode::OK,
+            Body::from(data_streaming_information),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(DATA_STREAMING_INFO_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the state of all active data
+/// streams and the state sync metadata of each peer.
+fn get_data_streaming_information_json(
+    aptos_data_client: AptosDataClient,
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
+) -> String {
+    let data_streaming_information = json!({
+        "data_streams": streaming_service_diagnostics.get_data_stream_summaries(),
+        "peers": get_peer_states_json(aptos_data_client),
+    });
+
+    // Return the data streaming information as a JSON string
+    match serde_json::to_string(&data_streaming_information) {
+        Ok(data_streaming_information) => data_streaming_information,
+        Err(error) => format!("Failed to get data streaming information! Error: {}", error),
+    }
+}
+
+/// Returns the state sync metadata of each peer (sorted by peer)
+fn get_peer_states_json(aptos_data_client: AptosDataClient) -> Vec<Value> {
+    // Get and sort the peer states
+    let peer_to_state = aptos_data_client.get_peer_states().get_peer_to_states();
+    let mut peer_states: Vec<_> = peer_to_state
+        .iter()
+        .map(|peer_state_entry| (*peer_state_entry.key(), peer_state_entry.value().clone()))
+        .collect();
+    peer_states.sort_by_key(|(peer, _)| *peer);
+
+    // Convert each peer state to JSON
+    peer_states
+        .into_iter()
+        .map(|(peer, peer_state)| {
+            json!({
+                "peer": peer.to_string(),
+                "bucket_id": peer_states::get_bucket_id_for_peer(peer),
+                "score": peer_state.get_score(),
+                "num_errors": peer_state.get_num_errors(),
+                "average_response_latency_secs": peer_state.get_average_response_latency_secs(),
+                "sent_requests_by_type": peer_state.get_sent_requests_by_type(),
+                "received_responses_by_type": peer_state.get_received_responses_by_type(),
+            })
+        })
+        .collect()
+}
@@ -4,6 +4,7 @@
 use crate::server::utils::CONTENT_TYPE_TEXT;
 use aptos_config::config::NodeConfig;
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::diagnostics::StreamingServiceDiagnostics;
 use aptos_logger::debug;
 use aptos_network::application::storage::PeersAndMetadata;
 use hyper::{
@@ -18,10 +19,13 @@ use std::{
 };
 
 mod configuration;
+mod consensus_flight_recorder;
+mod data_streaming_information;
 mod index;
 mod json_encoder;
 mod metrics;
 mod peer_information;
+mod quorum_store_information;
 mod system_information;
 pub mod utils;
 
@@ -30,11 +34,14 @@ mod tests;
 
 // The list of endpoints offered by the inspection service
 pub const CONFIGURATION_PATH: &str = "/configuration";
+pub const CONSENSUS_FLIGHT_RECORDER_PATH: &str = "/consensus_flight_recorder";
+pub const DATA_STREAMING_INFORMATION_PATH: &str = "/data_streaming_information";
 pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
+pub const QUORUM_STORE_INFORMATION_PATH: &str = "/quorum_store_information";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
 
 // Useful string constants
@@ -48,6 +55,7 @@ pub fn start_inspection_service(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
 ) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
@@ -75,6 +83,7 @@ pub fn start_inspection_service(
             let node_config = node_config.clone();
             let aptos_data_client = aptos_data_client.clone();
             let peers_and_metadata = peers_and_metadata.clone();
+            let streaming_service_diagnostics = streaming_service_diagnostics.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
                     serve_requests(
@@ -82,6 +91,7 @@ pub fn start_inspection_service(
                         node_config.clone(),
                         aptos_data_client.clone(),
                         peers_and_metadata.clone(),
+                        streaming_service_diagnostics.clone(),
                     )
                 }))
             }
@@ -103,6 +113,7 @@ async fn serve_requests(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
 ) -> Result<Response<Body>, hyper::Error> {
     // Process the request and get the response components
     let (status_code, body, content_type) = match req.uri().path() {
@@ -111,6 +122,20 @@ async fn serve_requests(
             // Exposes the node configuration
             configuration::handle_configuration_request(&node_config)
         },
+        CONSENSUS_FLIGHT_RECORDER_PATH => {
+            // /consensus_flight_recorder
+            // Exposes the recent consensus events of this node
+            consensus_flight_recorder::handle_consensus_flight_recorder_request(&node_config)
+        },
+        DATA_STREAMING_INFORMATION_PATH => {
+            // /data_streaming_information
+            // Exposes the state of all data streams and peers
+            data_streaming_information::handle_data_streaming_information_request(
+                &node_config,
+                aptos_data_client,
+                streaming_service_diagnostics,
+            )
+        },
         FORGE_METRICS_PATH => {
             // /forge_metrics
             // Exposes forge encoded metrics
@@ -140,6 +165,11 @@ async fn serve_requests(
                 peers_and_metadata,
             )
         },
+        QUORUM_STORE_INFORMATION_PATH => {
+            // /quorum_store_information
+            // Exposes the outstanding proofs, back pressure and batch fetch failures
+            quorum_store_information::handle_quorum_store_information_request(&node_config)
+        },
         SYSTEM_INFORMATION_PATH => {
             // /system_information
             // Exposes the system and build information
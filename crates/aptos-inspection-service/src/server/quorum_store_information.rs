@@ -0,0 +1,47 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_consensus_types::quorum_store_diagnostics::QUORUM_STORE_DIAGNOSTICS;
+use hyper::{Body, StatusCode};
+
+// The message to display when the quorum store information endpoint is disabled
+pub const QUORUM_STORE_INFO_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_quorum_store_information: true";
+
+/// Handles a new quorum store information request
+pub fn handle_quorum_store_information_request(
+    node_config: &NodeConfig,
+) -> (StatusCode, Body, String) {
+    // Only return the quorum store information if the endpoint is enabled
+    if node_config
+        .inspection_service
+        .expose_quorum_store_information
+    {
+        let quorum_store_information = get_quorum_store_information_json();
+        (
+            StatusCode::OK,
+            Body::from(quorum_store_information),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(QUORUM_STORE_INFO_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the outstanding proofs (and their
+/// local batch availability), the back pressure state and the fetch failures.
+fn get_quorum_store_information_json() -> String {
+    match serde_json::to_string(&QUORUM_STORE_DIAGNOSTICS.snapshot()) {
+        Ok(quorum_store_information) => quorum_store_information,
+        Err(error) => format!(
+            "Failed to get the quorum store information! Error: {}",
+            error
+        ),
+    }
+}
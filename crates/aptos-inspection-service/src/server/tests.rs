@@ -4,14 +4,19 @@
 use crate::{
     server::{
         configuration::CONFIGURATION_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
+        consensus_flight_recorder::CONSENSUS_FLIGHT_RECORDER_DISABLED_MESSAGE,
+        data_streaming_information::DATA_STREAMING_INFO_DISABLED_MESSAGE,
+        peer_information::PEER_INFO_DISABLED_MESSAGE,
+        quorum_store_information::QUORUM_STORE_INFO_DISABLED_MESSAGE, serve_requests,
         system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
     },
-    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    CONFIGURATION_PATH, CONSENSUS_FLIGHT_RECORDER_PATH, DATA_STREAMING_INFORMATION_PATH,
+    FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH, PEER_INFORMATION_PATH,
+    QUORUM_STORE_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
 };
 use aptos_config::config::{AptosDataClientConfig, BaseConfig, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::diagnostics::StreamingServiceDiagnostics;
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
@@ -57,6 +62,62 @@ async fn test_inspect_configuration() {
     assert!(response_body_string.contains("expose_configuration: true"));
 }
 
+#[tokio::test]
+async fn test_inspect_consensus_flight_recorder() {
+    // Create a validator config
+    let mut node_config = NodeConfig::get_default_validator_config();
+
+    // Disable the consensus flight recorder endpoint and ping it
+    node_config
+        .inspection_service
+        .expose_consensus_flight_recorder = false;
+    let mut response = send_get_request_to_path(&node_config, CONSENSUS_FLIGHT_RECORDER_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, CONSENSUS_FLIGHT_RECORDER_DISABLED_MESSAGE);
+
+    // Enable the consensus flight recorder endpoint and ping it
+    node_config
+        .inspection_service
+        .expose_consensus_flight_recorder = true;
+    let mut response = send_get_request_to_path(&node_config, CONSENSUS_FLIGHT_RECORDER_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("author"));
+    assert!(response_body_string.contains("events"));
+}
+
+#[tokio::test]
+async fn test_inspect_data_streaming_information() {
+    // Create a PFN config
+    let mut config = NodeConfig::get_default_pfn_config();
+
+    // Disable the peer information endpoint and ping the data streaming endpoint
+    config.inspection_service.expose_peer_information = false;
+    let mut response = send_get_request_to_path(&config, DATA_STREAMING_INFORMATION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, DATA_STREAMING_INFO_DISABLED_MESSAGE);
+
+    // Enable the peer information endpoint and ping the data streaming endpoint
+    config.inspection_service.expose_peer_information = true;
+    let mut response = send_get_request_to_path(&config, DATA_STREAMING_INFORMATION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("data_streams"));
+    assert!(response_body_string.contains("peers"));
+}
+
 #[tokio::test]
 async fn test_inspect_forge_metrics() {
     // Create a VFN config
@@ -86,10 +147,13 @@ async fn test_inspect_index() {
     // Verify that the response contains all the endpoints
     assert_eq!(response.status(), StatusCode::OK);
     assert!(response_body_string.contains(CONFIGURATION_PATH));
+    assert!(response_body_string.contains(CONSENSUS_FLIGHT_RECORDER_PATH));
+    assert!(response_body_string.contains(DATA_STREAMING_INFORMATION_PATH));
     assert!(response_body_string.contains(FORGE_METRICS_PATH));
     assert!(response_body_string.contains(JSON_METRICS_PATH));
     assert!(response_body_string.contains(METRICS_PATH));
     assert!(response_body_string.contains(PEER_INFORMATION_PATH));
+    assert!(response_body_string.contains(QUORUM_STORE_INFORMATION_PATH));
     assert!(response_body_string.contains(SYSTEM_INFORMATION_PATH));
 }
 
@@ -125,6 +189,37 @@ async fn test_inspect_metrics() {
     assert!(response_body_string.contains(INT_COUNTER_NAME));
 }
 
+#[tokio::test]
+async fn test_inspect_quorum_store_information() {
+    // Create a validator config
+    let mut node_config = NodeConfig::get_default_validator_config();
+
+    // Disable the quorum store information endpoint and ping it
+    node_config
+        .inspection_service
+        .expose_quorum_store_information = false;
+    let mut response = send_get_request_to_path(&node_config, QUORUM_STORE_INFORMATION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, QUORUM_STORE_INFO_DISABLED_MESSAGE);
+
+    // Enable the quorum store information endpoint and ping it
+    node_config
+        .inspection_service
+        .expose_quorum_store_information = true;
+    let mut response = send_get_request_to_path(&node_config, QUORUM_STORE_INFORMATION_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("outstanding_proofs"));
+    assert!(response_body_string.contains("back_pressure"));
+    assert!(response_body_string.contains("peer_fetch_failures"));
+}
+
 #[tokio::test]
 async fn test_inspect_system_information() {
     // Create a validator node config
@@ -276,6 +371,7 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        StreamingServiceDiagnostics::new(),
     )
     .await
     .unwrap()
@@ -47,6 +47,7 @@ pub enum ApiError {
     StateValueNotFound(Option<String>),
     VersionPruned(Option<String>),
     BlockPruned(Option<String>),
+    StateNotTracked(Option<String>),
     InvalidInput(Option<String>),
     InvalidTransactionUpdate(Option<String>),
     SequenceNumberTooOld(Option<String>),
@@ -96,6 +97,7 @@ impl ApiError {
             StateValueNotFound(None),
             VersionPruned(None),
             BlockPruned(None),
+            StateNotTracked(None),
             InvalidInput(None),
             InvalidTransactionUpdate(None),
             SequenceNumberTooOld(None),
@@ -142,6 +144,7 @@ impl ApiError {
             MempoolIsFull(_) => 32,
             CoinTypeFailedToBeFetched(_) => 33,
             StateValueNotFound(_) => 34,
+            StateNotTracked(_) => 35,
         }
     }
 
@@ -195,6 +198,7 @@ impl ApiError {
             ApiError::TableItemNotFound(_) => "Table item not found",
             ApiError::VersionPruned(_) => "Version pruned",
             ApiError::BlockPruned(_) => "Block pruned",
+            ApiError::StateNotTracked(_) => "State is not tracked by this node",
             ApiError::InvalidInput(_) => "Invalid input",
             ApiError::InvalidTransactionUpdate(_) => "Invalid transaction update.  Can only update gas unit price",
             ApiError::SequenceNumberTooOld(_) => "Sequence number too old.  Please create a new transaction with an updated sequence number",
@@ -225,6 +229,7 @@ impl ApiError {
             ApiError::BlockNotFound(inner) => inner,
             ApiError::VersionPruned(inner) => inner,
             ApiError::BlockPruned(inner) => inner,
+            ApiError::StateNotTracked(inner) => inner,
             ApiError::InvalidInput(inner) => inner,
             ApiError::InvalidTransactionUpdate(inner) => inner,
             ApiError::SequenceNumberTooOld(inner) => inner,
@@ -292,6 +297,9 @@ impl From<RestError> for ApiError {
                 },
                 AptosErrorCode::VersionPruned => ApiError::VersionPruned(Some(err.error.message)),
                 AptosErrorCode::BlockPruned => ApiError::BlockPruned(Some(err.error.message)),
+                AptosErrorCode::StateNotTracked => {
+                    ApiError::StateNotTracked(Some(err.error.message))
+                },
                 AptosErrorCode::InvalidInput => ApiError::InvalidInput(Some(err.error.message)),
                 AptosErrorCode::InvalidTransactionUpdate => {
                     ApiError::InvalidInput(Some(err.error.message))
@@ -3,8 +3,11 @@
 
 use crate::common::types::{CliError, CliTypedResult};
 use aptos_crypto::HashValue;
-use aptos_gas_profiling::FrameName;
-use aptos_move_debugger::aptos_debugger::AptosDebugger;
+use aptos_gas_profiling::{FrameName, GasProfileComparison, GasProfileSummary};
+use aptos_move_debugger::{
+    aptos_debugger::AptosDebugger,
+    step_debugger::{Breakpoint, StepDebugger},
+};
 use aptos_types::transaction::SignedTransaction;
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use aptos_vm_logging::log_schema::AdapterLogSchema;
@@ -99,6 +102,7 @@ pub fn profile_transaction_using_debugger(
     version: u64,
     transaction: SignedTransaction,
     hash: HashValue,
+    compare_to: Option<&Path>,
 ) -> CliTypedResult<(VMStatus, VMOutput)> {
     let (vm_status, vm_output, gas_log) = debugger
         .execute_transaction_at_version_with_gas_profiler(version, transaction)
@@ -131,5 +135,77 @@ pub fn profile_transaction_using_debugger(
 
     println!("Gas report saved to {}.", path.display());
 
+    // Compare against a previous profile, e.g., from before a package upgrade
+    if let Some(compare_to) = compare_to {
+        let before = GasProfileSummary::load(compare_to).map_err(|err| {
+            CliError::UnexpectedError(format!(
+                "failed to load the gas profile {}: {}",
+                compare_to.display(),
+                err
+            ))
+        })?;
+        let after = gas_log.to_summary();
+        let comparison_path = path.join("comparison");
+        GasProfileComparison::new(&before, &after).generate_html_report(
+            &comparison_path,
+            format!("Gas Report Comparison - {}", human_readable_name),
+        )?;
+
+        println!(
+            "Gas report comparison saved to {}.",
+            comparison_path.display()
+        );
+    }
+
+    Ok((vm_status, vm_output))
+}
+
+pub fn trace_transaction_using_debugger(
+    debugger: &AptosDebugger,
+    version: u64,
+    transaction: SignedTransaction,
+    hash: HashValue,
+) -> CliTypedResult<(VMStatus, VMOutput)> {
+    let (vm_status, vm_output, trace) = debugger
+        .execute_transaction_at_version_with_tracer(version, transaction)
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("failed to simulate txn with tracer: {}", err))
+        })?;
+
+    println!("{}", trace);
+
+    // Save the trace, so that it can be inspected by other tools
+    let dir = Path::new("traces");
+    std::fs::create_dir_all(dir).map_err(|err| CliError::IO(dir.display().to_string(), err))?;
+    let path = dir.join(format!("txn-{}.json", hash));
+    let json = serde_json::to_string_pretty(&trace)
+        .map_err(|err| CliError::UnexpectedError(format!("failed to serialize trace: {}", err)))?;
+    std::fs::write(&path, json).map_err(|err| CliError::IO(path.display().to_string(), err))?;
+
+    println!("Execution trace saved to {}.", path.display());
+
+    Ok((vm_status, vm_output))
+}
+
+pub fn debug_transaction_using_debugger(
+    debugger: &AptosDebugger,
+    version: u64,
+    transaction: SignedTransaction,
+    _hash: HashValue,
+) -> CliTypedResult<(VMStatus, VMOutput)> {
+    // Stop at the code of the payload, rather than in the prologue
+    let entry = Breakpoint::for_payload(transaction.payload());
+    let (vm_status, vm_output) = debugger
+        .execute_transaction_at_version_with_step_debugger(
+            version,
+            transaction,
+            StepDebugger::stdio(entry),
+        )
+        .map_err(|err| {
+            CliError::UnexpectedError(format!("failed to simulate txn with debugger: {}", err))
+        })?;
+
+    println!("Transaction finished with status {:?}", vm_status);
+
     Ok((vm_status, vm_output))
 }
@@ -1541,6 +1541,16 @@ pub struct TransactionOptions {
     /// flamegraphs that reflect the gas usage.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    /// A previous gas profile to compare the new one against, i.e., the directory of a previous
+    /// gas report or its `profile.json`. The comparison is saved next to the new report.
+    #[clap(long, requires = "profile_gas", value_parser)]
+    pub(crate) compare: Option<PathBuf>,
+
+    /// If this option is set, simulate the transaction locally and step through its execution
+    /// interactively, with breakpoints and inspection of locals and the operand stack.
+    #[clap(long)]
+    pub(crate) debug: bool,
 }
 
 impl TransactionOptions {
@@ -1886,13 +1896,30 @@ impl TransactionOptions {
         println!();
         println!("Simulating transaction locally using the gas profiler...");
 
-        self.simulate_using_debugger(
-            payload,
-            local_simulation::profile_transaction_using_debugger,
-        )
+        self.simulate_using_debugger(payload, |debugger, version, transaction, hash| {
+            local_simulation::profile_transaction_using_debugger(
+                debugger,
+                version,
+                transaction,
+                hash,
+                self.compare.as_deref(),
+            )
+        })
         .await
     }
 
+    /// Simulates the transaction locally with the interactive step debugger attached.
+    pub async fn debug_locally(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<TransactionSummary> {
+        println!();
+        println!("Debugging transaction locally (type 'help' for the available commands)...");
+
+        self.simulate_using_debugger(payload, local_simulation::debug_transaction_using_debugger)
+            .await
+    }
+
     pub async fn estimate_gas_price(&self) -> CliTypedResult<u64> {
         let client = self.rest_client()?;
         client
@@ -505,12 +505,19 @@ pub async fn profile_or_submit(
             "Cannot perform benchmarking and gas profiling at the same time.".to_string(),
         ));
     }
+    if txn_options_ref.debug && (txn_options_ref.profile_gas || txn_options_ref.benchmark) {
+        return Err(CliError::UnexpectedError(
+            "Cannot debug the transaction while benchmarking or profiling it.".to_string(),
+        ));
+    }
 
     // Profile gas if needed.
     if txn_options_ref.profile_gas {
         txn_options_ref.profile_gas(payload).await
     } else if txn_options_ref.benchmark {
         txn_options_ref.benchmark_locally(payload).await
+    } else if txn_options_ref.debug {
+        txn_options_ref.debug_locally(payload).await
     } else if txn_options_ref.local {
         txn_options_ref.simulate_locally(payload).await
     } else {
@@ -1593,6 +1593,22 @@ pub struct Replay {
     #[clap(long)]
     pub(crate) profile_gas: bool,
 
+    /// A previous gas profile to compare the new one against, i.e., the directory of a previous
+    /// gas report or its `profile.json`. The comparison is saved next to the new report.
+    #[clap(long, requires = "profile_gas", value_parser)]
+    pub(crate) compare: Option<PathBuf>,
+
+    /// If this option is set, trace the execution of the transaction, i.e., print its call tree
+    /// (with arguments, return values, resource accesses, events and gas used per call) and save
+    /// it as JSON.
+    #[clap(long)]
+    pub(crate) trace: bool,
+
+    /// If this option is set, step through the execution of the transaction interactively, with
+    /// breakpoints and inspection of locals and the operand stack.
+    #[clap(long)]
+    pub(crate) debug: bool,
+
     /// If present, skip the comparison against the expected transaction output.
     #[clap(long)]
     pub(crate) skip_comparison: bool,
@@ -1625,6 +1641,17 @@ impl CliCommand<TransactionSummary> for Replay {
                 "Cannot perform benchmarking and gas profiling at the same time.".to_string(),
             ));
         }
+        if self.trace && (self.profile_gas || self.benchmark) {
+            return Err(CliError::UnexpectedError(
+                "Cannot trace the transaction while benchmarking or profiling it.".to_string(),
+            ));
+        }
+        if self.debug && (self.trace || self.profile_gas || self.benchmark) {
+            return Err(CliError::UnexpectedError(
+                "Cannot debug the transaction while tracing, benchmarking or profiling it."
+                    .to_string(),
+            ));
+        }
 
         let rest_endpoint = match &self.network {
             Mainnet => "https://fullnode.mainnet.aptoslabs.com",
@@ -1663,6 +1690,23 @@ impl CliCommand<TransactionSummary> for Replay {
                 self.txn_id,
                 txn.clone(),
                 hash,
+                self.compare.as_deref(),
+            )?
+        } else if self.trace {
+            println!("Tracing transaction...");
+            local_simulation::trace_transaction_using_debugger(
+                &debugger,
+                self.txn_id,
+                txn.clone(),
+                hash,
+            )?
+        } else if self.debug {
+            println!("Debugging transaction (type 'help' for the available commands)...");
+            local_simulation::debug_transaction_using_debugger(
+                &debugger,
+                self.txn_id,
+                txn.clone(),
+                hash,
             )?
         } else if self.benchmark {
             println!("Benchmarking transaction...");
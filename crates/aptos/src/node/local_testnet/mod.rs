@@ -250,6 +250,7 @@ impl CliCommand<()> for RunLocalnet {
 
         // Build the node manager. We do this unconditionally.
         let node_manager = NodeManager::new(&self, bind_to, test_dir.clone())
+            .await
             .context("Failed to build node service manager")?;
         let node_health_checkers = node_manager.get_health_checkers();
 
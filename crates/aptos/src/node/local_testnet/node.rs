@@ -3,9 +3,10 @@
 
 use super::{health_checker::HealthChecker, traits::ServiceManager, RunLocalnet};
 use crate::node::local_testnet::utils::socket_addr_to_url;
-use anyhow::{anyhow, Context, Result};
-use aptos_config::config::{NodeConfig, DEFAULT_GRPC_STREAM_PORT};
+use anyhow::{anyhow, ensure, Context, Result};
+use aptos_config::config::{ForkConfig, NodeConfig, DEFAULT_GRPC_STREAM_PORT};
 use aptos_node::{load_node_config, start_test_environment_node};
+use aptos_rest_client::Client;
 use async_trait::async_trait;
 use clap::Parser;
 use maplit::hashset;
@@ -61,6 +62,21 @@ pub struct NodeArgs {
     #[clap(long, default_value_t = DEFAULT_GRPC_STREAM_PORT)]
     txn_stream_port: u16,
 
+    /// Fork the state of a remote network, given the URL of its REST API
+    /// (e.g., https://api.mainnet.aptoslabs.com/v1)
+    ///
+    /// The localnet runs on top of the state of the remote network: state that doesn't
+    /// exist locally is fetched from the remote network (and cached in the test dir).
+    /// The framework (i.e., the special addresses like 0x1) and the validator set still
+    /// come from the local genesis. A forked localnet keeps running on top of the same
+    /// fork when restarted; use --force-restart to fork again.
+    #[clap(long)]
+    pub fork_url: Option<Url>,
+
+    /// The version of the remote network to fork at. Defaults to the latest version.
+    #[clap(long, requires = "fork_url")]
+    pub fork_version: Option<u64>,
+
     /// If set we won't run the node at all.
     //
     // Note: I decided that since running multiple partial localnets is a rare
@@ -90,8 +106,56 @@ pub struct NodeManager {
     no_node: bool,
 }
 
+impl NodeArgs {
+    /// Returns the fork config of the node, given the fork config of the existing localnet
+    /// (if any). A localnet can only be forked when it's created.
+    async fn get_fork_config(
+        &self,
+        existing_localnet: bool,
+        existing_fork: Option<ForkConfig>,
+    ) -> Result<Option<ForkConfig>> {
+        let fork_url = match &self.fork_url {
+            Some(fork_url) => fork_url,
+            None => return Ok(existing_fork),
+        };
+
+        if let Some(existing_fork) = existing_fork {
+            ensure!(
+                existing_fork.url == *fork_url
+                    && self
+                        .fork_version
+                        .map_or(true, |version| version == existing_fork.version),
+                "The localnet was forked from {} at version {}. Use --force-restart to fork again.",
+                existing_fork.url,
+                existing_fork.version
+            );
+            return Ok(Some(existing_fork));
+        }
+        ensure!(
+            !existing_localnet,
+            "The localnet already exists, so it can't be forked. Use --force-restart to fork."
+        );
+
+        let version = match self.fork_version {
+            Some(version) => version,
+            None => {
+                Client::new(fork_url.clone())
+                    .get_ledger_information()
+                    .await
+                    .context("Failed to get the latest version of the network to fork")?
+                    .into_inner()
+                    .version
+            },
+        };
+        Ok(Some(ForkConfig {
+            url: fork_url.clone(),
+            version,
+        }))
+    }
+}
+
 impl NodeManager {
-    pub fn new(args: &RunLocalnet, bind_to: Ipv4Addr, test_dir: PathBuf) -> Result<Self> {
+    pub async fn new(args: &RunLocalnet, bind_to: Ipv4Addr, test_dir: PathBuf) -> Result<Self> {
         let rng = args
             .node_args
             .seed
@@ -101,6 +165,8 @@ impl NodeManager {
         // If there is a config on disk, this function will use that. If not, it will
         // create a new one, taking the config_path and test_config_override arguments
         // into account.
+        let node_config_path = test_dir.join("0").join("node.yaml");
+        let existing_localnet = node_config_path.exists();
         let mut node_config = load_node_config(
             &args.node_args.config_path,
             &args.node_args.test_config_override,
@@ -113,6 +179,25 @@ impl NodeManager {
         )
         .context("Failed to load / create config for node")?;
 
+        // In fork mode, the node runs on top of the state of a remote network. The fork is
+        // persisted in the config on disk, so that restarts keep running on the same fork.
+        let fork = args
+            .node_args
+            .get_fork_config(existing_localnet, node_config.storage.fork.clone())
+            .await?;
+        if fork != node_config.storage.fork {
+            let mut persisted_config = NodeConfig::load_from_path(&node_config_path)
+                .map_err(|error| anyhow!("Unable to load config: {:?}", error))?;
+            persisted_config.storage.fork = fork.clone();
+            persisted_config
+                .save_to_path(&node_config_path)
+                .context("Failed to save the fork config for the node")?;
+            node_config.storage.fork = fork;
+        }
+        if let Some(fork) = &node_config.storage.fork {
+            eprintln!("Forking {} at version {}", fork.url, fork.version);
+        }
+
         eprintln!();
 
         // Enable the grpc stream on the node if we will run a txn stream service.
@@ -0,0 +1,242 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::verified_block_feed::{VerifiedBlockFeed, VerifiedBlockUpdate};
+use aptos_logger::{info, warn};
+use aptos_protos::consensus::v1::{
+    consensus_feed_server::ConsensusFeed, subscribe_verified_blocks_response, CommitDecision,
+    OrderedBlocks, SubscribeVerifiedBlocksRequest, SubscribeVerifiedBlocksResponse, VerifiedBlock,
+};
+use futures::Stream;
+use std::pin::Pin;
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+
+pub struct ConsensusFeedService {
+    pub chain_id: u8,
+    pub verified_block_feed: VerifiedBlockFeed,
+}
+
+type ConsensusFeedResponseStream =
+    Pin<Box<dyn Stream<Item = Result<SubscribeVerifiedBlocksResponse, Status>> + Send>>;
+
+const RESPONSE_CHANNEL_SIZE: usize = 35;
+const SERVICE_TYPE: &str = "consensus_feed";
+
+#[tonic::async_trait]
+impl ConsensusFeed for ConsensusFeedService {
+    type SubscribeVerifiedBlocksStream = ConsensusFeedResponseStream;
+
+    /// Subscribes the client to the verified block feed. Every ordered block is streamed
+    /// as soon as it is ordered (and its payload is verified), and is followed later by the
+    /// commit decision that commits it. If the client falls too far behind, the stream is
+    /// terminated with a data loss error (and the client should resubscribe).
+    async fn subscribe_verified_blocks(
+        &self,
+        _req: Request<SubscribeVerifiedBlocksRequest>,
+    ) -> Result<Response<Self::SubscribeVerifiedBlocksStream>, Status> {
+        let chain_id = self.chain_id;
+        let mut feed_receiver = self.verified_block_feed.subscribe();
+
+        // Creates a channel to send the stream to the client
+        let (tx, rx) = mpsc::channel(RESPONSE_CHANNEL_SIZE);
+
+        tokio::spawn(async move {
+            info!(
+                chain_id = chain_id,
+                service_type = SERVICE_TYPE,
+                "[Consensus Feed] New subscription"
+            );
+            loop {
+                let response = match feed_receiver.recv().await {
+                    Ok(update) => convert_verified_block_update(&update, chain_id)
+                        .map_err(|error| Status::internal(error.to_string())),
+                    Err(RecvError::Lagged(num_missed_updates)) => {
+                        warn!(
+                            chain_id = chain_id,
+                            service_type = SERVICE_TYPE,
+                            "[Consensus Feed] Subscriber lagged behind by {} updates",
+                            num_missed_updates
+                        );
+                        Err(Status::data_loss(format!(
+                            "The subscriber lagged behind and missed {} updates!",
+                            num_missed_updates
+                        )))
+                    },
+                    Err(RecvError::Closed) => break, // The feed has been dropped
+                };
+
+                // Send the response, and terminate the stream on any error
+                let is_error = response.is_err();
+                if tx.send(response).await.is_err() {
+                    info!(
+                        chain_id = chain_id,
+                        service_type = SERVICE_TYPE,
+                        "[Consensus Feed] Client disconnected."
+                    );
+                    break;
+                }
+                if is_error {
+                    break;
+                }
+            }
+        });
+
+        let output_stream = ReceiverStream::new(rx);
+        Ok(Response::new(
+            Box::pin(output_stream) as Self::SubscribeVerifiedBlocksStream
+        ))
+    }
+}
+
+/// Converts the verified block update into a (BCS encoded) response for the given chain
+pub fn convert_verified_block_update(
+    update: &VerifiedBlockUpdate,
+    chain_id: u8,
+) -> anyhow::Result<SubscribeVerifiedBlocksResponse> {
+    let response = match update {
+        VerifiedBlockUpdate::OrderedBlocks {
+            blocks,
+            ordered_proof,
+        } => {
+            let mut verified_blocks = vec![];
+            for block in blocks {
+                let block_info = &block.block_info;
+                let transactions = block
+                    .transactions
+                    .iter()
+                    .map(bcs::to_bytes)
+                    .collect::<Result<Vec<_>, _>>()?;
+                verified_blocks.push(VerifiedBlock {
+                    epoch: block_info.epoch(),
+                    round: block_info.round(),
+                    id: block_info.id().to_vec(),
+                    timestamp_usecs: block_info.timestamp_usecs(),
+                    transactions,
+                });
+            }
+            subscribe_verified_blocks_response::Response::OrderedBlocks(OrderedBlocks {
+                blocks: verified_blocks,
+                ordered_proof: bcs::to_bytes(ordered_proof)?,
+            })
+        },
+        VerifiedBlockUpdate::CommitDecision { commit_proof } => {
+            let block_info = commit_proof.commit_info();
+            subscribe_verified_blocks_response::Response::CommitDecision(CommitDecision {
+                epoch: block_info.epoch(),
+                round: block_info.round(),
+                block_id: block_info.id().to_vec(),
+                version: block_info.version(),
+                commit_proof: bcs::to_bytes(commit_proof)?,
+            })
+        },
+    };
+
+    Ok(SubscribeVerifiedBlocksResponse {
+        response: Some(response),
+        chain_id: chain_id as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_consensus_types::verified_block_feed;
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature,
+        block_info::BlockInfo,
+        ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    };
+    use futures::StreamExt;
+
+    fn create_ledger_info(block_info: BlockInfo) -> LedgerInfoWithSignatures {
+        LedgerInfoWithSignatures::new(
+            LedgerInfo::new(block_info, HashValue::zero()),
+            AggregateSignature::empty(),
+        )
+    }
+
+    #[test]
+    fn test_convert_verified_block_update() {
+        // Convert an ordered blocks update
+        let block_info =
+            BlockInfo::new(2, 10, HashValue::random(), HashValue::zero(), 0, 100, None);
+        let ordered_proof = create_ledger_info(block_info.clone());
+        let update = VerifiedBlockUpdate::OrderedBlocks {
+            blocks: vec![verified_block_feed::VerifiedBlock::new(
+                block_info.clone(),
+                vec![],
+            )],
+            ordered_proof: ordered_proof.clone(),
+        };
+        let response = convert_verified_block_update(&update, 4).unwrap();
+
+        // Verify the converted ordered blocks
+        assert_eq!(response.chain_id, 4);
+        match response.response.unwrap() {
+            subscribe_verified_blocks_response::Response::OrderedBlocks(ordered_blocks) => {
+                assert_eq!(ordered_blocks.blocks, vec![VerifiedBlock {
+                    epoch: 2,
+                    round: 10,
+                    id: block_info.id().to_vec(),
+                    timestamp_usecs: 100,
+                    transactions: vec![],
+                }]);
+                let decoded_proof: LedgerInfoWithSignatures =
+                    bcs::from_bytes(&ordered_blocks.ordered_proof).unwrap();
+                assert_eq!(decoded_proof, ordered_proof);
+            },
+            response => panic!("Unexpected response: {:?}", response),
+        }
+
+        // Convert a commit decision update
+        let commit_proof = create_ledger_info(block_info.clone());
+        let update = VerifiedBlockUpdate::CommitDecision {
+            commit_proof: commit_proof.clone(),
+        };
+        let response = convert_verified_block_update(&update, 4).unwrap();
+
+        // Verify the converted commit decision
+        match response.response.unwrap() {
+            subscribe_verified_blocks_response::Response::CommitDecision(commit_decision) => {
+                assert_eq!(commit_decision.round, 10);
+                assert_eq!(commit_decision.block_id, block_info.id().to_vec());
+                let decoded_proof: LedgerInfoWithSignatures =
+                    bcs::from_bytes(&commit_decision.commit_proof).unwrap();
+                assert_eq!(decoded_proof, commit_proof);
+            },
+            response => panic!("Unexpected response: {:?}", response),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_verified_blocks() {
+        // Create the service and subscribe to the feed
+        let verified_block_feed = VerifiedBlockFeed::new(10);
+        let service = ConsensusFeedService {
+            chain_id: 4,
+            verified_block_feed: verified_block_feed.clone(),
+        };
+        let mut stream = service
+            .subscribe_verified_blocks(Request::new(SubscribeVerifiedBlocksRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Publish a commit decision and verify that it is streamed to the client
+        let block_info = BlockInfo::new(1, 5, HashValue::random(), HashValue::zero(), 0, 0, None);
+        verified_block_feed.publish(VerifiedBlockUpdate::CommitDecision {
+            commit_proof: create_ledger_info(block_info),
+        });
+        let response = stream.next().await.unwrap().unwrap();
+        match response.response.unwrap() {
+            subscribe_verified_blocks_response::Response::CommitDecision(commit_decision) => {
+                assert_eq!(commit_decision.epoch, 1);
+                assert_eq!(commit_decision.round, 5);
+            },
+            response => panic!("Unexpected response: {:?}", response),
+        }
+    }
+}
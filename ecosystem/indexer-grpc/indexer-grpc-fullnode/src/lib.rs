@@ -4,6 +4,7 @@
 use aptos_api::context::Context;
 use std::sync::Arc;
 
+pub mod consensus_feed_service;
 pub mod convert;
 pub mod counters;
 pub mod fullnode_data_service;
@@ -2,14 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    fullnode_data_service::FullnodeDataService, localnet_data_service::LocalnetDataService,
-    ServiceContext,
+    consensus_feed_service::ConsensusFeedService, fullnode_data_service::FullnodeDataService,
+    localnet_data_service::LocalnetDataService, ServiceContext,
 };
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
+use aptos_consensus_types::verified_block_feed::VerifiedBlockFeed;
 use aptos_logger::info;
 use aptos_mempool::MempoolClientSender;
 use aptos_protos::{
+    consensus::v1::{
+        consensus_feed_server::ConsensusFeedServer,
+        FILE_DESCRIPTOR_SET as CONSENSUS_V1_FILE_DESCRIPTOR_SET,
+    },
     indexer::v1::{
         raw_data_server::RawDataServer, FILE_DESCRIPTOR_SET as INDEXER_V1_FILE_DESCRIPTOR_SET,
     },
@@ -35,6 +40,7 @@ pub fn bootstrap(
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     indexer_reader: Option<Arc<dyn IndexerReader>>,
+    verified_block_feed: Option<VerifiedBlockFeed>,
 ) -> Option<Runtime> {
     if !config.indexer_grpc.enabled {
         return None;
@@ -69,6 +75,11 @@ pub fn bootstrap(
             service_context: service_context.clone(),
         };
         let localnet_data_server = LocalnetDataService { service_context };
+        let consensus_feed_server =
+            verified_block_feed.map(|verified_block_feed| ConsensusFeedService {
+                chain_id: chain_id.id(),
+                verified_block_feed,
+            });
 
         let reflection_service = tonic_reflection::server::Builder::configure()
             // Note: It is critical that the file descriptor set is registered for every
@@ -79,6 +90,7 @@ pub fn bootstrap(
             .register_encoded_file_descriptor_set(INDEXER_V1_FILE_DESCRIPTOR_SET)
             .register_encoded_file_descriptor_set(TRANSACTION_V1_TESTING_FILE_DESCRIPTOR_SET)
             .register_encoded_file_descriptor_set(UTIL_TIMESTAMP_FILE_DESCRIPTOR_SET)
+            .register_encoded_file_descriptor_set(CONSENSUS_V1_FILE_DESCRIPTOR_SET)
             .build()
             .expect("Failed to build reflection service");
 
@@ -87,7 +99,13 @@ pub fn bootstrap(
         let tonic_server = Server::builder()
             .http2_keepalive_interval(Some(std::time::Duration::from_secs(60)))
             .http2_keepalive_timeout(Some(std::time::Duration::from_secs(5)))
-            .add_service(reflection_service_clone);
+            .add_service(reflection_service_clone)
+            .add_optional_service(consensus_feed_server.map(|consensus_feed_server| {
+                ConsensusFeedServer::new(consensus_feed_server)
+                    .send_compressed(CompressionEncoding::Zstd)
+                    .accept_compressed(CompressionEncoding::Zstd)
+                    .accept_compressed(CompressionEncoding::Gzip)
+            }));
 
         let router = match use_data_service_interface {
             false => {
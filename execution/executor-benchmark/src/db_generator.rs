@@ -83,6 +83,7 @@ fn bootstrap_with_genesis(
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
             None,
+            None,
         )
         .expect("DB should open."),
     );
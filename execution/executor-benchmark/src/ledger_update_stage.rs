@@ -19,6 +19,7 @@ pub struct LedgerUpdateStage<V> {
     executor: Arc<BlockExecutor<V>>,
     commit_processing: CommitProcessing,
     version: Version,
+    epoch: u64,
 }
 
 impl<V> LedgerUpdateStage<V>
@@ -29,10 +30,12 @@ where
         executor: Arc<BlockExecutor<V>>,
         commit_processing: CommitProcessing,
         version: Version,
+        epoch: u64,
     ) -> Self {
         Self {
             executor,
             version,
+            epoch,
             commit_processing,
         }
     }
@@ -71,6 +74,7 @@ where
             },
             CommitProcessing::ExecuteInline => {
                 let ledger_info_with_sigs = super::transaction_committer::gen_li_with_sigs(
+                    self.epoch,
                     block_id,
                     output.root_hash(),
                     self.version,
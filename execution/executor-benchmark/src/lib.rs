@@ -11,6 +11,7 @@ mod ledger_update_stage;
 mod metrics;
 pub mod native_executor;
 pub mod pipeline;
+pub mod replay;
 pub mod transaction_committer;
 pub mod transaction_executor;
 pub mod transaction_generator;
@@ -68,6 +69,7 @@ where
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
             None,
+            None,
         )
         .expect("DB should open."),
     );
@@ -786,6 +788,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_replay_blocks() {
+        aptos_logger::Logger::new().init();
+
+        let storage_dir = TempPath::new();
+        let source_dir = TempPath::new();
+        let checkpoint_dir = TempPath::new();
+
+        crate::db_generator::create_db_with_accounts::<AptosVM>(
+            100,             /* num_accounts */
+            100_000_000_000, /* init_account_balance */
+            5,               /* block_size */
+            storage_dir.as_ref(),
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            true,
+            false,
+            PipelineConfig::default(),
+            Features::default(),
+        );
+
+        // Commits the blocks to replay on top of a checkpoint of the generated DB.
+        super::run_benchmark::<AptosVM>(
+            10,    /* block_size */
+            10,    /* num_blocks */
+            None,  /* transaction_mix */
+            2,     /* transactions per sender */
+            0,     /* connected txn groups in a block */
+            false, /* shuffle the connected txns in a block */
+            None,  /* maybe_hotspot_probability */
+            25,    /* num_main_signer_accounts */
+            30,    /* num_dst_pool_accounts */
+            storage_dir.as_ref(),
+            source_dir.as_ref(),
+            true,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            false,
+            PipelineConfig::default(),
+            Features::default(),
+        );
+
+        crate::replay::replay_blocks::<AptosVM>(
+            source_dir.as_ref(),
+            10, /* num_blocks */
+            storage_dir.as_ref(),
+            checkpoint_dir.as_ref(),
+            true, /* verify_outputs */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            false,
+            PipelineConfig::default(),
+        );
+    }
+
     #[test]
     fn test_native_benchmark() {
         // correct execution not yet implemented, so cannot be checked for validity
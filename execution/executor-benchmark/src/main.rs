@@ -24,6 +24,7 @@ use aptos_profiler::{ProfilerConfig, ProfilerHandler};
 use aptos_push_metrics::MetricsPusher;
 use aptos_transaction_generator_lib::{args::TransactionTypeArg, WorkflowProgress};
 use aptos_types::{
+    block_executor::config::AdaptiveConcurrencyConfig,
     on_chain_config::{FeatureFlag, Features},
     vm::configs::set_paranoid_type_checks,
 };
@@ -266,6 +267,16 @@ struct Opt {
 
     #[clap(long)]
     skip_paranoid_checks: bool,
+
+    /// If set, the conflicts observed during parallel execution of each block are written to
+    /// this directory, as JSON and Graphviz files.
+    #[clap(long)]
+    conflict_analytics_dir: Option<PathBuf>,
+
+    /// If set, the number of execution threads is adapted to the contention of the workload,
+    /// between this minimum and the (per shard) execution threads.
+    #[clap(long)]
+    adaptive_concurrency_min_level: Option<usize>,
 }
 
 impl Opt {
@@ -374,6 +385,28 @@ enum Command {
         #[clap(long, default_value_t = 1000000)]
         init_account_balance: u64,
     },
+    /// Replays committed blocks (e.g., from mainnet) on top of their pre-state, instead of
+    /// running a generated workload.
+    ReplayBlocks {
+        /// DB to read the committed blocks from
+        #[clap(long, value_parser)]
+        source_db_dir: PathBuf,
+
+        /// number of blocks to replay, starting right after the end of data_dir
+        #[clap(long, default_value_t = 1000)]
+        blocks: usize,
+
+        /// DB with the pre-state of the first block to replay
+        #[clap(long, value_parser)]
+        data_dir: PathBuf,
+
+        #[clap(long, value_parser)]
+        checkpoint_dir: PathBuf,
+
+        /// Don't compare the outputs of the replayed transactions with the source DB
+        #[clap(long)]
+        skip_output_verification: bool,
+    },
 }
 
 fn get_init_features(
@@ -499,6 +532,24 @@ where
                 Features::default(),
             );
         },
+        Command::ReplayBlocks {
+            source_db_dir,
+            blocks,
+            data_dir,
+            checkpoint_dir,
+            skip_output_verification,
+        } => {
+            aptos_executor_benchmark::replay::replay_blocks::<E>(
+                source_db_dir,
+                blocks,
+                data_dir,
+                checkpoint_dir,
+                !skip_output_verification,
+                opt.pruner_opt.pruner_config(),
+                opt.enable_storage_sharding,
+                opt.pipeline_opt.pipeline_config(),
+            );
+        },
     }
 }
 
@@ -567,6 +618,15 @@ fn main() {
     AptosVM::set_concurrency_level_once(execution_threads_per_shard);
     NativeExecutor::set_concurrency_level_once(execution_threads_per_shard);
     AptosVM::set_processed_transactions_detailed_counters();
+    if let Some(dir) = &opt.conflict_analytics_dir {
+        AptosVM::set_conflict_analytics_dir_once(dir.clone());
+    }
+    if let Some(min_concurrency_level) = opt.adaptive_concurrency_min_level {
+        AptosVM::set_adaptive_concurrency_once(AdaptiveConcurrencyConfig {
+            min_concurrency_level,
+            ..AdaptiveConcurrencyConfig::default()
+        });
+    }
 
     let config = ProfilerConfig::new_with_defaults();
     let handler = ProfilerHandler::new(config);
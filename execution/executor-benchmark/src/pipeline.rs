@@ -62,6 +62,14 @@ where
         num_blocks: Option<usize>,
     ) -> (Self, mpsc::SyncSender<Vec<Transaction>>) {
         let parent_block_id = executor.committed_block_id();
+        // The ledger infos generated on commit need to be in the epoch of the DB.
+        let epoch = executor
+            .db
+            .reader
+            .get_latest_ledger_info()
+            .unwrap()
+            .ledger_info()
+            .next_block_epoch();
         let executor_1 = Arc::new(executor);
         let executor_2 = executor_1.clone();
         let executor_3 = executor_1.clone();
@@ -128,7 +136,7 @@ where
             CommitProcessing::SendToQueue(commit_sender)
         };
         let mut ledger_update_stage =
-            LedgerUpdateStage::new(executor_2, commit_processing, version);
+            LedgerUpdateStage::new(executor_2, commit_processing, version, epoch);
 
         let (executable_block_sender, executable_block_receiver) =
             mpsc::sync_channel::<ExecuteBlockMessage>(3);
@@ -228,7 +236,7 @@ where
                     start_commit_rx.map(|rx| rx.recv());
                     info!("Starting commit thread");
                     let mut committer =
-                        TransactionCommitter::new(executor_3, version, commit_receiver);
+                        TransactionCommitter::new(executor_3, version, epoch, commit_receiver);
                     committer.run();
                 })
                 .expect("Failed to spawn transaction committer thread.");
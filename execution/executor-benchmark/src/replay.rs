@@ -0,0 +1,269 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Replays a range of committed blocks (e.g., from mainnet) through the pipeline, to measure the
+//! performance of execution, ledger update and commit on real traffic.
+//!
+//! The blocks are read from a source DB, and replayed on top of a pre-state DB, which needs to
+//! end right before the first block to replay, with a ledger info at its latest version (e.g., a
+//! copy of the DB of a node, or a DB restored from a backup to the end of an epoch). A backup can
+//! be restored into DBs with `aptos-db-tool restore bootstrap-db`.
+
+use crate::{
+    create_checkpoint, init_db_and_executor, log_total_supply, pipeline::Pipeline,
+    OverallMeasuring, PipelineConfig,
+};
+use anyhow::{ensure, Result};
+use aptos_config::config::{
+    NodeConfig, PrunerConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_executor::block_executor::TransactionBlockExecutor;
+use aptos_logger::{info, warn};
+use aptos_storage_interface::DbReader;
+use aptos_types::transaction::{Transaction, TransactionInfo, Version};
+use std::{path::Path, sync::Arc, time::Instant};
+
+const BATCH_SIZE: u64 = 1000;
+
+/// Replays up to num_blocks blocks committed in the DB at source_db_dir, on top of the DB at
+/// data_dir (whose checkpoint is created at checkpoint_dir, and is the one modified). The replay
+/// stops before the block that ends the epoch, as the next epoch would need to be replayed with
+/// the ledger infos of the source.
+///
+/// If verify_outputs is set, the transaction infos of the replayed transactions are compared to
+/// the ones in the source DB, which ensures that the replay executes the same workload.
+#[allow(clippy::too_many_arguments)]
+pub fn replay_blocks<V>(
+    source_db_dir: impl AsRef<Path>,
+    num_blocks: usize,
+    data_dir: impl AsRef<Path>,
+    checkpoint_dir: impl AsRef<Path>,
+    verify_outputs: bool,
+    pruner_config: PrunerConfig,
+    enable_storage_sharding: bool,
+    pipeline_config: PipelineConfig,
+) where
+    V: TransactionBlockExecutor + 'static,
+{
+    let source_db = open_source_db(source_db_dir.as_ref(), enable_storage_sharding);
+
+    create_checkpoint(
+        data_dir.as_ref(),
+        checkpoint_dir.as_ref(),
+        enable_storage_sharding,
+    );
+    let mut config = NodeConfig::default();
+    config.storage.dir = checkpoint_dir.as_ref().to_path_buf();
+    config.storage.storage_pruner_config = pruner_config;
+    config.storage.rocksdb_configs.enable_storage_sharding = enable_storage_sharding;
+    let (db, executor) = init_db_and_executor::<V>(&config);
+
+    let version = db.reader.get_synced_version().unwrap();
+    assert_eq!(
+        db.reader.get_latest_ledger_info_version().unwrap(),
+        version,
+        "The pre-state DB needs a ledger info at its latest version."
+    );
+    assert_eq!(
+        source_db.get_accumulator_root_hash(version).unwrap(),
+        db.reader.get_accumulator_root_hash(version).unwrap(),
+        "The pre-state DB doesn't have the same history as the source DB at version {}.",
+        version
+    );
+
+    let (blocks, end_version) = load_blocks(source_db.as_ref(), version + 1, num_blocks).unwrap();
+    let num_txns = blocks.iter().map(|block| block.len() as u64).sum::<u64>();
+    info!(
+        "Loaded {} blocks with {} transactions, from version {} to {}",
+        blocks.len(),
+        num_txns,
+        version + 1,
+        end_version - 1
+    );
+    let num_blocks = blocks.len();
+
+    let (pipeline, block_sender) =
+        Pipeline::new(executor, version, &pipeline_config, Some(num_blocks));
+
+    let mut overall_measuring = OverallMeasuring::start();
+    for block in blocks {
+        block_sender.send(block).unwrap();
+    }
+    if pipeline_config.delay_execution_start {
+        overall_measuring.start_time = Instant::now();
+    }
+    pipeline.start_execution();
+    drop(block_sender);
+    pipeline.join();
+
+    if !pipeline_config.skip_commit {
+        // Block ending transactions are not included in the transactions replayed, but appended
+        // by the executor.
+        assert_eq!(
+            db.reader.get_synced_version().unwrap() - version,
+            num_txns + num_blocks as u64
+        );
+        overall_measuring.print_end("Overall", num_txns);
+
+        if verify_outputs {
+            let num_mismatches = verify_transaction_infos(
+                source_db.as_ref(),
+                version + 1,
+                end_version,
+                db.reader.as_ref(),
+            )
+            .unwrap();
+            assert_eq!(
+                num_mismatches, 0,
+                "The outputs of {} replayed transactions differ from the source DB.",
+                num_mismatches
+            );
+        }
+        log_total_supply(&db.reader);
+    }
+
+    // Assert there were no error log lines in the run.
+    assert_eq!(0, aptos_logger::ERROR_LOG_COUNT.get());
+}
+
+fn open_source_db(db_dir: &Path, enable_storage_sharding: bool) -> Arc<dyn DbReader> {
+    let mut rocksdb_configs = RocksdbConfigs::default();
+    rocksdb_configs.enable_storage_sharding = enable_storage_sharding;
+    Arc::new(
+        AptosDB::open(
+            StorageDirPaths::from_path(db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            rocksdb_configs,
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+            None,
+        )
+        .expect("Source DB should open."),
+    )
+}
+
+/// Reads up to num_blocks blocks from the source DB, starting at first_version, which needs to be
+/// the first version of a block. Returns the transactions of the blocks without their block
+/// ending transactions (which the executor appends), and the version after the last block read.
+fn load_blocks(
+    source_db: &dyn DbReader,
+    first_version: Version,
+    num_blocks: usize,
+) -> Result<(Vec<Vec<Transaction>>, Version)> {
+    let ledger_version = source_db.get_latest_ledger_info_version()?;
+    ensure!(
+        first_version <= ledger_version,
+        "The source DB ends at version {}, before the first version to replay {}.",
+        ledger_version,
+        first_version
+    );
+
+    let mut blocks = vec![];
+    let mut current_block = vec![];
+    let mut version = first_version;
+    let mut end_version = first_version;
+    while blocks.len() < num_blocks && version <= ledger_version {
+        let batch = source_db.get_transactions(version, BATCH_SIZE, ledger_version, true)?;
+        let events = batch.events.unwrap_or_default();
+        ensure!(batch.transactions.len() == events.len());
+        for (txn, txn_events) in batch.transactions.into_iter().zip(events) {
+            version += 1;
+            if txn_events.iter().any(|event| event.is_new_epoch_event()) {
+                warn!(
+                    "Stopping the replay before the block ending the epoch at version {}.",
+                    version - 1
+                );
+                return Ok((blocks, end_version));
+            }
+            if txn.is_non_reconfig_block_ending() {
+                // Empty blocks are skipped, as the pipeline treats them as the end of a stage.
+                if !current_block.is_empty() {
+                    blocks.push(std::mem::take(&mut current_block));
+                }
+                end_version = version;
+                if blocks.len() == num_blocks {
+                    break;
+                }
+            } else {
+                current_block.push(txn);
+            }
+        }
+    }
+    if blocks.len() < num_blocks {
+        warn!(
+            "The source DB only has {} complete blocks to replay.",
+            blocks.len()
+        );
+    }
+    Ok((blocks, end_version))
+}
+
+/// Returns the non block ending transaction infos in [begin_version, end_version) of the source
+/// DB, or starting at begin_version in the replayed DB.
+fn get_transaction_infos(
+    db: &dyn DbReader,
+    begin_version: Version,
+    end_version: Version,
+) -> Result<Vec<TransactionInfo>> {
+    let mut txn_infos = vec![];
+    let mut version = begin_version;
+    while version < end_version {
+        let limit = std::cmp::min(BATCH_SIZE, end_version - version);
+        let txns = db.get_transaction_iterator(version, limit)?;
+        let infos = db.get_transaction_info_iterator(version, limit)?;
+        for (txn, info) in txns.zip(infos) {
+            if !txn?.is_non_reconfig_block_ending() {
+                txn_infos.push(info?);
+            }
+        }
+        version += limit;
+    }
+    Ok(txn_infos)
+}
+
+/// Compares the transaction infos of the replayed transactions with the ones of the source DB,
+/// and returns the number of transactions whose outputs differ.
+fn verify_transaction_infos(
+    source_db: &dyn DbReader,
+    begin_version: Version,
+    end_version: Version,
+    replayed_db: &dyn DbReader,
+) -> Result<usize> {
+    let expected = get_transaction_infos(source_db, begin_version, end_version)?;
+    let replayed = get_transaction_infos(
+        replayed_db,
+        begin_version,
+        replayed_db.get_synced_version()? + 1,
+    )?;
+    ensure!(
+        expected.len() == replayed.len(),
+        "Replayed {} transactions, but the source DB has {}.",
+        replayed.len(),
+        expected.len()
+    );
+
+    let mut num_mismatches = 0;
+    for (idx, (expected, replayed)) in expected.iter().zip(&replayed).enumerate() {
+        if expected != replayed {
+            num_mismatches += 1;
+            warn!(
+                "Transaction {} of the replay (hash {}) differs from the source DB: {:?}, expected {:?}",
+                idx,
+                expected.transaction_hash(),
+                replayed,
+                expected
+            );
+        }
+    }
+    info!(
+        "Verified the outputs of {} replayed transactions, {} differ from the source DB.",
+        expected.len(),
+        num_mismatches
+    );
+    Ok(num_mismatches)
+}
@@ -26,12 +26,13 @@ use std::{
 };
 
 pub(crate) fn gen_li_with_sigs(
+    epoch: u64,
     block_id: HashValue,
     root_hash: HashValue,
     version: Version,
 ) -> LedgerInfoWithSignatures {
     let block_info = BlockInfo::new(
-        1,        /* epoch */
+        epoch,    /* epoch */
         0,        /* round, doesn't matter */
         block_id, /* id, doesn't matter */
         root_hash, version, 0,    /* timestamp_usecs, doesn't matter */
@@ -50,6 +51,7 @@ pub(crate) fn gen_li_with_sigs(
 pub struct TransactionCommitter<V> {
     executor: Arc<BlockExecutor<V>>,
     version: Version,
+    epoch: u64,
     block_receiver: mpsc::Receiver<CommitBlockMessage>,
 }
 
@@ -60,10 +62,12 @@ where
     pub fn new(
         executor: Arc<BlockExecutor<V>>,
         version: Version,
+        epoch: u64,
         block_receiver: mpsc::Receiver<CommitBlockMessage>,
     ) -> Self {
         Self {
             version,
+            epoch,
             executor,
             block_receiver,
         }
@@ -89,7 +93,8 @@ where
 
             self.version += num_txns as u64;
             let commit_start = std::time::Instant::now();
-            let ledger_info_with_sigs = gen_li_with_sigs(block_id, root_hash, self.version);
+            let ledger_info_with_sigs =
+                gen_li_with_sigs(self.epoch, block_id, root_hash, self.version);
             self.executor
                 .commit_blocks_ext(vec![block_id], ledger_info_with_sigs, false)
                 .unwrap();
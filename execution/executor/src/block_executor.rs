@@ -232,7 +232,7 @@ where
                         StateViewId::BlockExecution { block_id },
                         Arc::clone(&self.db.reader),
                         parent_output.next_version(),
-                        parent_output.state().current.clone(),
+                        parent_output.state(),
                         Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
                     )?
                 };
@@ -161,7 +161,7 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
             StateViewId::ChunkExecution { first_version },
             self.db.reader.clone(),
             first_version,
-            latest_state.current.clone(),
+            latest_state,
             Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
         )?)
     }
@@ -12,9 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE,
-        MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, MessageRecorderConfig, NetworkConfig, Peer, PeerRole, PeerSet, RoleType,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONNECTION_DELAY_MS,
+        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
+        NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -26,6 +27,7 @@ use aptos_network::{
     connectivity_manager::{builder::ConnectivityManagerBuilder, ConnectivityRequest},
     constants::MAX_MESSAGE_SIZE,
     logging::NetworkSchema,
+    peer::recorder::MessageRecorder,
     peer_manager::{
         builder::{AuthenticationMode, PeerManagerBuilder},
         ConnectionRequestSender,
@@ -197,6 +199,10 @@ impl NetworkBuilder {
             ),
         );
 
+        if config.message_recorder.enabled {
+            network_builder.add_message_recorder(&config.message_recorder);
+        }
+
         network_builder.add_connection_monitoring(
             config.ping_interval_ms,
             config.ping_timeout_ms,
@@ -395,6 +401,30 @@ impl NetworkBuilder {
         }
     }
 
+    /// Add a recorder for all messages exchanged with peers on the network.
+    fn add_message_recorder(&mut self, config: &MessageRecorderConfig) -> &mut Self {
+        match MessageRecorder::new(self.network_context, self.time_service.clone(), config) {
+            Ok(message_recorder) => {
+                self.peer_manager_builder.set_message_recorder(message_recorder);
+                info!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Created message recorder (output directory: {:?})",
+                    self.network_context,
+                    config.output_dir
+                );
+            },
+            Err(error) => {
+                error!(
+                    NetworkSchema::new(&self.network_context),
+                    "{} Failed to create the message recorder! Error: {:?}",
+                    self.network_context,
+                    error
+                );
+            },
+        }
+        self
+    }
+
     /// Add a HealthChecker to the network.
     fn add_connection_monitoring(
         &mut self,
@@ -0,0 +1,392 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::read_recorded_messages;
+use anyhow::Result;
+use aptos_config::network_id::NetworkId;
+use aptos_consensus::{
+    consensus_observer::network_message::ConsensusObserverMessage, network_interface::ConsensusMsg,
+};
+use aptos_dkg_runtime::DKGMessage;
+use aptos_jwk_consensus::types::JWKConsensusMsg;
+use aptos_mempool::MempoolSyncMsg;
+use aptos_network::{
+    peer::recorder::{MessageDirection, RecordedMessage},
+    protocols::{
+        health_checker::HealthCheckerMsg,
+        wire::messaging::v1::{NetworkMessage, Priority, RequestId},
+    },
+    ProtocolId,
+};
+use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_storage_service_types::StorageServiceMessage;
+use aptos_types::PeerId;
+use clap::{Parser, ValueEnum};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per message (including the decoded payload)
+    Json,
+    /// One human readable summary line per message
+    Summary,
+}
+
+#[derive(Parser)]
+#[clap(about = "Decode the messages in network capture files")]
+pub struct Command {
+    /// Path to a capture file, or a directory containing capture files
+    #[clap(long, value_parser)]
+    input: PathBuf,
+
+    /// The output format of the decoded messages
+    #[clap(long, value_enum, default_value_t = OutputFormat::Json)]
+    format: OutputFormat,
+
+    /// Only decode the messages exchanged with the given peer
+    #[clap(long)]
+    peer_id: Option<PeerId>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        let mut message_decoder = MessageDecoder::default();
+        for recorded_message in read_recorded_messages(&self.input)? {
+            // Note: we decode all messages (even if they're filtered out) so
+            // that RPC responses can always be matched with their requests.
+            let decoded_message = message_decoder.decode(recorded_message);
+            if self
+                .peer_id
+                .map_or(false, |peer_id| peer_id != decoded_message.remote_peer_id)
+            {
+                continue;
+            }
+
+            match self.format {
+                OutputFormat::Json => println!("{}", serde_json::to_string(&decoded_message)?),
+                OutputFormat::Summary => println!("{}", decoded_message.summary()),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A human readable representation of a recorded message
+#[derive(Debug, Serialize)]
+pub struct DecodedMessage {
+    pub timestamp_usecs: u64,
+    pub network_id: NetworkId,
+    pub remote_peer_id: PeerId,
+    pub direction: MessageDirection,
+    pub message_type: &'static str,
+    pub protocol_id: Option<ProtocolId>,
+    pub request_id: Option<RequestId>,
+    pub priority: Option<Priority>,
+    pub payload_size_bytes: usize,
+    /// The payload decoded into its protocol message type (as JSON). Payloads
+    /// of unknown message types are hex encoded (and uncompressed).
+    pub payload: Value,
+}
+
+impl DecodedMessage {
+    /// Returns a single line summary of the message
+    pub fn summary(&self) -> String {
+        format!(
+            "{} {} {:?} {} {} {} {} {}",
+            self.timestamp_usecs,
+            self.network_id,
+            self.direction,
+            self.remote_peer_id,
+            self.message_type,
+            self.protocol_id
+                .map_or_else(|| "-".to_string(), |protocol_id| protocol_id.to_string()),
+            self.request_id
+                .map_or_else(|| "-".to_string(), |request_id| request_id.to_string()),
+            self.payload_size_bytes,
+        )
+    }
+}
+
+/// Decodes recorded messages. RPC responses don't carry a protocol id,
+/// so the decoder tracks the RPC requests it has seen to identify the
+/// protocol of each response.
+#[derive(Default)]
+pub struct MessageDecoder {
+    pending_rpc_protocols: HashMap<(PeerId, MessageDirection, RequestId), ProtocolId>,
+}
+
+impl MessageDecoder {
+    pub fn decode(&mut self, recorded_message: RecordedMessage) -> DecodedMessage {
+        let RecordedMessage {
+            timestamp_usecs,
+            network_id,
+            remote_peer_id,
+            direction,
+            message,
+        } = recorded_message;
+
+        let (message_type, protocol_id, request_id, priority, payload) = match &message {
+            NetworkMessage::DirectSendMsg(message) => (
+                "DirectSendMsg",
+                Some(message.protocol_id),
+                None,
+                Some(message.priority),
+                Some(&message.raw_msg),
+            ),
+            NetworkMessage::RpcRequest(request) => {
+                self.pending_rpc_protocols.insert(
+                    (remote_peer_id, direction, request.request_id),
+                    request.protocol_id,
+                );
+                (
+                    "RpcRequest",
+                    Some(request.protocol_id),
+                    Some(request.request_id),
+                    Some(request.priority),
+                    Some(&request.raw_request),
+                )
+            },
+            NetworkMessage::RpcResponse(response) => {
+                // The request was sent in the opposite direction to the response
+                let request_direction = match direction {
+                    MessageDirection::Inbound => MessageDirection::Outbound,
+                    MessageDirection::Outbound => MessageDirection::Inbound,
+                };
+                let protocol_id = self.pending_rpc_protocols.remove(&(
+                    remote_peer_id,
+                    request_direction,
+                    response.request_id,
+                ));
+                (
+                    "RpcResponse",
+                    protocol_id,
+                    Some(response.request_id),
+                    Some(response.priority),
+                    Some(&response.raw_response),
+                )
+            },
+            NetworkMessage::Error(_) => ("Error", None, None, None, None),
+        };
+
+        let (payload_size_bytes, payload) = match payload {
+            Some(payload) => (payload.len(), decode_payload(protocol_id, payload)),
+            None => {
+                let NetworkMessage::Error(error_code) = &message else {
+                    unreachable!("Only error messages are missing a payload!")
+                };
+                (0, json!(error_code))
+            },
+        };
+
+        DecodedMessage {
+            timestamp_usecs,
+            network_id,
+            remote_peer_id,
+            direction,
+            message_type,
+            protocol_id,
+            request_id,
+            priority,
+            payload_size_bytes,
+            payload,
+        }
+    }
+}
+
+/// Decodes the message payload into JSON, using the message type of the
+/// protocol (e.g., consensus, mempool and state sync messages). Payloads of
+/// unknown message types are returned as JSON (if the protocol uses JSON
+/// encoding), otherwise they are uncompressed and hex encoded.
+fn decode_payload(protocol_id: Option<ProtocolId>, payload: &[u8]) -> Value {
+    let Some(protocol_id) = protocol_id else {
+        return Value::String(hex::encode(payload));
+    };
+
+    let decoded_message = match protocol_id {
+        ProtocolId::ConsensusRpcBcs
+        | ProtocolId::ConsensusDirectSendBcs
+        | ProtocolId::ConsensusDirectSendJson
+        | ProtocolId::ConsensusRpcJson
+        | ProtocolId::ConsensusRpcCompressed
+        | ProtocolId::ConsensusDirectSendCompressed => {
+            decode_message::<ConsensusMsg>(protocol_id, payload)
+        },
+        ProtocolId::ConsensusObserver | ProtocolId::ConsensusObserverRpc => {
+            decode_message::<ConsensusObserverMessage>(protocol_id, payload)
+        },
+        ProtocolId::MempoolDirectSend => decode_message::<MempoolSyncMsg>(protocol_id, payload),
+        ProtocolId::StorageServiceRpc => {
+            decode_message::<StorageServiceMessage>(protocol_id, payload)
+        },
+        ProtocolId::HealthCheckerRpc => decode_message::<HealthCheckerMsg>(protocol_id, payload),
+        ProtocolId::PeerMonitoringServiceRpc => {
+            decode_message::<PeerMonitoringServiceMessage>(protocol_id, payload)
+        },
+        ProtocolId::DKGDirectSendCompressed
+        | ProtocolId::DKGDirectSendBcs
+        | ProtocolId::DKGDirectSendJson
+        | ProtocolId::DKGRpcCompressed
+        | ProtocolId::DKGRpcBcs
+        | ProtocolId::DKGRpcJson => decode_message::<DKGMessage>(protocol_id, payload),
+        ProtocolId::JWKConsensusDirectSendCompressed
+        | ProtocolId::JWKConsensusDirectSendBcs
+        | ProtocolId::JWKConsensusDirectSendJson
+        | ProtocolId::JWKConsensusRpcCompressed
+        | ProtocolId::JWKConsensusRpcBcs
+        | ProtocolId::JWKConsensusRpcJson => {
+            decode_message::<JWKConsensusMsg>(protocol_id, payload)
+        },
+        _ => return decode_untyped_payload(protocol_id, payload),
+    };
+
+    decoded_message.unwrap_or_else(|error| {
+        json!({
+            "decode_error": error.to_string(),
+            "raw_payload": decode_untyped_payload(protocol_id, payload),
+        })
+    })
+}
+
+/// Deserializes the payload into the given message type and converts it to JSON
+fn decode_message<T: DeserializeOwned + Serialize>(
+    protocol_id: ProtocolId,
+    payload: &[u8],
+) -> Result<Value> {
+    let message: T = protocol_id.from_bytes(payload)?;
+    Ok(serde_json::to_value(&message)?)
+}
+
+/// Decodes the payload into JSON (if the protocol uses JSON encoding).
+/// Otherwise, the payload is uncompressed and hex encoded.
+fn decode_untyped_payload(protocol_id: ProtocolId, payload: &[u8]) -> Value {
+    match protocol_id.decompress_bytes(payload) {
+        Ok(raw_bytes) if protocol_id.is_json_encoded() => serde_json::from_slice(&raw_bytes)
+            .unwrap_or_else(|_| Value::String(hex::encode(&raw_bytes))),
+        Ok(raw_bytes) => Value::String(hex::encode(raw_bytes)),
+        Err(error) => json!({
+            "decode_error": error.to_string(),
+            "raw_payload": hex::encode(payload),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_consensus_types::epoch_retrieval::EpochRetrievalRequest;
+    use aptos_network::protocols::wire::messaging::v1::{DirectSendMsg, RpcRequest, RpcResponse};
+
+    #[test]
+    fn test_decode_json_direct_send() {
+        // Create a JSON encoded consensus direct send message
+        let consensus_message =
+            ConsensusMsg::EpochRetrievalRequest(Box::new(EpochRetrievalRequest {
+                start_epoch: 2,
+                end_epoch: 10,
+            }));
+        let protocol_id = ProtocolId::ConsensusDirectSendJson;
+        let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id,
+            priority: 0,
+            raw_msg: protocol_id.to_bytes(&consensus_message).unwrap(),
+        });
+
+        // Decode the message and verify the payload is readable
+        let peer_id = PeerId::random();
+        let mut message_decoder = MessageDecoder::default();
+        let decoded_message = message_decoder.decode(create_recorded_message(
+            peer_id,
+            MessageDirection::Inbound,
+            message,
+        ));
+        assert_eq!(decoded_message.protocol_id, Some(protocol_id));
+        assert_eq!(
+            decoded_message.payload,
+            json!({"EpochRetrievalRequest": {"start_epoch": 2, "end_epoch": 10}})
+        );
+    }
+
+    #[test]
+    fn test_decode_rpc_response_protocol() {
+        // Create an outbound compressed RPC request and the inbound response
+        let peer_id = PeerId::random();
+        let protocol_id = ProtocolId::HealthCheckerRpc;
+        let request = NetworkMessage::RpcRequest(RpcRequest {
+            protocol_id,
+            request_id: 7,
+            priority: 0,
+            raw_request: protocol_id.to_bytes(&create_ping(1)).unwrap(),
+        });
+        let response = NetworkMessage::RpcResponse(RpcResponse {
+            request_id: 7,
+            priority: 0,
+            raw_response: protocol_id.to_bytes(&create_pong(1)).unwrap(),
+        });
+
+        // Decode the request and response
+        let mut message_decoder = MessageDecoder::default();
+        let decoded_request = message_decoder.decode(create_recorded_message(
+            peer_id,
+            MessageDirection::Outbound,
+            request,
+        ));
+        let decoded_response = message_decoder.decode(create_recorded_message(
+            peer_id,
+            MessageDirection::Inbound,
+            response,
+        ));
+
+        // Verify the response was matched with the request and both were decoded
+        assert_eq!(decoded_request.payload, json!({"Ping": 1}));
+        assert_eq!(decoded_response.protocol_id, Some(protocol_id));
+        assert_eq!(decoded_response.payload, json!({"Pong": 1}));
+        assert!(message_decoder.pending_rpc_protocols.is_empty());
+    }
+
+    #[test]
+    fn test_decode_invalid_and_unknown_payloads() {
+        // Verify an invalid consensus payload reports the error (and the raw payload)
+        let protocol_id = ProtocolId::ConsensusRpcCompressed;
+        let invalid_bytes = vec![250u8, 251, 252];
+        let payload = decode_payload(
+            Some(protocol_id),
+            &protocol_id.to_bytes(&invalid_bytes).unwrap(),
+        );
+        assert!(payload.get("decode_error").is_some());
+        assert_eq!(
+            payload["raw_payload"],
+            Value::String(hex::encode(bcs::to_bytes(&invalid_bytes).unwrap()))
+        );
+
+        // Verify payloads of unknown message types are hex encoded
+        let payload = decode_payload(Some(ProtocolId::NetbenchDirectSend), &[1, 2, 3]);
+        assert_eq!(payload, Value::String(hex::encode([1, 2, 3])));
+    }
+
+    /// Creates a health checker ping with the given nonce
+    fn create_ping(nonce: u32) -> HealthCheckerMsg {
+        serde_json::from_value(json!({ "Ping": nonce })).unwrap()
+    }
+
+    /// Creates a health checker pong with the given nonce
+    fn create_pong(nonce: u32) -> HealthCheckerMsg {
+        serde_json::from_value(json!({ "Pong": nonce })).unwrap()
+    }
+
+    /// Creates a recorded message for the given peer and direction
+    fn create_recorded_message(
+        remote_peer_id: PeerId,
+        direction: MessageDirection,
+        message: NetworkMessage,
+    ) -> RecordedMessage {
+        RecordedMessage {
+            timestamp_usecs: 0,
+            network_id: NetworkId::Validator,
+            remote_peer_id,
+            direction,
+            message,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tools for inspecting the network capture files written by the network
+//! message recorder (see [`aptos_network::peer::recorder`]).
+
+pub mod decode;
+pub mod replay;
+
+use anyhow::{Context, Result};
+use aptos_network::peer::recorder::{capture_files, CaptureFileReader, RecordedMessage};
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+#[clap(about = "Decode or replay network capture files")]
+pub enum Command {
+    Decode(decode::Command),
+    Replay(replay::Command),
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Command::Decode(cmd) => cmd.run(),
+            Command::Replay(cmd) => cmd.run().await,
+        }
+    }
+}
+
+/// Returns the capture files at the given path. If the path is a
+/// directory, all capture files in the directory are returned
+/// (ordered from oldest to newest).
+pub fn capture_file_paths(path: &Path) -> Result<Vec<PathBuf>> {
+    if path.is_dir() {
+        capture_files(path)
+            .with_context(|| format!("Failed to list the capture files in {:?}", path))
+    } else {
+        Ok(vec![path.to_path_buf()])
+    }
+}
+
+/// Reads all recorded messages from the capture files at the given path
+pub fn read_recorded_messages(path: &Path) -> Result<Vec<RecordedMessage>> {
+    let mut recorded_messages = vec![];
+    for file_path in capture_file_paths(path)? {
+        let reader = CaptureFileReader::open(&file_path)
+            .with_context(|| format!("Failed to open the capture file {:?}", file_path))?;
+        for recorded_message in reader {
+            recorded_messages.push(recorded_message.with_context(|| {
+                format!(
+                    "Failed to read a message from the capture file {:?}",
+                    file_path
+                )
+            })?);
+        }
+    }
+    Ok(recorded_messages)
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Command::command().debug_assert()
+}
@@ -0,0 +1,415 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::read_recorded_messages;
+use anyhow::{anyhow, Context, Result};
+use aptos_config::{
+    config::{RoleType, HANDSHAKE_VERSION, MAX_FRAME_SIZE, MAX_MESSAGE_SIZE},
+    network_id::{NetworkContext, NetworkId},
+};
+use aptos_crypto::{x25519, ValidCryptoMaterialStringExt};
+use aptos_logger::prelude::*;
+use aptos_memsocket::MemorySocket;
+use aptos_network::{
+    counters,
+    noise::{HandshakeAuthMode, NoiseUpgrader},
+    peer::recorder::{MessageDirection, RecordedMessage},
+    protocols::{
+        stream::OutboundStream,
+        wire::{
+            handshake::v1::ProtocolIdSet,
+            messaging::v1::{
+                MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream, NetworkMessage,
+            },
+        },
+    },
+    transport::{
+        resolve_and_connect, upgrade_outbound, TCPBufferCfg, TSocket, TcpSocket, UpgradeContext,
+        SUPPORTED_MESSAGING_PROTOCOL,
+    },
+};
+use aptos_types::{
+    account_address,
+    chain_id::ChainId,
+    network_address::{NetworkAddress, Protocol},
+    PeerId,
+};
+use clap::Parser;
+use futures::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite},
+    FutureExt, SinkExt, StreamExt,
+};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+// The maximum number of stream messages (i.e., the header and 255 fragments)
+const MAX_STREAM_MESSAGES: usize = 256;
+
+#[derive(Parser)]
+#[clap(about = "Replay the messages received from a peer against a running node")]
+pub struct Command {
+    /// Path to a capture file, or a directory containing capture files
+    #[clap(long, value_parser)]
+    input: PathBuf,
+
+    /// `NetworkAddress` of the node to replay the messages against, e.g.,
+    /// `/ip4/127.0.0.1/tcp/6180/noise-ik/<pubkey>/handshake/0`. Note: in-memory
+    /// `/memory/<port>` addresses are only reachable from within the same
+    /// process (e.g., in tests).
+    #[clap(long)]
+    address: NetworkAddress,
+
+    /// Hex encoded x25519 private key used to authenticate with the node.
+    /// The node must accept connections from the corresponding peer (e.g.,
+    /// any peer on a public network, or a trusted peer on a validator network).
+    #[clap(long)]
+    private_key: String,
+
+    /// `ChainId` of the node
+    #[clap(long)]
+    chain_id: ChainId,
+
+    /// `NetworkId` of the node interface
+    #[clap(long, default_value_t = NetworkId::Public)]
+    network_id: NetworkId,
+
+    /// Only replay the messages originally received from the given peer
+    #[clap(long)]
+    peer_id: Option<PeerId>,
+
+    /// Preserve the original delays between messages (instead of
+    /// replaying the messages as fast as possible)
+    #[clap(long)]
+    preserve_timing: bool,
+
+    /// Number of seconds to keep the connection open after the replay
+    /// (to allow the node to process the messages and respond)
+    #[clap(long, default_value_t = 5)]
+    linger_secs: u64,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        let messages = replayable_messages(read_recorded_messages(&self.input)?, self.peer_id);
+        info!(
+            "Replaying {} messages against {}",
+            messages.len(),
+            self.address
+        );
+
+        let private_key = x25519::PrivateKey::from_encoded_string(&self.private_key)
+            .map_err(|error| anyhow!("Invalid x25519 private key: {}", error))?;
+        let upgrade_context = build_upgrade_context(private_key, self.chain_id, self.network_id);
+        let linger_duration = Duration::from_secs(self.linger_secs);
+        let num_received_messages = match self.address.as_slice().first() {
+            Some(Protocol::Memory(port)) => {
+                let port = *port;
+                let fut_socket = async move { MemorySocket::connect(port) };
+                replay_to_address(
+                    upgrade_context,
+                    fut_socket,
+                    self.address,
+                    &messages,
+                    self.preserve_timing,
+                    linger_duration,
+                )
+                .await?
+            },
+            _ => {
+                let address = self.address.clone();
+                let fut_socket = async move {
+                    resolve_and_connect(address, TCPBufferCfg::new())
+                        .await
+                        .map(TcpSocket::new)
+                };
+                replay_to_address(
+                    upgrade_context,
+                    fut_socket,
+                    self.address,
+                    &messages,
+                    self.preserve_timing,
+                    linger_duration,
+                )
+                .await?
+            },
+        };
+
+        info!(
+            "Replay complete! Sent {} messages and received {} messages",
+            messages.len(),
+            num_received_messages
+        );
+        Ok(())
+    }
+}
+
+/// Returns the messages that can be replayed against a node, i.e., the direct
+/// sends and RPC requests the local node received (optionally from a single peer).
+pub fn replayable_messages(
+    recorded_messages: Vec<RecordedMessage>,
+    peer_id: Option<PeerId>,
+) -> Vec<RecordedMessage> {
+    recorded_messages
+        .into_iter()
+        .filter(|recorded_message| {
+            recorded_message.direction == MessageDirection::Inbound
+                && peer_id.map_or(true, |peer_id| peer_id == recorded_message.remote_peer_id)
+                && matches!(
+                    recorded_message.message,
+                    NetworkMessage::DirectSendMsg(_) | NetworkMessage::RpcRequest(_)
+                )
+        })
+        .collect()
+}
+
+/// Connects to the node at the given address (over Noise) and replays the
+/// messages. Returns the number of messages received from the node.
+async fn replay_to_address<T: TSocket>(
+    upgrade_context: Arc<UpgradeContext>,
+    fut_socket: impl std::future::Future<Output = std::io::Result<T>>,
+    address: NetworkAddress,
+    messages: &[RecordedMessage],
+    preserve_timing: bool,
+    linger_duration: Duration,
+) -> Result<usize> {
+    let remote_pubkey = address.find_noise_proto().ok_or_else(|| {
+        anyhow!(
+            "Failed to find noise protocol in {}, /noise-ik/<pubkey> missing",
+            address
+        )
+    })?;
+    let remote_peer_id = account_address::from_identity_public_key(remote_pubkey);
+    let connection = upgrade_outbound(
+        upgrade_context,
+        fut_socket,
+        address.clone(),
+        remote_peer_id,
+        remote_pubkey,
+    )
+    .await
+    .with_context(|| format!("Failed to connect to {}", address))?;
+
+    // Drain (and count) the messages sent by the node, so that it doesn't block
+    let (read_socket, write_socket) = connection.socket.split();
+    let num_received_messages = Arc::new(AtomicUsize::new(0));
+    let reader_task = tokio::spawn(count_received_messages(
+        read_socket,
+        num_received_messages.clone(),
+    ));
+
+    // Replay the messages and give the node time to respond
+    replay_messages(
+        write_socket,
+        messages,
+        MAX_FRAME_SIZE,
+        MAX_MESSAGE_SIZE,
+        preserve_timing,
+    )
+    .await?;
+    tokio::time::sleep(linger_duration).await;
+
+    // Close the connection and return the number of received messages
+    reader_task.abort();
+    Ok(num_received_messages.load(Ordering::Relaxed))
+}
+
+/// Counts the messages received on the given socket (until it is closed)
+async fn count_received_messages(
+    read_socket: impl AsyncRead + Unpin,
+    num_received_messages: Arc<AtomicUsize>,
+) {
+    let mut message_stream = MultiplexMessageStream::new(read_socket, MAX_FRAME_SIZE);
+    while let Some(Ok(_)) = message_stream.next().await {
+        num_received_messages.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Writes the given messages to the socket, using the same framing (and
+/// streaming for large messages) as the peer actor.
+pub async fn replay_messages<TWriteSocket: AsyncWrite + Unpin>(
+    write_socket: TWriteSocket,
+    messages: &[RecordedMessage],
+    max_frame_size: usize,
+    max_message_size: usize,
+    preserve_timing: bool,
+) -> Result<()> {
+    let mut message_sink = MultiplexMessageSink::new(write_socket, max_frame_size);
+    let (stream_tx, mut stream_rx) =
+        aptos_channels::new(MAX_STREAM_MESSAGES, &counters::PENDING_MULTIPLEX_STREAM);
+    let mut outbound_stream = OutboundStream::new(max_frame_size, max_message_size, stream_tx);
+
+    let mut previous_timestamp_usecs = None;
+    for recorded_message in messages {
+        // Wait for the original delay between messages (if required)
+        if preserve_timing {
+            if let Some(previous_timestamp_usecs) = previous_timestamp_usecs {
+                let delay_usecs = recorded_message
+                    .timestamp_usecs
+                    .saturating_sub(previous_timestamp_usecs);
+                tokio::time::sleep(Duration::from_micros(delay_usecs)).await;
+            }
+            previous_timestamp_usecs = Some(recorded_message.timestamp_usecs);
+        }
+
+        // Send the message (streaming it if it doesn't fit in a single frame)
+        let message = recorded_message.message.clone();
+        if outbound_stream.should_stream(&message) {
+            outbound_stream.stream_message(message).await?;
+            while let Some(Some(stream_message)) = stream_rx.next().now_or_never() {
+                message_sink.send(&stream_message).await?;
+            }
+        } else {
+            message_sink
+                .send(&MultiplexMessage::Message(message))
+                .await?;
+        }
+    }
+    message_sink.flush().await?;
+
+    Ok(())
+}
+
+/// Builds a listener free noise connector (similar to the network checker)
+fn build_upgrade_context(
+    private_key: x25519::PrivateKey,
+    chain_id: ChainId,
+    network_id: NetworkId,
+) -> Arc<UpgradeContext> {
+    let peer_id = account_address::from_identity_public_key(private_key.public_key());
+    let network_context = NetworkContext::new(RoleType::FullNode, network_id, peer_id);
+
+    let mut supported_protocols = BTreeMap::new();
+    supported_protocols.insert(SUPPORTED_MESSAGING_PROTOCOL, ProtocolIdSet::all_known());
+
+    Arc::new(UpgradeContext::new(
+        NoiseUpgrader::new(
+            network_context,
+            private_key,
+            HandshakeAuthMode::server_only(&[network_id]),
+        ),
+        HANDSHAKE_VERSION,
+        supported_protocols,
+        chain_id,
+        network_id,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_network::{
+        protocols::{
+            stream::{InboundStreamBuffer, StreamMessage},
+            wire::messaging::v1::{DirectSendMsg, RpcRequest, RpcResponse},
+        },
+        ProtocolId,
+    };
+
+    #[tokio::test]
+    async fn test_replay_messages() {
+        // Create a set of recorded messages (including a message that must be streamed)
+        let peer_id = PeerId::random();
+        let max_frame_size = 1024;
+        let max_message_size = 64 * 1024;
+        let messages = vec![
+            create_recorded_message(peer_id, MessageDirection::Inbound, direct_send(10)),
+            create_recorded_message(peer_id, MessageDirection::Outbound, direct_send(20)),
+            create_recorded_message(peer_id, MessageDirection::Inbound, rpc_request(1, 30)),
+            create_recorded_message(peer_id, MessageDirection::Inbound, rpc_response(2)),
+            create_recorded_message(PeerId::random(), MessageDirection::Inbound, direct_send(40)),
+            create_recorded_message(peer_id, MessageDirection::Inbound, direct_send(10_000)),
+        ];
+
+        // Verify only the inbound direct sends and RPC requests (from the peer) are replayable
+        let replayable_messages = replayable_messages(messages.clone(), Some(peer_id));
+        let expected_messages = vec![
+            messages[0].message.clone(),
+            messages[2].message.clone(),
+            messages[5].message.clone(),
+        ];
+        assert_eq!(
+            replayable_messages
+                .iter()
+                .map(|recorded_message| recorded_message.message.clone())
+                .collect::<Vec<_>>(),
+            expected_messages
+        );
+
+        // Replay the messages over an in-memory socket
+        let (write_socket, read_socket) = MemorySocket::new_pair();
+        replay_messages(
+            write_socket,
+            &replayable_messages,
+            max_frame_size,
+            max_message_size,
+            false,
+        )
+        .await
+        .unwrap();
+
+        // Read the messages from the other end of the socket (reassembling streams)
+        let mut message_stream = MultiplexMessageStream::new(read_socket, max_frame_size);
+        let mut inbound_stream = InboundStreamBuffer::new(max_message_size / max_frame_size);
+        let mut received_messages = vec![];
+        while received_messages.len() < expected_messages.len() {
+            match message_stream.next().await.unwrap().unwrap() {
+                MultiplexMessage::Message(message) => received_messages.push(message),
+                MultiplexMessage::Stream(StreamMessage::Header(header)) => {
+                    inbound_stream.new_stream(header).unwrap()
+                },
+                MultiplexMessage::Stream(StreamMessage::Fragment(fragment)) => {
+                    if let Some(message) = inbound_stream.append_fragment(fragment).unwrap() {
+                        received_messages.push(message);
+                    }
+                },
+            }
+        }
+        assert_eq!(received_messages, expected_messages);
+    }
+
+    fn direct_send(size: usize) -> NetworkMessage {
+        NetworkMessage::DirectSendMsg(DirectSendMsg {
+            protocol_id: ProtocolId::ConsensusDirectSendBcs,
+            priority: 0,
+            raw_msg: vec![1; size],
+        })
+    }
+
+    fn rpc_request(request_id: u32, size: usize) -> NetworkMessage {
+        NetworkMessage::RpcRequest(RpcRequest {
+            protocol_id: ProtocolId::StorageServiceRpc,
+            request_id,
+            priority: 0,
+            raw_request: vec![2; size],
+        })
+    }
+
+    fn rpc_response(request_id: u32) -> NetworkMessage {
+        NetworkMessage::RpcResponse(RpcResponse {
+            request_id,
+            priority: 0,
+            raw_response: vec![3; 10],
+        })
+    }
+
+    fn create_recorded_message(
+        remote_peer_id: PeerId,
+        direction: MessageDirection,
+        message: NetworkMessage,
+    ) -> RecordedMessage {
+        RecordedMessage {
+            timestamp_usecs: 0,
+            network_id: NetworkId::Public,
+            remote_peer_id,
+            direction,
+            message,
+        }
+    }
+}
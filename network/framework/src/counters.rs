@@ -655,3 +655,19 @@ pub fn inbound_queue_delay_observe(protocol_id: ProtocolId, seconds: f64) {
         .with_label_values(&[protocol_id.as_str()])
         .observe(seconds)
 }
+
+pub static NETWORK_RECORDED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_recorded_messages",
+        "Number of network messages handled by the message recorder",
+        &["network_id", "direction", "state"]
+    )
+    .unwrap()
+});
+
+/// Increments the recorded message counter for the given direction and state
+pub fn recorded_messages(network_context: &NetworkContext, direction: &str, state: &str) {
+    NETWORK_RECORDED_MESSAGES
+        .with_label_values(&[network_context.network_id().as_str(), direction, state])
+        .inc();
+}
@@ -21,6 +21,7 @@ use crate::{
         DECLINED_LABEL, FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL, UNKNOWN_LABEL,
     },
     logging::NetworkSchema,
+    peer::recorder::{MessageDirection, MessageRecorder},
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
@@ -61,6 +62,7 @@ mod test;
 
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod fuzzing;
+pub mod recorder;
 
 /// Requests [`Peer`] receives from the [`PeerManager`](crate::peer_manager::PeerManager).
 #[derive(Debug)]
@@ -130,6 +132,8 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// An optional recorder for all messages exchanged with the remote peer
+    message_recorder: Option<Arc<MessageRecorder>>,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -185,9 +189,16 @@ where
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            message_recorder: None,
         }
     }
 
+    /// Sets the recorder used to record all messages exchanged with the remote peer
+    pub fn with_message_recorder(mut self, message_recorder: Option<Arc<MessageRecorder>>) -> Self {
+        self.message_recorder = message_recorder;
+        self
+    }
+
     fn remote_peer_id(&self) -> PeerId {
         self.connection_metadata.remote_peer_id
     }
@@ -222,6 +233,7 @@ where
             writer,
             self.max_frame_size,
             self.max_message_size,
+            self.message_recorder.clone(),
         );
 
         // Start main Peer event loop.
@@ -325,6 +337,7 @@ where
         mut writer: MultiplexMessageSink<impl AsyncWrite + Unpin + Send + 'static>,
         max_frame_size: usize,
         max_message_size: usize,
+        message_recorder: Option<Arc<MessageRecorder>>,
     ) -> (
         aptos_channel::Sender<(), NetworkMessage>,
         oneshot::Sender<()>,
@@ -413,6 +426,11 @@ where
             let mut outbound_stream =
                 OutboundStream::new(max_frame_size, max_message_size, stream_msg_tx);
             while let Some(message) = write_reqs_rx.next().await {
+                // record the message before it is (potentially) fragmented
+                if let Some(message_recorder) = &message_recorder {
+                    message_recorder.record(remote_peer_id, MessageDirection::Outbound, &message);
+                }
+
                 // either channel full would block the other one
                 let result = if outbound_stream.should_stream(&message) {
                     outbound_stream.stream_message(message).await
@@ -441,6 +459,10 @@ where
         &mut self,
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
+        if let Some(message_recorder) = &self.message_recorder {
+            message_recorder.record(self.remote_peer_id(), MessageDirection::Inbound, &message);
+        }
+
         match &message {
             NetworkMessage::DirectSendMsg(direct) => {
                 let data_len = direct.raw_msg.len();
@@ -0,0 +1,467 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! An opt-in recorder for the [`NetworkMessage`]s exchanged with remote peers.
+//!
+//! When enabled, each [`Peer`](crate::peer::Peer) actor hands every decrypted and
+//! reassembled inbound message, and every outbound message (before it is fragmented),
+//! to the [`MessageRecorder`]. The recorder forwards the messages to a dedicated
+//! writer thread, which appends them to a set of rotating capture files.
+//!
+//! Each capture file is a sequence of records. Every record is a little-endian `u32`
+//! length prefix followed by the BCS bytes of a [`RecordedMessage`]. Capture files
+//! can be read back using [`CaptureFileReader`].
+
+use crate::{
+    counters::{self, FAILED_LABEL, INBOUND_LABEL, OUTBOUND_LABEL, SENT_LABEL},
+    protocols::wire::messaging::v1::NetworkMessage,
+    ProtocolId,
+};
+use aptos_config::{
+    config::MessageRecorderConfig,
+    network_id::{NetworkContext, NetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::{
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    thread,
+};
+
+/// The file extension used by all capture files
+pub const CAPTURE_FILE_EXTENSION: &str = "capture";
+
+// Labels for the recorded message counters
+const DROPPED_LABEL: &str = "dropped";
+
+/// The direction of a recorded message (relative to the local node)
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum MessageDirection {
+    Inbound,
+    Outbound,
+}
+
+impl MessageDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageDirection::Inbound => INBOUND_LABEL,
+            MessageDirection::Outbound => OUTBOUND_LABEL,
+        }
+    }
+}
+
+/// A single network message exchanged with a remote peer
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecordedMessage {
+    /// The time (in microseconds since the unix epoch) the message was recorded
+    pub timestamp_usecs: u64,
+    /// The network on which the message was exchanged
+    pub network_id: NetworkId,
+    /// The remote peer that sent (or received) the message
+    pub remote_peer_id: PeerId,
+    /// Whether the message was received or sent by the local node
+    pub direction: MessageDirection,
+    /// The framed network message
+    pub message: NetworkMessage,
+}
+
+impl RecordedMessage {
+    /// Returns the protocol id of the message. Note: RPC responses and
+    /// errors do not carry a protocol id, so `None` is returned for them.
+    pub fn protocol_id(&self) -> Option<ProtocolId> {
+        match &self.message {
+            NetworkMessage::DirectSendMsg(message) => Some(message.protocol_id),
+            NetworkMessage::RpcRequest(request) => Some(request.protocol_id),
+            NetworkMessage::RpcResponse(_) | NetworkMessage::Error(_) => None,
+        }
+    }
+}
+
+/// Records network messages to a set of rotating capture files
+pub struct MessageRecorder {
+    network_context: NetworkContext,
+    time_service: TimeService,
+    message_sender: SyncSender<RecordedMessage>,
+}
+
+impl MessageRecorder {
+    /// Creates a new message recorder and spawns the writer thread
+    /// responsible for persisting the recorded messages to disk.
+    pub fn new(
+        network_context: NetworkContext,
+        time_service: TimeService,
+        config: &MessageRecorderConfig,
+    ) -> io::Result<Arc<Self>> {
+        let file_writer = CaptureFileWriter::new(
+            config.output_dir.clone(),
+            network_context.network_id(),
+            config.max_file_size_bytes,
+            config.max_num_files,
+        )?;
+
+        // Spawn the writer thread. The thread terminates once all senders are dropped.
+        let (message_sender, message_receiver) = mpsc::sync_channel(config.max_pending_messages);
+        thread::Builder::new()
+            .name(format!("net-rec-{}", network_context.network_id()))
+            .spawn(move || run_writer_loop(network_context, file_writer, message_receiver))?;
+
+        Ok(Arc::new(Self {
+            network_context,
+            time_service,
+            message_sender,
+        }))
+    }
+
+    /// Records the given message. If the writer thread is unable to keep
+    /// up, the message is dropped (to avoid blocking the peer actor).
+    pub fn record(
+        &self,
+        remote_peer_id: PeerId,
+        direction: MessageDirection,
+        message: &NetworkMessage,
+    ) {
+        let recorded_message = RecordedMessage {
+            timestamp_usecs: self.time_service.now_unix_time().as_micros() as u64,
+            network_id: self.network_context.network_id(),
+            remote_peer_id,
+            direction,
+            message: message.clone(),
+        };
+
+        if let Err(error) = self.message_sender.try_send(recorded_message) {
+            counters::recorded_messages(&self.network_context, direction.as_str(), DROPPED_LABEL);
+            if let TrySendError::Disconnected(_) = error {
+                sample!(
+                    SampleRate::Duration(std::time::Duration::from_secs(10)),
+                    warn!(
+                        "{} The message recorder writer thread has terminated!",
+                        self.network_context
+                    )
+                );
+            }
+        }
+    }
+}
+
+/// Receives recorded messages and writes them to the capture files
+fn run_writer_loop(
+    network_context: NetworkContext,
+    mut file_writer: CaptureFileWriter,
+    message_receiver: Receiver<RecordedMessage>,
+) {
+    while let Ok(message) = message_receiver.recv() {
+        // Write the message and any others that are already pending
+        let mut next_message = Some(message);
+        while let Some(message) = next_message.take() {
+            let direction = message.direction;
+            let state_label = match file_writer.write_message(&message) {
+                Ok(()) => SENT_LABEL,
+                Err(error) => {
+                    warn!(
+                        "{} Failed to record network message! Error: {:?}",
+                        network_context, error
+                    );
+                    FAILED_LABEL
+                },
+            };
+            counters::recorded_messages(&network_context, direction.as_str(), state_label);
+            next_message = message_receiver.try_recv().ok();
+        }
+
+        // Flush the writer once the pending messages are drained
+        if let Err(error) = file_writer.flush() {
+            warn!(
+                "{} Failed to flush the network capture file! Error: {:?}",
+                network_context, error
+            );
+        }
+    }
+}
+
+/// Writes recorded messages to a set of rotating capture files
+struct CaptureFileWriter {
+    output_dir: PathBuf,
+    file_prefix: String,
+    max_file_size_bytes: u64,
+    max_num_files: usize,
+    current_file: Option<BufWriter<File>>,
+    current_file_size_bytes: u64,
+    next_file_index: u64,
+}
+
+impl CaptureFileWriter {
+    fn new(
+        output_dir: PathBuf,
+        network_id: NetworkId,
+        max_file_size_bytes: u64,
+        max_num_files: usize,
+    ) -> io::Result<Self> {
+        fs::create_dir_all(&output_dir)?;
+
+        // Continue numbering after any existing capture files for the network
+        let file_prefix = format!("{}_", network_id.as_str());
+        let next_file_index = capture_files_with_prefix(&output_dir, &file_prefix)?
+            .last()
+            .and_then(|path| parse_file_index(path, &file_prefix))
+            .map(|index| index + 1)
+            .unwrap_or(0);
+
+        Ok(Self {
+            output_dir,
+            file_prefix,
+            max_file_size_bytes,
+            max_num_files: max_num_files.max(1),
+            current_file: None,
+            current_file_size_bytes: 0,
+            next_file_index,
+        })
+    }
+
+    /// Appends the message to the current capture file (rotating it if required)
+    fn write_message(&mut self, message: &RecordedMessage) -> io::Result<()> {
+        let bytes = bcs::to_bytes(message)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let record_size = (bytes.len() + std::mem::size_of::<u32>()) as u64;
+
+        // Rotate the file if there's no file or if the record would overflow the file
+        if self.current_file.is_none()
+            || (self.current_file_size_bytes > 0
+                && self.current_file_size_bytes + record_size > self.max_file_size_bytes)
+        {
+            self.rotate_file()?;
+        }
+
+        let file = self
+            .current_file
+            .as_mut()
+            .expect("The capture file should have been created!");
+        file.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        file.write_all(&bytes)?;
+        self.current_file_size_bytes += record_size;
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.current_file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes the current capture file, opens a new one and
+    /// removes the oldest capture files (beyond the retention limit).
+    fn rotate_file(&mut self) -> io::Result<()> {
+        if let Some(mut file) = self.current_file.take() {
+            file.flush()?;
+        }
+
+        let file_name = format!(
+            "{}{:010}.{}",
+            self.file_prefix, self.next_file_index, CAPTURE_FILE_EXTENSION
+        );
+        let file = File::create(self.output_dir.join(file_name))?;
+        self.current_file = Some(BufWriter::new(file));
+        self.current_file_size_bytes = 0;
+        self.next_file_index += 1;
+
+        let capture_files = capture_files_with_prefix(&self.output_dir, &self.file_prefix)?;
+        let num_files_to_remove = capture_files.len().saturating_sub(self.max_num_files);
+        for path in capture_files.iter().take(num_files_to_remove) {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns all capture files in the given directory (sorted from oldest to newest)
+pub fn capture_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    capture_files_with_prefix(dir, "")
+}
+
+/// Returns all capture files with the given name prefix (sorted from oldest to newest)
+fn capture_files_with_prefix(dir: &Path, file_prefix: &str) -> io::Result<Vec<PathBuf>> {
+    let mut capture_files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_capture_file = path.extension().and_then(|ext| ext.to_str())
+            == Some(CAPTURE_FILE_EXTENSION)
+            && path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| name.starts_with(file_prefix));
+        if is_capture_file {
+            capture_files.push(path);
+        }
+    }
+    capture_files.sort();
+    Ok(capture_files)
+}
+
+/// Parses the index of the capture file from its name
+fn parse_file_index(path: &Path, file_prefix: &str) -> Option<u64> {
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(file_prefix)?
+        .parse()
+        .ok()
+}
+
+/// Reads the recorded messages from a capture file
+pub struct CaptureFileReader<R> {
+    reader: R,
+}
+
+impl CaptureFileReader<BufReader<File>> {
+    /// Opens the capture file at the given path
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> CaptureFileReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Reads the next recorded message. Returns `None` at the end of the file.
+    fn read_message(&mut self) -> io::Result<Option<RecordedMessage>> {
+        let mut length_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut length_bytes) {
+            Ok(()) => {},
+            Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(error),
+        }
+
+        let mut bytes = vec![0u8; u32::from_le_bytes(length_bytes) as usize];
+        self.reader.read_exact(&mut bytes)?;
+        bcs::from_bytes(&bytes)
+            .map(Some)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+impl<R: Read> Iterator for CaptureFileReader<R> {
+    type Item = io::Result<RecordedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_message().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocols::wire::messaging::v1::{DirectSendMsg, RpcResponse};
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_write_and_read_capture_file() {
+        // Create a capture file writer with a large file size
+        let output_dir = create_temp_dir();
+        let mut file_writer = CaptureFileWriter::new(
+            output_dir.path().to_path_buf(),
+            NetworkId::Validator,
+            1 << 20,
+            2,
+        )
+        .unwrap();
+
+        // Write several messages to the file
+        let messages: Vec<_> = (0..10).map(create_recorded_message).collect();
+        for message in &messages {
+            file_writer.write_message(message).unwrap();
+        }
+        file_writer.flush().unwrap();
+
+        // Read the messages back and verify they match
+        let capture_files = capture_files(output_dir.path()).unwrap();
+        assert_eq!(capture_files.len(), 1);
+        let read_messages: Vec<_> = CaptureFileReader::open(&capture_files[0])
+            .unwrap()
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(read_messages, messages);
+    }
+
+    #[test]
+    fn test_capture_file_rotation() {
+        // Create a capture file writer with a tiny file size (one message per file)
+        let output_dir = create_temp_dir();
+        let max_num_files = 3;
+        let mut file_writer = CaptureFileWriter::new(
+            output_dir.path().to_path_buf(),
+            NetworkId::Public,
+            1,
+            max_num_files,
+        )
+        .unwrap();
+
+        // Write several messages (each should be rotated into a new file)
+        let messages: Vec<_> = (0..10).map(create_recorded_message).collect();
+        for message in &messages {
+            file_writer.write_message(message).unwrap();
+        }
+        file_writer.flush().unwrap();
+
+        // Verify only the latest files are retained
+        let capture_files = capture_files(output_dir.path()).unwrap();
+        assert_eq!(capture_files.len(), max_num_files);
+        let read_messages: Vec<_> = capture_files
+            .iter()
+            .flat_map(|path| CaptureFileReader::open(path).unwrap())
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(read_messages, messages[messages.len() - max_num_files..]);
+
+        // Create a new writer and verify it continues after the existing files
+        let file_writer = CaptureFileWriter::new(
+            output_dir.path().to_path_buf(),
+            NetworkId::Public,
+            1,
+            max_num_files,
+        )
+        .unwrap();
+        assert_eq!(file_writer.next_file_index, messages.len() as u64);
+    }
+
+    /// Creates a temporary directory for the capture files
+    fn create_temp_dir() -> TempPath {
+        let temp_dir = TempPath::new();
+        temp_dir.create_as_dir().unwrap();
+        temp_dir
+    }
+
+    /// Creates a recorded message (alternating between direct sends and RPC responses)
+    fn create_recorded_message(index: u64) -> RecordedMessage {
+        let message = if index % 2 == 0 {
+            NetworkMessage::DirectSendMsg(DirectSendMsg {
+                protocol_id: ProtocolId::ConsensusDirectSendBcs,
+                priority: 0,
+                raw_msg: vec![index as u8; 10],
+            })
+        } else {
+            NetworkMessage::RpcResponse(RpcResponse {
+                request_id: index as u32,
+                priority: 0,
+                raw_response: vec![index as u8; 20],
+            })
+        };
+        RecordedMessage {
+            timestamp_usecs: index,
+            network_id: NetworkId::Validator,
+            remote_peer_id: PeerId::random(),
+            direction: MessageDirection::Inbound,
+            message,
+        }
+    }
+}
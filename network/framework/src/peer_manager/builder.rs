@@ -6,6 +6,7 @@ use crate::{
     application::storage::PeersAndMetadata,
     counters,
     noise::{stream::NoiseStream, HandshakeAuthMode},
+    peer::recorder::MessageRecorder,
     peer_manager::{
         conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
         PeerManagerRequest, PeerManagerRequestSender,
@@ -78,6 +79,7 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    message_recorder: Option<Arc<MessageRecorder>>,
 }
 
 impl PeerManagerContext {
@@ -116,6 +118,7 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            message_recorder: None,
         }
     }
 
@@ -225,6 +228,11 @@ impl PeerManagerBuilder {
             .clone()
     }
 
+    /// Sets the recorder used to record the messages exchanged with all peers
+    pub fn set_message_recorder(&mut self, message_recorder: Arc<MessageRecorder>) {
+        self.peer_manager_context().message_recorder = Some(message_recorder);
+    }
+
     fn transport_context(&mut self) -> &mut TransportContext {
         self.transport_context
             .as_mut()
@@ -340,7 +348,8 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
-        );
+        )
+        .with_message_recorder(pm_context.message_recorder);
 
         // PeerManager constructor appends a public key to the listen_address.
         self.listen_address = peer_mgr.listen_addr().clone();
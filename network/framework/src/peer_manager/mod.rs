@@ -15,7 +15,7 @@ use crate::{
     constants,
     counters::{self},
     logging::*,
-    peer::{Peer, PeerRequest},
+    peer::{recorder::MessageRecorder, Peer, PeerRequest},
     transport::{
         Connection, ConnectionId, ConnectionMetadata, TSocket as TransportTSocket,
         TRANSPORT_TIMEOUT,
@@ -117,6 +117,8 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// An optional recorder for the messages exchanged with all peers
+    message_recorder: Option<Arc<MessageRecorder>>,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -185,9 +187,16 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            message_recorder: None,
         }
     }
 
+    /// Sets the recorder used to record the messages exchanged with all peers
+    pub fn with_message_recorder(mut self, message_recorder: Option<Arc<MessageRecorder>>) -> Self {
+        self.message_recorder = message_recorder;
+        self
+    }
+
     pub fn update_connected_peers_metrics(&self) {
         let total = self.active_peers.len();
         let inbound = self
@@ -661,7 +670,8 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
-        );
+        )
+        .with_message_recorder(self.message_recorder.clone());
         self.executor.spawn(peer.start());
 
         // Save PeerRequest sender to `active_peers`.
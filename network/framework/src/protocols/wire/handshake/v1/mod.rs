@@ -252,6 +252,26 @@ impl ProtocolId {
         result
     }
 
+    /// Returns true iff messages for the protocol are encoded using JSON
+    pub fn is_json_encoded(&self) -> bool {
+        matches!(self.encoding(), Encoding::Json)
+    }
+
+    /// Returns the uncompressed (i.e., raw BCS or JSON) bytes of the
+    /// given message bytes. This is useful for inspecting messages
+    /// without knowing their concrete types (e.g., for debugging).
+    pub fn decompress_bytes(&self, bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+        match self.encoding() {
+            Encoding::Bcs(_) | Encoding::Json => Ok(bytes.to_vec()),
+            Encoding::CompressedBcs(_) => aptos_compression::decompress(
+                &bytes.to_vec(),
+                self.get_compression_client(),
+                MAX_APPLICATION_MESSAGE_SIZE,
+            )
+            .map_err(|e| anyhow!("{:?}", e)),
+        }
+    }
+
     /// Serializes the value using BCS encoding (with a specified limit)
     fn bcs_encode<T: Serialize>(&self, value: &T, limit: usize) -> anyhow::Result<Vec<u8>> {
         bcs::to_bytes_with_limit(value, limit).map_err(|e| anyhow!("{:?}", e))
@@ -3,6 +3,13 @@
 
 // @generated
 pub mod aptos {
+    pub mod consensus {
+        // @@protoc_insertion_point(attribute:aptos.consensus.v1)
+        pub mod v1 {
+            include!("aptos.consensus.v1.rs");
+            // @@protoc_insertion_point(aptos.consensus.v1)
+        }
+    }
     pub mod indexer {
         // @@protoc_insertion_point(attribute:aptos.indexer.v1)
         pub mod v1 {
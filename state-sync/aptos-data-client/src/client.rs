@@ -36,16 +36,19 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, StorageServiceRequest,
-        SubscribeTransactionOutputsWithProofRequest,
+        StateKeySelector, StateValuesByKeysWithProofRequest, StateValuesWithProofRequest,
+        StorageServiceRequest, SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
+    responses::{
+        StateValuesByKeysWithProof, StorageServerSummary, StorageServiceResponse,
+        TransactionOrOutputListWithProof,
+    },
     Epoch, StorageServiceMessage,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
@@ -773,6 +776,7 @@ impl AptosDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -794,6 +798,11 @@ impl AptosDataClient {
                 // Update the received response metrics
                 self.update_received_response_metrics(peer, &request);
 
+                // Update the response latency for the peer
+                let response_latency = self.time_service.now().duration_since(request_start_time);
+                self.peer_states
+                    .update_response_latency(peer, response_latency);
+
                 // For now, record all responses that at least pass the data
                 // client layer successfully. An alternative might also have the
                 // consumer notify both success and failure via the callback.
@@ -1013,6 +1022,21 @@ impl AptosDataClientInterface for AptosDataClient {
             .await
     }
 
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: StateKeySelector,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<StateValuesByKeysWithProof>> {
+        let data_request =
+            DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+                version,
+                state_keys,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_transaction_outputs_with_proof(
         &self,
         proof_version: Version,
@@ -1149,6 +1173,10 @@ impl ResponseCallback for AptosNetResponseCallback {
         self.data_client
             .notify_bad_response(self.id, self.peer, &self.request, error_type);
     }
+
+    fn get_peer(&self) -> Option<PeerNetworkId> {
+        Some(self.peer)
+    }
 }
 
 impl fmt::Debug for AptosNetResponseCallback {
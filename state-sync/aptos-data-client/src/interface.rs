@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{error, error::Error, global_summary::GlobalDataSummary};
-use aptos_storage_service_types::{responses::TransactionOrOutputListWithProof, Epoch};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_storage_service_types::{
+    requests::StateKeySelector,
+    responses::{StateValuesByKeysWithProof, TransactionOrOutputListWithProof},
+    Epoch,
+};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
@@ -87,6 +92,21 @@ pub trait AptosDataClientInterface {
         request_timeout_ms: u64,
     ) -> error::Result<Response<StateValueChunkWithProof>>;
 
+    /// Fetches the state values (each with a sparse Merkle proof) for the
+    /// selected state keys at the specified version. The response contains
+    /// the state root hash at the version, which must be checked against a
+    /// trusted root hash (see `StateValuesByKeysWithProof::verify`). Note:
+    /// the completeness of the state values of an account is not proven. In
+    /// some cases, fewer state values may be returned (e.g., to tolerate
+    /// network or chunk limits), in which case the response contains the next
+    /// state key to fetch. If the data cannot be fetched, an error is returned.
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: StateKeySelector,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValuesByKeysWithProof>>;
+
     /// Fetches a transaction output list with proof, with transaction
     /// outputs from start to end versions (inclusive). The proof is relative
     /// to the specified `proof_version`. In some cases, fewer outputs may be
@@ -202,6 +222,11 @@ pub trait ResponseCallback: fmt::Debug + Send + Sync + 'static {
     // consume the callback, which better communicates that you should only report
     // an error once. however, the current state-sync-v2 code makes this difficult...
     fn notify_bad_response(&self, error: ResponseError);
+
+    /// Returns the peer that served the response (if known)
+    fn get_peer(&self) -> Option<PeerNetworkId> {
+        None
+    }
 }
 
 /// A unique identifier for each response
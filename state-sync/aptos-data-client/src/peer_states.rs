@@ -41,6 +41,8 @@ const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
 const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
+/// The weight given to new latency samples in the moving average.
+const RESPONSE_LATENCY_SMOOTHING_FACTOR: f64 = 0.2;
 
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
@@ -73,6 +75,13 @@ pub struct PeerState {
     /// The number of requests sent to this peer (by data request label)
     sent_requests_by_type: Arc<DashMap<String, u64>>,
 
+    /// The (exponential moving) average response latency of the peer, or
+    /// `None` if we haven't received a response from them yet.
+    average_response_latency_secs: Option<f64>,
+
+    /// The number of errors (e.g., timeouts or bad responses) for this peer
+    num_errors: u64,
+
     /// The latest observed advertised data for this peer, or `None` if we
     /// haven't polled them yet.
     storage_summary: Option<StorageServerSummary>,
@@ -87,6 +96,8 @@ impl PeerState {
             data_client_config,
             received_responses_by_type: Arc::new(DashMap::new()),
             sent_requests_by_type: Arc::new(DashMap::new()),
+            average_response_latency_secs: None,
+            num_errors: 0,
             storage_summary: None,
             score: STARTING_SCORE,
         }
@@ -115,6 +126,16 @@ impl PeerState {
         self.score
     }
 
+    /// Returns the average response latency of the peer (in seconds)
+    pub fn get_average_response_latency_secs(&self) -> Option<f64> {
+        self.average_response_latency_secs
+    }
+
+    /// Returns the number of errors for the peer
+    pub fn get_num_errors(&self) -> u64 {
+        self.num_errors
+    }
+
     /// Returns the storage summary for the peer
     pub fn get_storage_summary(&self) -> Option<StorageServerSummary> {
         self.storage_summary.clone()
@@ -171,6 +192,20 @@ impl PeerState {
             ErrorType::Malicious => MALICIOUS_MULTIPLIER,
         };
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
+        self.num_errors += 1;
+    }
+
+    /// Updates the average response latency of the peer with a new sample
+    fn update_response_latency(&mut self, response_latency: Duration) {
+        let response_latency_secs = response_latency.as_secs_f64();
+        self.average_response_latency_secs = Some(match self.average_response_latency_secs {
+            Some(average_latency_secs) => {
+                average_latency_secs
+                    + RESPONSE_LATENCY_SMOOTHING_FACTOR
+                        * (response_latency_secs - average_latency_secs)
+            },
+            None => response_latency_secs,
+        });
     }
 
     /// Updates the storage summary for the peer
@@ -321,6 +356,13 @@ impl PeerStates {
         }
     }
 
+    /// Updates the average response latency for the given peer
+    pub fn update_response_latency(&self, peer: PeerNetworkId, response_latency: Duration) {
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.update_response_latency(response_latency);
+        }
+    }
+
     /// Updates the storage summary for the given peer
     pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
         self.peer_to_state
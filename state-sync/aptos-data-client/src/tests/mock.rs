@@ -31,7 +31,9 @@ use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_server::network::{NetworkRequest, ResponseSender};
 use aptos_storage_service_types::{
-    responses::TransactionOrOutputListWithProof, Epoch, StorageServiceMessage,
+    requests::StateKeySelector,
+    responses::{StateValuesByKeysWithProof, TransactionOrOutputListWithProof},
+    Epoch, StorageServiceMessage,
 };
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
@@ -315,6 +317,13 @@ mock! {
             request_timeout_ms: u64,
         ) -> Result<Response<StateValueChunkWithProof>>;
 
+        async fn get_state_values_by_keys_with_proof(
+            &self,
+            version: u64,
+            state_keys: StateKeySelector,
+            request_timeout_ms: u64,
+        ) -> Result<Response<StateValuesByKeysWithProof>>;
+
         async fn get_transaction_outputs_with_proof(
             &self,
             proof_version: Version,
@@ -8,5 +8,6 @@ mod multi_fetch;
 mod peers;
 mod poller;
 mod priority;
+mod state_values;
 mod utils;
 mod weighted_selection;
@@ -0,0 +1,108 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    interface::AptosDataClientInterface,
+    priority::PeerPriority,
+    tests::{mock::MockNetwork, utils},
+};
+use aptos_config::config::AptosDataClientConfig;
+use aptos_crypto::{hash::SPARSE_MERKLE_PLACEHOLDER_HASH, HashValue};
+use aptos_storage_service_types::{
+    requests::{DataRequest, StateKeySelector, StateValuesByKeysWithProofRequest},
+    responses::{
+        CompleteDataRange, DataResponse, StateValueWithProof, StateValuesByKeysWithProof,
+        StorageServiceResponse,
+    },
+};
+use aptos_types::{proof::SparseMerkleProof, state_store::state_key::StateKey};
+use claims::assert_matches;
+
+#[tokio::test]
+async fn fetch_state_values_by_keys() {
+    // Create the mock network and client
+    let data_client_config = AptosDataClientConfig::default();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add a connected peer that advertises the states up to the version
+    let version = 100;
+    let (peer, network_id) =
+        utils::add_peer_to_network(PeerPriority::HighPriority, &mut mock_network);
+    let mut storage_summary = utils::create_storage_summary(version);
+    storage_summary.data_summary.states = Some(CompleteDataRange::new(0, version).unwrap());
+    client.update_peer_storage_summary(peer, storage_summary);
+
+    // Create the response (i.e., non-existent keys in an empty state tree)
+    let state_keys = vec![StateKey::raw(&[0]), StateKey::raw(&[1])];
+    let state_values_by_keys_with_proof = StateValuesByKeysWithProof {
+        version,
+        root_hash: *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        state_values: state_keys
+            .iter()
+            .map(|state_key| StateValueWithProof {
+                state_key: state_key.clone(),
+                state_value: None,
+                proof: SparseMerkleProof::new(None, vec![]),
+            })
+            .collect(),
+        next_state_key: None,
+    };
+
+    // Handle the client's state values request
+    let expected_state_keys = state_keys.clone();
+    let response = state_values_by_keys_with_proof.clone();
+    tokio::spawn(async move {
+        // Verify the received network request
+        let network_request = utils::get_network_request(&mut mock_network, network_id).await;
+        assert_eq!(
+            network_request.storage_service_request.data_request,
+            DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+                version,
+                state_keys: StateKeySelector::Keys(expected_state_keys),
+            })
+        );
+
+        // Send the (compressed) response
+        let use_compression = network_request.storage_service_request.use_compression;
+        assert!(use_compression);
+        let data_response = DataResponse::StateValuesByKeysWithProof(response);
+        network_request
+            .response_sender
+            .send(Ok(StorageServiceResponse::new(
+                data_response,
+                use_compression,
+            )
+            .unwrap()));
+    });
+
+    // Fetch the state values and verify the response
+    let request_timeout = data_client_config.response_timeout_ms;
+    let response = client
+        .get_state_values_by_keys_with_proof(
+            version,
+            StateKeySelector::Keys(state_keys.clone()),
+            request_timeout,
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.payload, state_values_by_keys_with_proof);
+
+    // Verify the proofs only hold against the root hash of the state values
+    response
+        .payload
+        .verify(*SPARSE_MERKLE_PLACEHOLDER_HASH)
+        .unwrap();
+    response.payload.verify(HashValue::random()).unwrap_err();
+
+    // Verify that states which are not advertised can't be fetched
+    let error = client
+        .get_state_values_by_keys_with_proof(
+            version + 1,
+            StateKeySelector::Keys(state_keys),
+            request_timeout,
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::DataIsUnavailable(_));
+}
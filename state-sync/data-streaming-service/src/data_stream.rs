@@ -13,6 +13,7 @@ use crate::{
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
+    diagnostics::DataStreamSummary,
     dynamic_prefetching::DynamicPrefetchingState,
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
@@ -23,7 +24,10 @@ use crate::{
     streaming_service::StreamUpdateNotification,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::config::{AptosDataClientConfig, DataStreamingServiceConfig};
+use aptos_config::{
+    config::{AptosDataClientConfig, DataStreamingServiceConfig},
+    network_id::PeerNetworkId,
+};
 use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary},
     interface::{
@@ -74,6 +78,9 @@ pub struct DataStream<T> {
     // The unique ID for this data stream. This is useful for logging.
     data_stream_id: DataStreamId,
 
+    // The label of the stream request that created this stream
+    stream_request_label: &'static str,
+
     // The data client through which to fetch data from the Aptos network
     aptos_data_client: T,
 
@@ -123,6 +130,12 @@ pub struct DataStream<T> {
 
     // The dynamic prefetching state (if enabled)
     dynamic_prefetching_state: DynamicPrefetchingState,
+
+    // The number of data notifications sent along the stream
+    num_notifications_sent: u64,
+
+    // The number of responses (sent along the stream) served by each peer
+    responses_by_peer: BTreeMap<PeerNetworkId, u64>,
 }
 
 impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
@@ -154,6 +167,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             data_client_config,
             streaming_service_config: data_stream_config,
             data_stream_id,
+            stream_request_label: stream_request.get_label(),
             aptos_data_client,
             stream_engine,
             stream_update_notifier,
@@ -168,6 +182,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             subscription_stream_lag: None,
             time_service,
             dynamic_prefetching_state,
+            num_notifications_sent: 0,
+            responses_by_peer: BTreeMap::new(),
         };
 
         Ok((data_stream, data_stream_listener))
@@ -413,6 +429,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             self.send_failure = true;
             Err(error)
         } else {
+            self.num_notifications_sent += 1;
             Ok(())
         }
     }
@@ -784,6 +801,11 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                 response_context.creation_time,
             );
 
+            // Track the peer that served the response
+            if let Some(peer) = response_context.response_callback.get_peer() {
+                *self.responses_by_peer.entry(peer).or_insert(0) += 1;
+            }
+
             // Save the response context for this notification ID
             let notification_id = data_notification.notification_id;
             self.insert_notification_response_mapping(notification_id, response_context)?;
@@ -905,6 +927,40 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         Ok(num_pending_data_requests)
     }
 
+    /// Returns a summary of the current state of the data stream
+    pub fn get_data_stream_summary(&self) -> DataStreamSummary {
+        let (next_stream_item_type, next_stream_item) = self.stream_engine.get_next_stream_item();
+        let num_pending_requests = self
+            .sent_data_requests
+            .as_ref()
+            .map(|sent_data_requests| sent_data_requests.len() as u64)
+            .unwrap_or(0);
+        let max_concurrent_requests = self
+            .dynamic_prefetching_state
+            .get_max_concurrent_requests(&self.stream_engine);
+        let responses_by_peer = self
+            .responses_by_peer
+            .iter()
+            .map(|(peer, num_responses)| (peer.to_string(), *num_responses))
+            .collect();
+
+        DataStreamSummary {
+            data_stream_id: self.data_stream_id,
+            stream_request: self.stream_request_label.into(),
+            next_stream_item_type: next_stream_item_type.into(),
+            next_stream_item,
+            num_notifications_sent: self.num_notifications_sent,
+            num_pending_requests,
+            max_concurrent_requests,
+            request_failure_count: self.request_failure_count,
+            subscription_stream_lag: self
+                .subscription_stream_lag
+                .as_ref()
+                .map(|subscription_stream_lag| subscription_stream_lag.version_lag),
+            responses_by_peer,
+        }
+    }
+
     /// Assumes the caller has already verified that `sent_data_requests` has
     /// been initialized.
     fn get_sent_data_requests(&mut self) -> Result<&mut VecDeque<PendingClientResponse>, Error> {
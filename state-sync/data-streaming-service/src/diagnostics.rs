@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::data_stream::DataStreamId;
+use aptos_infallible::RwLock;
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// A summary of the state of a single data stream (e.g., for debugging
+/// slow syncing via the inspection service).
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct DataStreamSummary {
+    /// The unique ID of the data stream
+    pub data_stream_id: DataStreamId,
+
+    /// The label of the stream request that created the stream
+    pub stream_request: String,
+
+    /// The type of the next item to be sent along the stream (e.g., "version")
+    pub next_stream_item_type: String,
+
+    /// The next item (e.g., version, epoch or state index) to be sent along the stream
+    pub next_stream_item: u64,
+
+    /// The number of data notifications sent along the stream
+    pub num_notifications_sent: u64,
+
+    /// The number of data client requests that are currently pending
+    pub num_pending_requests: u64,
+
+    /// The maximum number of concurrent requests (i.e., the current prefetch level)
+    pub max_concurrent_requests: u64,
+
+    /// The failure count of the request at the head of the request queue
+    pub request_failure_count: u64,
+
+    /// The version lag of the subscription stream (if any)
+    pub subscription_stream_lag: Option<u64>,
+
+    /// The number of responses (sent along the stream) served by each peer
+    pub responses_by_peer: BTreeMap<String, u64>,
+}
+
+/// A shared and read-only view of all active data streams. This is updated
+/// by the data streaming service each time the stream progress is checked.
+#[derive(Clone, Debug, Default)]
+pub struct StreamingServiceDiagnostics {
+    data_stream_summaries: Arc<RwLock<BTreeMap<DataStreamId, DataStreamSummary>>>,
+}
+
+impl StreamingServiceDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the summaries of all active data streams (sorted by stream ID)
+    pub fn get_data_stream_summaries(&self) -> Vec<DataStreamSummary> {
+        self.data_stream_summaries
+            .read()
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Replaces the summaries of all active data streams
+    pub fn update_data_stream_summaries(&self, data_stream_summaries: Vec<DataStreamSummary>) {
+        *self.data_stream_summaries.write() = data_stream_summaries
+            .into_iter()
+            .map(|summary| (summary.data_stream_id, summary))
+            .collect();
+    }
+}
@@ -162,6 +162,25 @@ impl StreamEngine {
             ))),
         }
     }
+
+    /// Returns the type and value of the next item to be sent along
+    /// the stream (i.e., the progress of the stream).
+    pub fn get_next_stream_item(&self) -> (&'static str, u64) {
+        match self {
+            StreamEngine::ContinuousTransactionStreamEngine(stream_engine) => {
+                ("version", stream_engine.next_stream_version_and_epoch.0)
+            },
+            StreamEngine::EpochEndingStreamEngine(stream_engine) => {
+                ("epoch", stream_engine.next_stream_epoch)
+            },
+            StreamEngine::StateStreamEngine(stream_engine) => {
+                ("state_index", stream_engine.next_stream_index)
+            },
+            StreamEngine::TransactionStreamEngine(stream_engine) => {
+                ("version", stream_engine.next_stream_version)
+            },
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
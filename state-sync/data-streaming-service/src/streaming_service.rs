@@ -4,6 +4,7 @@
 
 use crate::{
     data_stream::{DataStream, DataStreamId, DataStreamListener},
+    diagnostics::StreamingServiceDiagnostics,
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
@@ -85,6 +86,9 @@ pub struct DataStreamingService<T> {
 
     // The time service used to track elapsed time (e.g., for stream progress checks)
     time_service: TimeService,
+
+    // The diagnostics of all active data streams (e.g., for the inspection service)
+    streaming_service_diagnostics: StreamingServiceDiagnostics,
 }
 
 impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<T> {
@@ -112,9 +116,15 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
             stream_id_generator: U64IdGenerator::new(),
             notification_id_generator: Arc::new(U64IdGenerator::new()),
             time_service,
+            streaming_service_diagnostics: StreamingServiceDiagnostics::new(),
         }
     }
 
+    /// Returns a handle to the diagnostics of all active data streams
+    pub fn get_streaming_service_diagnostics(&self) -> StreamingServiceDiagnostics {
+        self.streaming_service_diagnostics.clone()
+    }
+
     /// Starts the dedicated streaming service
     pub async fn start_service(mut self) {
         // Spawn a dedicated task that refreshes the global data summary
@@ -335,6 +345,15 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
 
         // Update the metrics
         metrics::set_active_data_streams(data_stream_ids.len());
+
+        // Update the diagnostics
+        let data_stream_summaries = self
+            .data_streams
+            .values()
+            .map(|data_stream| data_stream.get_data_stream_summary())
+            .collect();
+        self.streaming_service_diagnostics
+            .update_data_stream_summaries(data_stream_summaries);
     }
 
     /// Ensures that a data stream has in-flight data requests and handles
@@ -184,6 +184,69 @@ async fn test_stream_initialization() {
     assert_ne!(sent_requests.as_ref().unwrap().len(), 0);
 }
 
+#[tokio::test]
+async fn test_stream_summary() {
+    // Create an epoch ending data stream (with dynamic prefetching disabled)
+    let max_concurrent_requests = 3;
+    let streaming_service_config = DataStreamingServiceConfig {
+        dynamic_prefetching: DynamicPrefetchingConfig {
+            enable_dynamic_prefetching: false,
+            ..Default::default()
+        },
+        max_concurrent_requests,
+        ..Default::default()
+    };
+    let (mut data_stream, mut stream_listener) = create_epoch_ending_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_EPOCH_END,
+    );
+
+    // Initialize the data stream
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+
+    // Verify the stream summary
+    let data_stream_summary = data_stream.get_data_stream_summary();
+    assert_eq!(
+        data_stream_summary.stream_request,
+        "get_all_epoch_ending_ledger_infos"
+    );
+    assert_eq!(data_stream_summary.next_stream_item_type, "epoch");
+    assert_eq!(
+        data_stream_summary.next_stream_item,
+        MIN_ADVERTISED_EPOCH_END
+    );
+    assert_eq!(data_stream_summary.num_notifications_sent, 0);
+    assert_eq!(
+        data_stream_summary.num_pending_requests,
+        max_concurrent_requests
+    );
+    assert_eq!(
+        data_stream_summary.max_concurrent_requests,
+        max_concurrent_requests
+    );
+
+    // Set a response for the first request and verify a notification is sent
+    set_epoch_ending_response_in_queue(&mut data_stream, 0, 0);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    verify_epoch_ending_notification(
+        &mut stream_listener,
+        create_ledger_info(0, MIN_ADVERTISED_EPOCH_END, true),
+    )
+    .await;
+
+    // Verify the stream summary has been updated
+    let data_stream_summary = data_stream.get_data_stream_summary();
+    assert_eq!(
+        data_stream_summary.next_stream_item,
+        MIN_ADVERTISED_EPOCH_END + 1
+    );
+    assert_eq!(data_stream_summary.num_notifications_sent, 1);
+    assert_eq!(data_stream_summary.request_failure_count, 0);
+    assert!(data_stream_summary.responses_by_peer.is_empty()); // The mock callback has no peer
+}
+
 #[tokio::test]
 async fn test_stream_data_error() {
     // Create an epoch ending data stream
@@ -18,12 +18,16 @@ use aptos_storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        StateKeySelector, StateValuesByKeysWithProofRequest, StateValuesWithProofRequest,
+        SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, TransactionOrOutputListWithProof},
+    responses::{
+        CompleteDataRange, StateValueWithProof, StateValuesByKeysWithProof,
+        TransactionOrOutputListWithProof,
+    },
     Epoch,
 };
 use aptos_types::{
@@ -33,7 +37,7 @@ use aptos_types::{
     chain_id::ChainId,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    proof::SparseMerkleRangeProof,
+    proof::{SparseMerkleProof, SparseMerkleRangeProof},
     state_store::{
         state_key::StateKey,
         state_value::{StateValue, StateValueChunkWithProof},
@@ -327,6 +331,46 @@ impl AptosDataClientInterface for MockAptosDataClient {
         Ok(create_data_client_response(state_value_chunk_with_proof))
     }
 
+    async fn get_state_values_by_keys_with_proof(
+        &self,
+        version: Version,
+        state_keys: StateKeySelector,
+        request_timeout_ms: u64,
+    ) -> Result<Response<StateValuesByKeysWithProof>, aptos_data_client::error::Error> {
+        // Verify the request timeout
+        let data_request =
+            DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+                version,
+                state_keys: state_keys.clone(),
+            });
+        self.verify_request_timeout_value(request_timeout_ms, false, false, data_request);
+
+        // Emulate network latencies
+        self.emulate_network_latencies().await;
+
+        // Create a (non-existent) state value for each requested key
+        let state_values = match state_keys {
+            StateKeySelector::Account { .. } => vec![],
+            StateKeySelector::Keys(state_keys) => state_keys
+                .into_iter()
+                .map(|state_key| StateValueWithProof {
+                    state_key,
+                    state_value: None,
+                    proof: SparseMerkleProof::new(None, vec![]),
+                })
+                .collect(),
+        };
+        let state_values_by_keys_with_proof = StateValuesByKeysWithProof {
+            version,
+            root_hash: HashValue::zero(),
+            state_values,
+            next_state_key: None,
+        };
+
+        // Create and send a data client response
+        Ok(create_data_client_response(state_values_by_keys_with_proof))
+    }
+
     async fn get_epoch_ending_ledger_infos(
         &self,
         start_epoch: Epoch,
@@ -699,15 +699,7 @@ impl<
                 // rewrite the last persisted index (again!). This is a limitation
                 // of how the snapshot is persisted (i.e., in-memory sibling freezing).
                 // Thus, on each stream reset, we overlap every chunk by a single item.
-                self
-                    .metadata_storage
-                    .get_last_persisted_state_value_index(&target_ledger_info)
-                    .map_err(|error| {
-                        Error::StorageError(format!(
-                            "Failed to get the last persisted state value index at version {:?}! Error: {:?}",
-                            target_ledger_info_version, error
-                        ))
-                    })?
+                self.get_state_value_index_to_resume(&target_ledger_info)?
             } else {
                 0 // We need to start the snapshot sync from index 0
             };
@@ -727,6 +719,85 @@ impl<
         Ok(())
     }
 
+    /// Returns the state value index from which to resume the state snapshot
+    /// sync at the given target. If the state snapshot receiver has not yet
+    /// been initialized (e.g., after a reboot), the committed state value chunks
+    /// are reconciled with the restore progress in storage. This ensures we
+    /// resume from the last chunk that was actually persisted by storage.
+    fn get_state_value_index_to_resume(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+    ) -> Result<u64, Error> {
+        // Get the last persisted state value index
+        let target_ledger_info_version = target_ledger_info.ledger_info().version();
+        let last_persisted_state_value_index = self
+            .metadata_storage
+            .get_last_persisted_state_value_index(target_ledger_info)
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to get the last persisted state value index at version {:?}! Error: {:?}",
+                    target_ledger_info_version, error
+                ))
+            })?;
+
+        // If the state snapshot receiver is already running, the
+        // last persisted index is consistent with storage.
+        if self.state_value_syncer.initialized_state_snapshot_receiver {
+            return Ok(last_persisted_state_value_index);
+        }
+
+        // Get the restore progress of the state snapshot in storage
+        let expected_root_hash = self
+            .state_value_syncer
+            .transaction_output_to_sync
+            .as_ref()
+            .and_then(|output_to_sync| output_to_sync.proof.transaction_infos.first())
+            .ok_or_else(|| {
+                Error::UnexpectedError("Target transaction info does not exist!".into())
+            })?
+            .ensure_state_checkpoint_hash()
+            .map_err(|error| {
+                Error::UnexpectedError(format!("State checkpoint must exist! Error: {:?}", error))
+            })?;
+        let persisted_key_hash = match self
+            .storage_synchronizer
+            .get_state_snapshot_restore_progress(target_ledger_info_version, expected_root_hash)?
+        {
+            Some(persisted_key_hash) => persisted_key_hash,
+            None => {
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "No state values were persisted by storage for version {:?}. Restarting the \
+                    snapshot sync from index 0 (last persisted index: {:?})",
+                    target_ledger_info_version, last_persisted_state_value_index
+                )));
+                return Ok(0);
+            },
+        };
+
+        // Identify the last committed chunk that was persisted by storage
+        match self
+            .metadata_storage
+            .get_committed_state_value_chunk(target_ledger_info, persisted_key_hash)?
+        {
+            Some(chunk_progress) => {
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "Resuming the snapshot sync from the last committed chunk: {:?} \
+                    (last persisted index: {:?})",
+                    chunk_progress, last_persisted_state_value_index
+                )));
+                Ok(chunk_progress.last_state_value_index)
+            },
+            None => {
+                warn!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "No committed state value chunk was found for the persisted key hash: {:?}. \
+                    Resuming from the last persisted index: {:?}",
+                    persisted_key_hash, last_persisted_state_value_index
+                )));
+                Ok(last_persisted_state_value_index)
+            },
+        }
+    }
+
     /// Fetches all missing transaction data in order to bootstrap the node
     async fn fetch_missing_transaction_data(
         &mut self,
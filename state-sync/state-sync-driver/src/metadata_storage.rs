@@ -6,13 +6,14 @@ use crate::{
     metadata_storage::database_schema::{MetadataKey, MetadataSchema, MetadataValue},
 };
 use anyhow::{anyhow, Result};
+use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_schemadb::{
     define_schema,
     schema::{KeyCodec, ValueCodec},
     ColumnFamilyName, Options, SchemaBatch, DB,
 };
-use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
 use serde::{Deserialize, Serialize};
 use std::{path::Path, sync::Arc, time::Instant};
 
@@ -47,6 +48,23 @@ pub trait MetadataStorageInterface {
         last_persisted_state_value_index: u64,
         snapshot_sync_completed: bool,
     ) -> Result<(), Error>;
+
+    /// Returns the committed state value chunk (for the snapshot sync at the
+    /// specified target) with the highest last key hash that is less than or
+    /// equal to the given key hash. If no such chunk is found, None is returned.
+    fn get_committed_state_value_chunk(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+        key_hash: HashValue,
+    ) -> Result<Option<StateValueChunkProgress>, Error>;
+
+    /// Records that the given state value chunk has been verified and
+    /// committed for the state snapshot sync at the specified target.
+    fn record_committed_state_value_chunk(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+        chunk_progress: StateValueChunkProgress,
+    ) -> Result<(), Error>;
 }
 
 /// The name of the state sync db file
@@ -106,14 +124,66 @@ impl PersistentMetadataStorage {
                     ))
                 })?;
         match maybe_metadata_value {
-            Some(metadata_value) => {
-                let MetadataValue::StateSnapshotSync(snapshot_progress) = metadata_value;
+            Some(MetadataValue::StateSnapshotSync(snapshot_progress)) => {
                 Ok(Some(snapshot_progress))
             },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value found for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
             None => Ok(None),
         }
     }
 
+    /// Verifies that if any previous snapshot progress exists, it has the same target
+    fn verify_snapshot_sync_target(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+    ) -> Result<(), Error> {
+        if let Some(snapshot_progress) = self.get_snapshot_progress()? {
+            if target_ledger_info != &snapshot_progress.target_ledger_info {
+                return Err(Error::StorageError(format!("Failed to update the state snapshot sync progress! \
+                The given target does not match the previously stored target. Given target: {:?}, stored target: {:?}",
+                    target_ledger_info, snapshot_progress.target_ledger_info
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the keys of all committed state value chunks in the database
+    fn get_committed_state_value_chunk_keys(&self) -> Result<Vec<MetadataKey>, Error> {
+        let mut iterator = self.database.iter::<MetadataSchema>().map_err(|error| {
+            Error::StorageError(format!(
+                "Failed to create a metadata iterator! Error: {:?}",
+                error
+            ))
+        })?;
+        iterator
+            .seek(&MetadataKey::StateValueChunk(HashValue::zero()))
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to seek to the committed state value chunks! Error: {:?}",
+                    error
+                ))
+            })?;
+
+        let mut chunk_keys = vec![];
+        for entry in iterator {
+            let (metadata_key, _) = entry.map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to read a committed state value chunk! Error: {:?}",
+                    error
+                ))
+            })?;
+            match metadata_key {
+                MetadataKey::StateValueChunk(_) => chunk_keys.push(metadata_key),
+                _ => break, // We've iterated past all state value chunks
+            }
+        }
+        Ok(chunk_keys)
+    }
+
     /// Returns the snapshot sync progress recorded for the specified version.
     /// Returns an error if no progress was found.
     fn get_snapshot_progress_at_target(
@@ -142,6 +212,17 @@ impl PersistentMetadataStorage {
         &self,
         metadata_key: MetadataKey,
         metadata_value: MetadataValue,
+    ) -> Result<(), Error> {
+        self.commit_key_value_and_delete_keys(metadata_key, metadata_value, vec![])
+    }
+
+    /// Write the key value pair to the database and delete the
+    /// given keys (atomically, in the same batch).
+    fn commit_key_value_and_delete_keys(
+        &self,
+        metadata_key: MetadataKey,
+        metadata_value: MetadataValue,
+        keys_to_delete: Vec<MetadataKey>,
     ) -> Result<(), Error> {
         // Create the schema batch
         let batch = SchemaBatch::new();
@@ -152,6 +233,16 @@ impl PersistentMetadataStorage {
                     "Failed to batch put the metadata key and value. Key: {:?}, Value: {:?}. Error: {:?}", metadata_key, metadata_value, error
                 ))
             })?;
+        for key_to_delete in keys_to_delete {
+            batch
+                .delete::<MetadataSchema>(&key_to_delete)
+                .map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to batch delete the metadata key: {:?}. Error: {:?}",
+                        key_to_delete, error
+                    ))
+                })?;
+        }
 
         // Write the schema batch to the database
         self.database.write_schemas(batch).map_err(|error| {
@@ -204,14 +295,7 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
         snapshot_sync_completed: bool,
     ) -> Result<(), Error> {
         // Ensure that if any previous snapshot progress exists, it has the same target
-        if let Some(snapshot_progress) = self.get_snapshot_progress()? {
-            if target_ledger_info != &snapshot_progress.target_ledger_info {
-                return Err(Error::StorageError(format!("Failed to update the last persisted state value index! \
-                The given target does not match the previously stored target. Given target: {:?}, stored target: {:?}",
-                    target_ledger_info, snapshot_progress.target_ledger_info
-                )));
-            }
-        }
+        self.verify_snapshot_sync_target(target_ledger_info)?;
 
         // Create the key/value pair
         let metadata_key = MetadataKey::StateSnapshotSync;
@@ -221,7 +305,85 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
             target_ledger_info: target_ledger_info.clone(),
         });
 
+        // If the snapshot sync is complete, the committed chunks are no longer required
+        let keys_to_delete = if snapshot_sync_completed {
+            self.get_committed_state_value_chunk_keys()?
+        } else {
+            vec![]
+        };
+
         // Insert the new key/value pair
+        self.commit_key_value_and_delete_keys(metadata_key, metadata_value, keys_to_delete)
+    }
+
+    fn get_committed_state_value_chunk(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+        key_hash: HashValue,
+    ) -> Result<Option<StateValueChunkProgress>, Error> {
+        // Find the chunk with the highest last key hash <= the given key hash
+        let mut iterator = self
+            .database
+            .rev_iter::<MetadataSchema>()
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to create a metadata iterator! Error: {:?}",
+                    error
+                ))
+            })?;
+        iterator
+            .seek_for_prev(&MetadataKey::StateValueChunk(key_hash))
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to seek to the committed state value chunk for key hash: {:?}! Error: {:?}",
+                    key_hash, error
+                ))
+            })?;
+        let chunk_progress = match iterator.next().transpose().map_err(|error| {
+            Error::StorageError(format!(
+                "Failed to read the committed state value chunk for key hash: {:?}! Error: {:?}",
+                key_hash, error
+            ))
+        })? {
+            Some((
+                MetadataKey::StateValueChunk(_),
+                MetadataValue::StateValueChunk(chunk_progress),
+            )) => chunk_progress,
+            _ => return Ok(None), // No committed chunk was found
+        };
+
+        // Ensure the chunk was committed for the same target
+        let target_version = target_ledger_info.ledger_info().version();
+        if chunk_progress.target_version != target_version {
+            return Err(Error::UnexpectedError(format!(
+                "Expected a committed state value chunk for target version {:?}, but found {:?}!",
+                target_version, chunk_progress
+            )));
+        }
+
+        Ok(Some(chunk_progress))
+    }
+
+    fn record_committed_state_value_chunk(
+        &self,
+        target_ledger_info: &LedgerInfoWithSignatures,
+        chunk_progress: StateValueChunkProgress,
+    ) -> Result<(), Error> {
+        // Ensure that if any previous snapshot progress exists, it has the same target
+        self.verify_snapshot_sync_target(target_ledger_info)?;
+
+        // Verify the chunk target version
+        let target_version = target_ledger_info.ledger_info().version();
+        if chunk_progress.target_version != target_version {
+            return Err(Error::UnexpectedError(format!(
+                "The state value chunk does not match the target version {:?}! Chunk: {:?}",
+                target_version, chunk_progress
+            )));
+        }
+
+        // Insert the new key/value pair
+        let metadata_key = MetadataKey::StateValueChunk(chunk_progress.last_key_hash);
+        let metadata_value = MetadataValue::StateValueChunk(chunk_progress);
         self.commit_key_value(metadata_key, metadata_value)
     }
 }
@@ -234,6 +396,18 @@ pub struct StateSnapshotProgress {
     pub snapshot_sync_completed: bool,
 }
 
+/// A simple struct for recording a state value chunk that has been verified
+/// and committed during a state snapshot sync. Together with the progress of
+/// the state snapshot receiver in storage, this allows the sync to resume from
+/// the last committed chunk (e.g., after a crash or reboot).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueChunkProgress {
+    pub target_version: Version,
+    pub first_state_value_index: u64,
+    pub last_state_value_index: u64,
+    pub last_key_hash: HashValue,
+}
+
 /// The raw schema format used by the database
 pub mod database_schema {
     use super::*;
@@ -245,13 +419,18 @@ pub mod database_schema {
     //
     // |<-------key------->|<-----value----->|
     // |   metadata key    | metadata value  |
+    //
+    // Note: committed state value chunks are keyed by the hash of the last
+    // state key in each chunk. Given that the hash is encoded as fixed length
+    // bytes, the chunks are ordered by key hash (i.e., by state value index).
     define_schema!(MetadataSchema, MetadataKey, MetadataValue, METADATA_CF_NAME);
 
     /// A metadata key that can be inserted into the database
     #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[repr(u8)]
     pub enum MetadataKey {
-        StateSnapshotSync, // A state snapshot sync that was started
+        StateSnapshotSync,          // A state snapshot sync that was started
+        StateValueChunk(HashValue), // A state value chunk (keyed by the last key hash) that was committed
     }
 
     /// A metadata value that can be inserted into the database
@@ -259,6 +438,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataValue {
         StateSnapshotSync(StateSnapshotProgress), // A state snapshot sync progress marker
+        StateValueChunk(StateValueChunkProgress), // A committed state value chunk marker
     }
 
     impl KeyCodec<MetadataSchema> for MetadataKey {
@@ -5,7 +5,7 @@
 use crate::{
     error::Error,
     logging::{LogEntry, LogSchema},
-    metadata_storage::MetadataStorageInterface,
+    metadata_storage::{MetadataStorageInterface, StateValueChunkProgress},
     metrics,
     notification_handlers::{
         CommitNotification, CommittedTransactions, ErrorNotification, MempoolNotificationHandler,
@@ -14,6 +14,7 @@ use crate::{
     utils,
 };
 use aptos_config::config::StateSyncDriverConfig;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_data_streaming_service::data_notification::NotificationId;
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_executor_types::{ChunkCommitNotification, ChunkExecutorTrait};
@@ -92,6 +93,17 @@ pub trait StorageSynchronizerInterface {
     /// to be executed/applied or committed.
     fn pending_storage_data(&self) -> bool;
 
+    /// Returns the hash of the last state key persisted by any previous
+    /// (and interrupted) state snapshot restore at the specified version
+    /// and expected root hash. Returns None if no state values were persisted.
+    ///
+    /// Note: this must be called before `initialize_state_synchronizer`.
+    fn get_state_snapshot_restore_progress(
+        &self,
+        version: Version,
+        expected_root_hash: HashValue,
+    ) -> Result<Option<HashValue>, Error>;
+
     /// Saves the given state values to storage.
     ///
     /// Note: this requires that `initialize_state_synchronizer` has been
@@ -403,6 +415,32 @@ impl<
         load_pending_data_chunks(self.pending_data_chunks.clone()) > 0
     }
 
+    fn get_state_snapshot_restore_progress(
+        &self,
+        version: Version,
+        expected_root_hash: HashValue,
+    ) -> Result<Option<HashValue>, Error> {
+        // Create a (temporary) state snapshot receiver to read the restore progress
+        let state_snapshot_receiver = self
+            .storage
+            .writer
+            .get_state_snapshot_receiver(version, expected_root_hash)
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to create a state snapshot receiver at version: {:?}! Error: {:?}",
+                    version, error
+                ))
+            })?;
+        state_snapshot_receiver
+            .previous_key_hash()
+            .map_err(|error| {
+                Error::StorageError(format!(
+                    "Failed to get the state snapshot restore progress at version: {:?}! Error: {:?}",
+                    version, error
+                ))
+            })
+    }
+
     async fn save_state_values(
         &mut self,
         notification_id: NotificationId,
@@ -841,6 +879,16 @@ fn spawn_state_snapshot_receiver<
                     let all_states_synced = states_with_proof.is_last_chunk();
                     let last_committed_state_index = states_with_proof.last_index;
                     let num_state_values = states_with_proof.raw_values.len();
+                    let chunk_progress =
+                        states_with_proof
+                            .raw_values
+                            .last()
+                            .map(|(last_state_key, _)| StateValueChunkProgress {
+                                target_version: version,
+                                first_state_value_index: states_with_proof.first_index,
+                                last_state_value_index: last_committed_state_index,
+                                last_key_hash: CryptoHash::hash(last_state_key),
+                            });
 
                     let result = state_snapshot_receiver.add_chunk(
                         states_with_proof.raw_values,
@@ -874,6 +922,24 @@ fn spawn_state_snapshot_receiver<
                             );
 
                             if !all_states_synced {
+                                // Record the committed chunk so that it can be resumed from
+                                if let Some(chunk_progress) = chunk_progress {
+                                    if let Err(error) = metadata_storage
+                                        .record_committed_state_value_chunk(
+                                            &target_ledger_info,
+                                            chunk_progress,
+                                        )
+                                    {
+                                        let error = format!("Failed to record the committed state value chunk at version: {:?}! Error: {:?}", version, error);
+                                        send_storage_synchronizer_error(
+                                            error_notification_sender.clone(),
+                                            notification_id,
+                                            error,
+                                        )
+                                        .await;
+                                    }
+                                }
+
                                 // Update the metadata storage with the last committed state index
                                 if let Err(error) = metadata_storage
                                     .clone()
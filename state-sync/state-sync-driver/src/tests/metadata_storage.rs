@@ -5,9 +5,11 @@ use crate::{
     metadata_storage::{
         database_schema::{MetadataKey, MetadataSchema, MetadataValue},
         MetadataStorageInterface, PersistentMetadataStorage, StateSnapshotProgress,
+        StateValueChunkProgress,
     },
     tests::utils::{create_epoch_ending_ledger_info, create_ledger_info_at_version},
 };
+use aptos_crypto::HashValue;
 use aptos_schemadb::schema::fuzzing::assert_encode_decode;
 use aptos_temppath::TempPath;
 use claims::{assert_err, assert_none};
@@ -101,6 +103,112 @@ fn test_metadata_schema_encode_decode() {
     );
 }
 
+#[test]
+fn test_metadata_schema_encode_decode_chunk() {
+    let last_key_hash = HashValue::random();
+    assert_encode_decode::<MetadataSchema>(
+        &MetadataKey::StateValueChunk(last_key_hash),
+        &MetadataValue::StateValueChunk(StateValueChunkProgress {
+            target_version: 100,
+            first_state_value_index: 1000,
+            last_state_value_index: 1999,
+            last_key_hash,
+        }),
+    );
+}
+
+#[test]
+fn test_committed_state_value_chunks() {
+    // Create a new metadata storage
+    let tmp_dir = TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify that no committed chunks are found
+    let target_version = 5000;
+    let target_ledger_info = create_ledger_info_at_version(target_version);
+    assert_none!(metadata_storage
+        .get_committed_state_value_chunk(&target_ledger_info, HashValue::random())
+        .unwrap());
+
+    // Record several committed chunks (with increasing last key hashes)
+    let num_chunks = 10;
+    let chunk_size = 100;
+    let chunks = create_state_value_chunks(target_version, num_chunks, chunk_size);
+    for chunk_progress in &chunks {
+        metadata_storage
+            .record_committed_state_value_chunk(&target_ledger_info, chunk_progress.clone())
+            .unwrap();
+        metadata_storage
+            .update_last_persisted_state_value_index(
+                &target_ledger_info,
+                chunk_progress.last_state_value_index,
+                false,
+            )
+            .unwrap();
+    }
+
+    // Drop the handle to the storage (mimic a reboot)
+    drop(metadata_storage);
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify the chunks are found for exact key hashes
+    for chunk_progress in &chunks {
+        assert_eq!(
+            Some(chunk_progress.clone()),
+            metadata_storage
+                .get_committed_state_value_chunk(&target_ledger_info, chunk_progress.last_key_hash)
+                .unwrap()
+        );
+    }
+
+    // Verify the previous chunk is found for key hashes between chunks
+    for (index, chunk_progress) in chunks.iter().enumerate().skip(1) {
+        let key_hash = decrement_hash(chunk_progress.last_key_hash);
+        assert_eq!(
+            Some(chunks[index - 1].clone()),
+            metadata_storage
+                .get_committed_state_value_chunk(&target_ledger_info, key_hash)
+                .unwrap()
+        );
+    }
+
+    // Verify no chunk is found for key hashes before the first chunk
+    let key_hash = decrement_hash(chunks[0].last_key_hash);
+    assert_none!(metadata_storage
+        .get_committed_state_value_chunk(&target_ledger_info, key_hash)
+        .unwrap());
+
+    // Verify the last chunk is found for the highest key hash
+    let key_hash = HashValue::new([u8::MAX; HashValue::LENGTH]);
+    assert_eq!(
+        chunks.last().cloned(),
+        metadata_storage
+            .get_committed_state_value_chunk(&target_ledger_info, key_hash)
+            .unwrap()
+    );
+
+    // Verify that lookups and records fail for a different target
+    let different_target = create_ledger_info_at_version(target_version + 1);
+    assert_err!(metadata_storage
+        .get_committed_state_value_chunk(&different_target, chunks[0].last_key_hash));
+    assert_err!(
+        metadata_storage.record_committed_state_value_chunk(&different_target, chunks[0].clone())
+    );
+
+    // Mark the snapshot sync as complete and verify the chunks are removed
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, num_chunks * chunk_size, true)
+        .unwrap();
+    for chunk_progress in &chunks {
+        assert_none!(metadata_storage
+            .get_committed_state_value_chunk(&target_ledger_info, chunk_progress.last_key_hash)
+            .unwrap());
+    }
+    assert!(metadata_storage
+        .is_snapshot_sync_complete(&target_ledger_info)
+        .unwrap());
+}
+
 #[test]
 fn test_multiple_reads_and_writes() {
     // Create a new metadata storage
@@ -167,3 +275,39 @@ fn test_writes_to_different_targets() {
         .update_last_persisted_state_value_index(&target_ledger_info, 10101, false)
         .unwrap_err();
 }
+
+/// Creates a set of committed state value chunks with increasing last key hashes
+fn create_state_value_chunks(
+    target_version: u64,
+    num_chunks: u64,
+    chunk_size: u64,
+) -> Vec<StateValueChunkProgress> {
+    let mut last_key_hashes: Vec<_> = (0..num_chunks).map(|_| HashValue::random()).collect();
+    last_key_hashes.sort();
+    last_key_hashes
+        .into_iter()
+        .enumerate()
+        .map(|(index, last_key_hash)| {
+            let first_state_value_index = index as u64 * chunk_size;
+            StateValueChunkProgress {
+                target_version,
+                first_state_value_index,
+                last_state_value_index: first_state_value_index + chunk_size - 1,
+                last_key_hash,
+            }
+        })
+        .collect()
+}
+
+/// Returns the hash value immediately before the given hash value
+fn decrement_hash(hash_value: HashValue) -> HashValue {
+    let mut bytes = hash_value.to_vec();
+    for byte in bytes.iter_mut().rev() {
+        if *byte > 0 {
+            *byte -= 1;
+            break;
+        }
+        *byte = u8::MAX;
+    }
+    HashValue::from_slice(bytes).unwrap()
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     error::Error,
-    metadata_storage::MetadataStorageInterface,
+    metadata_storage::{MetadataStorageInterface, StateValueChunkProgress},
     storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
     tests::utils::{create_empty_epoch_state, create_epoch_ending_ledger_info},
 };
@@ -119,6 +119,9 @@ pub fn create_ready_storage_synchronizer(expect_reset_executor: bool) -> MockSto
     mock_storage_synchronizer
         .expect_pending_storage_data()
         .return_const(false);
+    mock_storage_synchronizer
+        .expect_get_state_snapshot_restore_progress()
+        .returning(|_, _| Ok(Some(HashValue::zero())));
     if expect_reset_executor {
         mock_storage_synchronizer
             .expect_finish_chunk_executor()
@@ -355,6 +358,18 @@ mock! {
             last_persisted_state_value_index: u64,
             snapshot_sync_completed: bool,
         ) -> Result<(), Error>;
+
+        fn get_committed_state_value_chunk(
+            &self,
+            target_ledger_info: &LedgerInfoWithSignatures,
+            key_hash: HashValue,
+        ) -> Result<Option<StateValueChunkProgress>, Error>;
+
+        fn record_committed_state_value_chunk(
+            &self,
+            target_ledger_info: &LedgerInfoWithSignatures,
+            chunk_progress: StateValueChunkProgress,
+        ) -> Result<(), Error>;
     }
 
     impl Clone for MetadataStorage {
@@ -371,6 +386,8 @@ mock! {
         fn finish(self) -> Result<()>;
 
         fn finish_box(self: Box<Self>) -> Result<()>;
+
+        fn previous_key_hash(&self) -> Result<Option<HashValue>>;
     }
 }
 
@@ -477,6 +494,12 @@ mock! {
 
         fn pending_storage_data(&self) -> bool;
 
+        fn get_state_snapshot_restore_progress(
+            &self,
+            version: Version,
+            expected_root_hash: HashValue,
+        ) -> AnyhowResult<Option<HashValue>, crate::error::Error>;
+
         async fn save_state_values(
             &mut self,
             notification_id: NotificationId,
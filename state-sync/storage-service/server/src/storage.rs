@@ -3,15 +3,24 @@
 
 use crate::{error::Error, metrics::increment_network_frame_overflow};
 use aptos_config::config::StorageServiceConfig;
+use aptos_crypto::HashValue;
 use aptos_logger::debug;
 use aptos_storage_interface::{AptosDbError, DbReader, Result as StorageResult};
-use aptos_storage_service_types::responses::{
-    CompleteDataRange, DataResponse, DataSummary, TransactionOrOutputListWithProof,
+use aptos_storage_service_types::{
+    requests::StateKeySelector,
+    responses::{
+        CompleteDataRange, DataResponse, DataSummary, StateValueWithProof,
+        StateValuesByKeysWithProof, TransactionOrOutputListWithProof,
+    },
 };
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    proof::SparseMerkleProof,
+    state_store::{
+        state_key::{filter::StateKeyFilter, prefix::StateKeyPrefix, StateKey},
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use serde::Serialize;
@@ -89,6 +98,18 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Returns the state values (each with a sparse Merkle proof) for the
+    /// selected state keys at the specified version. If the selector is an
+    /// account, all state values under the account are returned (in key
+    /// order, starting at the cursor). In some cases, less state values may
+    /// be returned (e.g., due to network or chunk limits), and the next
+    /// state key to fetch is included in the response.
+    fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: &StateKeySelector,
+    ) -> aptos_storage_service_types::Result<StateValuesByKeysWithProof, Error>;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -115,6 +136,16 @@ impl StorageReader {
         latest_version: Version,
         transactions_range: &Option<CompleteDataRange<Version>>,
     ) -> aptos_storage_service_types::Result<Option<CompleteDataRange<Version>>, Error> {
+        // Nodes that only store partial state can't serve states to peers
+        let partial_state = self
+            .storage
+            .get_state_key_filter()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+            .is_some();
+        if partial_state {
+            return Ok(None);
+        }
+
         let pruner_enabled = self
             .storage
             .is_state_merkle_pruner_enabled()
@@ -498,6 +529,122 @@ impl StorageReaderInterface for StorageReader {
             version, start_index, end_index
         )))
     }
+
+    fn get_state_values_by_keys_with_proof(
+        &self,
+        version: u64,
+        state_keys: &StateKeySelector,
+    ) -> aptos_storage_service_types::Result<StateValuesByKeysWithProof, Error> {
+        // Identify the state keys to fetch. We fetch one more key than
+        // the maximum so that we know where to resume if we truncate.
+        let max_num_state_values = self.config.max_state_chunk_size as usize;
+        let state_keys_to_fetch = match state_keys {
+            StateKeySelector::Account { address, cursor } => {
+                // Sharded state KV DBs can't iterate state values by account
+                // (the keys are stored by hash), so we reject these requests.
+                let sharding_enabled = self
+                    .storage
+                    .is_state_kv_sharding_enabled()
+                    .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+                if sharding_enabled {
+                    return Err(Error::InvalidRequest(format!(
+                        "State values by account are not supported by this node (its state \
+                        storage is sharded). Fetch the state values by key instead. Account: {}",
+                        address
+                    )));
+                }
+
+                self.storage
+                    .get_prefixed_state_value_iterator(
+                        &StateKeyPrefix::from(*address),
+                        cursor.as_ref(),
+                        version,
+                    )
+                    .and_then(|state_value_iterator| {
+                        state_value_iterator
+                            .take(max_num_state_values + 1)
+                            .map(|result| result.map(|(state_key, _)| state_key))
+                            .collect::<StorageResult<Vec<_>>>()
+                    })
+                    .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+            },
+            StateKeySelector::Keys(state_keys) => state_keys
+                .iter()
+                .take(max_num_state_values + 1)
+                .cloned()
+                .collect(),
+        };
+
+        // Fetch the state root hash at the version (i.e., of the state
+        // snapshot at the version), which all proofs are relative to.
+        let root_hash = match self
+            .storage
+            .get_state_snapshot_before(version + 1)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+        {
+            Some((snapshot_version, root_hash)) if snapshot_version == version => root_hash,
+            _ => {
+                return Err(Error::InvalidRequest(format!(
+                    "No state snapshot exists at version {}",
+                    version
+                )))
+            },
+        };
+
+        // Fetch the state values and proofs
+        let num_state_values_to_fetch = min(state_keys_to_fetch.len(), max_num_state_values);
+        let state_values = state_keys_to_fetch[..num_state_values_to_fetch]
+            .iter()
+            .map(|state_key| {
+                let (state_value, proof) = self
+                    .storage
+                    .get_state_value_with_proof_by_version(state_key, version)
+                    .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+                Ok(StateValueWithProof {
+                    state_key: state_key.clone(),
+                    state_value,
+                    proof,
+                })
+            })
+            .collect::<aptos_storage_service_types::Result<Vec<_>, Error>>()?;
+        let mut state_values_by_keys_with_proof = StateValuesByKeysWithProof {
+            version,
+            root_hash,
+            state_values,
+            next_state_key: state_keys_to_fetch.get(num_state_values_to_fetch).cloned(),
+        };
+
+        // Attempt to serve the request
+        let mut num_state_values_to_return = num_state_values_to_fetch;
+        while num_state_values_to_return > 1 {
+            // Attempt to divide up the response if it overflows the message size
+            let (overflow_frame, num_bytes) = check_overflow_network_frame(
+                &state_values_by_keys_with_proof,
+                self.config.max_network_chunk_bytes,
+            )?;
+            if !overflow_frame {
+                return Ok(state_values_by_keys_with_proof);
+            }
+
+            increment_network_frame_overflow(
+                DataResponse::StateValuesByKeysWithProof(state_values_by_keys_with_proof.clone())
+                    .get_label(),
+            );
+            let new_num_state_values_to_return = num_state_values_to_return / 2;
+            debug!("The response with {:?} state values was too large (num bytes: {:?}). Retrying with {:?}.",
+                num_state_values_to_return, num_bytes, new_num_state_values_to_return);
+            num_state_values_to_return = new_num_state_values_to_return; // Try again with half the amount of data
+
+            // Truncate the response and update the next state key
+            state_values_by_keys_with_proof
+                .state_values
+                .truncate(num_state_values_to_return);
+            state_values_by_keys_with_proof.next_state_key =
+                Some(state_keys_to_fetch[num_state_values_to_return].clone());
+        }
+
+        Ok(state_values_by_keys_with_proof) // We cannot return less than a single item
+    }
 }
 
 // A simple macro that wraps each storage read call with a timer
@@ -541,6 +688,10 @@ impl DbReader for TimedStorageReader {
     timed_read!(
         fn is_state_merkle_pruner_enabled(&self) -> StorageResult<bool>;
 
+        fn is_state_kv_sharding_enabled(&self) -> StorageResult<bool>;
+
+        fn get_state_key_filter(&self) -> StorageResult<Option<Arc<StateKeyFilter>>>;
+
         fn get_epoch_snapshot_prune_window(&self) -> StorageResult<usize>;
 
         fn get_first_txn_version(&self) -> StorageResult<Option<Version>>;
@@ -578,6 +729,24 @@ impl DbReader for TimedStorageReader {
             start_idx: usize,
             chunk_size: usize,
         ) -> StorageResult<StateValueChunkWithProof>;
+
+        fn get_prefixed_state_value_iterator(
+            &self,
+            key_prefix: &StateKeyPrefix,
+            cursor: Option<&StateKey>,
+            version: Version,
+        ) -> StorageResult<Box<dyn Iterator<Item = StorageResult<(StateKey, StateValue)>> + '_>>;
+
+        fn get_state_value_with_proof_by_version(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> StorageResult<(Option<StateValue>, SparseMerkleProof)>;
+
+        fn get_state_snapshot_before(
+            &self,
+            next_version: Version,
+        ) -> StorageResult<Option<(Version, HashValue)>>;
     );
 }
 
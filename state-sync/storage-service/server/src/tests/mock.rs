@@ -38,7 +38,7 @@ use aptos_types::{
     proof::{AccumulatorConsistencyProof, SparseMerkleProof, TransactionAccumulatorSummary},
     state_proof::StateProof,
     state_store::{
-        state_key::StateKey,
+        state_key::{filter::StateKeyFilter, StateKey},
         state_value::{StateValue, StateValueChunkWithProof},
     },
     transaction::{
@@ -325,6 +325,11 @@ mock! {
             version: Version,
         ) -> aptos_storage_interface::Result<(Option<StateValue>, SparseMerkleProof)>;
 
+        fn get_state_snapshot_before(
+            &self,
+            next_version: Version,
+        ) -> aptos_storage_interface::Result<Option<(Version, HashValue)>>;
+
         fn get_latest_executed_trees(&self) -> aptos_storage_interface::Result<ExecutedTrees>;
 
         fn get_epoch_ending_ledger_info(&self, known_version: u64) ->aptos_storage_interface::Result<LedgerInfoWithSignatures>;
@@ -354,6 +359,12 @@ mock! {
         fn get_epoch_snapshot_prune_window(&self) -> aptos_storage_interface::Result<usize>;
 
         fn is_state_merkle_pruner_enabled(&self) -> aptos_storage_interface::Result<bool>;
+
+        fn is_state_kv_sharding_enabled(&self) -> aptos_storage_interface::Result<bool>;
+
+        fn get_state_key_filter(
+            &self,
+        ) -> aptos_storage_interface::Result<Option<Arc<StateKeyFilter>>>;
     }
 }
 
@@ -382,6 +393,9 @@ pub fn create_mock_db_with_summary_updates(
     db_reader
         .expect_is_state_merkle_pruner_enabled()
         .returning(move || Ok(true));
+    db_reader
+        .expect_get_state_key_filter()
+        .returning(move || Ok(None));
 
     db_reader
 }
@@ -0,0 +1,272 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{
+    mock,
+    mock::{MockClient, MockDatabaseReader},
+    utils,
+};
+use aptos_config::config::StorageServiceConfig;
+use aptos_crypto::HashValue;
+use aptos_storage_service_types::{
+    requests::{DataRequest, StateKeySelector, StateValuesByKeysWithProofRequest},
+    responses::{
+        DataResponse, StateValueWithProof, StateValuesByKeysWithProof, StorageServiceResponse,
+    },
+    StorageServiceError,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    proof::SparseMerkleProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+};
+use claims::assert_matches;
+use mockall::predicate::eq;
+
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof() {
+    // Create test data (where only some of the keys exist)
+    let version = 101;
+    let state_values_with_proof: Vec<_> = (0..10)
+        .map(|index| {
+            let state_value = if index % 2 == 0 {
+                Some(StateValue::new_legacy(vec![index as u8].into()))
+            } else {
+                None
+            };
+            create_state_value_with_proof(index, state_value)
+        })
+        .collect();
+
+    // Create the mock db reader
+    let root_hash = HashValue::random();
+    let mut db_reader = mock::create_mock_db_reader();
+    expect_get_state_snapshot_before(&mut db_reader, version, Some((version, root_hash)));
+    for state_value_with_proof in &state_values_with_proof {
+        expect_get_state_value_with_proof_by_version(
+            &mut db_reader,
+            version,
+            state_value_with_proof.clone(),
+        );
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by keys
+    let state_keys = state_values_with_proof
+        .iter()
+        .map(|state_value_with_proof| state_value_with_proof.state_key.clone())
+        .collect();
+    let response = get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys)
+        .await
+        .unwrap();
+
+    // Verify the response is correct
+    assert_matches!(response, StorageServiceResponse::RawResponse(_));
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::StateValuesByKeysWithProof(StateValuesByKeysWithProof {
+            version,
+            root_hash,
+            state_values: state_values_with_proof,
+            next_state_key: None,
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof_chunk_limit() {
+    // Create test data (with more keys than the max chunk size)
+    let version = 101;
+    let max_state_chunk_size = StorageServiceConfig::default().max_state_chunk_size;
+    let state_values_with_proof: Vec<_> = (0..max_state_chunk_size * 2)
+        .map(|index| create_state_value_with_proof(index, None))
+        .collect();
+
+    // Create the mock db reader (that only expects the max chunk size)
+    let mut db_reader = mock::create_mock_db_reader();
+    expect_get_state_snapshot_before(
+        &mut db_reader,
+        version,
+        Some((version, HashValue::random())),
+    );
+    for state_value_with_proof in &state_values_with_proof[..max_state_chunk_size as usize] {
+        expect_get_state_value_with_proof_by_version(
+            &mut db_reader,
+            version,
+            state_value_with_proof.clone(),
+        );
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by keys
+    let state_keys = state_values_with_proof
+        .iter()
+        .map(|state_value_with_proof| state_value_with_proof.state_key.clone())
+        .collect();
+    let response = get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys)
+        .await
+        .unwrap();
+
+    // Verify the response was truncated and contains the next key to fetch
+    match response.get_data_response().unwrap() {
+        DataResponse::StateValuesByKeysWithProof(state_values_by_keys_with_proof) => {
+            assert_eq!(
+                state_values_by_keys_with_proof.state_values,
+                state_values_with_proof[..max_state_chunk_size as usize].to_vec()
+            );
+            assert_eq!(
+                state_values_by_keys_with_proof.next_state_key,
+                Some(
+                    state_values_with_proof[max_state_chunk_size as usize]
+                        .state_key
+                        .clone()
+                )
+            );
+        },
+        _ => panic!(
+            "Expected state values by keys with proof but got: {:?}",
+            response
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof_not_serviceable() {
+    // Create the storage client and server (that cannot service the request)
+    let version = 101;
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, version - 1, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by keys
+    let state_keys = vec![StateKey::raw(&[0])];
+    let response = get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys)
+        .await
+        .unwrap_err();
+
+    // Verify the request is not serviceable
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+#[tokio::test]
+async fn test_get_state_values_by_keys_with_proof_missing_snapshot() {
+    // Create a mock db reader where the latest snapshot precedes the version
+    let version = 101;
+    let mut db_reader = mock::create_mock_db_reader();
+    expect_get_state_snapshot_before(
+        &mut db_reader,
+        version,
+        Some((version - 1, HashValue::random())),
+    );
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by keys
+    let state_keys = vec![StateKey::raw(&[0])];
+    let response = get_state_values_by_keys_with_proof(&mut mock_client, version, state_keys)
+        .await
+        .unwrap_err();
+
+    // Verify the request was rejected (no proofs exist at the version)
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+#[tokio::test]
+async fn test_get_state_values_by_account_sharded_db() {
+    // Create a mock db reader with a sharded state KV DB
+    let version = 101;
+    let mut db_reader = mock::create_mock_db_reader();
+    db_reader
+        .expect_is_state_kv_sharding_enabled()
+        .times(1)
+        .returning(|| Ok(true));
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state values by account
+    let data_request =
+        DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+            version,
+            state_keys: StateKeySelector::Account {
+                address: AccountAddress::random(),
+                cursor: None,
+            },
+        });
+    let response = utils::send_storage_request(&mut mock_client, false, data_request)
+        .await
+        .unwrap_err();
+
+    // Verify the request was rejected
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+/// Creates a state value with proof for a unique state key (using the given index)
+fn create_state_value_with_proof(
+    index: u64,
+    state_value: Option<StateValue>,
+) -> StateValueWithProof {
+    StateValueWithProof {
+        state_key: StateKey::raw(&index.to_le_bytes()),
+        state_value,
+        proof: SparseMerkleProof::new(None, vec![]),
+    }
+}
+
+/// Sets an expectation on the given mock db for a call to fetch the state snapshot at the version
+fn expect_get_state_snapshot_before(
+    mock_db: &mut MockDatabaseReader,
+    version: u64,
+    state_snapshot: Option<(u64, HashValue)>,
+) {
+    mock_db
+        .expect_get_state_snapshot_before()
+        .times(1)
+        .with(eq(version + 1))
+        .returning(move |_| Ok(state_snapshot));
+}
+
+/// Sets an expectation on the given mock db for a call to fetch a state value with proof
+fn expect_get_state_value_with_proof_by_version(
+    mock_db: &mut MockDatabaseReader,
+    version: u64,
+    state_value_with_proof: StateValueWithProof,
+) {
+    mock_db
+        .expect_get_state_value_with_proof_by_version()
+        .times(1)
+        .with(eq(state_value_with_proof.state_key.clone()), eq(version))
+        .returning(move |_, _| {
+            Ok((
+                state_value_with_proof.state_value.clone(),
+                state_value_with_proof.proof.clone(),
+            ))
+        });
+}
+
+/// Sends a state values by keys request and processes the response
+async fn get_state_values_by_keys_with_proof(
+    mock_client: &mut MockClient,
+    version: u64,
+    state_keys: Vec<StateKey>,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request =
+        DataRequest::GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest {
+            version,
+            state_keys: StateKeySelector::Keys(state_keys),
+        });
+    utils::send_storage_request(mock_client, false, data_request).await
+}
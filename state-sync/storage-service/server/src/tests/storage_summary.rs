@@ -3,7 +3,7 @@
 
 use crate::{
     refresh_cached_storage_summary,
-    storage::StorageReader,
+    storage::{StorageReader, StorageReaderInterface},
     tests::{
         mock,
         mock::{MockClient, MockDatabaseReader},
@@ -21,7 +21,10 @@ use aptos_storage_service_types::{
     },
     StorageServiceError,
 };
-use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use aptos_types::{
+    account_address::AccountAddress, ledger_info::LedgerInfoWithSignatures,
+    state_store::state_key::filter::StateKeyFilter, transaction::Version,
+};
 use arc_swap::ArcSwap;
 use futures::StreamExt;
 use std::{ops::Deref, sync::Arc, time::Duration};
@@ -46,6 +49,7 @@ async fn test_refresh_cached_storage_summary() {
         lowest_version,
         state_prune_window,
         highest_ledger_info.clone(),
+        None,
     );
     let storage_reader = StorageReader::new(storage_service_config, Arc::new(db_reader));
 
@@ -160,6 +164,7 @@ async fn test_get_storage_server_summary_advance_time() {
         lowest_version,
         state_prune_window,
         highest_ledger_info.clone(),
+        None,
     );
 
     // Create the storage client and server
@@ -218,6 +223,7 @@ async fn test_get_storage_server_summary_notification() {
         lowest_version,
         state_prune_window,
         highest_ledger_info.clone(),
+        None,
     );
 
     // Create the storage client and server
@@ -262,12 +268,42 @@ async fn test_get_storage_server_summary_notification() {
     }
 }
 
+#[tokio::test]
+async fn test_get_data_summary_partial_state() {
+    // Create test data
+    let highest_version = 1000;
+    let highest_epoch = 430;
+    let lowest_version = 11;
+    let state_prune_window = 200;
+    let highest_ledger_info =
+        utils::create_test_ledger_info_with_sigs(highest_epoch, highest_version);
+
+    // Create a storage reader for a node that only stores partial state
+    let state_key_filter = StateKeyFilter::new([AccountAddress::random()], []);
+    let db_reader = create_db_reader_with_expectations(
+        lowest_version,
+        state_prune_window,
+        highest_ledger_info,
+        Some(Arc::new(state_key_filter)),
+    );
+    let storage_reader = StorageReader::new(StorageServiceConfig::default(), Arc::new(db_reader));
+
+    // Verify that the data summary does not advertise any states
+    let data_summary = storage_reader.get_data_summary().unwrap();
+    assert!(data_summary.states.is_none());
+    assert_eq!(
+        data_summary.transactions,
+        Some(CompleteDataRange::new(lowest_version, highest_version).unwrap())
+    );
+}
+
 /// Creates a mock database reader with the necessary
 /// expectations to satisfy the storage server summary request.
 fn create_db_reader_with_expectations(
     lowest_version: Version,
     state_prune_window: usize,
     highest_ledger_info: LedgerInfoWithSignatures,
+    state_key_filter: Option<Arc<StateKeyFilter>>,
 ) -> MockDatabaseReader {
     // Create the mock reader
     let mut db_reader = mock::create_mock_db_reader();
@@ -289,6 +325,9 @@ fn create_db_reader_with_expectations(
         .expect_is_state_merkle_pruner_enabled()
         .returning(move || Ok(true));
     db_reader
+        .expect_get_state_key_filter()
+        .returning(move || Ok(state_key_filter.clone()));
+    db_reader
 }
 
 /// Sends a storage summary request and processes the response
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::COMPRESSION_SUFFIX_LABEL;
-use aptos_types::transaction::Version;
+use aptos_types::{
+    account_address::AccountAddress, state_store::state_key::StateKey, transaction::Version,
+};
 use serde::{Deserialize, Serialize};
 
 /// A storage service request.
@@ -47,6 +49,7 @@ pub enum DataRequest {
     SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest), // Subscribes to transaction outputs with a proof
     SubscribeTransactionsOrOutputsWithProof(SubscribeTransactionsOrOutputsWithProofRequest), // Subscribes to transactions or outputs with a proof
     SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest), // Subscribes to transactions with a proof
+    GetStateValuesByKeysWithProof(StateValuesByKeysWithProofRequest), // Fetches a set of state values (by key) with proofs
 }
 
 impl DataRequest {
@@ -73,6 +76,7 @@ impl DataRequest {
                 "subscribe_transactions_or_outputs_with_proof"
             },
             Self::SubscribeTransactionsWithProof(_) => "subscribe_transactions_with_proof",
+            Self::GetStateValuesByKeysWithProof(_) => "get_state_values_by_keys_with_proof",
         }
     }
 
@@ -130,6 +134,32 @@ pub struct StateValuesWithProofRequest {
     pub end_index: u64,   // The index to stop fetching state values (inclusive)
 }
 
+/// A storage service request for fetching a set of state values (each
+/// with a sparse Merkle proof) at a specified version.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct StateValuesByKeysWithProofRequest {
+    pub version: u64,                 // The version to fetch the state values at
+    pub state_keys: StateKeySelector, // The state keys to fetch
+}
+
+/// Selects the state keys to fetch for a state values by keys request
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum StateKeySelector {
+    /// All state keys under the given account address, starting at the
+    /// (inclusive) cursor, if one is specified. Large accounts must be
+    /// fetched in several requests by following the returned cursor.
+    /// Note: each returned state value is proven, but the completeness of the
+    /// set is not (see `StateValuesByKeysWithProof::verify`). Servers with a
+    /// sharded state KV DB reject these requests.
+    Account {
+        address: AccountAddress,  // The account address to fetch the state values for
+        cursor: Option<StateKey>, // The state key to resume fetching from (inclusive)
+    },
+    /// An explicit set of state keys. Keys that do not exist at the
+    /// requested version are returned with a proof of non-inclusion.
+    Keys(Vec<StateKey>),
+}
+
 /// A storage service request for fetching a transaction output list with a
 /// corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -5,10 +5,11 @@ use crate::{
     requests::DataRequest::{
         GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
         GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
-        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-        GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
-        GetTransactionsWithProof, SubscribeTransactionOutputsWithProof,
-        SubscribeTransactionsOrOutputsWithProof, SubscribeTransactionsWithProof,
+        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesByKeysWithProof,
+        GetStateValuesWithProof, GetStorageServerSummary, GetTransactionOutputsWithProof,
+        GetTransactionsOrOutputsWithProof, GetTransactionsWithProof,
+        SubscribeTransactionOutputsWithProof, SubscribeTransactionsOrOutputsWithProof,
+        SubscribeTransactionsWithProof,
     },
     responses::Error::DegenerateRangeError,
     Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL,
@@ -17,11 +18,16 @@ use aptos_compression::{client::CompressionClient, CompressedData};
 use aptos_config::config::{
     AptosDataClientConfig, StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
+use aptos_crypto::hash::{CryptoHash, HashValue};
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    proof::SparseMerkleProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use num_traits::{PrimInt, Zero};
@@ -124,6 +130,68 @@ pub type TransactionOrOutputListWithProof = (
     Option<TransactionOutputListWithProof>,
 );
 
+/// A set of state values (fetched by key) at a specific version. Each
+/// state value is accompanied by a sparse Merkle proof against the state
+/// root hash at the version (which is included in the response). Clients
+/// must check the root hash against a trusted one (e.g., the state
+/// checkpoint hash of the transaction info at the version).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValuesByKeysWithProof {
+    pub version: Version,                       // The version of the state values
+    pub root_hash: HashValue,                   // The state root hash at the version
+    pub state_values: Vec<StateValueWithProof>, // The state values (in key order, for accounts)
+    pub next_state_key: Option<StateKey>, // The next key to fetch (if the response was truncated)
+}
+
+impl StateValuesByKeysWithProof {
+    /// Verifies that the root hash of the response is the given (trusted)
+    /// state root hash, and all state value proofs against it.
+    ///
+    /// Note: for account requests, this does not prove that the response
+    /// contains every state value under the account. The state Merkle tree
+    /// is keyed by the hash of each state key, so the state values under an
+    /// account are not contiguous in the tree, and no range proof can cover
+    /// them. Clients that require completeness must fetch the state values
+    /// by key (where missing keys are proven with non-inclusion proofs).
+    pub fn verify(&self, expected_root_hash: HashValue) -> Result<(), Error> {
+        if self.root_hash != expected_root_hash {
+            return Err(Error::UnexpectedResponseError(format!(
+                "The root hash of the state values doesn't match! Expected: {:?}, found: {:?}",
+                expected_root_hash, self.root_hash
+            )));
+        }
+        self.state_values
+            .iter()
+            .try_for_each(|state_value| state_value.verify(expected_root_hash))
+    }
+}
+
+/// A single state value (or its absence) with a sparse Merkle proof
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueWithProof {
+    pub state_key: StateKey,             // The state key
+    pub state_value: Option<StateValue>, // The state value (if the key exists)
+    pub proof: SparseMerkleProof,        // The inclusion or non-inclusion proof
+}
+
+impl StateValueWithProof {
+    /// Verifies the proof of the state value against the given state root hash
+    pub fn verify(&self, expected_root_hash: HashValue) -> Result<(), Error> {
+        self.proof
+            .verify(
+                expected_root_hash,
+                CryptoHash::hash(&self.state_key),
+                self.state_value.as_ref(),
+            )
+            .map_err(|error| {
+                Error::UnexpectedResponseError(format!(
+                    "Failed to verify the proof for state key {:?}: {:?}",
+                    self.state_key, error
+                ))
+            })
+    }
+}
+
 /// A single data response.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
@@ -139,6 +207,7 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    StateValuesByKeysWithProof(StateValuesByKeysWithProof),
 }
 
 impl DataResponse {
@@ -156,6 +225,7 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::StateValuesByKeysWithProof(_) => "state_values_by_keys_with_proof",
         }
     }
 }
@@ -193,6 +263,21 @@ impl TryFrom<StorageServiceResponse> for StateValueChunkWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for StateValuesByKeysWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::StateValuesByKeysWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected state_values_by_keys_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for EpochChangeProof {
     type Error = crate::responses::Error;
 
@@ -488,6 +573,20 @@ impl DataSummary {
 
                 can_serve_states && can_create_proof
             },
+            GetStateValuesByKeysWithProof(request) => {
+                let can_serve_states = self
+                    .states
+                    .map(|range| range.contains(request.version))
+                    .unwrap_or(false);
+
+                let can_create_proof = self
+                    .synced_ledger_info
+                    .as_ref()
+                    .map(|li| li.ledger_info().version() >= request.version)
+                    .unwrap_or(false);
+
+                can_serve_states && can_create_proof
+            },
             GetTransactionOutputsWithProof(request) => {
                 let desired_range =
                     match CompleteDataRange::new(request.start_version, request.end_version) {
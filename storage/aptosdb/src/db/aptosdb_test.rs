@@ -241,6 +241,7 @@ pub fn test_state_merkle_pruning_impl(
         BUFFERED_STATE_TARGET_ITEMS,
         DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         None,
+        None,
     )
     .unwrap();
 
@@ -37,7 +37,7 @@ use aptos_types::{
     state_proof::StateProof,
     state_store::{
         combine_sharded_state_updates,
-        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_key::{filter::StateKeyFilter, prefix::StateKeyPrefix, StateKey},
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueChunkWithProof},
         table, ShardedStateUpdates,
@@ -918,6 +918,14 @@ impl DbReader for FakeAptosDB {
         self.inner.is_state_merkle_pruner_enabled()
     }
 
+    fn is_state_kv_sharding_enabled(&self) -> Result<bool> {
+        self.inner.is_state_kv_sharding_enabled()
+    }
+
+    fn get_state_key_filter(&self) -> Result<Option<Arc<StateKeyFilter>>> {
+        self.inner.get_state_key_filter()
+    }
+
     fn get_epoch_snapshot_prune_window(&self) -> Result<usize> {
         self.inner.get_epoch_snapshot_prune_window()
     }
@@ -14,6 +14,7 @@ impl AptosDB {
         empty_buffered_state_for_restore: bool,
         skip_index_and_usage: bool,
         internal_indexer_db: Option<InternalIndexerDB>,
+        state_key_filter: Option<StateKeyFilter>,
     ) -> Self {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
@@ -40,6 +41,7 @@ impl AptosDB {
             empty_buffered_state_for_restore,
             skip_index_and_usage,
             internal_indexer_db.clone(),
+            state_key_filter,
         ));
 
         let ledger_pruner =
@@ -73,6 +75,7 @@ impl AptosDB {
         max_num_nodes_per_lru_cache_shard: usize,
         empty_buffered_state_for_restore: bool,
         internal_indexer_db: Option<InternalIndexerDB>,
+        state_key_filter: Option<StateKeyFilter>,
     ) -> Result<Self> {
         ensure!(
             pruner_config.eq(&NO_OP_STORAGE_PRUNER_CONFIG) || !readonly,
@@ -96,6 +99,7 @@ impl AptosDB {
             empty_buffered_state_for_restore,
             rocksdb_configs.enable_storage_sharding,
             internal_indexer_db,
+            state_key_filter,
         );
 
         if !readonly && enable_indexer {
@@ -170,6 +174,7 @@ impl AptosDB {
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             None,
+            None,
         )
         .expect("Unable to open AptosDB")
     }
@@ -226,6 +231,38 @@ impl AptosDB {
         Ok(())
     }
 
+    fn error_if_state_untracked(&self, state_key: &StateKey, version: Version) -> Result<()> {
+        if !self.state_store.is_state_key_tracked_at(state_key, version) {
+            return Err(AptosDbError::UntrackedState(format!(
+                "State value for key {:?} at version {}",
+                state_key, version
+            )));
+        }
+        Ok(())
+    }
+
+    /// The keys tracked since a later version than the first one (e.g., the state of objects
+    /// transferred to tracked accounts) are only stored if written since. So a missing value
+    /// does not mean that the key does not exist, unless the key is the object group (which is
+    /// always written when the object becomes tracked).
+    fn error_if_state_value_maybe_untracked<V>(
+        &self,
+        state_key: &StateKey,
+        value: &Option<V>,
+    ) -> Result<()> {
+        let is_partially_tracked = self
+            .state_store
+            .state_key_tracked_since(state_key)
+            .map_or(false, |tracked_since| tracked_since > 0);
+        if value.is_none() && is_partially_tracked && object_group_address(state_key).is_none() {
+            return Err(AptosDbError::UntrackedState(format!(
+                "State value for key {:?} written before it was tracked",
+                state_key
+            )));
+        }
+        Ok(())
+    }
+
     fn get_raw_block_info_by_height(&self, block_height: u64) -> Result<BlockInfo> {
         if !self.skip_index_and_usage {
             let (first_version, new_block_event) = self.event_store.get_event_by_key(
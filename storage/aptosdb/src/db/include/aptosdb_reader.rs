@@ -25,6 +25,18 @@ impl DbReader for AptosDB {
         gauged_api("get_prefixed_state_value_iterator", || {
             ensure!(!self.state_kv_db.enabled_sharding(), "This API is not supported with sharded DB");
             self.error_if_state_kv_pruned("StateValue", version)?;
+            if let Some(state_key_filter) = &self.state_store.state_key_filter {
+                // The state of partially tracked addresses is incomplete
+                let is_prefix_tracked = key_prefix.address().map_or(false, |address| {
+                    state_key_filter.is_address_fully_tracked(&address)
+                });
+                if !is_prefix_tracked {
+                    return Err(AptosDbError::UntrackedState(format!(
+                        "State values with prefix {:?}",
+                        key_prefix
+                    )));
+                }
+            }
 
             Ok(Box::new(
                 self.state_store
@@ -437,9 +449,13 @@ impl DbReader for AptosDB {
     ) -> Result<Option<StateValue>> {
         gauged_api("get_state_value_by_version", || {
             self.error_if_state_kv_pruned("StateValue", version)?;
+            self.error_if_state_untracked(state_store_key, version)?;
 
-            self.state_store
-                .get_state_value_by_version(state_store_key, version)
+            let state_value = self
+                .state_store
+                .get_state_value_by_version(state_store_key, version)?;
+            self.error_if_state_value_maybe_untracked(state_store_key, &state_value)?;
+            Ok(state_value)
         })
     }
 
@@ -450,9 +466,13 @@ impl DbReader for AptosDB {
     ) -> Result<Option<(Version, StateValue)>> {
         gauged_api("get_state_value_with_version_by_version", || {
             self.error_if_state_kv_pruned("StateValue", version)?;
+            self.error_if_state_untracked(state_key, version)?;
 
-            self.state_store
-                .get_state_value_with_version_by_version(state_key, version)
+            let state_value_with_version = self
+                .state_store
+                .get_state_value_with_version_by_version(state_key, version)?;
+            self.error_if_state_value_maybe_untracked(state_key, &state_value_with_version)?;
+            Ok(state_value_with_version)
         })
     }
 
@@ -479,9 +499,37 @@ impl DbReader for AptosDB {
     ) -> Result<(Option<StateValue>, SparseMerkleProofExt)> {
         gauged_api("get_state_value_with_proof_by_version_ext", || {
             self.error_if_state_merkle_pruned("State merkle", version)?;
+            self.error_if_state_untracked(state_store_key, version)?;
 
-            self.state_store
-                .get_state_value_with_proof_by_version_ext(state_store_key, version, root_depth)
+            if self.state_store.state_key_tracked_since(state_store_key) == Some(0) {
+                return self.state_store.get_state_value_with_proof_by_version_ext(
+                    state_store_key,
+                    version,
+                    root_depth,
+                );
+            }
+
+            // The value of a partially tracked key is only stored if written since the key was
+            // tracked, so it must match the leaf in the proof.
+            let state_value = self
+                .state_store
+                .get_state_value_by_version(state_store_key, version)?;
+            let proof = self.state_store.get_state_proof_by_version_ext(
+                state_store_key,
+                version,
+                root_depth,
+            )?;
+            let leaf_value_hash = proof
+                .leaf()
+                .filter(|leaf| leaf.key() == state_store_key.hash())
+                .map(|leaf| leaf.value_hash());
+            if state_value.as_ref().map(CryptoHash::hash) != leaf_value_hash {
+                return Err(AptosDbError::UntrackedState(format!(
+                    "State value for key {:?} written before it was tracked",
+                    state_store_key
+                )));
+            }
+            Ok((state_value, proof))
         })
     }
 
@@ -671,11 +719,25 @@ impl DbReader for AptosDB {
     ) -> Result<StateValueChunkWithProof> {
         gauged_api("get_state_value_chunk_with_proof", || {
             self.error_if_state_merkle_pruned("State merkle", version)?;
+            if self.state_store.state_key_filter.is_some() {
+                return Err(AptosDbError::UntrackedState(format!(
+                    "State value chunk at version {}",
+                    version
+                )));
+            }
             self.state_store
                 .get_value_chunk_with_proof(version, first_index, chunk_size)
         })
     }
 
+    fn is_state_kv_sharding_enabled(&self) -> Result<bool> {
+        Ok(self.state_kv_db.enabled_sharding())
+    }
+
+    fn get_state_key_filter(&self) -> Result<Option<Arc<StateKeyFilter>>> {
+        Ok(self.state_store.state_key_filter.clone())
+    }
+
     fn is_state_merkle_pruner_enabled(&self) -> Result<bool> {
         gauged_api("is_state_merkle_pruner_enabled", || {
             Ok(self
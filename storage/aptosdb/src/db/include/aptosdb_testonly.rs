@@ -40,6 +40,27 @@ impl AptosDB {
             BUFFERED_STATE_TARGET_ITEMS,
             max_node_cache,
             None,
+            None,
+        )
+        .expect("Unable to open AptosDB")
+    }
+
+    /// This opens db in non-readonly mode, without the pruner, and only stores
+    /// the state tracked by the given filter.
+    pub fn new_for_test_with_state_key_filter<P: AsRef<Path> + Clone>(
+        db_root_path: P,
+        state_key_filter: StateKeyFilter,
+    ) -> Self {
+        Self::open(
+            StorageDirPaths::from_path(db_root_path),
+            false,
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+            Some(state_key_filter),
         )
         .expect("Unable to open AptosDB")
     }
@@ -30,7 +30,7 @@ use crate::{
 use aptos_config::config::{
     PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
 };
-use aptos_crypto::HashValue;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_db_indexer::{db_indexer::InternalIndexerDB, Indexer};
 use aptos_experimental_runtimes::thread_manager::{optimal_min_len, THREAD_MANAGER};
 use aptos_logger::prelude::*;
@@ -58,7 +58,11 @@ use aptos_types::{
     },
     state_proof::StateProof,
     state_store::{
-        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_key::{
+            filter::{object_group_address, StateKeyFilter},
+            prefix::StateKeyPrefix,
+            StateKey,
+        },
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueChunkWithProof},
         table::{TableHandle, TableInfo},
@@ -124,6 +128,7 @@ impl AptosDB {
         buffered_state_target_items: usize,
         max_num_nodes_per_lru_cache_shard: usize,
         internal_indexer_db: Option<InternalIndexerDB>,
+        state_key_filter: Option<StateKeyFilter>,
     ) -> Result<Self> {
         Self::open_internal(
             &db_paths,
@@ -135,6 +140,7 @@ impl AptosDB {
             max_num_nodes_per_lru_cache_shard,
             false,
             internal_indexer_db,
+            state_key_filter,
         )
     }
 
@@ -158,6 +164,7 @@ impl AptosDB {
             max_num_nodes_per_lru_cache_shard,
             true,
             internal_indexer_db,
+            None,
         )
     }
 
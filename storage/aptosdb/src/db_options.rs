@@ -24,6 +24,7 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         LEDGER_INFO_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
+        TRACKED_STATE_CF_NAME,
         TRANSACTION_CF_NAME,
         TRANSACTION_ACCUMULATOR_CF_NAME,
         TRANSACTION_AUXILIARY_DATA_CF_NAME,
@@ -122,6 +123,7 @@ pub(super) fn state_kv_db_column_families() -> Vec<ColumnFamilyName> {
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
         STATE_VALUE_INDEX_CF_NAME,
+        TRACKED_STATE_CF_NAME,
     ]
 }
 
@@ -54,6 +54,7 @@ impl FastSyncStorageWrapper {
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
             internal_indexer_db,
+            config.storage.partial_state.state_key_filter(),
         )
         .map_err(|err| anyhow!("fast sync DB failed to open {}", err))?;
 
@@ -81,6 +82,7 @@ impl FastSyncStorageWrapper {
                 config.storage.buffered_state_target_items,
                 config.storage.max_num_nodes_per_lru_cache_shard,
                 None,
+                config.storage.partial_state.state_key_filter(),
             )
             .map_err(|err| anyhow!("Secondary DB failed to open {}", err))?;
 
@@ -20,6 +20,7 @@ pub(crate) mod stale_state_value_index;
 pub(crate) mod stale_state_value_index_by_key_hash;
 pub(crate) mod state_value;
 pub(crate) mod state_value_by_key_hash;
+pub(crate) mod tracked_state;
 pub(crate) mod transaction;
 pub(crate) mod transaction_accumulator;
 pub(crate) mod transaction_auxiliary_data;
@@ -49,6 +50,7 @@ pub const STALE_STATE_VALUE_INDEX_BY_KEY_HASH_CF_NAME: ColumnFamilyName =
 pub const STATE_VALUE_CF_NAME: ColumnFamilyName = "state_value";
 pub const STATE_VALUE_BY_KEY_HASH_CF_NAME: ColumnFamilyName = "state_value_by_key_hash";
 pub const STATE_VALUE_INDEX_CF_NAME: ColumnFamilyName = "state_value_index";
+pub const TRACKED_STATE_CF_NAME: ColumnFamilyName = "tracked_state";
 pub const TRANSACTION_CF_NAME: ColumnFamilyName = "transaction";
 pub const TRANSACTION_ACCUMULATOR_CF_NAME: ColumnFamilyName = "transaction_accumulator";
 pub const TRANSACTION_AUXILIARY_DATA_CF_NAME: ColumnFamilyName = "transaction_auxiliary_data";
@@ -0,0 +1,65 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines the physical storage schema for the state tracked by nodes that only store
+//! partial state (see `StateKeyFilter`), along with the first version since which it is tracked.
+//!
+//! ```text
+//! |<-----key----->|<----value---->|
+//! | tracked state | tracked since |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, TRACKED_STATE_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{
+    account_address::AccountAddress, state_store::table::TableHandle, transaction::Version,
+};
+use byteorder::{BigEndian, ReadBytesExt};
+use serde::{Deserialize, Serialize};
+use std::mem::size_of;
+
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub(crate) enum TrackedState {
+    /// An address tracked by the configuration
+    Address(AccountAddress),
+    /// A table handle tracked by the configuration
+    TableHandle(TableHandle),
+    /// An object owned by a tracked address
+    Object(AccountAddress),
+}
+
+define_schema!(
+    TrackedStateSchema,
+    TrackedState,
+    Version,
+    TRACKED_STATE_CF_NAME
+);
+
+impl KeyCodec<TrackedStateSchema> for TrackedState {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+impl ValueCodec<TrackedStateSchema> for Version {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+#[cfg(test)]
+mod test;
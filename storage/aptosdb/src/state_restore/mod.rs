@@ -279,4 +279,8 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotReceiver<K, V>
     fn finish_box(self: Box<Self>) -> Result<()> {
         self.finish()
     }
+
+    fn previous_key_hash(&self) -> Result<Option<HashValue>> {
+        StateSnapshotRestore::previous_key_hash(self)
+    }
 }
@@ -17,6 +17,7 @@ use crate::{
         stale_state_value_index_by_key_hash::StaleStateValueIndexByKeyHashSchema,
         state_value::StateValueSchema,
         state_value_by_key_hash::StateValueByKeyHashSchema,
+        tracked_state::{TrackedState, TrackedStateSchema},
         version_data::VersionDataSchema,
     },
     state_kv_db::StateKvDb,
@@ -58,7 +59,7 @@ use aptos_types::{
     proof::{definition::LeafCount, SparseMerkleProofExt, SparseMerkleRangeProof},
     state_store::{
         create_empty_sharded_state_updates,
-        state_key::{prefix::StateKeyPrefix, StateKey},
+        state_key::{filter::StateKeyFilter, prefix::StateKeyPrefix, StateKey},
         state_storage_usage::StateStorageUsage,
         state_value::{
             StaleStateValueByKeyHashIndex, StaleStateValueIndex, StateValue,
@@ -71,7 +72,11 @@ use aptos_types::{
 };
 use claims::{assert_ge, assert_le};
 use rayon::prelude::*;
-use std::{collections::HashSet, ops::Deref, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Deref,
+    sync::Arc,
+};
 
 pub(crate) mod buffered_state;
 mod state_merkle_batch_committer;
@@ -108,6 +113,9 @@ pub(crate) struct StateStore {
     buffered_state_target_items: usize,
     smt_ancestors: Mutex<SmtAncestors<StateValue>>,
     internal_indexer_db: Option<InternalIndexerDB>,
+    // If set, only the state values of the tracked state keys are stored (i.e.,
+    // the node only stores partial state). The state Merkle tree is always complete.
+    pub(crate) state_key_filter: Option<Arc<StateKeyFilter>>,
 }
 
 impl Deref for StateStore {
@@ -292,6 +300,7 @@ impl StateStore {
         empty_buffered_state_for_restore: bool,
         skip_usage: bool,
         internal_indexer_db: Option<InternalIndexerDB>,
+        state_key_filter: Option<StateKeyFilter>,
     ) -> Self {
         if !hack_for_tests && !empty_buffered_state_for_restore {
             Self::sync_commit_progress(
@@ -307,7 +316,9 @@ impl StateStore {
             state_merkle_pruner,
             epoch_snapshot_pruner,
             state_kv_pruner,
-            skip_usage,
+            // The storage usage is not tracked with partial state, as the
+            // state values of the untracked keys are not stored.
+            skip_usage: skip_usage || state_key_filter.is_some(),
         });
         let (buffered_state, smt_ancestors) = if empty_buffered_state_for_restore {
             BufferedState::new(
@@ -325,15 +336,103 @@ impl StateStore {
             .expect("buffered state creation failed.")
         };
 
+        let state_key_filter = state_key_filter.map(Arc::new);
+        if let Some(state_key_filter) = &state_key_filter {
+            Self::load_tracked_state(&state_db.ledger_db, &state_db.state_kv_db, state_key_filter)
+                .expect("Failed to load the tracked state.");
+        }
+
         Self {
             state_db,
             buffered_state: Mutex::new(buffered_state),
             buffered_state_target_items,
             smt_ancestors: Mutex::new(smt_ancestors),
             internal_indexer_db,
+            state_key_filter,
         }
     }
 
+    /// Restores the state tracked by the state key filter (i.e., the objects
+    /// tracked so far, along with the versions since which they are tracked),
+    /// and persists the configured addresses and table handles. Tracking new
+    /// addresses or table handles requires a re-sync, as their state written
+    /// before was not stored.
+    fn load_tracked_state(
+        ledger_db: &LedgerDb,
+        state_kv_db: &StateKvDb,
+        state_key_filter: &StateKeyFilter,
+    ) -> Result<()> {
+        ensure!(
+            !state_kv_db.enabled_sharding(),
+            "Partial state storage is not supported with sharded DB"
+        );
+
+        let synced_version = ledger_db.metadata_db().get_synced_version().ok();
+        let mut iter = state_kv_db.metadata_db().iter::<TrackedStateSchema>()?;
+        iter.seek_to_first();
+        let mut persisted_state = HashMap::new();
+        for result in iter {
+            let (tracked_state, tracked_since) = result?;
+            persisted_state.insert(tracked_state, tracked_since);
+        }
+
+        let batch = SchemaBatch::new();
+        let mut tracked_objects = vec![];
+        for (tracked_state, tracked_since) in persisted_state.iter() {
+            let is_still_tracked = match tracked_state {
+                TrackedState::Address(address) => {
+                    state_key_filter.tracked_addresses().contains(address)
+                },
+                TrackedState::TableHandle(handle) => {
+                    state_key_filter.tracked_table_handles().contains(handle)
+                },
+                // The state written after the overall commit progress was
+                // truncated, so the objects tracked since are tracked again
+                // when it is written again.
+                TrackedState::Object(address) => {
+                    let is_committed =
+                        synced_version.map_or(true, |version| *tracked_since <= version);
+                    if is_committed {
+                        tracked_objects.push((*address, *tracked_since));
+                    }
+                    is_committed
+                },
+            };
+            if !is_still_tracked {
+                batch.delete::<TrackedStateSchema>(tracked_state)?;
+            }
+        }
+
+        let configured_state = state_key_filter
+            .tracked_addresses()
+            .iter()
+            .map(|address| TrackedState::Address(*address))
+            .chain(
+                state_key_filter
+                    .tracked_table_handles()
+                    .iter()
+                    .map(|handle| TrackedState::TableHandle(*handle)),
+            );
+        for tracked_state in configured_state {
+            if !persisted_state.contains_key(&tracked_state) {
+                ensure!(
+                    synced_version.is_none(),
+                    "{:?} was not tracked by the partial state storage, a re-sync is required to track it.",
+                    tracked_state,
+                );
+                batch.put::<TrackedStateSchema>(&tracked_state, &0)?;
+            }
+        }
+        state_kv_db.metadata_db().write_schemas(batch)?;
+
+        info!(
+            num_tracked_objects = tracked_objects.len(),
+            "Loaded the objects tracked by the partial state storage."
+        );
+        state_key_filter.track_objects(tracked_objects);
+        Ok(())
+    }
+
     // We commit the overall commit progress at the last, and use it as the source of truth of the
     // commit progress.
     pub fn sync_commit_progress(
@@ -626,6 +725,10 @@ impl StateStore {
 
         let value_state_sets = value_state_sets_raw.iter().collect::<Vec<_>>();
 
+        self.track_owned_objects(
+            Self::versioned_writes(&value_state_sets, first_version),
+            &sharded_state_kv_batches[0],
+        )?;
         self.put_stats_and_indices(
             value_state_sets.as_slice(),
             first_version,
@@ -665,6 +768,11 @@ impl StateStore {
             .with_label_values(&["put_value_sets"])
             .start_timer();
 
+        // Track the newly owned objects before the writes are filtered
+        self.track_owned_objects(
+            Self::versioned_writes(&value_state_sets, first_version),
+            &sharded_state_kv_batches[0],
+        )?;
         self.put_stats_and_indices(
             &value_state_sets,
             first_version,
@@ -689,6 +797,23 @@ impl StateStore {
         )
     }
 
+    fn versioned_writes<'a>(
+        value_state_sets: &'a [&'a ShardedStateUpdates],
+        first_version: Version,
+    ) -> impl Iterator<Item = (Version, &'a StateKey, Option<&'a StateValue>)> {
+        value_state_sets
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, shards)| {
+                let version = first_version + i as Version;
+                shards.iter().flat_map(move |shard| {
+                    shard.iter().map(move |(state_key, state_value)| {
+                        (version, state_key, state_value.as_ref())
+                    })
+                })
+            })
+    }
+
     pub fn put_state_values(
         &self,
         value_state_sets: Vec<&ShardedStateUpdates>,
@@ -706,22 +831,62 @@ impl StateStore {
                     .flat_map_iter(|(i, shards)| {
                         let version = first_version + i as Version;
                         let kvs = &shards[shard_id];
-                        kvs.iter().map(move |(k, v)| {
-                            if enable_sharding {
-                                batch.put::<StateValueByKeyHashSchema>(
-                                    &(k.clone().hash(), version),
-                                    v,
-                                )
-                            } else {
-                                batch.put::<StateValueSchema>(&(k.clone(), version), v)
-                            }
-                        })
+                        kvs.iter()
+                            .filter(move |(k, _)| self.is_state_key_tracked_at(k, version))
+                            .map(move |(k, v)| {
+                                if enable_sharding {
+                                    batch.put::<StateValueByKeyHashSchema>(
+                                        &(k.clone().hash(), version),
+                                        v,
+                                    )
+                                } else {
+                                    batch.put::<StateValueSchema>(&(k.clone(), version), v)
+                                }
+                            })
                     })
                     .collect::<Result<_>>()
             })?;
         Ok(())
     }
 
+    /// Tracks the newly owned objects in the given writes (before the state
+    /// values are filtered), and persists them in the given batch
+    fn track_owned_objects<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (Version, &'a StateKey, Option<&'a StateValue>)>,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if let Some(state_key_filter) = &self.state_key_filter {
+            for (object_address, tracked_since) in state_key_filter.track_owned_objects(writes) {
+                batch.put::<TrackedStateSchema>(
+                    &TrackedState::Object(object_address),
+                    &tracked_since,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true iff the state value of the given key written at the given
+    /// version is stored
+    pub(crate) fn is_state_key_tracked_at(&self, state_key: &StateKey, version: Version) -> bool {
+        self.state_key_filter
+            .as_ref()
+            .map_or(true, |state_key_filter| {
+                state_key_filter.is_tracked_at(state_key, version)
+            })
+    }
+
+    /// Returns the first version whose writes to the given key are stored, or
+    /// None if the key is not tracked
+    pub(crate) fn state_key_tracked_since(&self, state_key: &StateKey) -> Option<Version> {
+        self.state_key_filter
+            .as_ref()
+            .map_or(Some(0), |state_key_filter| {
+                state_key_filter.tracked_since(state_key)
+            })
+    }
+
     pub fn get_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         let _timer = OTHER_TIMERS_SECONDS
             .with_label_values(&["get_usage"])
@@ -820,6 +985,11 @@ impl StateStore {
                     let mut bytes_delta = 0;
 
                     for (key, value) in kvs[shard_id].iter() {
+                        // The untracked state values are not stored (so neither are their
+                        // indices), and they are not accounted for in the usage.
+                        if !self.is_state_key_tracked_at(key, version) {
+                            continue;
+                        }
                         if let Some(value) = value {
                             items_delta += 1;
                             bytes_delta += (key.size() + value.size()) as i64;
@@ -859,10 +1029,18 @@ impl StateStore {
                             None
                         };
 
+                        // Old values written before the key was tracked were not stored.
+                        let old_version_and_value_opt = old_version_and_value_opt
+                            .map(|(old_version, old_value)| {
+                                let old_version = old_version
+                                    .context("Must have old version in cache.")
+                                    .unwrap();
+                                (old_version, old_value)
+                            })
+                            .filter(|(old_version, _)| {
+                                self.is_state_key_tracked_at(key, *old_version)
+                            });
                         if let Some((old_version, old_value)) = old_version_and_value_opt {
-                            let old_version = old_version
-                                .context("Must have old version in cache.")
-                                .unwrap();
                             items_delta -= 1;
                             bytes_delta -= (key.size() + old_value.size()) as i64;
                             // stale index of the old value at its version.
@@ -911,7 +1089,8 @@ impl StateStore {
             );
             let should_write_index_for_version =
                 (i == num_versions - 1) || Some(i) == last_checkpoint_index;
-            if !skip_usage || should_write_index_for_version {
+            // The usage is not tracked with partial state (see `StateDb::skip_usage`)
+            if self.state_key_filter.is_none() && (!skip_usage || should_write_index_for_version) {
                 let version = first_version + i as u64;
                 if should_write_index_for_version {
                     info!("Write usage at version {version}, {usage:?}, skip_usage: {skip_usage}.");
@@ -922,7 +1101,7 @@ impl StateStore {
             }
         }
 
-        if !expected_usage.is_untracked() {
+        if self.state_key_filter.is_none() && !expected_usage.is_untracked() {
             ensure!(
                 expected_usage == usage,
                 "Calculated state db usage at version {} not expected. expected: {:?}, calculated: {:?}, base version: {:?}, base version usage: {:?}",
@@ -934,8 +1113,10 @@ impl StateStore {
             );
         }
 
-        STATE_ITEMS.set(usage.items() as i64);
-        TOTAL_STATE_BYTES.set(usage.bytes() as i64);
+        if self.state_key_filter.is_none() {
+            STATE_ITEMS.set(usage.items() as i64);
+            TOTAL_STATE_BYTES.set(usage.bytes() as i64);
+        }
 
         Ok(())
     }
@@ -946,23 +1127,34 @@ impl StateStore {
         values: &StateValueBatch,
         enable_sharding: bool,
     ) -> Result<()> {
-        values.iter().for_each(|((key, version), value)| {
-            let shard_id = key.get_shard_id() as usize;
-            assert!(
-                shard_id < NUM_STATE_SHARDS,
-                "Invalid shard id: {}",
-                shard_id
-            );
-            if enable_sharding {
-                sharded_batch[shard_id]
-                    .put::<StateValueByKeyHashSchema>(&(key.hash(), *version), value)
-                    .expect("Inserting into sharded schema batch should never fail");
-            } else {
-                sharded_batch[shard_id]
-                    .put::<StateValueSchema>(&(key.clone(), *version), value)
-                    .expect("Inserting into sharded schema batch should never fail");
-            }
-        });
+        // Track the newly owned objects before filtering the state values
+        self.track_owned_objects(
+            values.iter().map(|((state_key, version), state_value)| {
+                (*version, state_key, state_value.as_ref())
+            }),
+            &sharded_batch[0],
+        )?;
+
+        values
+            .iter()
+            .filter(|((key, version), _)| self.is_state_key_tracked_at(key, *version))
+            .for_each(|((key, version), value)| {
+                let shard_id = key.get_shard_id() as usize;
+                assert!(
+                    shard_id < NUM_STATE_SHARDS,
+                    "Invalid shard id: {}",
+                    shard_id
+                );
+                if enable_sharding {
+                    sharded_batch[shard_id]
+                        .put::<StateValueByKeyHashSchema>(&(key.hash(), *version), value)
+                        .expect("Inserting into sharded schema batch should never fail");
+                } else {
+                    sharded_batch[shard_id]
+                        .put::<StateValueSchema>(&(key.clone(), *version), value)
+                        .expect("Inserting into sharded schema batch should never fail");
+                }
+            });
         Ok(())
     }
 
@@ -1133,7 +1325,10 @@ impl StateStore {
                             if let Some((version, _)) = version_and_value {
                                 entry.0 = Some(version);
                             } else {
-                                unreachable!();
+                                // With partial state, the values written before the key was
+                                // tracked were not stored (see `put_stats_and_indices`).
+                                assert!(self.state_key_filter.is_some());
+                                *entry.value_mut() = (Some(base_version), None);
                             }
                         }),
                         _ => {
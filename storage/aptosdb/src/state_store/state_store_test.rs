@@ -20,13 +20,18 @@ use aptos_storage_interface::{
 use aptos_temppath::TempPath;
 use aptos_types::{
     account_address::AccountAddress,
-    account_config::{AccountResource, ChainIdResource, CoinInfoResource, CoinStoreResource},
+    account_config::{
+        AccountResource, ChainIdResource, CoinInfoResource, CoinStoreResource, ObjectCoreResource,
+        ObjectGroupResource,
+    },
+    event::{EventHandle, EventKey},
     nibble::nibble_path::NibblePath,
-    state_store::state_key::inner::StateKeyTag,
+    state_store::{state_key::inner::StateKeyTag, table::TableHandle},
 };
 use arr_macro::arr;
+use move_core_types::move_resource::MoveStructType;
 use proptest::{collection::hash_map, prelude::*};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 fn put_value_set(
     state_store: &StateStore,
@@ -166,6 +171,126 @@ fn test_state_store_reader_writer() {
     verify_value_and_proof(store, key3, Some(&value3), 1, root);
 }
 
+#[test]
+fn test_state_store_partial_state() {
+    let tracked_address = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+    let untracked_address = AccountAddress::new([2u8; AccountAddress::LENGTH]);
+    let tracked_handle = TableHandle(AccountAddress::new([3u8; AccountAddress::LENGTH]));
+    let create_state_key_filter = || StateKeyFilter::new([tracked_address], [tracked_handle]);
+
+    // Create the state of the accounts, a table, and objects owned by the accounts
+    let owned_object = AccountAddress::new([4u8; AccountAddress::LENGTH]);
+    let unowned_object = AccountAddress::new([5u8; AccountAddress::LENGTH]);
+    let tracked_keys = vec![
+        StateKey::resource_typed::<AccountResource>(&tracked_address).unwrap(),
+        StateKey::table_item(&tracked_handle, b"key"),
+        object_group_key(owned_object),
+    ];
+    let untracked_keys = vec![
+        StateKey::resource_typed::<AccountResource>(&untracked_address).unwrap(),
+        StateKey::table_item(&TableHandle(tracked_address), b"key"),
+        object_group_key(unowned_object),
+    ];
+    let value = StateValue::from(b"value".to_vec());
+    let tracked_values = vec![
+        value.clone(),
+        value.clone(),
+        object_group_value(tracked_address),
+    ];
+    let untracked_values = vec![value.clone(), value, object_group_value(untracked_address)];
+    let value_set: Vec<_> = tracked_keys
+        .iter()
+        .cloned()
+        .zip(tracked_values)
+        .chain(untracked_keys.iter().cloned().zip(untracked_values))
+        .collect();
+
+    // Write the state and verify only the tracked state is stored
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test_with_state_key_filter(&tmp_dir, create_state_key_filter());
+    put_value_set(&db.state_store, value_set.clone(), 0, None);
+    for (state_key, state_value) in &value_set {
+        let result = db.get_state_value_by_version(state_key, 0);
+        if tracked_keys.contains(state_key) {
+            assert_eq!(result.unwrap().as_ref(), Some(state_value));
+        } else {
+            assert!(matches!(result, Err(AptosDbError::UntrackedState(_))));
+        }
+    }
+
+    // Transfer the unowned object to the tracked account, and verify only the
+    // state written since is stored (i.e., the object resources written before
+    // are untracked).
+    let object_resource_key = StateKey::resource_typed::<AccountResource>(&unowned_object).unwrap();
+    let transferred_object_key = object_group_key(unowned_object);
+    let transferred_object_value = object_group_value(tracked_address);
+    put_value_set(
+        &db.state_store,
+        vec![(
+            transferred_object_key.clone(),
+            transferred_object_value.clone(),
+        )],
+        1,
+        Some(0),
+    );
+    assert_eq!(
+        db.get_state_value_by_version(&transferred_object_key, 1)
+            .unwrap(),
+        Some(transferred_object_value)
+    );
+    assert!(matches!(
+        db.get_state_value_by_version(&transferred_object_key, 0),
+        Err(AptosDbError::UntrackedState(_))
+    ));
+    assert!(matches!(
+        db.get_state_value_by_version(&object_resource_key, 1),
+        Err(AptosDbError::UntrackedState(_))
+    ));
+    drop(db);
+
+    // Reopen the DB and verify the objects are still tracked (since the same versions)
+    let db = AptosDB::new_for_test_with_state_key_filter(&tmp_dir, create_state_key_filter());
+    let state_key_filter = db.get_state_key_filter().unwrap().unwrap();
+    assert!(state_key_filter.is_address_fully_tracked(&owned_object));
+    assert!(state_key_filter.is_address_tracked(&unowned_object));
+    assert!(!state_key_filter.is_address_fully_tracked(&unowned_object));
+    assert_eq!(
+        state_key_filter.tracked_since(&object_resource_key),
+        Some(1)
+    );
+
+    // Verify newly tracked addresses require a re-sync (once the DB is synced)
+    let batch = SchemaBatch::new();
+    batch
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::OverallCommitProgress,
+            &DbMetadataValue::Version(1),
+        )
+        .unwrap();
+    db.ledger_db.metadata_db().write_schemas(batch).unwrap();
+    let state_key_filter =
+        StateKeyFilter::new([tracked_address, untracked_address], [tracked_handle]);
+    assert!(
+        StateStore::load_tracked_state(&db.ledger_db, &db.state_kv_db, &state_key_filter).is_err()
+    );
+}
+
+/// Returns the state key of the object group at the given address
+fn object_group_key(object: AccountAddress) -> StateKey {
+    StateKey::resource_group(&object, &ObjectGroupResource::struct_tag())
+}
+
+/// Returns an object group (containing an object core) owned by the given owner
+fn object_group_value(owner: AccountAddress) -> StateValue {
+    let object_core =
+        ObjectCoreResource::new(0, owner, true, EventHandle::new(EventKey::new(0, owner), 0));
+    let resources = BTreeMap::from([(
+        ObjectCoreResource::struct_tag(),
+        bcs::to_bytes(&object_core).unwrap(),
+    )]);
+    StateValue::from(bcs::to_bytes(&resources).unwrap())
+}
+
 fn traverse_values(
     store: &StateStore,
     prefix: &StateKeyPrefix,
@@ -0,0 +1,250 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Context, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_consensus::{
+    util::db_tool::{BlockSummary, ConsensusDbInspector, QuorumCertSummary},
+    CONSENSUS_DB_NAME,
+};
+use aptos_db::AptosDB;
+use aptos_storage_interface::DbReader;
+use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::{fs, path::PathBuf};
+
+/// Offline inspection and repair of the ConsensusDB. Note: the node must be stopped.
+#[derive(Subcommand)]
+pub enum Cmd {
+    ListBlocks(ListBlocks),
+    ListQuorumCerts(ListQuorumCerts),
+    ListDagNodes(ListDagNodes),
+    ShowRecoveryData(ShowRecoveryData),
+    Prune(Prune),
+    ExportJson(ExportJson),
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Cmd::ListBlocks(cmd) => cmd.run(),
+            Cmd::ListQuorumCerts(cmd) => cmd.run(),
+            Cmd::ListDagNodes(cmd) => cmd.run(),
+            Cmd::ShowRecoveryData(cmd) => cmd.run(),
+            Cmd::Prune(cmd) => cmd.run(),
+            Cmd::ExportJson(cmd) => cmd.run(),
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct DbDir {
+    /// The storage directory of the node (i.e., the directory containing the consensus_db)
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+}
+
+impl DbDir {
+    /// Opens the ConsensusDB in readonly mode
+    fn open_consensus_db(&self) -> Result<ConsensusDbInspector> {
+        ConsensusDbInspector::new_readonly(&self.db_dir)
+            .with_context(|| format_err!("Failed to open ConsensusDB."))
+    }
+
+    /// Opens the ConsensusDB in read-write mode (e.g., for pruning)
+    fn open_consensus_db_for_write(&self) -> Result<ConsensusDbInspector> {
+        // Opening the ConsensusDB creates it if missing, so verify that it exists
+        let consensus_db_path = self.db_dir.join(CONSENSUS_DB_NAME);
+        ensure!(
+            consensus_db_path.exists(),
+            "ConsensusDB not found at: {:?}",
+            consensus_db_path
+        );
+        Ok(ConsensusDbInspector::new(&self.db_dir))
+    }
+
+    /// Returns the latest committed ledger info stored in the AptosDB
+    fn get_latest_ledger_info(&self) -> Result<LedgerInfoWithSignatures> {
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            None,
+            None,
+        )
+        .with_context(|| format_err!("Failed to open AptosDB."))?;
+        Ok(db.get_latest_ledger_info()?)
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[derive(Parser)]
+#[clap(about = "List all blocks stored in the ConsensusDB.")]
+pub struct ListBlocks {
+    #[clap(flatten)]
+    db_dir: DbDir,
+}
+
+impl ListBlocks {
+    pub fn run(self) -> Result<()> {
+        let blocks = self.db_dir.open_consensus_db()?.get_blocks()?;
+        let summaries: Vec<_> = blocks.iter().map(BlockSummary::from).collect();
+        print_json(&summaries)
+    }
+}
+
+#[derive(Parser)]
+#[clap(about = "List all quorum certs stored in the ConsensusDB.")]
+pub struct ListQuorumCerts {
+    #[clap(flatten)]
+    db_dir: DbDir,
+}
+
+impl ListQuorumCerts {
+    pub fn run(self) -> Result<()> {
+        let quorum_certs = self.db_dir.open_consensus_db()?.get_quorum_certs()?;
+        let summaries: Vec<_> = quorum_certs.iter().map(QuorumCertSummary::from).collect();
+        print_json(&summaries)
+    }
+}
+
+#[derive(Parser)]
+#[clap(about = "List all DAG nodes stored in the ConsensusDB.")]
+pub struct ListDagNodes {
+    #[clap(flatten)]
+    db_dir: DbDir,
+}
+
+impl ListDagNodes {
+    pub fn run(self) -> Result<()> {
+        print_json(&self.db_dir.open_consensus_db()?.get_dag_nodes()?)
+    }
+}
+
+#[derive(Parser)]
+#[clap(about = "Show the recovery data consensus would construct on startup.")]
+pub struct ShowRecoveryData {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// Whether order votes are enabled (this changes how the root is recovered)
+    #[clap(long)]
+    order_vote_enabled: bool,
+}
+
+impl ShowRecoveryData {
+    pub fn run(self) -> Result<()> {
+        let latest_ledger_info = self.db_dir.get_latest_ledger_info()?;
+        let recovery_data = self
+            .db_dir
+            .open_consensus_db()?
+            .get_recovery_data(latest_ledger_info, self.order_vote_enabled)
+            .with_context(|| format_err!("Consensus would fail to recover!"))?;
+        print_json(&recovery_data)
+    }
+}
+
+#[derive(Parser)]
+#[clap(about = "Delete all blocks (and quorum certs) above the committed root.")]
+#[clap(group(clap::ArgGroup::new("backup")
+        .required(true)
+        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint", "dry_run"]),
+))]
+pub struct Prune {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    #[clap(long, value_parser, group = "backup")]
+    backup_checkpoint_dir: Option<PathBuf>,
+
+    #[clap(long, group = "backup")]
+    opt_out_backup_checkpoint: bool,
+
+    /// Only print the blocks and quorum certs that would be deleted
+    #[clap(long, group = "backup")]
+    dry_run: bool,
+}
+
+impl Prune {
+    pub fn run(self) -> Result<()> {
+        let latest_ledger_info = self.db_dir.get_latest_ledger_info()?;
+        let consensus_db = if self.dry_run {
+            self.db_dir.open_consensus_db()?
+        } else {
+            self.db_dir.open_consensus_db_for_write()?
+        };
+
+        if let Some(backup_checkpoint_dir) = self.backup_checkpoint_dir {
+            ensure!(
+                !backup_checkpoint_dir.exists(),
+                "Backup dir already exists."
+            );
+            println!("Creating backup at: {:?}", &backup_checkpoint_dir);
+            fs::create_dir_all(&backup_checkpoint_dir)?;
+            consensus_db.create_checkpoint(&backup_checkpoint_dir)?;
+            println!("Done!");
+        } else if self.opt_out_backup_checkpoint {
+            println!("Opted out backup creation!.");
+        }
+
+        let (pruned_blocks, pruned_quorum_certs) =
+            consensus_db.prune_blocks_above_root(&latest_ledger_info, self.dry_run)?;
+        let action = if self.dry_run {
+            "Would prune"
+        } else {
+            "Pruned"
+        };
+        println!(
+            "{} {} blocks and {} quorum certs above the committed root (epoch: {}, round: {}).",
+            action,
+            pruned_blocks.len(),
+            pruned_quorum_certs.len(),
+            latest_ledger_info.commit_info().epoch(),
+            latest_ledger_info.commit_info().round(),
+        );
+        for block_id in pruned_blocks {
+            println!("Block: {}", block_id);
+        }
+        for certified_block_id in pruned_quorum_certs {
+            println!("Quorum cert for block: {}", certified_block_id);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+#[clap(about = "Export the entire content of the ConsensusDB as JSON.")]
+pub struct ExportJson {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// The output file (the JSON is printed to stdout if not provided)
+    #[clap(long, value_parser)]
+    output_file: Option<PathBuf>,
+}
+
+impl ExportJson {
+    pub fn run(self) -> Result<()> {
+        let json = self.db_dir.open_consensus_db()?.export_json()?;
+        match self.output_file {
+            Some(output_file) => {
+                fs::write(&output_file, serde_json::to_string_pretty(&json)?)?;
+                println!("Exported the ConsensusDB to: {:?}", output_file);
+                Ok(())
+            },
+            None => print_json(&json),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Context, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_consensus::util::leader_reputation_simulator::{
+    simulate_leader_reputation, SimulationHistory, SimulationReport,
+};
+use aptos_db::AptosDB;
+use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReader};
+use aptos_types::on_chain_config::{
+    LeaderReputationType, OnChainConfig, OnChainConsensusConfig, ProposerElectionType,
+};
+use clap::Parser;
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// The exclude round used if it can't be read from the on-chain consensus config
+const DEFAULT_EXCLUDE_ROUND: u64 = 40;
+
+#[derive(Parser)]
+#[clap(
+    about = "Replay the block metadata history under alternative leader reputation configs, \
+    and report the elected leaders' fairness, failed proposer rate and per-validator share."
+)]
+pub struct Command {
+    /// The storage directory of the node. To simulate over a backup, restore it first.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The number of latest blocks to replay
+    #[clap(long, default_value_t = 10000)]
+    num_blocks: usize,
+
+    /// The alternative configs to simulate, each a YAML file containing a
+    /// `LeaderReputationType` (e.g., `proposer_and_voter_v2: { active_weight: 1000, ... }`).
+    /// The on-chain config is always simulated as well, as the baseline.
+    #[clap(long, value_parser, num_args = 0..)]
+    config_files: Vec<PathBuf>,
+
+    /// The number of most recent rounds excluded from the reputation window
+    /// (defaults to the on-chain value)
+    #[clap(long)]
+    exclude_round: Option<u64>,
+
+    /// The file to write the full (per-validator) JSON report to
+    #[clap(long, value_parser)]
+    output_file: Option<PathBuf>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        let db: Arc<dyn DbReader> = Arc::new(
+            AptosDB::open(
+                StorageDirPaths::from_path(&self.db_dir),
+                true, /* readonly */
+                NO_OP_STORAGE_PRUNER_CONFIG,
+                RocksdbConfigs::default(),
+                false, /* indexer */
+                BUFFERED_STATE_TARGET_ITEMS,
+                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+                None,
+                None,
+            )
+            .with_context(|| format_err!("Failed to open AptosDB."))?,
+        );
+
+        // Collect the configs to simulate, starting with the on-chain config (if any)
+        let onchain_config =
+            OnChainConsensusConfig::fetch_config(&db.latest_state_checkpoint_view()?)
+                .filter(|config| !config.is_dag_enabled());
+        let mut configs = vec![];
+        if let Some(onchain_config) = &onchain_config {
+            match onchain_config.proposer_election_type() {
+                ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+                    configs.push(("on-chain".to_string(), leader_reputation_type.clone()))
+                },
+                _ => println!("The on-chain config doesn't use leader reputation!"),
+            }
+        }
+        for config_file in &self.config_files {
+            let contents = fs::read_to_string(config_file)
+                .with_context(|| format!("Failed to read the config file {:?}", config_file))?;
+            let leader_reputation_type: LeaderReputationType = serde_yaml::from_str(&contents)
+                .with_context(|| format!("Failed to parse the config file {:?}", config_file))?;
+            configs.push((config_file.display().to_string(), leader_reputation_type));
+        }
+        let exclude_round = self.exclude_round.unwrap_or_else(|| {
+            onchain_config.map_or(DEFAULT_EXCLUDE_ROUND, |config| {
+                config.leader_reputation_exclude_round()
+            })
+        });
+
+        let history = Arc::new(SimulationHistory::load_from_db(
+            db.as_ref(),
+            self.num_blocks,
+        )?);
+        println!(
+            "Simulating {} configs over {} blocks (exclude round: {})",
+            configs.len(),
+            history.num_blocks(),
+            exclude_round
+        );
+
+        let reports: Vec<SimulationReport> = configs
+            .iter()
+            .map(|(name, leader_reputation_type)| {
+                simulate_leader_reputation(&history, name, leader_reputation_type, exclude_round)
+            })
+            .collect();
+        for report in &reports {
+            println!(
+                "{}: rounds: {}, leader match rate: {:.3}, failed rounds (actual): {:.3}, \
+                failed rounds (estimated): {:.3}, fairness index: {:.3}",
+                report.name,
+                report.num_rounds,
+                report.leader_match_rate,
+                report.actual_failed_round_rate,
+                report.estimated_failed_round_rate,
+                report.fairness_index,
+            );
+        }
+
+        if let Some(output_file) = &self.output_file {
+            fs::write(output_file, serde_json::to_string_pretty(&reports)?)?;
+            println!("Wrote the full report to {:?}", output_file);
+        }
+
+        Ok(())
+    }
+}
@@ -6,6 +6,8 @@ extern crate core;
 mod backup;
 mod backup_maintenance;
 mod bootstrap;
+mod consensus;
+mod leader_reputation;
 mod replay_verify;
 pub mod restore;
 #[cfg(test)]
@@ -28,6 +30,9 @@ pub enum DBTool {
 
     Bootstrap(bootstrap::Command),
 
+    #[clap(subcommand)]
+    Consensus(consensus::Cmd),
+
     #[clap(subcommand)]
     Debug(db_debugger::Cmd),
 
@@ -35,6 +40,8 @@ pub enum DBTool {
 
     #[clap(subcommand)]
     Restore(restore::Command),
+
+    SimulateLeaderReputation(leader_reputation::Command),
 }
 
 impl DBTool {
@@ -43,6 +50,7 @@ impl DBTool {
             DBTool::Backup(cmd) => cmd.run().await,
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Bootstrap(cmd) => cmd.run(),
+            DBTool::Consensus(cmd) => cmd.run(),
             DBTool::Debug(cmd) => Ok(cmd.run()?),
             DBTool::ReplayVerify(cmd) => {
                 let ret = cmd.run().await;
@@ -50,6 +58,7 @@ impl DBTool {
                 ret
             },
             DBTool::Restore(cmd) => cmd.run().await,
+            DBTool::SimulateLeaderReputation(cmd) => cmd.run(),
         }
     }
 }
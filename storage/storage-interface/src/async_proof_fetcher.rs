@@ -1,8 +1,8 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{metrics::TIMER, DbReader};
-use anyhow::{anyhow, Result};
+use crate::{metrics::TIMER, DbReader, Result};
+use anyhow::anyhow;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::{error, sample, sample::SampleRate};
 use aptos_types::{
@@ -36,6 +36,13 @@ struct Proof {
     proof: SparseMerkleProofExt,
 }
 
+/// The value hash a proof is verified against
+enum ExpectedValueHash {
+    Known(Option<HashValue>),
+    /// The value is unknown, so the value hash in the proof leaf (if any) is used
+    InProof,
+}
+
 pub struct AsyncProofFetcher {
     reader: Arc<dyn DbReader>,
     data_sender: Sender<Proof>,
@@ -60,12 +67,7 @@ impl AsyncProofFetcher {
         state_key: &StateKey,
         version: Version,
     ) -> Result<Option<(Version, StateValue)>> {
-        let _timer = TIMER
-            .with_label_values(&["async_proof_fetcher_fetch"])
-            .start_timer();
-        Ok(self
-            .reader
-            .get_state_value_with_version_by_version(state_key, version)?)
+        self.read_state_value(state_key, version)
     }
 
     pub fn fetch_state_value_with_version_and_schedule_proof_read(
@@ -75,20 +77,52 @@ impl AsyncProofFetcher {
         subtree_root_depth: usize,
         subtree_root_hash: Option<HashValue>,
     ) -> Result<Option<(Version, StateValue)>> {
-        let version_and_value_opt = self.fetch_state_value(state_key, version)?;
+        let version_and_value_opt = self.read_state_value(state_key, version)?;
         self.schedule_proof_read(
             state_key.clone(),
             version,
             subtree_root_depth,
             subtree_root_hash,
-            version_and_value_opt.as_ref().map(|v| {
+            ExpectedValueHash::Known(version_and_value_opt.as_ref().map(|v| {
                 let state_value = &v.1;
                 state_value.hash()
-            }),
+            })),
         );
         Ok(version_and_value_opt)
     }
 
+    /// Schedules the proof read of a key whose value is not stored by this node (i.e., it only
+    /// stores partial state). The proof is still required to update the tree, and it is verified
+    /// against the value hash in its own leaf (which still ensures that the updated tree is
+    /// consistent with the subtree root hash).
+    pub fn schedule_proof_read_for_untracked_state(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+        subtree_root_depth: usize,
+        subtree_root_hash: HashValue,
+    ) {
+        self.schedule_proof_read(
+            state_key.clone(),
+            version,
+            subtree_root_depth,
+            Some(subtree_root_hash),
+            ExpectedValueHash::InProof,
+        );
+    }
+
+    fn read_state_value(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<(Version, StateValue)>> {
+        let _timer = TIMER
+            .with_label_values(&["async_proof_fetcher_fetch"])
+            .start_timer();
+        self.reader
+            .get_state_value_with_version_by_version(state_key, version)
+    }
+
     pub fn get_proof_cache(&self) -> HashMap<HashValue, SparseMerkleProofExt> {
         self.wait()
     }
@@ -122,7 +156,7 @@ impl AsyncProofFetcher {
         version: Version,
         subtree_root_depth: usize,
         subtree_root_hash: Option<HashValue>,
-        value_hash: Option<HashValue>,
+        value_hash: ExpectedValueHash,
     ) {
         let _timer = TIMER
             .with_label_values(&["schedule_async_proof_read"])
@@ -138,6 +172,13 @@ impl AsyncProofFetcher {
             // proof fetcher.
             drop(reader);
             if let Some(subtree_root_hash) = subtree_root_hash {
+                let value_hash = match value_hash {
+                    ExpectedValueHash::Known(value_hash) => value_hash,
+                    ExpectedValueHash::InProof => proof
+                        .leaf()
+                        .filter(|leaf| leaf.key() == state_key.hash())
+                        .map(|leaf| leaf.value_hash()),
+                };
                 proof
                     .verify_by_hash(subtree_root_hash, state_key.hash(), value_hash)
                     .map_err(|err| {
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    async_proof_fetcher::AsyncProofFetcher, metrics::TIMER, state_view::DbStateView, DbReader,
+    async_proof_fetcher::AsyncProofFetcher, metrics::TIMER, state_delta::StateDelta,
+    state_view::DbStateView, DbReader, StateFallback,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
-use aptos_scratchpad::{FrozenSparseMerkleTree, SparseMerkleTree, StateStoreStatus};
+use aptos_scratchpad::{FrozenSparseMerkleTree, StateStoreStatus};
 use aptos_types::{
     proof::SparseMerkleProofExt,
     state_store::{
@@ -135,6 +136,12 @@ pub struct CachedStateView {
     /// in JMT node.
     sharded_state_cache: ShardedStateCache,
     proof_fetcher: Arc<AsyncProofFetcher>,
+
+    /// Serves the state that doesn't exist in the DB (e.g., in fork mode). The values served by
+    /// the fallback are not added to the cache (and the tree), as they don't exist in the DB.
+    fallback: Option<Arc<dyn StateFallback>>,
+    /// The state keys deleted by the speculative state (only tracked if there is a fallback)
+    speculative_deletions: HashSet<StateKey>,
 }
 
 impl Debug for CachedStateView {
@@ -145,30 +152,38 @@ impl Debug for CachedStateView {
 
 impl CachedStateView {
     /// Constructs a [`CachedStateView`] with persistent state view in the DB and the in-memory
-    /// speculative state represented by `speculative_state`. The persistent state view is the
-    /// latest one preceding `next_version`
+    /// speculative state represented by `state`. The persistent state view is the latest one
+    /// preceding `next_version`
     pub fn new(
         id: StateViewId,
         reader: Arc<dyn DbReader>,
         next_version: Version,
-        speculative_state: SparseMerkleTree<StateValue>,
+        state: &StateDelta,
         proof_fetcher: Arc<AsyncProofFetcher>,
     ) -> Result<Self> {
         // n.b. Freeze the state before getting the state snapshot, otherwise it's possible that
         // after we got the snapshot, in-mem trees newer than it gets dropped before being frozen,
         // due to a commit happening from another thread.
         let base_smt = reader.get_buffered_state_base()?;
-        let speculative_state = speculative_state.freeze(&base_smt);
+        let speculative_state = state.current.clone().freeze(&base_smt);
         let snapshot = reader
             .get_state_snapshot_before(next_version)
             .map_err(Into::<StateviewError>::into)?;
 
-        Ok(Self::new_impl(
-            id,
-            snapshot,
-            speculative_state,
-            proof_fetcher,
-        ))
+        let mut state_view = Self::new_impl(id, snapshot, speculative_state, proof_fetcher);
+        if let Some(fallback) = reader.get_state_fallback() {
+            // The state deleted by the speculative state (e.g., by blocks that are not committed
+            // yet) doesn't exist in the tree or the DB, but must not be served by the fallback.
+            state_view.speculative_deletions = state
+                .updates_since_base
+                .iter()
+                .flatten()
+                .filter(|(_, state_value)| state_value.is_none())
+                .map(|(state_key, _)| state_key.clone())
+                .collect();
+            state_view.fallback = Some(fallback);
+        }
+        Ok(state_view)
     }
 
     pub fn new_impl(
@@ -183,6 +198,8 @@ impl CachedStateView {
             speculative_state,
             sharded_state_cache: ShardedStateCache::default(),
             proof_fetcher,
+            fallback: None,
+            speculative_deletions: HashSet::new(),
         }
     }
 
@@ -199,13 +216,34 @@ impl CachedStateView {
                 .into_iter()
                 .for_each(|key| {
                     s.spawn(move |_| {
-                        self.get_state_value_bytes(key).expect("Must succeed.");
+                        self.prime_cache_by_key(key).expect("Must succeed.");
                     })
                 });
         });
         Ok(())
     }
 
+    /// Primes the cache with the value of the given key (that is about to be overwritten).
+    /// Nodes only storing partial state don't have the values of untracked keys, in which case
+    /// only the proof is read, as it is still required to update the tree.
+    fn prime_cache_by_key(&self, state_key: &StateKey) -> Result<()> {
+        match self.get_state_value_bytes(state_key) {
+            Ok(_) => Ok(()),
+            Err(StateviewError::UntrackedState(_)) => {
+                if let (
+                    StateStoreStatus::UnknownSubtreeRoot { hash, depth },
+                    Some((version, _root_hash)),
+                ) = (self.speculative_state.get(state_key.hash()), self.snapshot)
+                {
+                    self.proof_fetcher
+                        .schedule_proof_read_for_untracked_state(state_key, version, depth, hash);
+                }
+                Ok(())
+            },
+            Err(error) => Err(error),
+        }
+    }
+
     pub fn into_state_cache(self) -> StateCache {
         StateCache {
             frozen_base: self.speculative_state,
@@ -263,6 +301,21 @@ impl CachedStateView {
             Some((version, value)) => (Some(version), Some(value)),
         })
     }
+
+    /// Returns the given value, or the value served by the fallback if the value doesn't exist
+    /// (i.e., neither in the speculative state nor in the DB)
+    fn maybe_get_from_fallback(
+        &self,
+        state_key: &StateKey,
+        value_opt: Option<StateValue>,
+    ) -> Result<Option<StateValue>> {
+        match (value_opt, &self.fallback) {
+            (None, Some(fallback)) if !self.speculative_deletions.contains(state_key) => {
+                fallback.get_state_value(state_key).map_err(Into::into)
+            },
+            (value_opt, _) => Ok(value_opt),
+        }
+    }
 }
 
 pub struct StateCache {
@@ -281,25 +334,26 @@ impl TStateView for CachedStateView {
     fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
         let _timer = TIMER.with_label_values(&["get_state_value"]).start_timer();
         // First check if the cache has the state value.
-        if let Some(version_and_value_opt) = self
+        let cached_value_opt = self
             .sharded_state_cache
             .shard(state_key.get_shard_id())
             .get(state_key)
-        {
+            .map(|version_and_value_opt| version_and_value_opt.1.clone());
+        if let Some(value_opt) = cached_value_opt {
             // This can return None, which means the value has been deleted from the DB.
-            let value_opt = &version_and_value_opt.1;
-            return Ok(value_opt.clone());
+            return self.maybe_get_from_fallback(state_key, value_opt);
         }
         let version_and_state_value_option =
             self.get_version_and_state_value_internal(state_key)?;
         // Update the cache if still empty
-        let new_version_and_value = self
+        let value_opt = self
             .sharded_state_cache
             .shard(state_key.get_shard_id())
             .entry(state_key.clone())
-            .or_insert(version_and_state_value_option);
-        let value_opt = &new_version_and_value.1;
-        Ok(value_opt.clone())
+            .or_insert(version_and_state_value_option)
+            .1
+            .clone();
+        self.maybe_get_from_fallback(state_key, value_opt)
     }
 
     fn get_usage(&self) -> Result<StateStorageUsage> {
@@ -18,6 +18,9 @@ pub enum AptosDbError {
     TooManyRequested(u64, u64),
     #[error("Missing state root node at version {0}, probably pruned.")]
     MissingRootError(u64),
+    /// The requested state is not stored by this node (i.e., it only stores partial state).
+    #[error("{0} is not tracked by this node.")]
+    UntrackedState(String),
     /// Other non-classified error.
     #[error("AptosDB Other Error: {0}")]
     Other(String),
@@ -69,6 +72,7 @@ impl From<AptosDbError> for StateviewError {
     fn from(error: AptosDbError) -> Self {
         match error {
             AptosDbError::NotFound(msg) => StateviewError::NotFound(msg),
+            AptosDbError::UntrackedState(msg) => StateviewError::UntrackedState(msg),
             AptosDbError::Other(msg) => StateviewError::Other(msg),
             _ => StateviewError::Other(format!("{}", error)),
         }
@@ -79,6 +83,7 @@ impl From<StateviewError> for AptosDbError {
     fn from(error: StateviewError) -> Self {
         match error {
             StateviewError::NotFound(msg) => AptosDbError::NotFound(msg),
+            StateviewError::UntrackedState(msg) => AptosDbError::UntrackedState(msg),
             StateviewError::Other(msg) => AptosDbError::Other(msg),
             StateviewError::BcsError(err) => AptosDbError::BcsError(err.to_string()),
         }
@@ -19,7 +19,7 @@ use aptos_types::{
     },
     state_proof::StateProof,
     state_store::{
-        state_key::StateKey,
+        state_key::{filter::StateKeyFilter, StateKey},
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueChunkWithProof},
         table::{TableHandle, TableInfo},
@@ -59,12 +59,23 @@ pub type Result<T, E = AptosDbError> = std::result::Result<T, E>;
 // like the API and State Sync, etc.
 pub const MAX_REQUEST_LIMIT: u64 = 20_000;
 
+/// Serves the state values that are not stored in the DB, e.g., the state of the remote chain
+/// that a local testnet was forked from (see [`DbReader::get_state_fallback`]).
+pub trait StateFallback: Send + Sync {
+    /// Returns the value of the given state key (which doesn't exist in the DB)
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>>;
+}
+
 pub trait StateSnapshotReceiver<K, V>: Send {
     fn add_chunk(&mut self, chunk: Vec<(K, V)>, proof: SparseMerkleRangeProof) -> Result<()>;
 
     fn finish(self) -> Result<()>;
 
     fn finish_box(self: Box<Self>) -> Result<()>;
+
+    /// Returns the hash of the last key persisted by the receiver (e.g., by a
+    /// previous restore that was interrupted). Returns None if nothing was persisted.
+    fn previous_key_hash(&self) -> Result<Option<HashValue>>;
 }
 
 #[derive(Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
@@ -433,6 +444,14 @@ pub trait DbReader: Send + Sync {
         /// Returns if the state store pruner is enabled.
         fn is_state_merkle_pruner_enabled(&self) -> Result<bool>;
 
+        /// Returns if the state KV DB is sharded (in which case, state values
+        /// cannot be iterated by key prefix without the internal indexer).
+        fn is_state_kv_sharding_enabled(&self) -> Result<bool>;
+
+        /// Returns the state key filter if the node only stores partial state
+        /// (i.e., the state values of untracked state keys are not stored).
+        fn get_state_key_filter(&self) -> Result<Option<Arc<StateKeyFilter>>>;
+
         /// Get the state prune window config value.
         fn get_epoch_snapshot_prune_window(&self) -> Result<usize>;
 
@@ -472,6 +491,12 @@ pub trait DbReader: Send + Sync {
             .map(|li| li.ledger_info().version())
     }
 
+    /// Returns the fallback for the state values that don't exist in the DB (if any). State
+    /// views consult the fallback when a state key doesn't exist in the DB.
+    fn get_state_fallback(&self) -> Option<Arc<dyn StateFallback>> {
+        None
+    }
+
     /// Returns the latest version and committed block timestamp
     fn get_latest_commit_metadata(&self) -> Result<(Version, u64)> {
         let ledger_info_with_sig = self.get_latest_ledger_info()?;
@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::DbReader;
+use crate::{AptosDbError, DbReader, StateFallback};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_types::{
     ledger_info::LedgerInfo,
@@ -12,7 +12,10 @@ use aptos_types::{
     },
     transaction::Version,
 };
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 
 type Result<T, E = StateviewError> = std::result::Result<T, E>;
 
@@ -20,19 +23,64 @@ pub struct DbStateView {
     db: Arc<dyn DbReader>,
     version: Option<Version>,
     verify_against_state_root_hash: Option<HashValue>,
+    // Set if the view attempted to read state that is not stored by the
+    // node (i.e., the node only stores partial state).
+    untracked_state_read: AtomicBool,
+    // Serves the state that doesn't exist in the DB (e.g., in fork mode)
+    fallback: Option<Arc<dyn StateFallback>>,
 }
 
 impl DbStateView {
+    fn new(
+        db: Arc<dyn DbReader>,
+        version: Option<Version>,
+        verify_against_state_root_hash: Option<HashValue>,
+    ) -> Self {
+        let fallback = db.get_state_fallback();
+        Self {
+            db,
+            version,
+            verify_against_state_root_hash,
+            untracked_state_read: AtomicBool::new(false),
+            fallback,
+        }
+    }
+
+    /// Returns true iff the view attempted to read state that is not stored
+    /// by the node. This allows callers to identify failures (e.g., of view
+    /// functions) caused by partial state storage.
+    pub fn has_read_untracked_state(&self) -> bool {
+        self.untracked_state_read.load(Ordering::Relaxed)
+    }
+
     fn get(&self, key: &StateKey) -> Result<Option<StateValue>> {
+        let result = self.get_from_db(key);
+        if let Err(AptosDbError::UntrackedState(_)) = &result {
+            self.untracked_state_read.store(true, Ordering::Relaxed);
+        }
+        result.map_err(Into::into)
+    }
+
+    fn get_from_db(&self, key: &StateKey) -> crate::Result<Option<StateValue>> {
         Ok(if let Some(version) = self.version {
             if let Some(root_hash) = self.verify_against_state_root_hash {
                 let (value, proof) = self
                     .db
                     .get_state_value_with_proof_by_version(key, version)?;
-                proof.verify(root_hash, CryptoHash::hash(key), value.as_ref())?;
+                proof
+                    .verify(root_hash, CryptoHash::hash(key), value.as_ref())
+                    .map_err(AptosDbError::from)?;
                 value
             } else {
-                self.db.get_state_value_by_version(key, version)?
+                match self.db.get_state_value_by_version(key, version)? {
+                    Some(value) => Some(value),
+                    // Values served by the fallback can't be verified, so
+                    // the fallback is only consulted by unverified views.
+                    None => match &self.fallback {
+                        Some(fallback) => fallback.get_state_value(key)?,
+                        None => None,
+                    },
+                }
             }
         } else {
             None
@@ -60,13 +108,12 @@ pub trait LatestDbStateCheckpointView {
 
 impl LatestDbStateCheckpointView for Arc<dyn DbReader> {
     fn latest_state_checkpoint_view(&self) -> Result<DbStateView> {
-        Ok(DbStateView {
-            db: self.clone(),
-            version: self
-                .get_latest_state_checkpoint_version()
+        Ok(DbStateView::new(
+            self.clone(),
+            self.get_latest_state_checkpoint_version()
                 .map_err(Into::<StateviewError>::into)?,
-            verify_against_state_root_hash: None,
-        })
+            None,
+        ))
     }
 }
 
@@ -76,11 +123,7 @@ pub trait DbStateViewAtVersion {
 
 impl DbStateViewAtVersion for Arc<dyn DbReader> {
     fn state_view_at_version(&self, version: Option<Version>) -> Result<DbStateView> {
-        Ok(DbStateView {
-            db: self.clone(),
-            version,
-            verify_against_state_root_hash: None,
-        })
+        Ok(DbStateView::new(self.clone(), version, None))
     }
 }
 
@@ -111,17 +154,9 @@ impl VerifiedStateViewAtVersion for Arc<dyn DbReader> {
                 .state_checkpoint_hash()
                 .ok_or_else(|| StateviewError::NotFound("state_checkpoint_hash".to_string()))?;
 
-            Ok(DbStateView {
-                db,
-                version: Some(version),
-                verify_against_state_root_hash: Some(state_root_hash),
-            })
+            Ok(DbStateView::new(db, Some(version), Some(state_root_hash)))
         } else {
-            Ok(DbStateView {
-                db,
-                version: None,
-                verify_against_state_root_hash: None,
-            })
+            Ok(DbStateView::new(db, None, None))
         }
     }
 }
@@ -93,6 +93,13 @@ if os.environ.get("ENABLE_PRUNER"):
 else:
     DB_PRUNER_FLAGS = ""
 
+# Run with the number of execution threads adapted to the contention of the workload,
+# e.g. to compare the conflicting workloads (hotspot, global resources) against a static level
+if os.environ.get("ADAPTIVE_CONCURRENCY_MIN_LEVEL"):
+    ADAPTIVE_CONCURRENCY_FLAGS = f"--adaptive-concurrency-min-level {os.environ['ADAPTIVE_CONCURRENCY_MIN_LEVEL']}"
+else:
+    ADAPTIVE_CONCURRENCY_FLAGS = ""
+
 HIDE_OUTPUT = os.environ.get("HIDE_OUTPUT")
 SKIP_MOVE_E2E = os.environ.get("SKIP_MOVE_E2E")
 
@@ -589,7 +596,7 @@ with tempfile.TemporaryDirectory() as tmpdirname:
             * (1 if test.key_extra.smaller_working_set else NUM_BLOCKS)
         )
 
-        common_command_suffix = f"{executor_type_str} {txn_emitter_prefix_str} --block-size {cur_block_size} {DB_CONFIG_FLAGS} {DB_PRUNER_FLAGS} run-executor {workload_args_str} --module-working-set-size {test.key.module_working_set_size} --main-signer-accounts {MAIN_SIGNER_ACCOUNTS} --additional-dst-pool-accounts {ADDITIONAL_DST_POOL_ACCOUNTS} --data-dir {tmpdirname}/db  --checkpoint-dir {tmpdirname}/cp"
+        common_command_suffix = f"{executor_type_str} {txn_emitter_prefix_str} --block-size {cur_block_size} {DB_CONFIG_FLAGS} {DB_PRUNER_FLAGS} {ADAPTIVE_CONCURRENCY_FLAGS} run-executor {workload_args_str} --module-working-set-size {test.key.module_working_set_size} --main-signer-accounts {MAIN_SIGNER_ACCOUNTS} --additional-dst-pool-accounts {ADDITIONAL_DST_POOL_ACCOUNTS} --data-dir {tmpdirname}/db  --checkpoint-dir {tmpdirname}/cp"
 
         number_of_threads_results = {}
 
@@ -6,7 +6,8 @@ use crate::{
     interpreter::Interpreter,
     loader::{Function, Loader},
 };
-use move_binary_format::file_format::Bytecode;
+use move_binary_format::{errors::PartialVMResult, file_format::Bytecode};
+use move_core_types::language_storage::ModuleId;
 use move_vm_types::values::{self, Locals};
 use std::{
     collections::BTreeSet,
@@ -194,3 +195,84 @@ impl DebugContext {
         }
     }
 }
+
+/// A hook which is called by the interpreter right before every instruction it executes. It can
+/// be used to implement debuggers on top of the VM, see [`crate::tracing::set_debug_hook`].
+pub trait DebugHook: Send {
+    fn on_instruction(&mut self, state: &InstructionState);
+}
+
+/// The state of the interpreter right before an instruction is executed. Values are rendered
+/// with the debug printer of the VM when they are requested.
+pub struct InstructionState<'a> {
+    function: &'a Function,
+    locals: &'a Locals,
+    pc: u16,
+    instr: &'a Bytecode,
+    interp: &'a Interpreter,
+}
+
+impl<'a> InstructionState<'a> {
+    pub(crate) fn new(
+        function: &'a Function,
+        locals: &'a Locals,
+        pc: u16,
+        instr: &'a Bytecode,
+        interp: &'a Interpreter,
+    ) -> Self {
+        Self {
+            function,
+            locals,
+            pc,
+            instr,
+            interp,
+        }
+    }
+
+    /// The module of the current function, or `None` if it is a script.
+    pub fn module_id(&self) -> Option<&ModuleId> {
+        self.function.module_id()
+    }
+
+    pub fn function_name(&self) -> &str {
+        self.function.name()
+    }
+
+    /// The fully qualified name of the current function, e.g., `0x1::coin::transfer`.
+    pub fn function_string(&self) -> String {
+        self.function.pretty_string()
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    pub fn instruction(&self) -> &Bytecode {
+        self.instr
+    }
+
+    /// The code of the current function.
+    pub fn code(&self) -> &[Bytecode] {
+        self.function.code()
+    }
+
+    /// The number of callers of the current function.
+    pub fn call_depth(&self) -> usize {
+        self.interp.debug_call_depth()
+    }
+
+    /// The callers of the current function (outermost first), together with the offsets of the
+    /// calls they are suspended at.
+    pub fn callers(&self) -> Vec<(String, u16)> {
+        self.interp.debug_callers()
+    }
+
+    pub fn locals(&self) -> PartialVMResult<Vec<String>> {
+        values::debug::locals_to_strings(self.locals)
+    }
+
+    /// The values on the operand stack (bottom first).
+    pub fn operand_stack(&self) -> PartialVMResult<Vec<String>> {
+        self.interp.debug_operand_stack()
+    }
+}
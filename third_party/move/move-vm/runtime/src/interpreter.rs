@@ -1048,6 +1048,37 @@ impl Interpreter {
         Ok(())
     }
 
+    /// Returns the number of callers of the current function.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_call_depth(&self) -> usize {
+        self.call_stack.0.len()
+    }
+
+    /// Returns the callers of the current function (outermost first), together with the offsets
+    /// of the calls they are suspended at.
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_callers(&self) -> Vec<(String, u16)> {
+        self.call_stack
+            .0
+            .iter()
+            .map(|frame| (frame.function.pretty_string(), frame.pc))
+            .collect()
+    }
+
+    /// Returns the values on the operand stack (bottom first).
+    #[cfg(any(debug_assertions, feature = "debugging"))]
+    pub(crate) fn debug_operand_stack(&self) -> PartialVMResult<Vec<String>> {
+        self.operand_stack
+            .value
+            .iter()
+            .map(|val| {
+                let mut buf = String::new();
+                values::debug::print_value(&mut buf, val)?;
+                Ok(buf)
+            })
+            .collect()
+    }
+
     /// Generate a string which is the status of the interpreter: call stack, current bytecode
     /// stream, locals and operand stack.
     ///
@@ -2322,6 +2353,13 @@ impl Frame {
                     },
                     Bytecode::Ret => {
                         gas_meter.charge_simple_instr(S::Ret)?;
+                        if gas_meter.observes_return_values() {
+                            gas_meter.observe_return_values(
+                                interpreter
+                                    .operand_stack
+                                    .last_n(self.function.return_tys().len())?,
+                            )?;
+                        }
                         return Ok(ExitCode::Return);
                     },
                     Bytecode::BrTrue(offset) => {
@@ -31,4 +31,6 @@ mod debug;
 
 mod access_control;
 
+#[cfg(any(debug_assertions, feature = "debugging"))]
+pub use debug::{DebugHook, InstructionState};
 pub use loader::LoadedFunction;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 #[cfg(any(debug_assertions, feature = "debugging"))]
-use crate::debug::DebugContext;
+use crate::debug::{DebugContext, DebugHook, InstructionState};
 #[cfg(any(debug_assertions, feature = "debugging"))]
 use crate::{
     interpreter::Interpreter,
@@ -18,7 +18,10 @@ use ::{
         env,
         fs::{File, OpenOptions},
         io::Write,
-        sync::Mutex,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Mutex,
+        },
     },
 };
 
@@ -64,6 +67,22 @@ pub static SINGLE_STEP_FLUSHING: Lazy<bool> =
 #[cfg(any(debug_assertions, feature = "debugging"))]
 static DEBUG_CONTEXT: Lazy<Mutex<DebugContext>> = Lazy::new(|| Mutex::new(DebugContext::new()));
 
+#[cfg(any(debug_assertions, feature = "debugging"))]
+static DEBUG_HOOK: Lazy<Mutex<Option<Box<dyn DebugHook>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Avoids taking the lock of the debug hook for every instruction when no hook is installed.
+#[cfg(any(debug_assertions, feature = "debugging"))]
+static DEBUG_HOOK_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Installs a hook which is called before every instruction executed by any VM in this process,
+/// or removes the current one if `None` is passed. Returns the previously installed hook.
+#[cfg(any(debug_assertions, feature = "debugging"))]
+pub fn set_debug_hook(hook: Option<Box<dyn DebugHook>>) -> Option<Box<dyn DebugHook>> {
+    let mut current = DEBUG_HOOK.lock().unwrap();
+    DEBUG_HOOK_INSTALLED.store(hook.is_some(), Ordering::SeqCst);
+    std::mem::replace(&mut *current, hook)
+}
+
 // Only include in debug builds
 #[cfg(any(debug_assertions, feature = "debugging"))]
 pub(crate) fn trace(
@@ -89,6 +108,17 @@ pub(crate) fn trace(
             .unwrap()
             .debug_loop(function_desc, locals, pc, instr, loader, interp);
     }
+    if DEBUG_HOOK_INSTALLED.load(Ordering::Relaxed) {
+        if let Some(hook) = DEBUG_HOOK.lock().unwrap().as_mut() {
+            hook.on_instruction(&InstructionState::new(
+                function_desc,
+                locals,
+                pc,
+                instr,
+                interp,
+            ));
+        }
+    }
 }
 
 #[macro_export]
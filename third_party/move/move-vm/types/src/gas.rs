@@ -329,6 +329,23 @@ pub trait GasMeter {
         locals: impl Iterator<Item = impl ValueView> + Clone,
     ) -> PartialVMResult<()>;
 
+    /// Returns whether the gas meter observes the values returned by functions. If not (i.e., by
+    /// default), the interpreter doesn't collect the return values for `observe_return_values`.
+    fn observes_return_values(&self) -> bool {
+        false
+    }
+
+    /// Called when a function returns, with the values it returns (only if
+    /// `observes_return_values` is true). Returning is already charged by `charge_simple_instr`,
+    /// so this does nothing by default, but it allows gas meter adapters (e.g., execution
+    /// tracers) to observe the return values.
+    fn observe_return_values(
+        &mut self,
+        _ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
+
     fn charge_create_ty(&mut self, num_nodes: NumTypeNodes) -> PartialVMResult<()>;
 
     fn charge_dependency(
@@ -3,6 +3,67 @@
 
 use crate::on_chain_config::BlockGasLimitType;
 use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Configuration for adapting the number of workers in parallel execution to how contended
+/// the workload is. The number of workers is adjusted after each block, between
+/// min_concurrency_level and the concurrency level of the local config.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdaptiveConcurrencyConfig {
+    pub min_concurrency_level: usize,
+    // Fewer workers are used for later blocks if the number of aborts (each leading to
+    // a re-execution) per 100 transactions of a block exceeds this value.
+    pub max_aborts_per_100_txns: u64,
+    // Fewer workers are used for later blocks if the workers were idle (i.e. without
+    // a task, or waiting on a dependency) for a larger percentage of a block's execution.
+    pub max_idle_percentage: u64,
+}
+
+impl Default for AdaptiveConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            min_concurrency_level: 2,
+            max_aborts_per_100_txns: 50,
+            max_idle_percentage: 30,
+        }
+    }
+}
+
+/// Adaptive concurrency, along with the number of workers chosen for the next block. Clones
+/// share the chosen number of workers, so every executor should create its own instance.
+#[derive(Clone, Debug)]
+pub struct AdaptiveConcurrency {
+    pub config: AdaptiveConcurrencyConfig,
+    // The number of workers chosen for the next block, or 0 if none was chosen yet.
+    next_level: Arc<AtomicUsize>,
+}
+
+impl AdaptiveConcurrency {
+    pub fn new(config: AdaptiveConcurrencyConfig) -> Self {
+        Self {
+            config,
+            next_level: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn next_level(&self) -> Option<usize> {
+        match self.next_level.load(Ordering::Relaxed) {
+            0 => None,
+            level => Some(level),
+        }
+    }
+
+    pub fn set_next_level(&self, level: usize) {
+        self.next_level.store(level, Ordering::Relaxed);
+    }
+}
 
 /// Local, per-node configuration.
 #[derive(Clone, Debug)]
@@ -14,6 +75,12 @@ pub struct BlockExecutorLocalConfig {
     // If true, we will discard the failed blocks and continue with the next block.
     // (allow_fallback needs to be set)
     pub discard_failed_blocks: bool,
+    // If specified, the conflicts observed during parallel execution of each block
+    // (dependencies between transactions, aborts, hot keys) are written to this directory.
+    pub conflict_analytics_dir: Option<PathBuf>,
+    // If specified, the number of workers is adapted to the contention of the workload,
+    // with concurrency_level being the maximum.
+    pub adaptive_concurrency: Option<AdaptiveConcurrency>,
 }
 
 /// Configuration from on-chain configuration, that is
@@ -73,6 +140,8 @@ impl BlockExecutorConfig {
                 concurrency_level,
                 allow_fallback: true,
                 discard_failed_blocks: false,
+                conflict_analytics_dir: None,
+                adaptive_concurrency: None,
             },
             onchain: BlockExecutorConfigFromOnchain::new_no_block_limit(),
         }
@@ -87,6 +156,8 @@ impl BlockExecutorConfig {
                 concurrency_level,
                 allow_fallback: true,
                 discard_failed_blocks: false,
+                conflict_analytics_dir: None,
+                adaptive_concurrency: None,
             },
             onchain: BlockExecutorConfigFromOnchain::new_maybe_block_limit(maybe_block_gas_limit),
         }
@@ -460,6 +460,18 @@ pub struct ProposerAndVoterConfig {
 pub enum AnchorElectionMode {
     RoundRobin,
     LeaderReputation(LeaderReputationType),
+    // Anchors are chosen pseudo-randomly, weighted by voting power. The choice
+    // is seeded by (the hash of) the epoch state, i.e., the validator set, so
+    // the anchors of the whole epoch are predictable (as with RoundRobin) and
+    // upcoming anchors can be targeted (e.g., by DoS attacks).
+    StakeWeightedPseudoRandom,
+    // Same as LeaderReputation, but the reputation of the anchor for a round
+    // only considers the anchors committed in the last `lookback_rounds` rounds
+    // (of the current epoch), so that past failures are eventually forgiven.
+    LeaderReputationWithLookback {
+        reputation_type: LeaderReputationType,
+        lookback_rounds: u64,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -528,6 +540,34 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_dag_anchor_election_mode_serialization() {
+        let reputation_type = match DagConsensusConfigV1::default().anchor_election_mode {
+            AnchorElectionMode::LeaderReputation(reputation_type) => reputation_type,
+            mode => panic!("Unexpected default anchor election mode: {:?}", mode),
+        };
+        for anchor_election_mode in [
+            AnchorElectionMode::StakeWeightedPseudoRandom,
+            AnchorElectionMode::LeaderReputationWithLookback {
+                reputation_type,
+                lookback_rounds: 100,
+            },
+        ] {
+            let config = DagConsensusConfigV1 {
+                anchor_election_mode,
+                ..DagConsensusConfigV1::default()
+            };
+
+            let s = serde_yaml::to_string(&config).unwrap();
+            assert_eq!(
+                serde_yaml::from_str::<DagConsensusConfigV1>(&s).unwrap(),
+                config
+            );
+            let s = bcs::to_bytes(&config).unwrap();
+            assert_eq!(bcs::from_bytes::<DagConsensusConfigV1>(&s).unwrap(), config);
+        }
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
@@ -158,6 +158,19 @@ pub enum TransactionShufflerType {
         platform_use_case_spread_factor: usize,
         user_use_case_spread_factor: usize,
     },
+    // Note: the variants below require a binary upgrade of all validators before they
+    // are enabled on-chain, as older binaries can't deserialize them.
+    /// Orders the transactions by gas unit price (highest first), while
+    /// preserving the relative order of the transactions of each sender.
+    GasPricePriority,
+    /// Spreads out transactions with conflicting read/write hints (only
+    /// coin transfers and account creation have precise hints).
+    ConflictAware {
+        conflict_window_size: u32,
+    },
+    /// Applies the given shufflers in sequence. The shufflers may not be composed
+    /// themselves (otherwise, no shuffling is done).
+    Composed(Vec<TransactionShufflerType>),
 }
 
 impl TransactionShufflerType {
@@ -349,6 +362,28 @@ mod test {
         bcs::from_bytes::<OnChainExecutionConfig>(&s).unwrap();
     }
 
+    #[test]
+    fn test_composed_shuffler_type_serialization() {
+        let shuffler_type = TransactionShufflerType::Composed(vec![
+            TransactionShufflerType::GasPricePriority,
+            TransactionShufflerType::ConflictAware {
+                conflict_window_size: 16,
+            },
+        ]);
+
+        let s = bcs::to_bytes(&shuffler_type).unwrap();
+        assert_eq!(
+            bcs::from_bytes::<TransactionShufflerType>(&s).unwrap(),
+            shuffler_type
+        );
+
+        let s = serde_yaml::to_string(&shuffler_type).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<TransactionShufflerType>(&s).unwrap(),
+            shuffler_type
+        );
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = OnChainExecutionConfig::V1(ExecutionConfigV1 {
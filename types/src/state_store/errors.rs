@@ -8,6 +8,9 @@ use thiserror::Error;
 pub enum StateviewError {
     #[error("{0} not found.")]
     NotFound(String),
+    /// The requested state is not stored by this node (i.e., it only stores partial state).
+    #[error("{0} is not tracked by this node.")]
+    UntrackedState(String),
     /// Other non-classified error.
     #[error("{0}")]
     Other(String),
@@ -0,0 +1,316 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    access_path::Path,
+    account_config::{ObjectCoreResource, ObjectGroupResource},
+    state_store::{
+        state_key::{inner::StateKeyInner, StateKey},
+        state_value::StateValue,
+        table::TableHandle,
+    },
+    transaction::Version,
+};
+use aptos_infallible::RwLock;
+use move_core_types::{
+    account_address::AccountAddress, language_storage::StructTag, move_resource::MoveStructType,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// Struct for deciding which state keys are tracked (i.e., stored) by a node
+// that only stores partial state for a subset of accounts. Special addresses
+// (e.g., the framework at 0x1) are always tracked, as the node cannot function
+// without them. Objects owned (directly or transitively) by tracked accounts
+// are also tracked, e.g., the primary fungible stores of the accounts.
+#[derive(Debug, Default)]
+pub struct StateKeyFilter {
+    tracked_addresses: HashSet<AccountAddress>,
+    tracked_table_handles: HashSet<TableHandle>,
+    // The objects owned by tracked accounts, along with the versions since
+    // which they are tracked. These are discovered as the objects are written
+    // (see `track_owned_objects`), and never untracked.
+    tracked_objects: RwLock<HashMap<AccountAddress, Version>>,
+}
+
+impl StateKeyFilter {
+    pub fn new(
+        tracked_addresses: impl IntoIterator<Item = AccountAddress>,
+        tracked_table_handles: impl IntoIterator<Item = TableHandle>,
+    ) -> Self {
+        Self {
+            tracked_addresses: tracked_addresses.into_iter().collect(),
+            tracked_table_handles: tracked_table_handles.into_iter().collect(),
+            tracked_objects: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the (configured) addresses tracked since the first version
+    pub fn tracked_addresses(&self) -> &HashSet<AccountAddress> {
+        &self.tracked_addresses
+    }
+
+    /// Returns the (configured) table handles tracked since the first version
+    pub fn tracked_table_handles(&self) -> &HashSet<TableHandle> {
+        &self.tracked_table_handles
+    }
+
+    /// Returns true iff the given address (i.e., account or object) is
+    /// currently tracked
+    pub fn is_address_tracked(&self, address: &AccountAddress) -> bool {
+        self.address_tracked_since(address).is_some()
+    }
+
+    /// Returns true iff all the state of the given address has been tracked
+    /// since the first version. This is not the case for the objects that
+    /// became owned by tracked addresses later (e.g., on transfer), as their
+    /// state written before was not stored.
+    pub fn is_address_fully_tracked(&self, address: &AccountAddress) -> bool {
+        self.address_tracked_since(address) == Some(0)
+    }
+
+    /// Returns true iff the given state key is currently tracked. Resources
+    /// and modules are tracked by address, and table items by table handle.
+    /// Table handles are not derived from their owners, so they must be
+    /// tracked explicitly.
+    pub fn is_tracked(&self, state_key: &StateKey) -> bool {
+        self.tracked_since(state_key).is_some()
+    }
+
+    /// Returns true iff the writes to the given state key at the given
+    /// version are stored
+    pub fn is_tracked_at(&self, state_key: &StateKey, version: Version) -> bool {
+        self.tracked_since(state_key)
+            .map_or(false, |tracked_since| tracked_since <= version)
+    }
+
+    /// Returns the first version whose writes to the given state key are
+    /// stored, or None if the state key is not tracked
+    pub fn tracked_since(&self, state_key: &StateKey) -> Option<Version> {
+        match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => {
+                self.address_tracked_since(&access_path.address)
+            },
+            StateKeyInner::TableItem { handle, .. } => {
+                self.tracked_table_handles.contains(handle).then_some(0)
+            },
+            StateKeyInner::Raw(_) => Some(0),
+        }
+    }
+
+    fn address_tracked_since(&self, address: &AccountAddress) -> Option<Version> {
+        if address.is_special() || self.tracked_addresses.contains(address) {
+            Some(0)
+        } else {
+            self.tracked_objects.read().get(address).cloned()
+        }
+    }
+
+    /// Tracks the objects (in the given writes) that are owned by tracked
+    /// addresses, and returns the newly tracked objects (with the versions
+    /// since which they are tracked). This must be called before the writes
+    /// are filtered, so that the state of newly owned objects is stored.
+    ///
+    /// Note: an object is tracked from the first write to its object group at
+    /// which its owner is tracked. E.g., the objects owned by an object that
+    /// is transferred to a tracked account are only tracked once they are
+    /// written again, as their object groups were not stored.
+    pub fn track_owned_objects<'a>(
+        &self,
+        writes: impl IntoIterator<Item = (Version, &'a StateKey, Option<&'a StateValue>)>,
+    ) -> Vec<(AccountAddress, Version)> {
+        // Identify the owner of each written object (at each version)
+        let mut object_owners_by_version: BTreeMap<
+            Version,
+            HashMap<AccountAddress, AccountAddress>,
+        > = BTreeMap::new();
+        for (version, state_key, state_value) in writes {
+            let Some(object_address) = object_group_address(state_key) else {
+                continue;
+            };
+            if let Some(owner) = state_value.and_then(object_owner) {
+                object_owners_by_version
+                    .entry(version)
+                    .or_default()
+                    .insert(object_address, owner);
+            }
+        }
+
+        // Track the objects owned by tracked addresses, in version order (and
+        // until no more objects are found at each version, as objects can be
+        // owned by other objects written at the same version).
+        let mut newly_tracked_objects = vec![];
+        for (version, mut object_owners) in object_owners_by_version {
+            object_owners.retain(|object_address, _| !self.is_address_tracked(object_address));
+            loop {
+                let owned_objects: Vec<_> = object_owners
+                    .iter()
+                    .filter(|(_, owner)| self.is_address_tracked(owner))
+                    .map(|(object_address, _)| *object_address)
+                    .collect();
+                if owned_objects.is_empty() {
+                    break;
+                }
+
+                let mut tracked_objects = self.tracked_objects.write();
+                for object_address in owned_objects {
+                    object_owners.remove(&object_address);
+                    tracked_objects.insert(object_address, version);
+                    newly_tracked_objects.push((object_address, version));
+                }
+            }
+        }
+        newly_tracked_objects
+    }
+
+    /// Tracks the given objects since the given versions (e.g., the objects
+    /// persisted in storage, on startup)
+    pub fn track_objects(
+        &self,
+        tracked_objects: impl IntoIterator<Item = (AccountAddress, Version)>,
+    ) {
+        self.tracked_objects.write().extend(tracked_objects);
+    }
+}
+
+/// Returns the object address if the state key is an object group
+pub fn object_group_address(state_key: &StateKey) -> Option<AccountAddress> {
+    match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) if access_path.is_resource_group() => {
+            match access_path.get_path() {
+                Path::ResourceGroup(struct_tag)
+                    if struct_tag == ObjectGroupResource::struct_tag() =>
+                {
+                    Some(access_path.address)
+                },
+                _ => None,
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Returns the owner of the object (if the object group contains an object core)
+fn object_owner(object_group: &StateValue) -> Option<AccountAddress> {
+    let resources: BTreeMap<StructTag, Vec<u8>> = bcs::from_bytes(object_group.bytes()).ok()?;
+    let object_core = resources.get(&ObjectCoreResource::struct_tag())?;
+    bcs::from_bytes::<ObjectCoreResource>(object_core)
+        .ok()
+        .map(|object_core| object_core.owner())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        account_config::{AccountResource, ObjectCoreResource, ObjectGroupResource},
+        event::{EventHandle, EventKey},
+        state_store::{
+            state_key::{filter::StateKeyFilter, StateKey},
+            state_value::StateValue,
+            table::TableHandle,
+        },
+    };
+    use move_core_types::{account_address::AccountAddress, move_resource::MoveStructType};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_state_key_filter() {
+        let tracked_address = AccountAddress::new([12u8; AccountAddress::LENGTH]);
+        let untracked_address = AccountAddress::new([22u8; AccountAddress::LENGTH]);
+        let tracked_handle = TableHandle(AccountAddress::new([32u8; AccountAddress::LENGTH]));
+        let state_key_filter = StateKeyFilter::new([tracked_address], [tracked_handle]);
+
+        // Resources are tracked by account address
+        let tracked_key = StateKey::resource_typed::<AccountResource>(&tracked_address).unwrap();
+        let untracked_key =
+            StateKey::resource_typed::<AccountResource>(&untracked_address).unwrap();
+        assert!(state_key_filter.is_tracked(&tracked_key));
+        assert!(!state_key_filter.is_tracked(&untracked_key));
+
+        // Table items are tracked by table handle (and not by address)
+        let tracked_key = StateKey::table_item(&tracked_handle, &[1]);
+        let untracked_key = StateKey::table_item(&TableHandle(tracked_address), &[1]);
+        assert!(state_key_filter.is_tracked(&tracked_key));
+        assert!(!state_key_filter.is_tracked(&untracked_key));
+
+        // Special addresses are always tracked
+        let framework_key =
+            StateKey::resource_typed::<AccountResource>(&AccountAddress::ONE).unwrap();
+        assert!(state_key_filter.is_tracked(&framework_key));
+    }
+
+    #[test]
+    fn test_state_key_filter_owned_objects() {
+        let tracked_address = AccountAddress::new([12u8; AccountAddress::LENGTH]);
+        let untracked_address = AccountAddress::new([22u8; AccountAddress::LENGTH]);
+        let state_key_filter = StateKeyFilter::new([tracked_address], []);
+
+        // Create objects owned by the tracked account, by another (tracked)
+        // object, and by the untracked account.
+        let owned_object = AccountAddress::new([1u8; AccountAddress::LENGTH]);
+        let nested_object = AccountAddress::new([2u8; AccountAddress::LENGTH]);
+        let untracked_object = AccountAddress::new([3u8; AccountAddress::LENGTH]);
+        let writes = [
+            create_object_group(nested_object, owned_object),
+            create_object_group(untracked_object, untracked_address),
+            create_object_group(owned_object, tracked_address),
+        ];
+
+        // Verify only the objects owned by the tracked account are tracked
+        let newly_tracked_objects = state_key_filter.track_owned_objects(
+            writes
+                .iter()
+                .map(|(state_key, state_value)| (5, state_key, Some(state_value))),
+        );
+        assert_eq!(newly_tracked_objects.len(), 2);
+        for (object, expect_tracked) in [
+            (owned_object, true),
+            (nested_object, true),
+            (untracked_object, false),
+        ] {
+            let (state_key, _) = create_object_group(object, untracked_address);
+            assert_eq!(state_key_filter.is_tracked(&state_key), expect_tracked);
+            assert_eq!(
+                state_key_filter.is_tracked_at(&state_key, 5),
+                expect_tracked
+            );
+            assert!(!state_key_filter.is_tracked_at(&state_key, 4));
+        }
+        assert!(state_key_filter.is_address_fully_tracked(&tracked_address));
+        assert!(!state_key_filter.is_address_fully_tracked(&owned_object));
+
+        // Transfer the untracked object (which owns another object) to the
+        // tracked account, and verify the object is tracked since the transfer,
+        // but the object it owns is not (as its object group wasn't written).
+        let child_object = AccountAddress::new([4u8; AccountAddress::LENGTH]);
+        let (child_key, child_value) = create_object_group(child_object, untracked_object);
+        let (transfer_key, transfer_value) = create_object_group(untracked_object, tracked_address);
+        let newly_tracked_objects = state_key_filter.track_owned_objects([
+            (6, &child_key, Some(&child_value)),
+            (7, &transfer_key, Some(&transfer_value)),
+        ]);
+        assert_eq!(newly_tracked_objects, vec![(untracked_object, 7)]);
+        assert!(!state_key_filter.is_tracked_at(&transfer_key, 6));
+        assert!(state_key_filter.is_tracked_at(&transfer_key, 7));
+        assert!(!state_key_filter.is_tracked(&child_key));
+    }
+
+    /// Creates an object group (with an object core) for the given object and owner
+    fn create_object_group(
+        object: AccountAddress,
+        owner: AccountAddress,
+    ) -> (StateKey, StateValue) {
+        let object_core = ObjectCoreResource::new(
+            0,
+            owner,
+            true,
+            EventHandle::new(EventKey::new(0, object), 0),
+        );
+        let resources = BTreeMap::from([(
+            ObjectCoreResource::struct_tag(),
+            bcs::to_bytes(&object_core).unwrap(),
+        )]);
+        let state_key = StateKey::resource_group(&object, &ObjectGroupResource::struct_tag());
+        let state_value = StateValue::new_legacy(bcs::to_bytes(&resources).unwrap().into());
+        (state_key, state_value)
+    }
+}
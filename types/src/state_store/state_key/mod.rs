@@ -3,6 +3,7 @@
 
 #![allow(clippy::non_canonical_partial_ord_impl)]
 
+pub mod filter;
 pub mod inner;
 pub mod prefix;
 pub mod registry;
@@ -24,6 +24,17 @@ impl StateKeyPrefix {
         Ok(out)
     }
 
+    /// Returns the account address covered by the prefix (if the prefix is
+    /// long enough to identify one).
+    pub fn address(&self) -> Option<AccountAddress> {
+        match self.tag {
+            StateKeyTag::AccessPath if self.bytes.len() >= AccountAddress::LENGTH => {
+                AccountAddress::from_bytes(&self.bytes[..AccountAddress::LENGTH]).ok()
+            },
+            _ => None,
+        }
+    }
+
     /// Checks if the current prefix is a valid prefix of a particular state_key
     pub fn is_prefix(&self, state_key: &StateKey) -> anyhow::Result<bool> {
         let encoded_key = state_key.encoded();
@@ -65,5 +76,12 @@ mod tests {
 
         assert!(!account1_key_prefx.is_prefix(&key2).unwrap());
         assert!(!account2_key_prefx.is_prefix(&key1).unwrap());
+
+        assert_eq!(account1_key_prefx.address(), Some(address1));
+        assert_eq!(account2_key_prefx.address(), Some(address2));
+        assert_eq!(
+            StateKeyPrefix::new(StateKeyTag::TableItem, address1.to_vec()).address(),
+            None
+        );
     }
 }
@@ -7,8 +7,8 @@ use crate::{
     on_chain_config::{CurrentTimeMicroseconds, Features, TransactionFeeBurnCap},
     state_store::{state_key::StateKey, table::TableHandle},
     transaction::{
-        signature_verified_transaction::SignatureVerifiedTransaction, Transaction,
-        TransactionPayload,
+        signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
+        Transaction, TransactionPayload,
     },
 };
 use aptos_crypto::HashValue;
@@ -232,63 +232,75 @@ pub fn empty_rw_set() -> (Vec<StorageLocation>, Vec<StorageLocation>) {
 }
 
 trait AnalyzedTransactionProvider {
-    fn get_read_write_hints(&self) -> (Vec<StorageLocation>, Vec<StorageLocation>);
+    fn get_read_write_hints(&self) -> (Vec<StorageLocation>, Vec<StorageLocation>) {
+        self.try_get_read_write_hints().expect(
+            "Only coin transfer and create account entry function transactions are supported for now",
+        )
+    }
+
+    /// Returns the read/write hints, or `None` if they can't be derived for the transaction
+    fn try_get_read_write_hints(&self) -> Option<(Vec<StorageLocation>, Vec<StorageLocation>)>;
+}
+
+/// Returns the read/write hints of the given user transaction, or `None` if they can't be
+/// derived. Note: hints are only supported for coin transfers and account creation.
+pub fn try_get_user_transaction_read_write_hints(
+    signed_txn: &SignedTransaction,
+) -> Option<(Vec<StorageLocation>, Vec<StorageLocation>)> {
+    match signed_txn.payload() {
+        TransactionPayload::EntryFunction(func) => {
+            let sender_address = signed_txn.sender();
+            match (
+                *func.module().address(),
+                func.module().name().as_str(),
+                func.function().as_str(),
+            ) {
+                (AccountAddress::ONE, "coin", "transfer") => {
+                    let receiver_address = bcs::from_bytes(func.args().first()?).ok()?;
+                    Some(rw_set_for_coin_transfer(
+                        sender_address,
+                        receiver_address,
+                        true,
+                    ))
+                },
+                (AccountAddress::ONE, "aptos_account", "transfer") => {
+                    let receiver_address = bcs::from_bytes(func.args().first()?).ok()?;
+                    Some(rw_set_for_coin_transfer(
+                        sender_address,
+                        receiver_address,
+                        false,
+                    ))
+                },
+                (AccountAddress::ONE, "aptos_account", "create_account") => {
+                    let receiver_address = bcs::from_bytes(func.args().first()?).ok()?;
+                    Some(rw_set_for_create_account(sender_address, receiver_address))
+                },
+                _ => None,
+            }
+        },
+        _ => None,
+    }
 }
 
 impl AnalyzedTransactionProvider for Transaction {
-    fn get_read_write_hints(&self) -> (Vec<StorageLocation>, Vec<StorageLocation>) {
+    fn try_get_read_write_hints(&self) -> Option<(Vec<StorageLocation>, Vec<StorageLocation>)> {
         match self {
-            Transaction::UserTransaction(signed_txn) => match signed_txn.payload() {
-                TransactionPayload::EntryFunction(func) => {
-                    match (
-                        *func.module().address(),
-                        func.module().name().as_str(),
-                        func.function().as_str(),
-                    ) {
-                        (AccountAddress::ONE, "coin", "transfer") => {
-                            let sender_address = signed_txn.sender();
-                            let receiver_address = bcs::from_bytes(&func.args()[0]).unwrap();
-                            rw_set_for_coin_transfer(
-                                sender_address,
-                                receiver_address,
-                                true,
-                            )
-                        },
-                        (AccountAddress::ONE, "aptos_account", "transfer") => {
-                            let sender_address = signed_txn.sender();
-                            let receiver_address = bcs::from_bytes(&func.args()[0]).unwrap();
-                            rw_set_for_coin_transfer(
-                                sender_address,
-                                receiver_address,
-                                false,
-                            )
-                        },
-                        (AccountAddress::ONE, "aptos_account", "create_account") => {
-                            let sender_address = signed_txn.sender();
-                            let receiver_address = bcs::from_bytes(&func.args()[0]).unwrap();
-                            rw_set_for_create_account(
-                                sender_address,
-                                receiver_address,
-                            )
-                        },
-                        _ => todo!("Only coin transfer and create account transactions are supported for now")
-                    }
-                },
-                _ => todo!("Only entry function transactions are supported for now"),
+            Transaction::UserTransaction(signed_txn) => {
+                try_get_user_transaction_read_write_hints(signed_txn)
             },
-            _ => empty_rw_set(),
+            _ => Some(empty_rw_set()),
         }
     }
 }
 
 impl AnalyzedTransactionProvider for SignatureVerifiedTransaction {
-    fn get_read_write_hints(&self) -> (Vec<StorageLocation>, Vec<StorageLocation>) {
+    fn try_get_read_write_hints(&self) -> Option<(Vec<StorageLocation>, Vec<StorageLocation>)> {
         match self {
-            SignatureVerifiedTransaction::Valid(txn) => txn.get_read_write_hints(),
+            SignatureVerifiedTransaction::Valid(txn) => txn.try_get_read_write_hints(),
             SignatureVerifiedTransaction::Invalid(_) => {
                 // Invalid transactions are not execute by the VM, so we don't need to provide
                 // read/write hints for them.
-                empty_rw_set()
+                Some(empty_rw_set())
             },
         }
     }